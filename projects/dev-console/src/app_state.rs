@@ -2,9 +2,11 @@
 
 use crate::settings_manager::SettingsManager;
 use crate::field_editor::{FieldEditorState, SettingsFields};
-use crate::dashboard::DashboardState;
+use crate::dashboard::{DashboardState, lock_state};
 use crate::process_manager::ProcessManager;
 use crate::profile_state::ProfileState;
+use crate::theme::Theme;
+use crate::clipboard::SystemClipboard;
 use std::sync::{Arc, Mutex};
 
 /// Application state structure
@@ -15,6 +17,9 @@ pub struct AppState {
     pub profile_state: ProfileState,
     pub dashboard: Arc<Mutex<DashboardState>>,
     pub process_manager: Arc<ProcessManager>,
+    pub theme: Theme,
+    /// Held open for the session so copied text stays available on X11/Wayland
+    pub clipboard: SystemClipboard,
 }
 
 impl AppState {
@@ -37,13 +42,15 @@ impl AppState {
             profile_state,
             dashboard,
             process_manager,
+            theme: Theme::default(),
+            clipboard: SystemClipboard::default(),
         }
     }
     
     /// Start a command execution (common setup for all commands)
     #[allow(dead_code)]
     pub fn start_command(&self, command: &str) {
-        let mut state = self.dashboard.lock().unwrap();
+        let mut state = lock_state(&self.dashboard);
         state.is_running = true;
         state.progress_percent = 0.0;
         state.set_progress_stage("Initializing");
@@ -56,7 +63,7 @@ impl AppState {
     #[allow(dead_code)]
     pub fn cancel_command(&self) {
         self.process_manager.kill_all();
-        let mut state = self.dashboard.lock().unwrap();
+        let mut state = lock_state(&self.dashboard);
         state.is_running = false;
         state.set_status_text("Command cancelled");
         state.add_output_line("Command cancelled by user".to_string());
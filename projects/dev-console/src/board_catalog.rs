@@ -0,0 +1,125 @@
+// Board catalog
+// Friendly board names mapped to FQBNs, parsed once from `arduino-cli board listall` and cached
+
+use lazy_static::lazy_static;
+use std::process::Command;
+use std::sync::Mutex;
+use std::thread;
+
+/// Default command whose output lists "Board Name   FQBN" rows
+pub const DEFAULT_BOARD_LIST_COMMAND: &str = "arduino-cli board listall";
+
+lazy_static! {
+    static ref BOARD_LIST_COMMAND: Mutex<String> = Mutex::new(DEFAULT_BOARD_LIST_COMMAND.to_string());
+    static ref CATALOG: Mutex<Option<BoardCatalog>> = Mutex::new(None);
+}
+
+/// A board as listed by arduino-cli
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoardEntry {
+    pub name: String,
+    pub fqbn: String,
+}
+
+/// Known boards, in the order the tool listed them
+#[derive(Debug, Clone, Default)]
+pub struct BoardCatalog {
+    boards: Vec<BoardEntry>,
+}
+
+impl BoardCatalog {
+    /// Parse `board listall` / `board search` table output
+    ///
+    /// The FQBN is the last whitespace-separated column containing ':'; the header
+    /// row and lines without an FQBN are skipped.
+    pub fn parse(output: &str) -> Self { //>
+        let boards = output
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim();
+                let (name, fqbn) = line.rsplit_once(char::is_whitespace)?;
+                let fqbn = fqbn.trim();
+                if !fqbn.contains(':') {
+                    return None;
+                }
+                Some(BoardEntry { name: name.trim().to_string(), fqbn: fqbn.to_string() })
+            })
+            .collect();
+        Self { boards }
+    } //<
+
+    /// Friendly names for the Board Model dropdown
+    pub fn names(&self) -> Vec<String> {
+        self.boards.iter().map(|b| b.name.clone()).collect()
+    }
+
+    /// FQBN for a friendly name (case-insensitive)
+    pub fn fqbn_for(&self, name: &str) -> Option<&str> {
+        self.boards
+            .iter()
+            .find(|b| b.name.eq_ignore_ascii_case(name.trim()))
+            .map(|b| b.fqbn.as_str())
+    }
+}
+
+/// Override the command used to list boards (from `application.board_list_command`)
+pub fn set_board_list_command(command: &str) {
+    *BOARD_LIST_COMMAND.lock().unwrap() = command.to_string();
+}
+
+/// Run the board list command; a failed or missing command yields an empty catalog
+fn load() -> BoardCatalog { //>
+    let command = BOARD_LIST_COMMAND.lock().unwrap().clone();
+    let mut parts = command.split_whitespace();
+    parts
+        .next()
+        .and_then(|program| Command::new(program).args(parts).output().ok())
+        .filter(|output| output.status.success())
+        .map(|output| BoardCatalog::parse(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or_default()
+} //<
+
+/// List boards on a background thread so the UI never waits on arduino-cli
+///
+/// Call after `set_board_list_command`; until it finishes `loaded()` returns `None`.
+pub fn start_loading() {
+    thread::spawn(|| {
+        let loaded = load();
+        CATALOG.lock().unwrap().get_or_insert(loaded);
+    });
+}
+
+/// The catalog if it has finished loading, without blocking
+pub fn loaded() -> Option<BoardCatalog> {
+    CATALOG.lock().unwrap().clone()
+}
+
+/// The cached catalog, running the board list command in place if it has not loaded yet
+///
+/// Blocks, so only for non-interactive paths (`--settings-schema`). A failed command
+/// caches an empty catalog so the tool is not re-run.
+pub fn cached() -> BoardCatalog { //>
+    if let Some(catalog) = loaded() {
+        return catalog;
+    }
+    let loaded = load();
+    CATALOG.lock().unwrap().get_or_insert(loaded).clone()
+} //<
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_listall_table() {
+        let catalog = BoardCatalog::parse(
+            "Board Name                      FQBN\n\
+             ESP32S3 Dev Module              esp32:esp32:esp32s3\n\
+             ESP32C3 Dev Module              esp32:esp32:esp32c3\n\
+             Arduino Uno                     arduino:avr:uno\n",
+        );
+        assert_eq!(catalog.names(), vec!["ESP32S3 Dev Module", "ESP32C3 Dev Module", "Arduino Uno"]);
+        assert_eq!(catalog.fqbn_for("arduino uno"), Some("arduino:avr:uno"));
+        assert_eq!(catalog.fqbn_for("Board Name"), None);
+    }
+}
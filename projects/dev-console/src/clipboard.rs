@@ -0,0 +1,38 @@
+// System clipboard access
+// Keeps one clipboard handle alive for the whole session and reports copies as dashboard toasts
+
+use crate::dashboard::DashboardState;
+
+/// Destination for copied text; the system clipboard in the app, a recorder in tests
+pub trait ClipboardSink {
+    fn set_text(&mut self, text: String) -> Result<(), String>;
+}
+
+/// The system clipboard, opened on first use
+///
+/// On X11/Wayland the copied text is only served while the handle is alive,
+/// so it is kept for the lifetime of the app instead of dropped after each copy.
+#[derive(Default)]
+pub struct SystemClipboard {
+    clipboard: Option<arboard::Clipboard>,
+}
+
+impl ClipboardSink for SystemClipboard {
+    fn set_text(&mut self, text: String) -> Result<(), String> { //>
+        if self.clipboard.is_none() {
+            self.clipboard = Some(arboard::Clipboard::new().map_err(|e| e.to_string())?);
+        }
+        let clipboard = self.clipboard.as_mut().expect("clipboard opened above");
+        clipboard.set_text(text).map_err(|e| e.to_string())
+    } //<
+}
+
+/// Copy `text` and queue a confirmation toast, or an error toast when no clipboard is available
+///
+/// The error case happens under some SSH/headless sessions.
+pub fn copy_with_notice(state: &mut DashboardState, clipboard: &mut dyn ClipboardSink, text: String, what: &str) {
+    match clipboard.set_text(text) {
+        Ok(()) => state.notice = Some(format!("{} copied to clipboard", what)),
+        Err(e) => state.error_notice = Some(format!("Clipboard unavailable: {}", e)),
+    }
+}
@@ -1,12 +1,50 @@
 // Command execution helper - eliminates duplication in command handlers
 
-use crate::dashboard::DashboardState;
+use crate::config::CompileBackend;
+use crate::dashboard::{DashboardState, lock_state};
 use crate::process_manager::ProcessManager;
 use crate::settings::Settings;
-use crate::commands::{execute_upload_rust, execute_progress_rust, execute_monitor_serial_rust, execute_monitor_mqtt_rust};
+use crate::commands::{execute_upload_rust, execute_progress_rust, execute_monitor_serial_rust, execute_monitor_mqtt_rust, execute_test_connection_rust};
+use crate::commands::pmake::execute_pmake_command;
 use std::sync::{Arc, Mutex};
 use std::thread;
 
+/// Progress bar label for the compile half of "Build"
+const COMPILE_PHASE: &str = "Compiling";
+
+/// Progress bar label for the upload half of "Build"
+const UPLOAD_PHASE: &str = "Flashing";
+
+/// Executor function a compile backend routes to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompileExecutor {
+    /// `execute_pmake_command` with the "Compile" target
+    Pmake,
+    /// `execute_progress_rust`
+    ProgressRust,
+}
+
+/// Pick the executor for the configured compile backend
+pub fn compile_executor(backend: CompileBackend) -> CompileExecutor {
+    match backend {
+        CompileBackend::Python => CompileExecutor::Pmake,
+        CompileBackend::Native => CompileExecutor::ProgressRust,
+    }
+}
+
+/// Run the compile step on the configured backend; true if it succeeded
+fn run_compile(
+    backend: CompileBackend,
+    dashboard: Arc<Mutex<DashboardState>>,
+    settings: Settings,
+    process_manager: Arc<ProcessManager>,
+) -> bool { //>
+    match compile_executor(backend) {
+        CompileExecutor::Pmake => execute_pmake_command(dashboard, settings, "Compile".to_string(), process_manager),
+        CompileExecutor::ProgressRust => execute_progress_rust(dashboard, settings, process_manager),
+    }
+} //<
+
 /// Execute a command with common setup
 /// This eliminates the duplication across all command handlers
 pub fn execute_command(
@@ -16,15 +54,23 @@ pub fn execute_command(
     process_manager: Arc<ProcessManager>,
 ) {
     // Common setup for all commands
-    {
-        let mut state = dashboard.lock().unwrap();
+    let compile_backend = {
+        let mut state = lock_state(&dashboard);
         state.is_running = true;
+        state.cancel_requested = false;
+        state.running_child = None;
+        state.command_started = Some(std::time::Instant::now());
+        state.last_elapsed = None;
         state.progress_percent = 0.0;
         state.set_progress_stage("Initializing");
         state.set_current_file("");
+        state.progress_phase = std::sync::Arc::from("");
         state.set_status_text(&format!("Running: {}", command));
         state.add_output_line(format!("> {}", command));
-    }
+        state.last_command = Some(command.to_string());
+        state.command_scroll.insert(command.to_string(), 0);
+        state.compile_backend
+    };
     
     // Spawn command-specific thread
     let dashboard_clone = dashboard.clone();
@@ -33,7 +79,16 @@ pub fn execute_command(
     match command {
         "Compile" => {
             thread::spawn(move || {
-                execute_progress_rust(dashboard_clone, settings, process_manager_clone);
+                run_compile(compile_backend, dashboard_clone, settings, process_manager_clone);
+            });
+        }
+        "Build" => {
+            thread::spawn(move || {
+                lock_state(&dashboard_clone).begin_phase(COMPILE_PHASE);
+                if run_compile(compile_backend, dashboard_clone.clone(), settings.clone(), process_manager_clone.clone()) {
+                    lock_state(&dashboard_clone).begin_phase(UPLOAD_PHASE);
+                    execute_upload_rust(dashboard_clone, settings, process_manager_clone);
+                }
             });
         }
         "Upload" => {
@@ -41,6 +96,11 @@ pub fn execute_command(
                 execute_upload_rust(dashboard_clone, settings, process_manager_clone);
             });
         }
+        "Test" => {
+            thread::spawn(move || {
+                execute_test_connection_rust(dashboard_clone, settings, process_manager_clone);
+            });
+        }
         "Monitor-Serial" => {
             thread::spawn(move || {
                 execute_monitor_serial_rust(dashboard_clone, settings, process_manager_clone);
@@ -53,7 +113,7 @@ pub fn execute_command(
         }
         _ => {
             // For other commands, use regular status
-            let mut state = dashboard.lock().unwrap();
+            let mut state = lock_state(&dashboard);
             state.is_running = false;
             state.set_progress_stage("");
             state.set_status_text(&format!("Running: {}", command));
@@ -62,3 +122,18 @@ pub fn execute_command(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compile_backend_selects_executor() {
+        assert_eq!(compile_executor(CompileBackend::Native), CompileExecutor::ProgressRust);
+        assert_eq!(compile_executor(CompileBackend::Python), CompileExecutor::Pmake);
+        assert_eq!(compile_executor(CompileBackend::default()), CompileExecutor::ProgressRust);
+
+        let backend: CompileBackend = serde_yaml::from_str("python").unwrap();
+        assert_eq!(compile_executor(backend), CompileExecutor::Pmake);
+    }
+}
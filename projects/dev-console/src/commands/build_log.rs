@@ -0,0 +1,109 @@
+// Per-run build log
+// Tees raw command output to `<sketch_directory>/build/<command>-<timestamp>.log` when `create_log` is set
+
+use crate::dashboard::{DashboardState, lock_state};
+use crate::settings::Settings;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Raw output log for one command run, shared by the stdout and stderr readers
+///
+/// A disabled log (create_log off, or the file could not be created) ignores writes.
+#[derive(Debug, Clone, Default)]
+pub struct BuildLog {
+    path: Option<PathBuf>,
+    writer: Arc<Mutex<Option<BufWriter<File>>>>,
+}
+
+impl BuildLog {
+    /// Log file for `command` under the sketch's build folder, e.g. `build/compile-1718000000.log`
+    pub fn log_path(sketch_directory: &Path, command: &str, timestamp: u64) -> PathBuf {
+        sketch_directory.join("build").join(format!("{}-{}.log", command.to_lowercase(), timestamp))
+    }
+
+    /// Create the log file (and its folder) at `path`
+    pub fn create(path: PathBuf) -> io::Result<Self> { //>
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = File::create(&path)?;
+        Ok(Self { path: Some(path), writer: Arc::new(Mutex::new(Some(BufWriter::new(file)))) })
+    } //<
+
+    /// Open the log for a run of `command` if `settings.create_log` is on
+    ///
+    /// A file that cannot be created is reported once in the output; the command runs without a log.
+    pub fn start(settings: &Settings, command: &str, dashboard: &Arc<Mutex<DashboardState>>) -> Self { //>
+        if !settings.create_log {
+            return Self::default();
+        }
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        let path = Self::log_path(Path::new(&settings.sketch_directory), command, timestamp);
+        match Self::create(path.clone()) {
+            Ok(log) => log,
+            Err(e) => {
+                lock_state(dashboard).add_output_line(format!(
+                    "[WARNING] Could not create build log {}: {} (continuing without a log)",
+                    path.display(), e
+                ));
+                Self::default()
+            }
+        }
+    } //<
+
+    /// Append one raw output line (ANSI codes included)
+    pub fn write_line(&self, line: &str) {
+        if let Some(writer) = self.writer.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+            let _ = writeln!(writer, "{}", line);
+        }
+    }
+
+    /// Flush and close the file, then note its location in the output
+    ///
+    /// Lines written after this are dropped.
+    pub fn finish(&self, state: &mut DashboardState) { //>
+        let Some(mut writer) = self.writer.lock().unwrap_or_else(|e| e.into_inner()).take() else { return };
+        let Some(path) = &self.path else { return };
+        match writer.flush() {
+            Ok(()) => state.add_output_line(format!("Build log written to {}", path.display())),
+            Err(e) => state.add_output_line(format!("[WARNING] Failed to write build log {}: {}", path.display(), e)),
+        }
+    } //<
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_log_keeps_raw_lines_and_reports_path() {
+        let sketch_dir = std::env::temp_dir().join("dev-console-build-log-test");
+        let _ = std::fs::remove_dir_all(&sketch_dir);
+        let path = BuildLog::log_path(&sketch_dir, "Compile", 1718000000);
+        assert!(path.ends_with("build/compile-1718000000.log"));
+
+        let log = BuildLog::create(path.clone()).unwrap();
+        let stderr_log = log.clone();
+        log.write_line("\x1b[32mCompiling sketch...\x1b[0m");
+        stderr_log.write_line("warning: unused variable");
+
+        let mut state = DashboardState::new();
+        log.finish(&mut state);
+        stderr_log.write_line("after finish");
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "\x1b[32mCompiling sketch...\x1b[0m\nwarning: unused variable\n"
+        );
+        assert_eq!(state.output_lines.last().unwrap(), &format!("Build log written to {}", path.display()));
+
+        // Disabled logs ignore writes and add nothing to the output
+        let disabled = BuildLog::default();
+        disabled.write_line("ignored");
+        disabled.finish(&mut state);
+        assert_eq!(state.output_lines.len(), 1);
+        let _ = std::fs::remove_dir_all(&sketch_dir);
+    }
+}
@@ -15,6 +15,12 @@ lazy_static! {
     static ref RE_COMPILED_FILE: Regex = Regex::new(
         r"(?i)\.(cpp|c|ino|S)\.o|gcc-ar|compiled\s+[^\s]+\.(cpp|c|ino|S)|using previously compiled file"
     ).unwrap();
+    static ref RE_NINJA_STEP: Regex = Regex::new(
+        r"^\[(\d+)/(\d+)\]\s+(.*)$"
+    ).unwrap();
+    static ref RE_NINJA_OBJECT: Regex = Regex::new(
+        r"(?i)building (?:c|cxx|asm) object\s+(\S+?)\.obj"
+    ).unwrap();
 }
 
 /// Parse a line and detect compilation stage changes
@@ -120,6 +126,131 @@ pub fn parse_compilation_info(line: &str, compile_state: &mut CompileState) {
         if !compile_state.compiled_lines_seen.contains(trimmed) {
             compile_state.compiled_lines_seen.insert(trimmed.to_string());
             compile_state.files_compiled = compile_state.compiled_lines_seen.len();
+            if line_lower.contains("using previously compiled file") {
+                compile_state.files_reused += 1;
+            }
+        }
+    }
+}
+
+/// Parse an ESP-IDF (`idf.py build`) line, tracking ninja `[current/total]` steps
+/// Returns (stage_changed, should_continue) like `detect_stage_change`
+pub fn detect_idf_stage_change(line: &str, compile_state: &mut CompileState, current_progress: f64) -> (bool, bool) { //>
+    use crate::commands::compile_state::CompileStage;
+
+    let cleaned = remove_ansi_escapes(line);
+    let trimmed = cleaned.trim();
+    let line_lower = trimmed.to_lowercase();
+
+    if trimmed.is_empty() {
+        return (false, true);
+    }
+    if line_lower.contains("error:") || line_lower.starts_with("failed:") {
+        return (false, false);
+    }
+
+    let after_link = compile_state.stage.rank() >= CompileStage::Linking.rank();
+    let next_stage = if let Some(captures) = RE_NINJA_STEP.captures(trimmed) {
+        let current: usize = captures[1].parse().unwrap_or(0);
+        let total: usize = captures[2].parse().unwrap_or(0);
+        // Sub-builds (bootloader) run their own smaller ninja; only the outer build counts
+        if total < compile_state.total_files {
+            return (false, true);
+        }
+        compile_state.files_compiled = current;
+        compile_state.total_files = total;
+        let action = &captures[3];
+        let action_lower = action.to_lowercase();
+        if let Some(object) = RE_NINJA_OBJECT.captures(action) {
+            compile_state.current_file = object[1].rsplit('/').next().unwrap_or(&object[1]).to_string();
+        }
+        if action_lower.starts_with("linking") {
+            Some(CompileStage::Linking)
+        } else if after_link && (action_lower.contains("esptool.py") || action_lower.starts_with("generating binary image")) {
+            Some(CompileStage::Generating)
+        } else {
+            Some(CompileStage::Compiling)
+        }
+    } else if after_link && (line_lower.contains("esptool.py") && line_lower.contains("elf2image")
+        || line_lower.starts_with("creating esp32")) {
+        Some(CompileStage::Generating)
+    } else if line_lower.starts_with("project build complete") {
+        Some(CompileStage::Complete)
+    } else {
+        None
+    };
+
+    // Ninja interleaves steps, so a stage only ever moves forward
+    let stage_changed = match next_stage {
+        Some(stage) if stage.rank() > compile_state.stage.rank() => {
+            compile_state.previous_stage_progress = current_progress;
+            compile_state.stage = stage;
+            match stage {
+                CompileStage::Compiling => compile_state.compile_stage_start = Some(std::time::Instant::now()),
+                CompileStage::Linking => compile_state.link_stage_start = Some(std::time::Instant::now()),
+                CompileStage::Generating => compile_state.generate_stage_start = Some(std::time::Instant::now()),
+                CompileStage::Initializing | CompileStage::Complete => {}
+            }
+            if stage != CompileStage::Compiling {
+                compile_state.current_file.clear();
+            }
+            true
         }
+        _ => false,
+    };
+
+    (stage_changed, true)
+} //<
+
+/// Build progress (0-100) from the ninja `[current/total]` ratio
+pub fn idf_progress_percent(compile_state: &CompileState) -> f64 {
+    use crate::commands::compile_state::CompileStage;
+
+    match compile_state.stage {
+        CompileStage::Complete => 100.0,
+        _ if compile_state.total_files == 0 => 0.0,
+        _ => (compile_state.files_compiled as f64 / compile_state.total_files as f64 * 100.0).min(99.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::compile_state::CompileStage;
+
+    #[test]
+    fn test_idf_ninja_steps_drive_stage_and_percent() {
+        let mut state = CompileState::new();
+
+        let (changed, _) = detect_idf_stage_change(
+            "[12/400] Building CXX object esp-idf/main/CMakeFiles/__idf_main.dir/app_main.cpp.obj",
+            &mut state,
+            0.0,
+        );
+        assert!(changed);
+        assert_eq!(state.stage, CompileStage::Compiling);
+        assert_eq!(state.current_file, "app_main.cpp");
+        assert_eq!(idf_progress_percent(&state), 3.0);
+
+        // The bootloader sub-build's own step counter is ignored
+        let (changed, _) = detect_idf_stage_change("[1/1] Linking C executable bootloader.elf", &mut state, 3.0);
+        assert!(!changed);
+        assert_eq!(state.total_files, 400);
+
+        detect_idf_stage_change("[399/400] Linking CXX executable blink.elf", &mut state, 3.0);
+        assert_eq!(state.stage, CompileStage::Linking);
+
+        // A late compile step does not move the stage backwards
+        detect_idf_stage_change("[398/400] Building C object esp-idf/log/CMakeFiles/log.c.obj", &mut state, 90.0);
+        assert_eq!(state.stage, CompileStage::Linking);
+
+        detect_idf_stage_change("[400/400] Generating binary image from built executable", &mut state, 99.0);
+        assert_eq!(state.stage, CompileStage::Generating);
+        detect_idf_stage_change("esptool.py v4.7.0", &mut state, 99.0);
+        assert_eq!(state.stage, CompileStage::Generating);
+
+        detect_idf_stage_change("Project build complete. To flash, run:", &mut state, 99.0);
+        assert_eq!(state.stage, CompileStage::Complete);
+        assert_eq!(idf_progress_percent(&state), 100.0);
     }
 }
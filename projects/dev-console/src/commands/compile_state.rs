@@ -12,6 +12,19 @@ pub enum CompileStage {
     Complete,
 }
 
+impl CompileStage {
+    /// Position in the normal compile sequence (higher is later)
+    pub fn rank(self) -> u8 {
+        match self {
+            CompileStage::Initializing => 0,
+            CompileStage::Compiling => 1,
+            CompileStage::Linking => 2,
+            CompileStage::Generating => 3,
+            CompileStage::Complete => 4,
+        }
+    }
+}
+
 /// Compilation state tracking structure
 pub struct CompileState {
     pub stage: CompileStage,
@@ -20,6 +33,7 @@ pub struct CompileState {
     pub total_files: usize,
     pub compile_lines_seen: std::collections::HashSet<String>,
     pub compiled_lines_seen: std::collections::HashSet<String>,
+    pub files_reused: usize, // "Using previously compiled file" hits from the build cache
     pub start_time: Instant,
     pub compile_stage_start: Option<Instant>,
     pub link_stage_start: Option<Instant>,
@@ -37,6 +51,7 @@ impl CompileState {
             total_files: 0,
             compile_lines_seen: std::collections::HashSet::new(),
             compiled_lines_seen: std::collections::HashSet::new(),
+            files_reused: 0,
             start_time: Instant::now(),
             compile_stage_start: None,
             link_stage_start: None,
@@ -46,6 +61,17 @@ impl CompileState {
         }
     }
     
+    /// Undo a backward stage transition made since `previous`
+    /// Returns the warning to log; the stage is restored so the display never regresses
+    pub fn check_stage_regression(&mut self, previous: CompileStage) -> Option<String> {
+        if self.stage.rank() >= previous.rank() {
+            return None;
+        }
+        let warning = format!("[warn] stage regressed {:?}→{:?}", previous, self.stage);
+        self.stage = previous;
+        Some(warning)
+    }
+    
     /// Calculate progress percentage based on current stage and state
     pub fn calculate_progress(&self) -> f64 {
         match self.stage {
@@ -98,3 +124,55 @@ impl CompileState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::compile_parser::{detect_stage_change, parse_compilation_info};
+    use crate::dashboard::DashboardState;
+
+    #[test]
+    fn test_backward_stage_transition_logged_not_displayed() {
+        let mut state = CompileState::new();
+        state.stage = CompileStage::Generating;
+        state.link_stage_start = Some(Instant::now());
+
+        // A stray "detecting libraries" line would flip the parser back to Compiling
+        let previous = state.stage;
+        detect_stage_change("Detecting libraries used...", &mut state, 90.0);
+        assert_eq!(state.stage, CompileStage::Compiling);
+
+        let warning = state.check_stage_regression(previous);
+        assert_eq!(warning.as_deref(), Some("[warn] stage regressed Generating→Compiling"));
+        assert_eq!(state.stage, CompileStage::Generating);
+
+        // Forward transitions pass through untouched
+        state.stage = CompileStage::Complete;
+        assert!(state.check_stage_regression(CompileStage::Generating).is_none());
+        assert_eq!(state.stage, CompileStage::Complete);
+    }
+
+    #[test]
+    fn test_cache_hits_and_fresh_compiles_counted() {
+        let mut state = CompileState::new();
+        let lines = [
+            "xtensa-esp32s3-elf-g++ -c -Os /build/sketch/main.ino.cpp -o /build/sketch/main.ino.cpp.o",
+            "xtensa-esp32s3-elf-g++ -c -Os /build/sketch/leds.cpp -o /build/sketch/leds.cpp.o",
+            "Using previously compiled file: /build/core/esp32-hal-gpio.c.o",
+            "Using previously compiled file: /build/core/main.cpp.o",
+            "Using previously compiled file: /build/core/main.cpp.o", // repeated line counts once
+            "Using previously compiled file: /build/libraries/WiFi/WiFi.cpp.o",
+        ];
+        for line in lines {
+            parse_compilation_info(line, &mut state);
+        }
+        assert_eq!(state.total_files, 2);
+        assert_eq!(state.files_reused, 3);
+
+        let mut dashboard = DashboardState::new();
+        assert_eq!(dashboard.compile_cache_summary(), None);
+        dashboard.files_compiled_fresh = state.total_files;
+        dashboard.files_reused = state.files_reused;
+        assert_eq!(dashboard.compile_cache_summary().as_deref(), Some("Compiled 2 files, reused 3"));
+    }
+}
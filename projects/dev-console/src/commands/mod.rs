@@ -1,6 +1,7 @@
 // Commands module - command execution functions
 
 pub mod utils;
+pub mod build_log;
 pub mod pmake;
 pub mod upload;
 pub mod progress_rust;
@@ -10,8 +11,10 @@ pub mod compile_parser;
 pub mod process_handler;
 pub mod monitor_serial;
 pub mod monitor_mqtt;
+pub mod test_connection;
 
 pub use upload::execute_upload_rust;
 pub use progress_rust::execute_progress_rust;
 pub use monitor_serial::execute_monitor_serial_rust;
-pub use monitor_mqtt::execute_monitor_mqtt_rust;
\ No newline at end of file
+pub use monitor_mqtt::execute_monitor_mqtt_rust;
+pub use test_connection::execute_test_connection_rust;
\ No newline at end of file
@@ -1,6 +1,6 @@
 // Monitor-MQTT command execution (Rust-based, direct MQTT connection)
 
-use crate::dashboard::DashboardState;
+use crate::dashboard::{DashboardState, lock_state};
 use crate::settings::Settings;
 use crate::process_manager::ProcessManager;
 use std::sync::{Arc, Mutex};
@@ -24,9 +24,9 @@ pub fn execute_monitor_mqtt_rust(
     
     // Clear status and output panels before starting monitor
     {
-        let mut state = dashboard.lock().unwrap();
+        let mut state = lock_state(&dashboard);
         // Clear output lines
-        state.output_lines.clear();
+        state.clear_output();
         // Reset progress
         state.progress_percent = 0.0;
         state.set_progress_stage("");
@@ -42,7 +42,7 @@ pub fn execute_monitor_mqtt_rust(
     
     // Add initial message
     {
-        let mut state = dashboard.lock().unwrap();
+        let mut state = lock_state(&dashboard);
         state.add_output_line(format!("Connecting to MQTT broker at {}:{}...", mqtt_host, mqtt_port));
         state.add_output_line(format!("Subscribing to topic: {}", mqtt_topic));
         state.set_progress_stage("Connecting");
@@ -52,7 +52,7 @@ pub fn execute_monitor_mqtt_rust(
     let mut stream = match TcpStream::connect(format!("{}:{}", mqtt_host, mqtt_port)) {
         Ok(stream) => stream,
         Err(e) => {
-            let mut state = dashboard.lock().unwrap();
+            let mut state = lock_state(&dashboard);
             state.is_running = false;
             state.set_status_text(&format!("Error: Failed to connect to MQTT broker: {}", e));
             state.add_output_line(format!("Error: Failed to connect to MQTT broker: {}", e));
@@ -77,7 +77,7 @@ pub fn execute_monitor_mqtt_rust(
     
     // Log authentication status
     {
-        let mut state = dashboard.lock().unwrap();
+        let mut state = lock_state(&dashboard);
         state.add_output_line(format!("Using MQTT authentication (username: {})", mqtt_username));
     }
     let connect_packet = Packet::Connect(connect);
@@ -86,7 +86,7 @@ pub fn execute_monitor_mqtt_rust(
     match encode_slice(&connect_packet, &mut buf) {
         Ok(len) => {
             if let Err(e) = stream.write_all(&buf[..len]) {
-                let mut state = dashboard.lock().unwrap();
+                let mut state = lock_state(&dashboard);
                 state.is_running = false;
                 state.set_status_text(&format!("Error: Failed to send CONNECT: {}", e));
                 state.add_output_line(format!("Error: Failed to send CONNECT: {}", e));
@@ -94,7 +94,7 @@ pub fn execute_monitor_mqtt_rust(
             }
         }
         Err(e) => {
-            let mut state = dashboard.lock().unwrap();
+            let mut state = lock_state(&dashboard);
             state.is_running = false;
             state.set_status_text(&format!("Error: Failed to encode CONNECT: {}", e));
             state.add_output_line(format!("Error: Failed to encode CONNECT: {}", e));
@@ -108,26 +108,26 @@ pub fn execute_monitor_mqtt_rust(
         Ok(n) if n > 0 => {
             match decode_slice(&read_buf[..n]) {
                 Ok(Some(Packet::Connack { .. })) => {
-                    let mut state = dashboard.lock().unwrap();
+                    let mut state = lock_state(&dashboard);
                     state.set_progress_stage("Monitoring");
                     state.add_output_line(format!("✅ Connected to MQTT broker at {}:{}", mqtt_host, mqtt_port));
                 }
                 Ok(Some(_)) => {
-                    let mut state = dashboard.lock().unwrap();
+                    let mut state = lock_state(&dashboard);
                     state.is_running = false;
                     state.set_status_text("Error: Unexpected packet type in response");
                     state.add_output_line("Error: Unexpected packet type in response".to_string());
                     return;
                 }
                 Ok(None) => {
-                    let mut state = dashboard.lock().unwrap();
+                    let mut state = lock_state(&dashboard);
                     state.is_running = false;
                     state.set_status_text("Error: Incomplete CONNACK packet");
                     state.add_output_line("Error: Incomplete CONNACK packet".to_string());
                     return;
                 }
                 Err(e) => {
-                    let mut state = dashboard.lock().unwrap();
+                    let mut state = lock_state(&dashboard);
                     state.is_running = false;
                     state.set_status_text(&format!("Error: Failed to decode CONNACK: {}", e));
                     state.add_output_line(format!("Error: Failed to decode CONNACK: {}", e));
@@ -136,14 +136,14 @@ pub fn execute_monitor_mqtt_rust(
             }
         }
         Ok(_) => {
-            let mut state = dashboard.lock().unwrap();
+            let mut state = lock_state(&dashboard);
             state.is_running = false;
             state.set_status_text("Error: No response from broker");
             state.add_output_line("Error: No response from broker".to_string());
             return;
         }
         Err(e) => {
-            let mut state = dashboard.lock().unwrap();
+            let mut state = lock_state(&dashboard);
             state.is_running = false;
             state.set_status_text(&format!("Error: Failed to read CONNACK: {}", e));
             state.add_output_line(format!("Error: Failed to read CONNACK: {}", e));
@@ -164,7 +164,7 @@ pub fn execute_monitor_mqtt_rust(
     match encode_slice(&subscribe_packet, &mut buf) {
         Ok(len) => {
             if let Err(e) = stream.write_all(&buf[..len]) {
-                let mut state = dashboard.lock().unwrap();
+                let mut state = lock_state(&dashboard);
                 state.is_running = false;
                 state.set_status_text(&format!("Error: Failed to send SUBSCRIBE: {}", e));
                 state.add_output_line(format!("Error: Failed to send SUBSCRIBE: {}", e));
@@ -172,7 +172,7 @@ pub fn execute_monitor_mqtt_rust(
             }
         }
         Err(e) => {
-            let mut state = dashboard.lock().unwrap();
+            let mut state = lock_state(&dashboard);
             state.is_running = false;
             state.set_status_text(&format!("Error: Failed to encode SUBSCRIBE: {}", e));
             state.add_output_line(format!("Error: Failed to encode SUBSCRIBE: {}", e));
@@ -185,7 +185,7 @@ pub fn execute_monitor_mqtt_rust(
     match stream.read(&mut read_buf) {
         Ok(0) => {
             // Connection closed by broker
-            let mut state = dashboard.lock().unwrap();
+            let mut state = lock_state(&dashboard);
             state.is_running = false;
             state.set_status_text("MQTT broker closed connection during subscription");
             state.add_output_line("MQTT broker closed connection during subscription".to_string());
@@ -195,26 +195,26 @@ pub fn execute_monitor_mqtt_rust(
             // Received data - try to decode packet
             match decode_slice(&read_buf[..n]) {
                 Ok(Some(Packet::Suback { .. })) => {
-                    let mut state = dashboard.lock().unwrap();
+                    let mut state = lock_state(&dashboard);
                     state.add_output_line(format!("✅ Subscribed to topic: {}", mqtt_topic));
                 }
                 Ok(Some(Packet::Pingresp)) => {
                     // Ping response - unexpected here but handle gracefully
-                    let mut state = dashboard.lock().unwrap();
+                    let mut state = lock_state(&dashboard);
                     state.add_output_line("Warning: Received PINGRESP instead of SUBACK".to_string());
                 }
                 Ok(Some(_)) => {
                     // Other packet types - log and continue
-                    let mut state = dashboard.lock().unwrap();
+                    let mut state = lock_state(&dashboard);
                     state.add_output_line("Warning: Received unexpected packet type, but continuing".to_string());
                 }
                 Ok(None) => {
                     // Incomplete packet - log and continue
-                    let mut state = dashboard.lock().unwrap();
+                    let mut state = lock_state(&dashboard);
                     state.add_output_line("Warning: Incomplete SUBACK packet, but continuing".to_string());
                 }
                 Err(e) => {
-                    let mut state = dashboard.lock().unwrap();
+                    let mut state = lock_state(&dashboard);
                     state.add_output_line(format!("Warning: Failed to decode SUBACK: {} - continuing anyway", e));
                 }
             }
@@ -224,7 +224,7 @@ pub fn execute_monitor_mqtt_rust(
             if e.kind() == std::io::ErrorKind::ConnectionReset || 
                e.kind() == std::io::ErrorKind::BrokenPipe ||
                e.raw_os_error() == Some(10053) {
-                let mut state = dashboard.lock().unwrap();
+                let mut state = lock_state(&dashboard);
                 state.is_running = false;
                 state.set_status_text("MQTT broker closed connection during subscription");
                 state.add_output_line("MQTT broker closed connection during subscription".to_string());
@@ -235,7 +235,7 @@ pub fn execute_monitor_mqtt_rust(
                 stream.set_read_timeout(Some(Duration::from_millis(500))).ok();
                 match stream.read(&mut read_buf) {
                     Ok(0) => {
-                        let mut state = dashboard.lock().unwrap();
+                        let mut state = lock_state(&dashboard);
                         state.is_running = false;
                         state.set_status_text("MQTT broker closed connection during subscription");
                         state.add_output_line("MQTT broker closed connection during subscription".to_string());
@@ -244,23 +244,23 @@ pub fn execute_monitor_mqtt_rust(
                     Ok(n) => {
                         match decode_slice(&read_buf[..n]) {
                             Ok(Some(Packet::Suback { .. })) => {
-                                let mut state = dashboard.lock().unwrap();
+                                let mut state = lock_state(&dashboard);
                                 state.add_output_line(format!("✅ Subscribed to topic: {}", mqtt_topic));
                             }
                             _ => {
-                                let mut state = dashboard.lock().unwrap();
+                                let mut state = lock_state(&dashboard);
                                 state.add_output_line("Warning: No SUBACK received, but continuing anyway".to_string());
                             }
                         }
                     }
                     Err(_) => {
-                        let mut state = dashboard.lock().unwrap();
+                        let mut state = lock_state(&dashboard);
                         state.add_output_line("Warning: SUBACK timeout, but continuing anyway - subscription may have succeeded".to_string());
                     }
                 }
             } else {
                 // Other error - log and continue
-                let mut state = dashboard.lock().unwrap();
+                let mut state = lock_state(&dashboard);
                 state.add_output_line(format!("Warning: Error reading SUBACK: {} - continuing anyway", e));
             }
         }
@@ -276,7 +276,7 @@ pub fn execute_monitor_mqtt_rust(
     
     // Enable TCP nodelay to reduce latency
     if let Err(e) = stream.set_nodelay(true) {
-        let mut state = dashboard.lock().unwrap();
+        let mut state = lock_state(&dashboard);
         state.add_output_line(format!("Warning: Failed to set TCP_NODELAY: {}", e));
     }
     
@@ -303,13 +303,13 @@ pub fn execute_monitor_mqtt_rust(
                         last_activity_time = std::time::Instant::now();
                     }
                     Err(e) => {
-                        let mut state = dashboard.lock().unwrap();
+                        let mut state = lock_state(&dashboard);
                         state.add_output_line(format!("Warning: Failed to send initial PINGREQ: {}", e));
                     }
                 }
             }
             Err(e) => {
-                let mut state = dashboard.lock().unwrap();
+                let mut state = lock_state(&dashboard);
                 state.add_output_line(format!("Warning: Failed to encode initial PINGREQ: {}", e));
             }
         }
@@ -335,7 +335,7 @@ pub fn execute_monitor_mqtt_rust(
             match encode_slice(&pingreq_packet, &mut ping_buf) {
                 Ok(len) => {
                     if let Err(e) = stream.write_all(&ping_buf[..len]) {
-                        let mut state = dashboard.lock().unwrap();
+                        let mut state = lock_state(&dashboard);
                         state.is_running = false;
                         state.set_status_text(&format!("Error: Failed to send PINGREQ: {}", e));
                         state.add_output_line(format!("Error: Failed to send PINGREQ: {}", e));
@@ -345,7 +345,7 @@ pub fn execute_monitor_mqtt_rust(
                     last_activity_time = now; // Sending ping counts as activity
                 }
                 Err(e) => {
-                    let mut state = dashboard.lock().unwrap();
+                    let mut state = lock_state(&dashboard);
                     state.is_running = false;
                     state.set_status_text(&format!("Error: Failed to encode PINGREQ: {}", e));
                     state.add_output_line(format!("Error: Failed to encode PINGREQ: {}", e));
@@ -356,7 +356,7 @@ pub fn execute_monitor_mqtt_rust(
         
         // Check if connection is dead (no activity for full keep-alive period)
         if now.duration_since(last_activity_time) >= Duration::from_secs(keep_alive_seconds) {
-            let mut state = dashboard.lock().unwrap();
+            let mut state = lock_state(&dashboard);
             state.is_running = false;
             state.set_status_text("MQTT connection timeout - no activity");
             state.add_output_line("MQTT connection timeout - no activity".to_string());
@@ -367,7 +367,7 @@ pub fn execute_monitor_mqtt_rust(
         if read_pos >= packet_buf.len() {
             // Buffer is full - this shouldn't happen with normal MQTT packets
             // Reset buffer and log warning
-            let mut state = dashboard.lock().unwrap();
+            let mut state = lock_state(&dashboard);
             state.add_output_line("Warning: MQTT packet buffer overflow - resetting".to_string());
             read_pos = 0;
         }
@@ -376,7 +376,7 @@ pub fn execute_monitor_mqtt_rust(
         match stream.read(&mut packet_buf[read_pos..]) {
             Ok(0) => {
                 // Connection closed gracefully by broker
-                let mut state = dashboard.lock().unwrap();
+                let mut state = lock_state(&dashboard);
                 state.is_running = false;
                 state.set_status_text("MQTT broker disconnected");
                 state.add_output_line("MQTT broker disconnected".to_string());
@@ -389,7 +389,7 @@ pub fn execute_monitor_mqtt_rust(
                 // Check for buffer overflow after reading
                 if read_pos > packet_buf.len() {
                     // This shouldn't happen, but handle it gracefully
-                    let mut state = dashboard.lock().unwrap();
+                    let mut state = lock_state(&dashboard);
                     state.add_output_line("Warning: MQTT packet buffer overflow - resetting".to_string());
                     read_pos = 0;
                     continue;
@@ -441,7 +441,7 @@ pub fn execute_monitor_mqtt_rust(
                         }
                         Err(_) => {
                             // Decode error - reset buffer to prevent infinite loop
-                            let mut state = dashboard.lock().unwrap();
+                            let mut state = lock_state(&dashboard);
                             state.add_output_line("Warning: MQTT packet decode error - resetting buffer".to_string());
                             read_pos = 0;
                             break;
@@ -454,7 +454,7 @@ pub fn execute_monitor_mqtt_rust(
                 // But check if we've been idle too long
                 let now = std::time::Instant::now();
                 if now.duration_since(last_activity_time) >= Duration::from_secs(keep_alive_seconds) {
-                    let mut state = dashboard.lock().unwrap();
+                    let mut state = lock_state(&dashboard);
                     state.is_running = false;
                     state.set_status_text("MQTT connection timeout - no activity");
                     state.add_output_line("MQTT connection timeout - no activity".to_string());
@@ -473,7 +473,7 @@ pub fn execute_monitor_mqtt_rust(
                     format!("MQTT read error: {}", e)
                 };
                 
-                let mut state = dashboard.lock().unwrap();
+                let mut state = lock_state(&dashboard);
                 state.is_running = false;
                 state.set_status_text(&error_msg);
                 state.add_output_line(error_msg.clone());
@@ -501,7 +501,8 @@ pub fn execute_monitor_mqtt_rust(
     
     // Flush any remaining pending lines before exiting
     if !pending_lines.is_empty() {
-        if let Ok(mut state) = dashboard.lock() {
+        {
+            let mut state = lock_state(&dashboard);
             for pending_line in pending_lines.drain(..) {
                 state.add_output_line(pending_line);
             }
@@ -510,7 +511,7 @@ pub fn execute_monitor_mqtt_rust(
     
     // Update state
     {
-        let mut state = dashboard.lock().unwrap();
+        let mut state = lock_state(&dashboard);
         state.is_running = false;
         state.set_status_text("MQTT monitor closed");
         state.add_output_line("MQTT monitor closed".to_string());
@@ -1,6 +1,6 @@
 // Monitor command execution (Rust-based, direct serial port connection)
 
-use crate::dashboard::DashboardState;
+use crate::dashboard::{DashboardState, lock_state};
 use crate::settings::Settings;
 use crate::process_manager::ProcessManager;
 use std::sync::{Arc, Mutex};
@@ -15,9 +15,9 @@ pub fn execute_monitor_serial_rust(
 ) {
     // Clear status and output panels before starting monitor
     {
-        let mut state = dashboard.lock().unwrap();
+        let mut state = lock_state(&dashboard);
         // Clear output lines
-        state.output_lines.clear();
+        state.clear_output();
         // Reset progress
         state.progress_percent = 0.0;
         state.set_progress_stage("");
@@ -33,7 +33,7 @@ pub fn execute_monitor_serial_rust(
     
     // Add initial message
     {
-        let mut state = dashboard.lock().unwrap();
+        let mut state = lock_state(&dashboard);
         state.add_output_line(format!("Opening serial monitor on {} at {} baud...", settings.port, settings.baudrate));
         state.add_output_line(format!("Connecting directly to serial port: {}", settings.port));
         state.set_progress_stage("Monitoring");
@@ -46,7 +46,7 @@ pub fn execute_monitor_serial_rust(
     {
         Ok(port) => {
             // Port opened successfully - add success message
-            let mut state = dashboard.lock().unwrap();
+            let mut state = lock_state(&dashboard);
             state.add_output_line(format!("✅ Serial monitor connected successfully on {} at {} baud", settings.port, settings.baudrate));
             state.add_output_line("Monitor is live - waiting for data...".to_string());
             state.add_output_line("".to_string());
@@ -60,7 +60,7 @@ pub fn execute_monitor_serial_rust(
             port
         },
         Err(e) => {
-            let mut state = dashboard.lock().unwrap();
+            let mut state = lock_state(&dashboard);
             state.is_running = false;
             let error_msg = format!("Error: Failed to open serial port {}: {}", settings.port, e);
             state.set_status_text(&error_msg);
@@ -188,7 +188,7 @@ pub fn execute_monitor_serial_rust(
             }
             Err(e) => {
                 // Error reading from port
-                let mut state = dashboard.lock().unwrap();
+                let mut state = lock_state(&dashboard);
                 state.is_running = false;
                 state.set_status_text(&format!("Error reading from serial port: {}", e));
                 state.add_output_line(format!("Error reading from serial port: {}", e));
@@ -199,7 +199,8 @@ pub fn execute_monitor_serial_rust(
     
     // Flush any remaining pending lines before exiting
     if !pending_lines.is_empty() || !line_buffer.trim().is_empty() {
-        if let Ok(mut state) = dashboard.lock() {
+        {
+            let mut state = lock_state(&dashboard);
             for pending_line in pending_lines.drain(..) {
                 state.add_output_line(pending_line);
             }
@@ -214,7 +215,7 @@ pub fn execute_monitor_serial_rust(
     drop(port); // Close the port
     
     {
-        let mut state = dashboard.lock().unwrap();
+        let mut state = lock_state(&dashboard);
         state.is_running = false;
         state.set_status_text("Monitor closed");
         state.add_output_line("Monitor closed".to_string());
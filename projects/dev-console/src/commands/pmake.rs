@@ -2,9 +2,11 @@
 
 // IMPORTS ------------------>> 
 
-use crate::dashboard::DashboardState;
+use crate::dashboard::{DashboardState, lock_state};
 use crate::settings::Settings;
-use crate::commands::utils::remove_ansi_escapes;
+use crate::commands::utils::{remove_ansi_escapes, failure_status, extract_progress};
+use crate::commands::build_log::BuildLog;
+use crate::commands::process_handler::wait_shared;
 use crate::process_manager::ProcessManager;
 use crate::path_utils::{find_workspace_root, find_pmake_script};
 use std::io::{BufRead, BufReader};
@@ -16,22 +18,24 @@ use std::thread;
 //--------------------------------------------------------<<
 
 /// Execute pmake command (Build, Compile, Upload) and capture output
-#[allow(dead_code)]
+///
+/// Returns true if the script exited successfully.
 pub fn execute_pmake_command(
     dashboard: Arc<Mutex<DashboardState>>,
     settings: Settings,
     command: String,
     process_manager: Arc<ProcessManager>,
-) {
+) -> bool {
     let sketch_dir = PathBuf::from(&settings.sketch_directory);
     
     let script_path = match find_pmake_script(&sketch_dir) {
         Some(path) => path,
         None => {
-            let mut state = dashboard.lock().unwrap();
+            let mut state = lock_state(&dashboard);
             state.set_status_text("Error: pmake.py not found");
             state.add_output_line("Error: Could not find pmake.py script".to_string());
-            return;
+            state.is_running = false;
+            return false;
         }
     };
     
@@ -42,10 +46,11 @@ pub fn execute_pmake_command(
         "Compile" => "compile",
         "Upload" => "upload",
         _ => {
-            let mut state = dashboard.lock().unwrap();
+            let mut state = lock_state(&dashboard);
             state.set_status_text(&format!("Error: Unknown command: {}", command));
-            state.output_lines.push(format!("Error: Unknown command: {}", command));
-            return;
+            state.add_output_line(format!("Error: Unknown command: {}", command));
+            state.is_running = false;
+            return false;
         }
     };
     
@@ -70,6 +75,9 @@ pub fn execute_pmake_command(
     cmd.stderr(Stdio::piped());
     cmd.env("PYTHONUNBUFFERED", "1");
     
+    // Raw output is teed to `build/<command>-<timestamp>.log` when create_log is on
+    let build_log = BuildLog::start(&settings, &command, &dashboard);
+    
     let mut child = match cmd.spawn() {
         Ok(child) => {
             // Register process with process manager for cleanup tracking
@@ -77,10 +85,11 @@ pub fn execute_pmake_command(
             child
         }
         Err(e) => {
-            let mut state = dashboard.lock().unwrap();
+            let mut state = lock_state(&dashboard);
             state.set_status_text(&format!("Error: {}", e));
-            state.output_lines.push(format!("Failed to execute command: {}", e));
-            return;
+            state.add_output_line(format!("Failed to execute command: {}", e));
+            state.is_running = false;
+            return false;
         }
     };
     
@@ -89,9 +98,12 @@ pub fn execute_pmake_command(
     
     let stdout = child.stdout.take();
     let stderr = child.stderr.take();
+    let child = Arc::new(Mutex::new(child));
+    lock_state(&dashboard).attach_child(child.clone());
     
     let dashboard_stderr = dashboard.clone();
-    if let Some(stderr) = stderr {
+    let stderr_log = build_log.clone();
+    let stderr_thread = stderr.map(|stderr| {
         let stderr_reader = BufReader::new(stderr);
         thread::spawn(move || {
             for line in stderr_reader.lines() {
@@ -99,6 +111,7 @@ pub fn execute_pmake_command(
                     Ok(l) => l,
                     Err(_) => break,
                 };
+                stderr_log.write_line(&line);
                 
                 let line_trimmed = line.trim();
                 if line_trimmed.is_empty() {
@@ -106,15 +119,15 @@ pub fn execute_pmake_command(
                 }
                 
                 {
-                    let mut state = dashboard_stderr.lock().unwrap();
-                    state.output_lines.push(format!("[stderr] {}", line));
+                    let mut state = lock_state(&dashboard_stderr);
+                    state.add_output_line(format!("[stderr] {}", line));
                     if state.output_lines.len() > 1 {
                         // Don't auto-scroll - let user control scrolling manually
                     }
                 }
             }
-        });
-    }
+        })
+    });
     
     if let Some(stdout) = stdout {
         let reader = BufReader::new(stdout);
@@ -124,14 +137,21 @@ pub fn execute_pmake_command(
                 Ok(l) => l,
                 Err(_) => break,
             };
+            build_log.write_line(&line);
             
+            // Escapes are stripped for parsing only; the raw line keeps colours and OSC 8 links
             let cleaned_line = remove_ansi_escapes(&line);
             let line_trimmed = cleaned_line.trim();
             
             if !line_trimmed.is_empty() {
                 {
-                    let mut state = dashboard.lock().unwrap();
-                    state.output_lines.push(cleaned_line.clone());
+                    let mut state = lock_state(&dashboard);
+                    state.add_output_line(line.trim_end().to_string());
+                    // "NN%" from arduino-cli/esptool, or "[current/total]" from ninja and PlatformIO;
+                    // the bar never moves backwards
+                    if let Some(percent) = extract_progress(line_trimmed) {
+                        state.progress_percent = percent.max(state.progress_percent);
+                    }
                     if state.output_lines.len() > 1 {
                         // Don't auto-scroll - let user control scrolling manually
                     }
@@ -140,27 +160,41 @@ pub fn execute_pmake_command(
         }
     }
     
-    let exit_status = child.wait();
+    let exit_status = wait_shared(&child);
     
     // Unregister process from process manager (completed normally)
     process_manager.unregister(pid);
     
-    {
-        let mut state = dashboard.lock().unwrap();
-        match exit_status {
-            Ok(status) => {
-                if status.success() {
-                    state.set_status_text(&format!("{} completed successfully", command));
-                    state.output_lines.push(format!("{} completed successfully", command));
-                } else {
-                    state.set_status_text(&format!("{} failed with exit code: {:?}", command, status.code()));
-                    state.output_lines.push(format!("{} failed with exit code: {:?}", command, status.code()));
-                }
-            }
-            Err(e) => {
-                state.set_status_text(&format!("Command execution error: {}", e));
-                state.output_lines.push(format!("Command execution error: {}", e));
+    // stderr hits EOF once the process exits; wait for it so the log is complete
+    if let Some(handle) = stderr_thread {
+        let _ = handle.join();
+    }
+    
+    let mut state = lock_state(&dashboard);
+    build_log.finish(&mut state);
+    if state.detach_child() {
+        return false;
+    }
+    state.is_running = false;
+    match exit_status {
+        Ok(status) => {
+            if status.success() {
+                state.progress_percent = 100.0;
+                state.set_status_text(&format!("{} completed successfully", command));
+                state.add_output_line(format!("{} completed successfully", command));
+            } else {
+                let message = failure_status(&command, status.code());
+                state.set_status_text(&message);
+                state.add_output_line(message);
             }
+            state.report_elapsed(&command, status.success());
+            status.success()
+        }
+        Err(e) => {
+            state.set_status_text(&format!("Command execution error: {}", e));
+            state.add_output_line(format!("Command execution error: {}", e));
+            state.report_elapsed(&command, false);
+            false
         }
     }
 }
@@ -1,16 +1,32 @@
 // Process execution and output handling
 
-use crate::dashboard::DashboardState;
+use crate::commands::build_log::BuildLog;
+use crate::dashboard::{DashboardState, lock_state};
 use crate::process_manager::ProcessManager;
 use std::io::{BufRead, BufReader, Write};
 use std::process::{Child, Command, Stdio};
 use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::Duration;
 use std::fs::File;
 
+/// Child process of the running command, shared with the dashboard so Esc can kill it
+pub type SharedChild = Arc<Mutex<Child>>;
+
+/// Wait for a shared child without holding its lock, so a concurrent kill is never blocked
+pub fn wait_shared(child: &SharedChild) -> std::io::Result<std::process::ExitStatus> { //>
+    loop {
+        let status = child.lock().unwrap_or_else(|e| e.into_inner()).try_wait()?;
+        if let Some(status) = status {
+            return Ok(status);
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+} //<
+
 /// Handle process execution with stdout/stderr reading
 pub struct ProcessHandler {
-    child: Child,
+    child: SharedChild,
     pid: u32,
 }
 
@@ -32,29 +48,34 @@ impl ProcessHandler {
         let pid = child.id();
         
         Ok(Self {
-            child,
+            child: Arc::new(Mutex::new(child)),
             pid,
         })
     }
     
-    /// Start reading stderr in a separate thread
+    /// Start reading stderr in a separate thread, teeing raw lines into `build_log`
+    ///
+    /// Join the returned handle after the process exits so the last stderr lines reach the log.
     pub fn start_stderr_reader(
         &mut self,
         dashboard: Arc<Mutex<DashboardState>>,
         log_file: Arc<Mutex<File>>,
-    ) {
+        build_log: BuildLog,
+    ) -> Option<thread::JoinHandle<()>> {
         let dashboard_stderr = dashboard.clone();
         let log_file_stderr = log_file.clone();
         
-        if let Some(stderr) = self.child.stderr.take() {
+        let stderr = self.child.lock().unwrap_or_else(|e| e.into_inner()).stderr.take();
+        stderr.map(|stderr| {
             thread::spawn(move || {
                 let reader = BufReader::new(stderr);
                 for line in reader.lines() {
                     if let Ok(line) = line {
+                        build_log.write_line(&line);
                         // Preserve ANSI codes for colorization - only trim whitespace
                         let trimmed = line.trim();
                         if !trimmed.is_empty() {
-                            let mut state = dashboard_stderr.lock().unwrap();
+                            let mut state = lock_state(&dashboard_stderr);
                             state.add_output_line(trimmed.to_string());
                             // Log to file
                             if let Ok(mut log) = log_file_stderr.lock() {
@@ -64,13 +85,18 @@ impl ProcessHandler {
                         }
                     }
                 }
-            });
-        }
+            })
+        })
     }
     
     /// Take stdout for reading (consumes the handler's stdout)
     pub fn take_stdout(&mut self) -> Option<std::process::ChildStdout> {
-        self.child.stdout.take()
+        self.child.lock().unwrap_or_else(|e| e.into_inner()).stdout.take()
+    }
+    
+    /// Shared handle to the child, for `DashboardState::attach_child`
+    pub fn shared_child(&self) -> SharedChild {
+        self.child.clone()
     }
     
     /// Get the process ID
@@ -80,8 +106,8 @@ impl ProcessHandler {
     }
     
     /// Wait for the process to finish and return the exit status
-    pub fn wait(mut self, process_manager: Arc<ProcessManager>) -> std::io::Result<std::process::ExitStatus> {
-        let exit_status = self.child.wait()?;
+    pub fn wait(self, process_manager: Arc<ProcessManager>) -> std::io::Result<std::process::ExitStatus> {
+        let exit_status = wait_shared(&self.child)?;
         // Unregister process from process manager (completed normally)
         process_manager.unregister(self.pid);
         Ok(exit_status)
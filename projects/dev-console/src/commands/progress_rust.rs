@@ -1,27 +1,35 @@
 // Progress command execution (Rust-based, direct arduino-cli call)
 
-use crate::dashboard::DashboardState;
+use crate::dashboard::{DashboardState, lock_state};
 use crate::settings::Settings;
-use crate::commands::utils::remove_ansi_escapes;
+use crate::commands::utils::{remove_ansi_escapes, failure_status};
 use crate::commands::compile_state::{CompileState, CompileStage};
-use crate::commands::compile_parser::{detect_stage_change, parse_compilation_info};
+use crate::commands::compile_parser::{detect_stage_change, parse_compilation_info, detect_idf_stage_change, idf_progress_percent};
+use crate::commands::build_log::BuildLog;
 use crate::commands::process_handler::ProcessHandler;
 use crate::process_manager::ProcessManager;
 use crate::path_utils::{find_project_root, find_arduino_cli, get_library_path};
 use crate::progress_tracker::{ProgressStage, EstimateMethod};
-use crate::progress_history::ProgressHistory;
+use crate::progress_history::{BuildDurations, ProgressHistory};
 use std::io::{BufRead, BufReader, Write};
 use std::path::PathBuf;
 use std::process::Command;
 use std::sync::{Arc, Mutex};
 use std::fs::{File, OpenOptions};
+use std::time::Instant;
+use crate::update_throttle::UpdateThrottle;
 
-/// Execute progress command using Rust (direct arduino-cli call)
+/// Execute progress command using Rust (direct arduino-cli call, or `idf.py build` for esp-idf)
+/// Returns true when the compilation succeeded
 pub fn execute_progress_rust(
     dashboard: Arc<Mutex<DashboardState>>,
     settings: Settings,
     process_manager: Arc<ProcessManager>,
-) {
+) -> bool {
+    if settings.env == "esp-idf" {
+        return execute_progress_idf(dashboard, settings, process_manager);
+    }
+    
     // Build arduino-cli command
     let sketch_dir = PathBuf::from(&settings.sketch_directory);
     // Add .ino extension if not already present (sketch_name from dropdown is without extension)
@@ -33,7 +41,7 @@ pub fn execute_progress_rust(
     
     // Debug: Log settings being used
     {
-        let mut state = dashboard.lock().unwrap();
+        let mut state = lock_state(&dashboard);
         state.add_output_line(format!("[DEBUG] Sketch directory: '{}'", settings.sketch_directory));
         state.add_output_line(format!("[DEBUG] Sketch name from settings: '{}'", settings.sketch_name));
         state.add_output_line(format!("[DEBUG] Sketch file path: {:?}", sketch_file));
@@ -41,7 +49,7 @@ pub fn execute_progress_rust(
     
     // Validate that the sketch file exists
     if !sketch_file.exists() {
-        let mut state = dashboard.lock().unwrap();
+        let mut state = lock_state(&dashboard);
         state.is_running = false;
         let error_msg = format!(
             "Error: Sketch file not found: {:?}\nAvailable .ino files in directory:",
@@ -80,7 +88,7 @@ pub fn execute_progress_rust(
             }
         }
         
-        return;
+        return false;
     }
     
     let build_path = sketch_dir.join("build");
@@ -107,7 +115,7 @@ pub fn execute_progress_rust(
             
             // Create temp directory
             if let Err(e) = std::fs::create_dir_all(&temp_dir) {
-                let mut state = dashboard.lock().unwrap();
+                let mut state = lock_state(&dashboard);
                 state.is_running = false;
                 let error_msg = format!(
                     "Error: Failed to create temporary compile directory: {:?}\n{}",
@@ -115,13 +123,13 @@ pub fn execute_progress_rust(
                 );
                 state.set_status_text(&error_msg);
                 state.add_output_line(error_msg);
-                return;
+                return false;
             }
             
             // Copy the sketch file to temp directory with matching name
             let temp_sketch_file = temp_dir.join(format!("{}.ino", sketch_file_name));
             if let Err(e) = std::fs::copy(&sketch_file, &temp_sketch_file) {
-                let mut state = dashboard.lock().unwrap();
+                let mut state = lock_state(&dashboard);
                 state.is_running = false;
                 let error_msg = format!(
                     "Error: Failed to copy sketch file to temporary directory: {:?}\n{}",
@@ -131,7 +139,7 @@ pub fn execute_progress_rust(
                 state.add_output_line(error_msg);
                 // Clean up temp directory
                 let _ = std::fs::remove_dir_all(&temp_dir);
-                return;
+                return false;
             }
             
             // Copy any other files from the sketch directory (for includes, etc.)
@@ -160,7 +168,7 @@ pub fn execute_progress_rust(
             
             // Log temporary directory creation
             {
-                let mut state = dashboard.lock().unwrap();
+                let mut state = lock_state(&dashboard);
                 state.add_output_line(format!(
                     "[DEBUG] Sketch name '{}' doesn't match directory name '{}'",
                     sketch_file_name, dir_name
@@ -220,6 +228,12 @@ pub fn execute_progress_rust(
             last_updated: h.last_updated,
         });
     
+    // Median of past compile times for this sketch drives the "~38s remaining" estimate
+    let durations_file = project_root.join(".dev-console").join("build_durations.json");
+    let mut durations = BuildDurations::load(durations_file);
+    let expected_secs = durations.median(&settings.sketch_name).map(|d| d.as_secs_f64());
+    let compile_started = Instant::now();
+    
     // Calculate library path
     let library_path = get_library_path(&project_root, &settings.board_model);
     
@@ -246,7 +260,7 @@ pub fn execute_progress_rust(
     
     // Add initial message
     {
-        let mut state = dashboard.lock().unwrap();
+        let mut state = lock_state(&dashboard);
         let mut lines = vec![
             format!("Executing: {:?} compile --fqbn {} --libraries {:?} --verbose {:?}", 
                 arduino_cli, settings.fqbn, library_path, compile_dir),
@@ -272,6 +286,11 @@ pub fn execute_progress_rust(
         log_output(&log_file, "{{commanded progress bar percent: 0.0}}");
         log_output(&log_file, "");
         
+        state.files_compiled_fresh = 0;
+        state.files_reused = 0;
+        state.expected_secs = expected_secs;
+        state.eta_secs = None;
+        
         // Initialize progress tracking with time estimates
         state.start_progress_tracking(None, historical_data);
         if let Some(ref mut tracker) = state.progress_tracker {
@@ -281,7 +300,7 @@ pub fn execute_progress_rust(
     
     // Check if arduino-cli exists (unless it's in PATH)
     if !arduino_cli.exists() && arduino_cli.to_string_lossy() != "arduino-cli" {
-        let mut state = dashboard.lock().unwrap();
+        let mut state = lock_state(&dashboard);
         state.is_running = false;
         let error_msg1 = format!("Error: arduino-cli not found at: {:?}", arduino_cli);
         let error_msg2 = "Please ensure arduino-cli.exe is installed in the Arduino directory at the workspace root.".to_string();
@@ -290,14 +309,17 @@ pub fn execute_progress_rust(
         state.add_output_line(error_msg2.clone());
         log_output(&log_file, &error_msg1);
         log_output(&log_file, &error_msg2);
-        return;
+        return false;
     }
     
+    // Raw output is teed to `build/compile-<timestamp>.log` when create_log is on
+    let build_log = BuildLog::start(&settings, "Compile", &dashboard);
+    
     // Spawn process using process handler
     let mut process_handler = match ProcessHandler::spawn(cmd, process_manager.clone()) {
         Ok(handler) => handler,
         Err(e) => {
-            let mut state = dashboard.lock().unwrap();
+            let mut state = lock_state(&dashboard);
             state.is_running = false;
             state.set_status_text(&format!("Error: Failed to start arduino-cli: {}", e));
             state.add_output_line(format!("Error: Failed to start arduino-cli: {}", e));
@@ -305,16 +327,22 @@ pub fn execute_progress_rust(
             if !arduino_cli.exists() && arduino_cli.to_string_lossy() != "arduino-cli" {
                 state.add_output_line("The arduino-cli executable was not found at the expected location.".to_string());
             }
-            return;
+            return false;
         }
     };
     
     // Start stderr reader in separate thread
-    process_handler.start_stderr_reader(dashboard.clone(), log_file.clone());
+    let stderr_thread = process_handler.start_stderr_reader(dashboard.clone(), log_file.clone(), build_log.clone());
+    lock_state(&dashboard).attach_child(process_handler.shared_child());
     
     // Read stdout and parse
     let mut compile_state = CompileState::new();
-    let mut pending_lines: Vec<String> = Vec::new(); // Buffer for lines when lock is busy
+    let mut pending_lines: Vec<String> = Vec::new(); // Lines captured since the last dashboard flush
+    let (mut throttle, mut current_progress) = {
+        let state = lock_state(&dashboard);
+        (UpdateThrottle::new(state.progress_updates_per_sec), state.progress_percent)
+    };
+    let mut stage_pending = false; // Stage change coalesced but not yet applied to the tracker
     
     if let Some(stdout) = process_handler.take_stdout() {
         let reader = BufReader::new(stdout);
@@ -324,6 +352,7 @@ pub fn execute_progress_rust(
                 Ok(l) => l,
                 Err(_) => break,
             };
+            build_log.write_line(&line);
             
             // Preserve ANSI codes for colorization - only clean for parsing
             let cleaned = remove_ansi_escapes(&line);
@@ -337,151 +366,80 @@ pub fn execute_progress_rust(
             // Log to file immediately (no lock needed)
             log_output(&log_file, trimmed_line);
             
-            // Try to add line to dashboard - use try_lock to avoid blocking UI thread
-            let current_progress = if let Ok(mut state) = dashboard.try_lock() {
-                // Got the lock - add pending lines first, then this one
-                for pending_line in pending_lines.drain(..) {
-                    state.add_output_line(pending_line);
-                }
-                state.add_output_line(trimmed_line.to_string());
-                // Get current progress while we have the lock
-                state.progress_percent
-            } else {
-                // Lock is busy (UI thread is rendering) - queue this line for later
-                // This prevents blocking the UI thread during rapid output bursts
-                pending_lines.push(trimmed_line.to_string());
-                // Use calculated progress as fallback
-                compile_state.calculate_progress()
-            };
+            // Every line is captured; it reaches the dashboard on the next throttled flush
+            pending_lines.push(trimmed_line.to_string());
             // Auto-scroll is handled during rendering with correct visible_height
             
             // Parse line for compilation state using parser module
-            let (stage_changed, should_continue) = detect_stage_change(&line, &mut compile_state, current_progress);
+            let previous_stage = compile_state.stage;
+            let (mut stage_changed, should_continue) = detect_stage_change(&line, &mut compile_state, current_progress);
             if !should_continue {
                 // Error detected - already added to output
                 continue;
             }
             
+            // Stages only move forward; a backward flip means a misdetected or interleaved line
+            if let Some(warning) = compile_state.check_stage_regression(previous_stage) {
+                stage_changed = false;
+                pending_lines.push(warning);
+            }
+            
             // Parse compilation info (files, commands, etc.)
             parse_compilation_info(&line, &mut compile_state);
+            stage_pending |= stage_changed;
             
-            // Calculate progress BEFORE locking (expensive operations outside lock)
-            let stage_progress = compile_state.calculate_progress();
+            // Coalesce bursts: lock (and trigger a redraw) at most once per throttle window
+            if !throttle.request(Instant::now()) {
+                continue;
+            }
             
-            // Update dashboard state with progress tracking - SINGLE LOCK for all updates
-            // Only update if stage changed OR progress changed significantly to reduce lock contention
-            let should_update = stage_changed || {
-                // Check if progress would change significantly (do this calculation outside lock)
-                let current_tracker_progress = if compile_state.total_files > 0 {
-                    // Estimate based on files
-                    (compile_state.files_compiled as f64 / compile_state.total_files as f64) * 60.0 + 5.0
-                } else {
-                    stage_progress
-                };
-                // Only update if progress would change by more than 0.5%
-                (current_tracker_progress - compile_state.last_logged_progress).abs() > 0.5
+            // Try to get lock, but don't block - if busy, the update stays pending
+            // and lines and progress are applied on the next line
+            let mut state = match dashboard.try_lock() {
+                Ok(s) => s,
+                Err(_) => continue,
             };
             
-            if should_update {
-                // Try to get lock, but don't block - if busy, skip this update
-                // Progress will be updated on next successful lock acquisition
-                let mut state = match dashboard.try_lock() {
-                    Ok(s) => s,
-                    Err(_) => {
-                        // Lock busy - skip this update, will catch up later
-                        continue;
-                    }
-                };
-                
-                // Flush any pending lines while we have the lock
-                if !pending_lines.is_empty() {
-                    for pending_line in pending_lines.drain(..) {
-                        state.add_output_line(pending_line);
-                    }
-                }
-                
-                // Update stage if changed
-                if stage_changed {
-                    let new_stage = match compile_state.stage {
-                        CompileStage::Initializing => ProgressStage::Initializing,
-                        CompileStage::Compiling => ProgressStage::Compiling,
-                        CompileStage::Linking => ProgressStage::Linking,
-                        CompileStage::Generating => ProgressStage::Generating,
-                        CompileStage::Complete => ProgressStage::Complete,
-                    };
-                    state.transition_progress_stage(new_stage);
-                }
-                
-                match compile_state.stage {
-                    CompileStage::Initializing => state.set_progress_stage("Initializing"),
-                    CompileStage::Compiling => state.set_progress_stage("Compiling"),
-                    CompileStage::Linking => state.set_progress_stage("Linking"),
-                    CompileStage::Generating => state.set_progress_stage("Generating"),
-                    CompileStage::Complete => state.set_progress_stage("Complete"),
-                }
-                
-                state.set_current_file(&compile_state.current_file);
-                
-                // Update progress tracker - ensure cumulative progress across stages
-                let old_progress = state.progress_percent;
-                
-                if let Some(ref mut tracker) = state.progress_tracker {
-                    // Use weighted estimation (70% current rate, 30% historical)
-                    let method = EstimateMethod::Weighted {
-                        current_weight: 0.7,
-                        historical_weight: 0.3,
-                    };
-                    
-                    // Update based on files compiled (more accurate than percentage)
-                    if compile_state.total_files > 0 {
-                        // Set total_items BEFORE updating progress
-                        tracker.total_items = Some(compile_state.total_files);
-                        tracker.update_progress(compile_state.files_compiled, method);
-                        // Sync tracker's progress_percent back to state (this is the source of truth)
-                        state.progress_percent = tracker.progress_percent;
-                    } else {
-                        // Fallback: use stage-based progress but ensure it's cumulative
-                        let new_progress = stage_progress.max(tracker.progress_percent);
-                        if new_progress > tracker.progress_percent {
-                            tracker.set_progress_percent(new_progress);
-                        }
-                        // Still update time estimates
-                        tracker.update_progress((tracker.progress_percent * 100.0) as usize, method);
-                        state.progress_percent = tracker.progress_percent;
-                    }
-                } else {
-                    // Fallback if no tracker - use stage-based progress, but don't decrease
-                    state.progress_percent = stage_progress.max(state.progress_percent);
-                }
-                
-                // Log progress update if it changed significantly
-                let new_progress = state.progress_percent;
-                if (new_progress - old_progress).abs() > 0.01 {
-                    // Progress changed significantly, log it
-                    compile_state.last_logged_progress = new_progress;
-                    log_output(&log_file, "");
-                    log_output(&log_file, &format!("{{commanded progress bar percent: {:.2}}}", new_progress));
-                    log_output(&log_file, "");
-                }
+            for pending_line in pending_lines.drain(..) {
+                state.add_output_line(pending_line);
             }
+            if let Some(new_progress) = apply_compile_progress(&mut state, &mut compile_state, &mut stage_pending) {
+                log_output(&log_file, "");
+                log_output(&log_file, &format!("{{commanded progress bar percent: {:.2}}}", new_progress));
+                log_output(&log_file, "");
+            }
+            state.update_eta(compile_started.elapsed());
+            current_progress = state.progress_percent;
+            throttle.flushed(Instant::now());
         }
         
-        // Flush any remaining pending lines before exiting
-        if !pending_lines.is_empty() {
-            if let Ok(mut state) = dashboard.lock() {
-                for pending_line in pending_lines.drain(..) {
-                    state.add_output_line(pending_line);
-                }
+        // Flush any remaining pending lines, and the last throttled progress update, before exiting
+        let mut state = lock_state(&dashboard);
+        for pending_line in pending_lines.drain(..) {
+            state.add_output_line(pending_line);
+        }
+        if throttle.has_pending() {
+            if let Some(new_progress) = apply_compile_progress(&mut state, &mut compile_state, &mut stage_pending) {
+                log_output(&log_file, &format!("{{commanded progress bar percent: {:.2}}}", new_progress));
             }
+            state.update_eta(compile_started.elapsed());
         }
+        state.files_compiled_fresh = compile_state.total_files;
+        state.files_reused = compile_state.files_reused;
     }
     
     // Wait for process to finish
     let exit_status = process_handler.wait(process_manager);
+    let succeeded = matches!(exit_status, Ok(ref status) if status.success());
+    
+    // stderr hits EOF once the process exits; wait for it so the log is complete
+    if let Some(handle) = stderr_thread {
+        let _ = handle.join();
+    }
     
     // Record completion and timing data
     let (total_time, stage_times) = {
-        let state = dashboard.lock().unwrap();
+        let state = lock_state(&dashboard);
         if let Some(ref tracker) = state.progress_tracker {
             let total = tracker.elapsed_time;
             let mut stages = std::collections::HashMap::new();
@@ -495,10 +453,16 @@ pub fn execute_progress_rust(
     };
     
     {
-        let mut state = dashboard.lock().unwrap();
+        let mut state = lock_state(&dashboard);
+        build_log.finish(&mut state);
+        let cancelled = state.detach_child();
         state.is_running = false;
+        state.expected_secs = None;
+        state.eta_secs = None;
         
         match exit_status {
+            // Cancelled from the dashboard; the "Cancelled" status stays
+            _ if cancelled => {}
             Ok(status) => {
                 if status.success() {
                     state.progress_percent = 100.0;
@@ -510,22 +474,29 @@ pub fn execute_progress_rust(
                         tracker.progress_percent = 100.0;
                     }
                     
-                    state.set_status_text("Compilation completed successfully");
+                    match state.compile_cache_summary() {
+                        Some(summary) => state.set_status_text(&format!("Compilation completed successfully ({})", summary)),
+                        None => state.set_status_text("Compilation completed successfully"),
+                    }
+                    state.report_elapsed("Compile", true);
                     // Log final progress update
                     log_output(&log_file, "");
                     log_output(&log_file, "{{commanded progress bar percent: 100.0}}");
                     log_output(&log_file, "");
                     
                     // Record successful completion to history
+                    durations.record(&settings.sketch_name, compile_started.elapsed());
+                    let _ = durations.save();
                     if !stage_times.is_empty() {
                         let _ = history.record_completion(sketch_dir.clone(), stage_times, total_time);
                         let _ = history.save();
                     }
                 } else {
-                    let error_msg = format!("Compilation failed with exit code: {:?}", status.code());
+                    let error_msg = failure_status("Compile", status.code());
                     state.set_status_text(&error_msg);
                     state.add_output_line(error_msg.clone());
                     log_output(&log_file, &error_msg);
+                    state.report_elapsed("Compile", false);
                 }
             }
             Err(e) => {
@@ -533,6 +504,7 @@ pub fn execute_progress_rust(
                 state.set_status_text(&error_msg);
                 state.add_output_line(error_msg.clone());
                 log_output(&log_file, &error_msg);
+                state.report_elapsed("Compile", false);
             }
         }
     }
@@ -540,11 +512,306 @@ pub fn execute_progress_rust(
     // Clean up temporary directory if it was created
     if temp_dir_created {
         if let Err(e) = std::fs::remove_dir_all(&compile_dir) {
-            let mut state = dashboard.lock().unwrap();
+            let mut state = lock_state(&dashboard);
             state.add_output_line(format!(
                 "[WARNING] Failed to clean up temporary directory {:?}: {}",
                 compile_dir, e
             ));
         }
     }
+    
+    succeeded
+}
+
+
+/// Stage label shown in the progress bar for a compile stage
+fn stage_label(stage: CompileStage) -> &'static str {
+    match stage {
+        CompileStage::Initializing => "Initializing",
+        CompileStage::Compiling => "Compiling",
+        CompileStage::Linking => "Linking",
+        CompileStage::Generating => "Generating",
+        CompileStage::Complete => "Complete",
+    }
+}
+
+/// Progress stage tracked by the dashboard for a compile stage
+fn progress_stage(stage: CompileStage) -> ProgressStage {
+    match stage {
+        CompileStage::Initializing => ProgressStage::Initializing,
+        CompileStage::Compiling => ProgressStage::Compiling,
+        CompileStage::Linking => ProgressStage::Linking,
+        CompileStage::Generating => ProgressStage::Generating,
+        CompileStage::Complete => ProgressStage::Complete,
+    }
+}
+
+/// Apply the arduino-cli compile state to the dashboard progress bar and tracker
+///
+/// Only touches the dashboard if the stage changed or progress moved by more than 0.5%.
+/// Returns the new percentage when it changed enough to be logged.
+fn apply_compile_progress(
+    state: &mut DashboardState,
+    compile_state: &mut CompileState,
+    stage_pending: &mut bool,
+) -> Option<f64> { //>
+    let stage_progress = compile_state.calculate_progress();
+    let should_update = *stage_pending || {
+        let current_tracker_progress = if compile_state.total_files > 0 {
+            // Estimate based on files
+            (compile_state.files_compiled as f64 / compile_state.total_files as f64) * 60.0 + 5.0
+        } else {
+            stage_progress
+        };
+        (current_tracker_progress - compile_state.last_logged_progress).abs() > 0.5
+    };
+    if !should_update {
+        return None;
+    }
+
+    if std::mem::take(stage_pending) {
+        state.transition_progress_stage(progress_stage(compile_state.stage));
+    }
+    state.set_progress_stage(stage_label(compile_state.stage));
+    state.set_current_file(&compile_state.current_file);
+
+    // Update progress tracker - ensure cumulative progress across stages
+    let old_progress = state.progress_percent;
+    if let Some(ref mut tracker) = state.progress_tracker {
+        // Use weighted estimation (70% current rate, 30% historical)
+        let method = EstimateMethod::Weighted {
+            current_weight: 0.7,
+            historical_weight: 0.3,
+        };
+
+        // Update based on files compiled (more accurate than percentage)
+        if compile_state.total_files > 0 {
+            // Set total_items BEFORE updating progress
+            tracker.total_items = Some(compile_state.total_files);
+            tracker.update_progress(compile_state.files_compiled, method);
+            // Sync tracker's progress_percent back to state (this is the source of truth)
+            state.progress_percent = tracker.progress_percent;
+        } else {
+            // Fallback: use stage-based progress but ensure it's cumulative
+            let new_progress = stage_progress.max(tracker.progress_percent);
+            if new_progress > tracker.progress_percent {
+                tracker.set_progress_percent(new_progress);
+            }
+            // Still update time estimates
+            tracker.update_progress((tracker.progress_percent * 100.0) as usize, method);
+            state.progress_percent = tracker.progress_percent;
+        }
+    } else {
+        // Fallback if no tracker - use stage-based progress, but don't decrease
+        state.progress_percent = stage_progress.max(state.progress_percent);
+    }
+
+    let new_progress = state.progress_percent;
+    if (new_progress - old_progress).abs() > 0.01 {
+        compile_state.last_logged_progress = new_progress;
+        return Some(new_progress);
+    }
+    None
+} //<
+
+/// Apply the ESP-IDF build state (ninja steps) to the dashboard progress bar and tracker
+fn apply_idf_progress(state: &mut DashboardState, compile_state: &CompileState, stage_pending: &mut bool) {
+    if std::mem::take(stage_pending) {
+        state.transition_progress_stage(progress_stage(compile_state.stage));
+    }
+    state.set_progress_stage(stage_label(compile_state.stage));
+    state.set_current_file(&compile_state.current_file);
+    let percent = idf_progress_percent(compile_state).max(state.progress_percent);
+    state.progress_percent = percent;
+    if let Some(ref mut tracker) = state.progress_tracker {
+        tracker.set_progress_percent(percent);
+    }
+}
+
+/// Build an ESP-IDF project with `idf.py build`, driving progress from ninja's `[current/total]` steps
+fn execute_progress_idf(
+    dashboard: Arc<Mutex<DashboardState>>,
+    settings: Settings,
+    process_manager: Arc<ProcessManager>,
+) -> bool {
+    let project_dir = PathBuf::from(&settings.sketch_directory);
+    let project_root = find_project_root(&project_dir);
+    
+    let log_file_path = project_root.join(".dev-console").join("compile_output.log");
+    if let Some(parent) = log_file_path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let log_file = match File::create(&log_file_path) {
+        Ok(file) => Arc::new(Mutex::new(file)),
+        Err(e) => {
+            let mut state = lock_state(&dashboard);
+            state.is_running = false;
+            let error_msg = format!("Error: Could not open log file {:?}: {}", log_file_path, e);
+            state.set_status_text(&error_msg);
+            state.add_output_line(error_msg);
+            return false;
+        }
+    };
+    let log_output = |line: &str| {
+        if let Ok(mut log) = log_file.lock() {
+            let _ = writeln!(log, "{}", line);
+        }
+    };
+    
+    let mut cmd = Command::new("idf.py");
+    cmd.arg("build");
+    cmd.current_dir(&project_dir);
+    let build_log = BuildLog::start(&settings, "Compile", &dashboard);
+    let durations_file = project_root.join(".dev-console").join("build_durations.json");
+    let mut durations = BuildDurations::load(durations_file);
+    let compile_started = Instant::now();
+    
+    {
+        let mut state = lock_state(&dashboard);
+        let line = format!("Executing: idf.py build (in {:?})", project_dir);
+        state.add_output_line(line.clone());
+        log_output(&line);
+        state.is_running = true;
+        state.set_progress_stage("Initializing");
+        state.progress_percent = 0.0;
+        state.start_progress_tracking(None, None);
+        state.expected_secs = durations.median(&settings.sketch_name).map(|d| d.as_secs_f64());
+        state.eta_secs = None;
+    }
+    
+    let mut process_handler = match ProcessHandler::spawn(cmd, process_manager.clone()) {
+        Ok(handler) => handler,
+        Err(e) => {
+            let mut state = lock_state(&dashboard);
+            state.is_running = false;
+            state.set_status_text(&format!("Error: Failed to start idf.py: {}", e));
+            state.add_output_line(format!("Error: Failed to start idf.py: {}", e));
+            state.add_output_line("Run the ESP-IDF export script so idf.py is on PATH.".to_string());
+            return false;
+        }
+    };
+    let stderr_thread = process_handler.start_stderr_reader(dashboard.clone(), log_file.clone(), build_log.clone());
+    lock_state(&dashboard).attach_child(process_handler.shared_child());
+    
+    let mut compile_state = CompileState::new();
+    let mut pending_lines: Vec<String> = Vec::new();
+    let mut throttle = UpdateThrottle::new(lock_state(&dashboard).progress_updates_per_sec);
+    let mut stage_pending = false;
+    
+    if let Some(stdout) = process_handler.take_stdout() {
+        for line in BufReader::new(stdout).lines() {
+            let Ok(line) = line else { break };
+            build_log.write_line(&line);
+            let trimmed_line = line.trim();
+            if remove_ansi_escapes(trimmed_line).is_empty() {
+                continue;
+            }
+            log_output(trimmed_line);
+            pending_lines.push(trimmed_line.to_string());
+            
+            let current_progress = idf_progress_percent(&compile_state);
+            let (stage_changed, _) = detect_idf_stage_change(&line, &mut compile_state, current_progress);
+            stage_pending |= stage_changed;
+            
+            if !throttle.request(Instant::now()) {
+                continue;
+            }
+            // Busy lock: the update stays pending and is applied on the next line
+            let mut state = match dashboard.try_lock() {
+                Ok(s) => s,
+                Err(_) => continue,
+            };
+            for pending_line in pending_lines.drain(..) {
+                state.add_output_line(pending_line);
+            }
+            apply_idf_progress(&mut state, &compile_state, &mut stage_pending);
+            state.update_eta(compile_started.elapsed());
+            throttle.flushed(Instant::now());
+        }
+        
+        let mut state = lock_state(&dashboard);
+        for pending_line in pending_lines.drain(..) {
+            state.add_output_line(pending_line);
+        }
+        if throttle.has_pending() {
+            apply_idf_progress(&mut state, &compile_state, &mut stage_pending);
+            state.update_eta(compile_started.elapsed());
+        }
+    }
+    
+    let exit_status = process_handler.wait(process_manager);
+    let succeeded = matches!(exit_status, Ok(ref status) if status.success());
+    
+    // stderr hits EOF once the process exits; wait for it so the log is complete
+    if let Some(handle) = stderr_thread {
+        let _ = handle.join();
+    }
+    
+    let mut state = lock_state(&dashboard);
+    build_log.finish(&mut state);
+    let cancelled = state.detach_child();
+    state.is_running = false;
+    state.expected_secs = None;
+    state.eta_secs = None;
+    match exit_status {
+        _ if cancelled => {}
+        Ok(status) if status.success() => {
+            state.progress_percent = 100.0;
+            state.set_progress_stage("Complete");
+            if let Some(ref mut tracker) = state.progress_tracker {
+                tracker.transition_stage(ProgressStage::Complete);
+                tracker.progress_percent = 100.0;
+            }
+            state.set_status_text("Compilation completed successfully");
+            state.report_elapsed("Compile", true);
+            durations.record(&settings.sketch_name, compile_started.elapsed());
+            let _ = durations.save();
+        }
+        Ok(status) => {
+            let error_msg = failure_status("Compile", status.code());
+            state.set_status_text(&error_msg);
+            state.add_output_line(error_msg.clone());
+            log_output(&error_msg);
+            state.report_elapsed("Compile", false);
+        }
+        Err(e) => {
+            let error_msg = format!("Error waiting for process: {}", e);
+            state.set_status_text(&error_msg);
+            state.add_output_line(error_msg.clone());
+            log_output(&error_msg);
+            state.report_elapsed("Compile", false);
+        }
+    }
+    
+    succeeded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_trailing_throttled_update_reaches_dashboard() {
+        let mut throttle = UpdateThrottle::new(1);
+        let mut state = DashboardState::new();
+        let mut compile_state = CompileState::new();
+        let mut stage_pending = true;
+        let start = Instant::now();
+
+        assert!(throttle.request(start));
+        apply_compile_progress(&mut state, &mut compile_state, &mut stage_pending);
+        throttle.flushed(start);
+        assert_eq!(&*state.progress_stage, "Initializing");
+
+        // The build reaches linking inside the same window, then output ends
+        compile_state.stage = CompileStage::Linking;
+        stage_pending = true;
+        assert!(!throttle.request(start + Duration::from_millis(10)));
+
+        assert!(throttle.has_pending());
+        apply_compile_progress(&mut state, &mut compile_state, &mut stage_pending);
+        assert_eq!(&*state.progress_stage, "Linking");
+        assert!(!stage_pending);
+    }
 }
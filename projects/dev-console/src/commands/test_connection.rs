@@ -0,0 +1,103 @@
+// Test Connection command
+// Opens the configured port briefly to confirm it is usable before a long upload
+
+use crate::dashboard::{DashboardState, lock_state};
+use crate::settings::Settings;
+use crate::process_manager::ProcessManager;
+use serialport::ErrorKind;
+use std::io::Read;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// How long to collect banner bytes after the port opens
+pub const BANNER_WINDOW_MS: u64 = 300;
+
+/// Short reason for a failed open, separating a busy port from a missing one
+///
+/// Windows reports "Access is denied" for a port held by another program as `NoDevice`,
+/// so the description is checked as well as the kind.
+pub fn open_error_reason(error: &serialport::Error) -> &'static str { //>
+    let description = error.description.to_lowercase();
+    match error.kind() {
+        ErrorKind::Io(std::io::ErrorKind::PermissionDenied) => "permission denied (port busy or no access)",
+        ErrorKind::NoDevice if description.contains("access is denied") => "port busy (open in another program)",
+        ErrorKind::NoDevice | ErrorKind::Io(std::io::ErrorKind::NotFound) => "no such device",
+        ErrorKind::InvalidInput => "invalid port settings",
+        ErrorKind::Io(_) => "I/O error",
+        ErrorKind::Unknown => "unknown error",
+    }
+} //<
+
+/// Execute the Test Connection command: open `port` at `baudrate`, print any banner, close
+pub fn execute_test_connection_rust(
+    dashboard: Arc<Mutex<DashboardState>>,
+    settings: Settings,
+    _process_manager: Arc<ProcessManager>,
+) { //>
+    {
+        let mut state = lock_state(&dashboard);
+        state.set_progress_stage("Testing");
+        state.add_output_line(format!("Opening {} at {} baud...", settings.port, settings.baudrate));
+    }
+
+    let mut port = match serialport::new(&settings.port, settings.baudrate)
+        .timeout(Duration::from_millis(50))
+        .open()
+    {
+        Ok(port) => port,
+        Err(e) => {
+            let reason = open_error_reason(&e);
+            let mut state = lock_state(&dashboard);
+            state.is_running = false;
+            state.set_progress_stage("");
+            state.add_output_line(format!("Error: could not open {}: {} ({})", settings.port, reason, e));
+            state.set_status_text(&format!("Connection failed: {}", reason));
+            state.error_notice = Some(format!("{}: {}", settings.port, reason));
+            return;
+        }
+    };
+
+    // Collect whatever the board prints right after the port opens (e.g. a boot banner)
+    let mut banner = Vec::new();
+    let mut buffer = [0u8; 256];
+    let started = Instant::now();
+    while started.elapsed() < Duration::from_millis(BANNER_WINDOW_MS) {
+        match port.read(&mut buffer) {
+            Ok(n) => banner.extend_from_slice(&buffer[..n]),
+            Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {}
+            Err(_) => break,
+        }
+    }
+    drop(port);
+
+    let mut state = lock_state(&dashboard);
+    state.add_output_line(format!("✅ {} opened at {} baud", settings.port, settings.baudrate));
+    let banner = String::from_utf8_lossy(&banner);
+    if banner.trim().is_empty() {
+        state.add_output_line(format!("(no data within {}ms)", BANNER_WINDOW_MS));
+    } else {
+        for line in banner.lines().filter(|l| !l.trim().is_empty()) {
+            state.add_output_line(line.to_string());
+        }
+    }
+    state.is_running = false;
+    state.progress_percent = 100.0;
+    state.set_progress_stage("");
+    state.set_status_text("Connection OK");
+    state.notice = Some(format!("{} opened at {} baud", settings.port, settings.baudrate));
+} //<
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_error_reason_tells_busy_from_missing() {
+        let busy = serialport::Error::new(ErrorKind::Io(std::io::ErrorKind::PermissionDenied), "Permission denied");
+        let busy_windows = serialport::Error::new(ErrorKind::NoDevice, "Access is denied.");
+        let missing = serialport::Error::new(ErrorKind::NoDevice, "No such file or directory");
+        assert!(open_error_reason(&busy).starts_with("permission denied"));
+        assert!(open_error_reason(&busy_windows).starts_with("port busy"));
+        assert_eq!(open_error_reason(&missing), "no such device");
+    }
+}
@@ -1,8 +1,9 @@
 // Upload command execution (Rust-based, direct arduino-cli call)
 
-use crate::dashboard::DashboardState;
+use crate::dashboard::{DashboardState, lock_state};
 use crate::settings::Settings;
-use crate::commands::utils::remove_ansi_escapes;
+use crate::commands::utils::{remove_ansi_escapes, failure_status};
+use crate::commands::process_handler::wait_shared;
 use crate::process_manager::ProcessManager;
 use crate::path_utils::{find_project_root, find_arduino_cli};
 use std::io::{BufRead, BufReader};
@@ -15,10 +16,116 @@ use lazy_static::lazy_static;
 
 lazy_static! {
     static ref RE_WRITING_AT: Regex = Regex::new(
-        r"(?i)Writing at (0x[0-9a-fA-F]+).*?(\d+\.?\d*)%"
+        r"(?i)Writing at (0x[0-9a-fA-F]+).*?(\d+\.?\d*)\s*%"
     ).unwrap();
 }
 
+/// Progress stage shown while esptool writes to flash
+pub const FLASHING_STAGE: &str = "Flashing";
+
+/// Flash write progress reported by one esptool "Writing at" line
+#[derive(Debug, Clone, PartialEq)]
+pub struct EsptoolProgress {
+    pub address: String,
+    pub percent: f64,
+}
+
+/// Parse an esptool line such as "Writing at 0x00010000... (23 %)"
+pub fn parse_esptool_progress(line: &str) -> Option<EsptoolProgress> {
+    let captures = RE_WRITING_AT.captures(line)?;
+    let percent = captures.get(2)?.as_str().parse::<f64>().ok()?;
+    Some(EsptoolProgress {
+        address: captures.get(1)?.as_str().to_string(),
+        percent,
+    })
+}
+
+/// Error fragments emitted by esptool/arduino-cli when the port is held by another program
+const PORT_BUSY_PATTERNS: &[&str] = &[
+    "could not open port",
+    "access is denied",
+    "resource busy",
+    "port is busy",
+];
+
+/// Map a toolchain error line to a friendly message if it indicates the port is in use
+pub fn port_busy_message(line: &str, port: &str) -> Option<String> {
+    let line_lower = line.to_lowercase();
+    if PORT_BUSY_PATTERNS.iter().any(|pattern| line_lower.contains(pattern)) {
+        Some(format!("Port {} is in use — close other serial programs", port))
+    } else {
+        None
+    }
+}
+
+/// Parser state for arduino-cli/esptool stdout during one upload
+#[derive(Debug, Default)]
+struct UploadOutput {
+    flash_count: usize,
+    busy_message: Option<String>,
+}
+
+impl UploadOutput {
+    /// Apply one stdout line to the dashboard
+    ///
+    /// Escape sequences are stripped only for parsing; the line is shown as received
+    /// (trimmed) so colours and OSC 8 hyperlinks reach the output panel.
+    fn handle_line(&mut self, state: &mut DashboardState, line: &str, port: &str) { //>
+        let cleaned = remove_ansi_escapes(line);
+        let line_lower = cleaned.to_lowercase();
+        let trimmed = cleaned.trim();
+        let display = line.trim().to_string();
+        
+        if trimmed.is_empty() {
+            return;
+        }
+        
+        if self.busy_message.is_none() {
+            self.busy_message = port_busy_message(trimmed, port);
+        }
+        
+        // Suppress "Hash of data verified" (like Python version)
+        if line_lower.contains("hash of data verified") {
+            return;
+        }
+        
+        // Suppress "Compressed" lines after first block (like Python version)
+        if line_lower.contains("compressed") && line_lower.contains("bytes to") && self.flash_count > 0 {
+            return;
+        }
+        
+        // Handle "Writing at" lines - drive the progress bar from esptool's percentage
+        if let Some(progress) = parse_esptool_progress(trimmed) {
+            state.progress_percent = progress.percent;
+            state.set_progress_stage(FLASHING_STAGE);
+            state.set_current_file(&progress.address);
+            state.add_output_line(display);
+            return;
+        }
+        
+        // Handle "Wrote" lines - flash complete
+        if trimmed.contains("Wrote") && trimmed.contains("compressed") {
+            self.flash_count += 1;
+            state.progress_percent = 100.0;
+            state.set_progress_stage("Upload complete");
+            state.add_output_line(display);
+            return;
+        }
+        
+        // Handle "Hard resetting"
+        if trimmed.contains("Hard resetting") {
+            state.add_output_line(display);
+            return;
+        }
+        
+        // Add regular output
+        state.add_output_line(display);
+        if state.output_lines.len() > 1 {
+            state.output_scroll = state.output_lines.len().saturating_sub(1);
+        }
+    } //<
+}
+
 /// Execute upload command using Rust (direct arduino-cli call)
 pub fn execute_upload_rust(
     dashboard: Arc<Mutex<DashboardState>>,
@@ -48,9 +155,9 @@ pub fn execute_upload_rust(
     
     // Clear status and output panels before starting upload
     {
-        let mut state = dashboard.lock().unwrap();
+        let mut state = lock_state(&dashboard);
         // Clear output lines
-        state.output_lines.clear();
+        state.clear_output();
         // Reset progress
         state.progress_percent = 0.0;
         state.set_progress_stage("");
@@ -66,7 +173,7 @@ pub fn execute_upload_rust(
     
     // Add initial message
     {
-        let mut state = dashboard.lock().unwrap();
+        let mut state = lock_state(&dashboard);
         state.add_output_line(format!("Uploading to {} on port {}...", settings.board_model, settings.port));
         state.add_output_line(format!("Executing: {:?} upload -p {} --fqbn {} --build-path {:?} {:?}", 
             arduino_cli, settings.port, settings.fqbn, build_path, sketch_dir));
@@ -75,7 +182,7 @@ pub fn execute_upload_rust(
     
     // Check if arduino-cli exists
     if !arduino_cli.exists() && arduino_cli.to_string_lossy() != "arduino-cli" {
-        let mut state = dashboard.lock().unwrap();
+        let mut state = lock_state(&dashboard);
         state.is_running = false;
         state.set_status_text(&format!("Error: arduino-cli not found at: {:?}", arduino_cli));
         state.add_output_line(format!("Error: arduino-cli not found at: {:?}", arduino_cli));
@@ -90,10 +197,10 @@ pub fn execute_upload_rust(
             child
         }
         Err(e) => {
-            let mut state = dashboard.lock().unwrap();
+            let mut state = lock_state(&dashboard);
             state.is_running = false;
             state.set_status_text(&format!("Error: Failed to start arduino-cli: {}", e));
-            state.output_lines.push(format!("Error: Failed to start arduino-cli: {}", e));
+            state.add_output_line(format!("Error: Failed to start arduino-cli: {}", e));
             return;
         }
     };
@@ -101,30 +208,39 @@ pub fn execute_upload_rust(
     // Store PID for unregistering when process completes
     let pid = child.id();
     
-    // Read stderr in separate thread
+    // Read stderr in separate thread (returns the port-busy message if one was seen)
     let dashboard_stderr = dashboard.clone();
-    if let Some(stderr) = child.stderr.take() {
+    let port_stderr = settings.port.clone();
+    let stderr_handle = child.stderr.take().map(|stderr| {
         thread::spawn(move || {
+            let mut busy_message: Option<String> = None;
             let reader = BufReader::new(stderr);
             for line in reader.lines() {
                 if let Ok(line) = line {
                     let cleaned = remove_ansi_escapes(&line);
                     let trimmed = cleaned.trim();
                     if !trimmed.is_empty() {
-                        let mut state = dashboard_stderr.lock().unwrap();
-                        state.add_output_line(trimmed.to_string());
+                        if busy_message.is_none() {
+                            busy_message = port_busy_message(trimmed, &port_stderr);
+                        }
+                        let mut state = lock_state(&dashboard_stderr);
+                        state.add_output_line(line.trim().to_string());
                     }
                 }
             }
-        });
-    }
+            busy_message
+        })
+    });
     
     // Track upload state
-    let mut current_address: Option<String> = None;
-    let mut flash_count = 0;
+    let mut upload_output = UploadOutput::default();
     
     // Read stdout and parse upload progress
-    if let Some(stdout) = child.stdout.take() {
+    let stdout = child.stdout.take();
+    let child = Arc::new(Mutex::new(child));
+    lock_state(&dashboard).attach_child(child.clone());
+    
+    if let Some(stdout) = stdout {
         let reader = BufReader::new(stdout);
         
         for line_result in reader.lines() {
@@ -132,100 +248,30 @@ pub fn execute_upload_rust(
                 Ok(l) => l,
                 Err(_) => break,
             };
-            
-            let cleaned = remove_ansi_escapes(&line);
-            let line_lower = cleaned.to_lowercase();
-            let trimmed = cleaned.trim();
-            
-            if trimmed.is_empty() {
-                continue;
-            }
-            
-            // Suppress "Hash of data verified" (like Python version)
-            if line_lower.contains("hash of data verified") {
-                continue;
-            }
-            
-            // Suppress "Compressed" lines after first block (like Python version)
-            if line_lower.contains("compressed") && line_lower.contains("bytes to") {
-                if flash_count > 0 {
-                    continue;
-                }
-            }
-            
-            // Handle "Writing at" lines - extract progress
-            if trimmed.contains("Writing at") {
-                if let Some(captures) = RE_WRITING_AT.captures(&trimmed) {
-                    if let (Some(addr_match), Some(percent_match)) = (captures.get(1), captures.get(2)) {
-                        let addr = addr_match.as_str().to_string();
-                        if let Ok(percent) = percent_match.as_str().parse::<f64>() {
-                            current_address = Some(addr.clone());
-                            
-                            {
-                                let mut state = dashboard.lock().unwrap();
-                                state.progress_percent = percent;
-                                state.set_progress_stage(&format!("Writing at {}", addr));
-                                state.set_current_file(&addr);
-                                
-                                // Add progress line to output
-                                state.output_lines.push(trimmed.to_string());
-                                if state.output_lines.len() > 1 {
-                                    // Don't auto-scroll - let user control scrolling manually
-                                }
-                            }
-                            continue;
-                        }
-                    }
-                }
-            }
-            
-            // Handle "Wrote" lines - flash complete
-            if trimmed.contains("Wrote") && trimmed.contains("compressed") {
-                flash_count += 1;
-                current_address = None;
-                
-                {
-                    let mut state = dashboard.lock().unwrap();
-                    state.progress_percent = 100.0;
-                    state.set_progress_stage("Upload complete");
-                    state.add_output_line(trimmed.to_string());
-                }
-                continue;
-            }
-            
-            // Handle "Hard resetting"
-            if trimmed.contains("Hard resetting") {
-                {
-                    let mut state = dashboard.lock().unwrap();
-                    state.add_output_line(trimmed.to_string());
-                }
-                continue;
-            }
-            
-            // Add other output lines (but skip empty lines if we have a progress bar)
-            if current_address.is_some() && trimmed.is_empty() {
-                continue;
-            }
-            
-            // Add regular output
-            {
-                let mut state = dashboard.lock().unwrap();
-                state.output_lines.push(trimmed.to_string());
-                if state.output_lines.len() > 1 {
-                    state.output_scroll = state.output_lines.len().saturating_sub(1);
-                }
-            }
+            let mut state = lock_state(&dashboard);
+            upload_output.handle_line(&mut state, &line, &settings.port);
         }
     }
+    let mut busy_message = upload_output.busy_message;
     
     // Wait for process to finish
-    let exit_status = child.wait();
+    let exit_status = wait_shared(&child);
     
     // Unregister process from process manager (completed normally)
     process_manager.unregister(pid);
     
+    // Collect a port-busy diagnosis from stderr if stdout didn't already report one
+    if let Some(handle) = stderr_handle {
+        if let Ok(Some(message)) = handle.join() {
+            busy_message.get_or_insert(message);
+        }
+    }
+    
     {
-        let mut state = dashboard.lock().unwrap();
+        let mut state = lock_state(&dashboard);
+        if state.detach_child() {
+            return;
+        }
         state.is_running = false;
         
         match exit_status {
@@ -235,9 +281,13 @@ pub fn execute_upload_rust(
                     state.set_progress_stage("Upload complete");
                     state.set_status_text("Upload completed successfully");
                     state.add_output_line("Upload completed successfully".to_string());
+                } else if let Some(message) = &busy_message {
+                    state.set_status_text(message);
+                    state.add_output_line(message.clone());
                 } else {
-                    state.set_status_text(&format!("Upload failed with exit code: {:?}", status.code()));
-                    state.add_output_line(format!("Upload failed with exit code: {:?}", status.code()));
+                    let message = failure_status("Upload", status.code());
+                    state.set_status_text(&message);
+                    state.add_output_line(message);
                 }
             }
             Err(e) => {
@@ -247,3 +297,44 @@ pub fn execute_upload_rust(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_port_busy_message_maps_busy_errors() {
+        let line = "A fatal error occurred: Could not open COM5, the port doesn't exist or is busy: could not open port 'COM5': PermissionError(13, 'Access is denied.', None, 5)";
+        assert_eq!(
+            port_busy_message(line, "COM5"),
+            Some("Port COM5 is in use — close other serial programs".to_string())
+        );
+        assert_eq!(port_busy_message("Failed to connect to ESP32-S3: No serial data received.", "COM5"), None);
+    }
+
+    #[test]
+    fn test_parse_esptool_progress() {
+        assert_eq!(
+            parse_esptool_progress("Writing at 0x00010000... (23 %)"),
+            Some(EsptoolProgress { address: "0x00010000".to_string(), percent: 23.0 })
+        );
+        assert_eq!(
+            parse_esptool_progress("Writing at 0x0003c000 [=====>    ] 57.5% 196608/344064 bytes..."),
+            Some(EsptoolProgress { address: "0x0003c000".to_string(), percent: 57.5 })
+        );
+        assert_eq!(parse_esptool_progress("Wrote 344064 bytes (190511 compressed) at 0x00010000"), None);
+    }
+
+    #[test]
+    fn test_upload_output_keeps_hyperlinks_for_display() {
+        let mut state = DashboardState::new();
+        let mut output = UploadOutput::default();
+        let link = "  See \x1b]8;;https://docs.espressif.com/esptool\x1b\\esptool docs\x1b]8;;\x1b\\ for help  ";
+        output.handle_line(&mut state, link, "COM5");
+        output.handle_line(&mut state, "Writing at 0x00010000... (23 %)", "COM5");
+
+        assert_eq!(state.output_lines[0], link.trim());
+        assert_eq!(state.output_lines[1], "Writing at 0x00010000... (23 %)");
+        assert_eq!(state.progress_percent, 23.0);
+    }
+}
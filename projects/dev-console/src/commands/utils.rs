@@ -4,12 +4,15 @@ use regex::Regex;
 use lazy_static::lazy_static;
 
 lazy_static! {
-    static ref ANSI_RE: Regex = Regex::new(r"\x1B\[[0-9;]*[a-zA-Z]").unwrap();
+    static ref ANSI_RE: Regex = Regex::new(r"\x1B\[[0-9;]*[a-zA-Z]|\x1B\][^\x07\x1B]*(?:\x07|\x1B\\)").unwrap();
     static ref PERCENT_RE: Regex = Regex::new(r"(\d+\.?\d*)%").unwrap();
+    static ref FRACTION_RE: Regex = Regex::new(r"\[(\d+)/(\d+)\]").unwrap();
+    // A percentage opening or closing the line, optionally in parentheses ("45%", "Writing... (45 %)")
+    static ref PROGRESS_RE: Regex = Regex::new(r"^\s*\(?(\d+\.?\d*)\s*%|(\d+\.?\d*)\s*%\)?\s*$").unwrap();
     static ref FILE_RE: Regex = Regex::new(r"(?:-\s+)?([^\s\[\]()]+\.(cpp|c|ino|S))").unwrap();
 }
 
-/// Remove ANSI escape sequences from a string
+/// Remove ANSI escape sequences (CSI codes and OSC sequences such as hyperlinks) from a string
 pub fn remove_ansi_escapes(s: &str) -> String {
     ANSI_RE.replace_all(s, "").to_string()
 }
@@ -25,6 +28,31 @@ pub fn extract_percentage(line: &str) -> Option<f64> {
     None
 }
 
+/// Extract ninja/PlatformIO step progress such as `[3/10]` as a percentage
+///
+/// A zero total or a step past the total is treated as not progress.
+pub fn extract_fraction(line: &str) -> Option<f64> { //>
+    let captures = FRACTION_RE.captures(line)?;
+    let current = captures[1].parse::<u64>().ok()?;
+    let total = captures[2].parse::<u64>().ok()?;
+    if total == 0 || current > total {
+        return None;
+    }
+    Some(current as f64 / total as f64 * 100.0)
+} //<
+
+/// Progress from a line: an explicit progress percentage wins, otherwise a `[current/total]` step
+///
+/// Only a percentage at the start or end of the line counts, so arduino-cli's
+/// "Sketch uses … (79%) of program storage space" size report is not taken as progress.
+pub fn extract_progress(line: &str) -> Option<f64> { //>
+    let explicit = PROGRESS_RE.captures(line)
+        .and_then(|c| c.get(1).or_else(|| c.get(2)))
+        .and_then(|m| m.as_str().parse::<f64>().ok())
+        .map(|percent| percent.min(100.0));
+    explicit.or_else(|| extract_fraction(line))
+} //<
+
 /// Extract current file from a line
 #[allow(dead_code)]
 pub fn extract_current_file(line: &str) -> Option<String> {
@@ -33,3 +61,50 @@ pub fn extract_current_file(line: &str) -> Option<String> {
     }
     None
 }
+
+/// Status text for a command that exited unsuccessfully, e.g. "Compile failed (exit 1)"
+///
+/// A `None` code means the process was killed by a signal rather than exiting.
+pub fn failure_status(label: &str, code: Option<i32>) -> String {
+    match code {
+        Some(code) => format!("{} failed (exit {})", label, code),
+        None => format!("{} failed (terminated by signal)", label),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failure_status_reports_exit_code_or_signal() {
+        assert_eq!(failure_status("Compile", Some(1)), "Compile failed (exit 1)");
+        assert_eq!(failure_status("Upload", Some(-2)), "Upload failed (exit -2)");
+        assert_eq!(failure_status("Compile", None), "Compile failed (terminated by signal)");
+    }
+
+    #[test]
+    fn test_extract_fraction_and_percentage_preference() {
+        assert_eq!(extract_fraction("[0/5] Building CXX object main.cpp.obj"), Some(0.0));
+        assert_eq!(extract_fraction("[5/5] Linking CXX executable app.elf"), Some(100.0));
+        assert_eq!(extract_fraction("[3/10] Compiling foo.cpp"), Some(30.0));
+
+        // Malformed brackets are not progress
+        assert_eq!(extract_fraction("[3/0] nothing to do"), None);
+        assert_eq!(extract_fraction("[6/5] overrun"), None);
+        assert_eq!(extract_fraction("[3/] Compiling"), None);
+        assert_eq!(extract_fraction("[/10] Compiling"), None);
+        assert_eq!(extract_fraction("[a/b] Compiling"), None);
+        assert_eq!(extract_fraction("3/10 Compiling"), None);
+
+        // An explicit percentage is preferred over the step count
+        assert_eq!(extract_progress("[1/4] Uploading 75%"), Some(75.0));
+        assert_eq!(extract_progress("[1/4] Compiling"), Some(25.0));
+        assert_eq!(extract_progress("Compiling"), None);
+        assert_eq!(extract_progress("Writing at 0x00010000... (14 %)"), Some(14.0));
+
+        // Memory-usage figures from arduino-cli's size report are not progress
+        assert_eq!(extract_progress("Sketch uses 1045109 bytes (79%) of program storage space. Maximum is 1310720 bytes."), None);
+        assert_eq!(extract_progress("Global variables use 43428 bytes (13%) of dynamic memory, leaving 284252 bytes for local variables. Maximum is 327680 bytes."), None);
+    }
+}
@@ -27,12 +27,66 @@ pub struct ApplicationConfig { //>
     pub title: String,
     pub bindings: Vec<BindingConfigYaml>,
     pub status_bar: StatusBarConfigYaml,
+    #[serde(default)]
+    pub output_placeholder: OutputPlaceholderConfigYaml,
+    /// Output lines longer than this are truncated for display
+    #[serde(default = "default_max_line_width")]
+    pub max_line_width: usize,
+    /// Dim the dashboard command list while a command is running
+    #[serde(default)]
+    pub dim_commands_while_running: bool,
+    /// Maximum progress/state updates applied per second while parsing output (0 = unthrottled)
+    #[serde(default = "default_progress_updates_per_sec")]
+    pub progress_updates_per_sec: u32,
+    /// Command listing "Board Name  FQBN" rows for the Board Model dropdown
+    #[serde(default)]
+    pub board_list_command: Option<String>,
+    /// Tool that runs "Compile" and the compile half of "Build"
+    #[serde(default)]
+    pub compile_backend: CompileBackend,
+    /// Command name -> `env` values it applies to; other commands are always listed
+    #[serde(default)]
+    pub command_environments: std::collections::HashMap<String, Vec<String>>,
+    /// Seconds without input after which an open field edit is committed and saved (0 = off)
+    #[serde(default)]
+    pub edit_autosave_secs: u64,
+    /// Color overrides for borders and highlights
+    #[serde(default)]
+    pub theme: ThemeConfigYaml,
 } //<
 
+/// Theme color overrides; each is any ratatui color name or hex value (e.g. "white", "#666666")
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ThemeConfigYaml { //>
+    #[serde(default)]
+    pub border_default: Option<String>,
+    #[serde(default)]
+    pub border_selected: Option<String>,
+    #[serde(default)]
+    pub border_editing: Option<String>,
+    #[serde(default)]
+    pub accent: Option<String>,
+} //<
+
+/// Compile backend: the native Rust progress parser or the python `pmake.py` script
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CompileBackend {
+    Python,
+    #[default]
+    Native,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct BindingConfigYaml { //>
     pub key: String,
     pub description: String,
+    /// Semantic action triggered by this binding (e.g. "quit", "cancel")
+    #[serde(default)]
+    pub action: Option<String>,
+    /// Physical keys that trigger `action` (e.g. ["q", "ctrl+c"])
+    #[serde(default)]
+    pub keys: Vec<String>,
 } //<
 
 #[derive(Debug, Clone, Deserialize)]
@@ -40,9 +94,45 @@ pub struct StatusBarConfigYaml { //>
     pub default_text: String,
     #[serde(default)]
     pub modal_text: Option<String>,
+    /// Dashboard status restored by the clear_status action (defaults to "Ready")
+    #[serde(default)]
+    pub idle_text: Option<String>,
+} //<
+
+/// Text shown in the output panel before any command has run
+#[derive(Debug, Clone, Deserialize)]
+pub struct OutputPlaceholderConfigYaml { //>
+    #[serde(default = "default_placeholder_text")]
+    pub text: String,
+    /// Any ratatui color name or hex value (e.g. "gray", "#808080")
+    #[serde(default)]
+    pub color: Option<String>,
+    /// Optional quick-start line rendered below the placeholder text
+    #[serde(default)]
+    pub hint: Option<String>,
+} //<
+
+impl Default for OutputPlaceholderConfigYaml { //>
+    fn default() -> Self {
+        Self {
+            text: default_placeholder_text(),
+            color: None,
+            hint: None,
+        }
+    }
 } //<
 
+fn default_max_line_width() -> usize {
+    crate::constants::DEFAULT_MAX_LINE_WIDTH
+}
 
+fn default_progress_updates_per_sec() -> u32 {
+    crate::constants::DEFAULT_PROGRESS_UPDATES_PER_SEC
+}
+
+fn default_placeholder_text() -> String {
+    "No output yet. Select a command to run.".to_string()
+}
 
 pub fn load_config(config_path: Option<PathBuf>) -> Result<AppConfig, Box<dyn std::error::Error>> {
     let path = config_path.unwrap_or_else(|| {
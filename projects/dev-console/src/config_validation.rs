@@ -118,12 +118,24 @@ tab_content:
                         BindingConfigYaml {
                             key: "q".to_string(),
                             description: "Quit".to_string(),
+                            action: Some("quit".to_string()),
+                            keys: vec!["q".to_string()],
                         },
                     ],
                     status_bar: StatusBarConfigYaml {
                         default_text: "Ready".to_string(),
                         modal_text: None,
+                        idle_text: None,
                     },
+                    output_placeholder: Default::default(),
+                    max_line_width: crate::constants::DEFAULT_MAX_LINE_WIDTH,
+                    dim_commands_while_running: false,
+                    progress_updates_per_sec: crate::constants::DEFAULT_PROGRESS_UPDATES_PER_SEC,
+                    board_list_command: None,
+                    compile_backend: Default::default(),
+                    command_environments: HashMap::new(),
+                    edit_autosave_secs: 0,
+                    theme: Default::default(),
                 },
                 tab_bars: HashMap::new(),
                 tab_content: vec![],
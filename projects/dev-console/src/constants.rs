@@ -1,9 +1,9 @@
 // Application constants
 
-/// Minimum terminal width in pixels
+/// Default minimum settings tab width in columns (`min_cols` in settings)
 pub const MIN_WIDTH_PIXELS: u16 = 80;
 
-/// Minimum terminal height in pixels
+/// Default minimum settings tab height in rows (`min_rows` in settings)
 pub const MIN_HEIGHT_PIXELS: u16 = 21;
 
 /// Field height in lines (for settings fields)
@@ -18,8 +18,20 @@ pub const CONTENT_WIDTH_PERCENT: u16 = 50;
 /// Content area height percentage (50% of available space)
 pub const CONTENT_HEIGHT_PERCENT: u16 = 50;
 
-/// Maximum output lines to keep in memory
-pub const MAX_OUTPUT_LINES: usize = 1000;
+/// Default cap on dashboard output lines kept in memory (`max_output_lines` in settings)
+pub const DEFAULT_MAX_OUTPUT_LINES: usize = 2000;
+
+/// Default maximum characters kept for display in a single output line
+pub const DEFAULT_MAX_LINE_WIDTH: usize = 4096;
+
+/// Smallest `max_line_width` applied; a zero width would reduce every line to "…"
+pub const MIN_MAX_LINE_WIDTH: usize = 16;
+
+/// Default cap on progress/state updates applied to the dashboard per second
+pub const DEFAULT_PROGRESS_UPDATES_PER_SEC: u32 = 30;
+
+/// How often the open Port dropdown re-enumerates serial ports, in milliseconds
+pub const PORT_RESCAN_INTERVAL_MS: u64 = 500;
 
 /// Toast display duration in seconds
 #[allow(dead_code)] // For future use
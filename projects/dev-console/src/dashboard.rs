@@ -1,15 +1,85 @@
 // Dashboard state management module
 
-use crate::constants::MAX_OUTPUT_LINES;
+use crate::commands::process_handler::SharedChild;
+use crate::config::{CompileBackend, OutputPlaceholderConfigYaml};
+use crate::constants::{DEFAULT_MAX_LINE_WIDTH, DEFAULT_MAX_OUTPUT_LINES, DEFAULT_PROGRESS_UPDATES_PER_SEC, MIN_MAX_LINE_WIDTH};
 use crate::progress_tracker::{ProgressTracker, EstimateMethod};
-use std::sync::Arc;
+use ratatui::style::Color;
+use regex::Regex;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use tui_input::Input;
+
+/// Set once the first poisoned lock has been reported
+static POISON_WARNED: AtomicBool = AtomicBool::new(false);
+
+/// Lock the shared dashboard state, recovering if a thread panicked while holding it
+/// Logs a one-time warning to the output panel so the UI stays alive after a reader-thread panic
+pub fn lock_state(dashboard: &Arc<Mutex<DashboardState>>) -> MutexGuard<'_, DashboardState> {
+    dashboard.lock().unwrap_or_else(|poisoned| {
+        let mut state = poisoned.into_inner();
+        if !POISON_WARNED.swap(true, Ordering::SeqCst) {
+            state.add_output_line("Warning: a background thread panicked; recovered dashboard state".to_string());
+        }
+        state
+    })
+}
+
+/// Placeholder shown in the output panel while it is empty
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputPlaceholder {
+    pub text: String,
+    pub color: Color,
+    pub hint: Option<String>,
+}
+
+impl Default for OutputPlaceholder {
+    fn default() -> Self {
+        Self::from_config(&OutputPlaceholderConfigYaml::default())
+    }
+}
+
+impl OutputPlaceholder {
+    /// Build from config, falling back to the default gray for unknown colors
+    pub fn from_config(config: &OutputPlaceholderConfigYaml) -> Self {
+        let color = config.color.as_deref()
+            .and_then(|c| Color::from_str(c).ok())
+            .unwrap_or(Color::Rgb(128, 128, 128));
+        Self {
+            text: config.text.clone(),
+            color,
+            hint: config.hint.clone().filter(|h| !h.trim().is_empty()),
+        }
+    }
+}
+
+/// e.g. "Compile finished in 42.3s" or "Compile failed after 3.1s"
+pub fn elapsed_message(label: &str, succeeded: bool, elapsed: Duration) -> String {
+    if succeeded {
+        format!("{} finished in {:.1}s", label, elapsed.as_secs_f64())
+    } else {
+        format!("{} failed after {:.1}s", label, elapsed.as_secs_f64())
+    }
+}
 
 /// Dashboard state structure
 #[derive(Debug, Clone)]
 pub struct DashboardState {
+    /// Commands listed for the current environment
     pub commands: Vec<String>,
+    /// Every known command, before environment filtering
+    pub all_commands: Vec<String>,
+    /// Command name -> environments it applies to; unlisted commands apply everywhere
+    pub command_environments: HashMap<String, Vec<String>>,
+    /// Environment `commands` was last filtered for
+    commands_env: Option<String>,
     pub selected_command: usize,
     pub status_text: Arc<str>,  // Use Arc<str> for string interning
+    /// Status restored when the user clears a lingering message
+    pub default_status_text: Arc<str>,
     pub output_lines: Vec<String>,
     pub output_scroll: usize,
     /// Auto-scroll enabled flag - when true, new lines automatically scroll to bottom
@@ -19,11 +89,74 @@ pub struct DashboardState {
     pub progress_percent: f64,
     pub progress_stage: Arc<str>,  // Use Arc<str> for string interning
     pub current_file: Arc<str>,  // Use Arc<str> for string interning
+    /// Phase of a chained command ("Compiling", "Flashing"); empty for single commands
+    pub progress_phase: Arc<str>,
+    /// Name of the most recently started command, shown in the output title
+    pub last_command: Option<String>,
     // Batch update tracking (for future use)
     #[allow(dead_code)]
     pending_updates: Vec<DashboardUpdate>,
     // Advanced progress tracking with time estimates
     pub progress_tracker: Option<ProgressTracker>,
+    /// Shown in place of output before any command has run
+    pub output_placeholder: OutputPlaceholder,
+    /// Lines longer than this many characters are truncated for display
+    pub max_line_width: usize,
+    /// Oldest output lines are dropped once the buffer exceeds this many
+    pub max_output_lines: usize,
+    /// Dim the command list while a command is running
+    pub dim_commands_while_running: bool,
+    /// Cap on progress/state updates applied per second by command parsers
+    pub progress_updates_per_sec: u32,
+    /// Backend that runs "Compile" and the compile half of "Build"
+    pub compile_backend: CompileBackend,
+    /// Files freshly compiled by the last build
+    pub files_compiled_fresh: usize,
+    /// Files the last build reused from the compile cache
+    pub files_reused: usize,
+    /// Full text of truncated lines, keyed by absolute line number
+    full_lines: HashMap<usize, String>,
+    /// Number of lines dropped from the front of `output_lines` so far
+    dropped_lines: usize,
+    /// Hyperlinks visible in the output panel, recorded by the last render
+    pub output_links: Vec<OutputLink>,
+    /// Informational message waiting to be shown as a toast
+    pub notice: Option<String>,
+    /// Error message waiting to be shown as a toast
+    pub error_notice: Option<String>,
+    /// When the running command started (set by `execute_command`)
+    pub command_started: Option<Instant>,
+    /// How long the last finished command took, shown in the Status box
+    pub last_elapsed: Option<Duration>,
+    /// Average past duration of the running compile, when this sketch has history
+    pub expected_secs: Option<f64>,
+    /// Smoothed seconds left in the running compile, shown as "~38s remaining"
+    pub eta_secs: Option<f64>,
+    /// Output scroll offset remembered per command name
+    pub command_scroll: HashMap<String, usize>,
+    /// Process of the running build/upload, killed when the user cancels
+    pub running_child: Option<SharedChild>,
+    /// Set when the user cancelled, so the command thread leaves the "Cancelled" status alone
+    pub cancel_requested: bool,
+    /// Pattern output lines are filtered by; the buffer itself is never changed
+    pub filter: Option<Regex>,
+    /// Whether `filter` is currently applied to the output panel
+    pub filter_enabled: bool,
+    /// Show the lines that do NOT match `filter` instead
+    pub filter_inverted: bool,
+    /// Pattern being typed after `/`, while the filter input is open
+    pub filter_input: Option<Input>,
+    /// Why the typed pattern was rejected, shown next to the input
+    pub filter_error: Option<String>,
+}
+
+/// Clickable OSC 8 link in the output panel, in screen coordinates
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputLink {
+    pub row: u16,
+    pub start_col: u16,
+    pub end_col: u16,
+    pub url: String,
 }
 
 /// Sentinel value to indicate "scroll to bottom" - renderer will calculate actual position
@@ -45,23 +178,27 @@ impl DashboardState {
     /// Create a new dashboard state
     pub fn new() -> Self { //>
         use crate::string_intern::common;
+        let all_commands = vec![ //>
+            // Compile runs on `application.compile_backend` (python pmake or native)
+            "Compile".to_string(),
+            "Upload".to_string(),
+            "Build".to_string(),
+            // Opens the configured port briefly to check it before a long upload
+            "Test".to_string(),
+            "Monitor-Serial".to_string(),
+            "Monitor-MQTT".to_string(),
+            "Clean".to_string(),
+            "All".to_string(),
+            "Help".to_string(),
+        ]; //<
         Self {
-            commands: vec![ //>
-                // "Build".to_string(),         // orriginal python pmake command
-                // "Compile-py".to_string(),       // orriginal python pmake command
-                // "Progress-py".to_string(),      // orriginal python pmake command
-                "Compile".to_string(),
-                // "Upload".to_string(),        // orriginal python pmake command
-                // "Upload_custom".to_string(), // orriginal python pmake command
-                "Upload".to_string(),
-                "Monitor-Serial".to_string(),
-                "Monitor-MQTT".to_string(),
-                "Clean".to_string(),
-                "All".to_string(),
-                "Help".to_string(),
-            ], //<
+            commands: all_commands.clone(),
+            all_commands,
+            command_environments: HashMap::new(),
+            commands_env: None,
             selected_command: 0,
             status_text: common::READY.clone(),
+            default_status_text: common::READY.clone(),
             output_lines: Vec::new(),
             output_scroll: 0,
             auto_scroll_enabled: true,  // Auto-scroll enabled by default
@@ -69,11 +206,154 @@ impl DashboardState {
             progress_percent: 0.0,
             progress_stage: Arc::from(""),
             current_file: Arc::from(""),
+            progress_phase: Arc::from(""),
+            last_command: None,
             pending_updates: Vec::new(),
             progress_tracker: None,
+            output_placeholder: OutputPlaceholder::default(),
+            max_line_width: DEFAULT_MAX_LINE_WIDTH,
+            max_output_lines: DEFAULT_MAX_OUTPUT_LINES,
+            dim_commands_while_running: false,
+            progress_updates_per_sec: DEFAULT_PROGRESS_UPDATES_PER_SEC,
+            compile_backend: CompileBackend::default(),
+            files_compiled_fresh: 0,
+            files_reused: 0,
+            full_lines: HashMap::new(),
+            dropped_lines: 0,
+            output_links: Vec::new(),
+            notice: None,
+            error_notice: None,
+            command_started: None,
+            last_elapsed: None,
+            expected_secs: None,
+            eta_secs: None,
+            command_scroll: HashMap::new(),
+            running_child: None,
+            cancel_requested: false,
+            filter: None,
+            filter_enabled: false,
+            filter_inverted: false,
+            filter_input: None,
+            filter_error: None,
+        }
+    } //<
+    
+    /// Track the running command's process so `cancel_running` can kill it
+    ///
+    /// A cancel that arrived before the process was spawned kills it right away.
+    pub fn attach_child(&mut self, child: SharedChild) { //>
+        if self.cancel_requested {
+            let _ = child.lock().unwrap_or_else(|e| e.into_inner()).kill();
+            return;
+        }
+        self.running_child = Some(child);
+    } //<
+    
+    /// Release the finished process; true if the command was cancelled by the user
+    pub fn detach_child(&mut self) -> bool {
+        self.running_child = None;
+        self.cancel_requested
+    }
+    
+    /// Record how long the command took and report it in the output and a toast
+    pub fn report_elapsed(&mut self, label: &str, succeeded: bool) { //>
+        let elapsed = self.command_started.map(|started| started.elapsed()).unwrap_or_default();
+        self.last_elapsed = Some(elapsed);
+        let message = elapsed_message(label, succeeded, elapsed);
+        self.add_output_line(message.clone());
+        if succeeded {
+            self.notice = Some(message);
+        } else {
+            self.error_notice = Some(message);
+        }
+    } //<
+    
+    /// Refresh the remaining-time estimate from `progress_percent`; nothing without history
+    pub fn update_eta(&mut self, elapsed: Duration) { //>
+        let Some(expected) = self.expected_secs else { return };
+        let estimate = crate::progress_history::estimate_remaining(expected, elapsed.as_secs_f64(), self.progress_percent);
+        let smoothing = crate::progress_history::ETA_SMOOTHING;
+        self.eta_secs = Some(match self.eta_secs {
+            Some(previous) => smoothing * estimate + (1.0 - smoothing) * previous,
+            None => estimate,
+        });
+    } //<
+    
+    /// Stop the running command, killing its process if one is attached
+    ///
+    /// Killing closes the child's pipes, so the command's reader threads hit EOF and exit.
+    /// Returns false if nothing was running.
+    pub fn cancel_running(&mut self) -> bool { //>
+        if !self.is_running {
+            return false;
+        }
+        if let Some(child) = self.running_child.take() {
+            let mut child = child.lock().unwrap_or_else(|e| e.into_inner());
+            let _ = child.kill();
         }
+        self.cancel_requested = true;
+        self.is_running = false;
+        self.set_status_text("Cancelled");
+        self.add_output_line("Command cancelled by user".to_string());
+        true
     } //<
     
+    /// Filter the command list to those applicable to `env` (e.g. "arduino", "esp-idf")
+    ///
+    /// Hidden commands take no list slot; the selection stays on the same command when it remains visible.
+    pub fn apply_environment(&mut self, env: &str) { //>
+        if self.commands_env.as_deref() == Some(env) {
+            return;
+        }
+        let selected = self.selected_command_name().map(str::to_string);
+        self.commands = self.all_commands
+            .iter()
+            .filter(|command| {
+                self.command_environments
+                    .get(command.as_str())
+                    .is_none_or(|envs| envs.iter().any(|e| e.eq_ignore_ascii_case(env)))
+            })
+            .cloned()
+            .collect();
+        self.selected_command = selected
+            .and_then(|name| self.commands.iter().position(|c| *c == name))
+            .unwrap_or(0)
+            .min(self.commands.len().saturating_sub(1));
+        self.commands_env = Some(env.to_string());
+    } //<
+
+    /// Name of the highlighted command, or `None` when the list is empty
+    pub fn selected_command_name(&self) -> Option<&str> {
+        self.commands.get(self.selected_command).map(|c| c.as_str())
+    }
+    
+    /// URL of the output link under a screen position, if any
+    pub fn link_at(&self, column: u16, row: u16) -> Option<&str> {
+        self.output_links.iter()
+            .find(|link| link.row == row && column >= link.start_col && column < link.end_col)
+            .map(|link| link.url.as_str())
+    }
+    
+    /// Highlight the command at `index`, restoring its scroll offset while the output is still its own
+    pub fn select_command(&mut self, index: usize) { //>
+        self.selected_command = index;
+        let Some(name) = self.commands.get(index) else { return };
+        if self.output_lines.is_empty() || self.last_command.as_ref() != Some(name) {
+            return;
+        }
+        if let Some(&offset) = self.command_scroll.get(name) {
+            self.output_scroll = offset;
+            self.auto_scroll_enabled = false;
+        }
+    } //<
+
+    /// Store the current scroll offset for the command whose output is shown
+    pub fn remember_scroll(&mut self) {
+        if let Some(command) = &self.last_command {
+            self.command_scroll.insert(command.clone(), self.output_scroll);
+        }
+    }
+
     /// Scroll output up - disables auto-scroll when user manually scrolls
     pub fn scroll_output_up(&mut self, amount: usize) {
         // User manually scrolled - disable auto-scroll
@@ -135,15 +415,32 @@ impl DashboardState {
         self.auto_scroll_enabled = true;
     }
     
+    /// Set the display width for output lines, raised to `MIN_MAX_LINE_WIDTH` if smaller
+    pub fn set_max_line_width(&mut self, width: usize) {
+        self.max_line_width = width.max(MIN_MAX_LINE_WIDTH);
+    }
+    
     /// Add a line to output, enforcing size limit
     /// If auto-scroll is enabled, marks scroll position for "scroll to bottom" during render
+    /// Lines over `max_line_width` are stored truncated with an ellipsis; the full text stays retrievable
     pub fn add_output_line(&mut self, line: String) {
+        let line = match line.char_indices().nth(self.max_line_width) {
+            Some((cut, _)) => {
+                let display = format!("{}…", &line[..cut]);
+                self.full_lines.insert(self.dropped_lines + self.output_lines.len(), line);
+                display
+            }
+            None => line,
+        };
         self.output_lines.push(line);
         
         // Enforce size limit by removing oldest lines
-        if self.output_lines.len() > MAX_OUTPUT_LINES {
-            let remove_count = self.output_lines.len() - MAX_OUTPUT_LINES;
+        if self.output_lines.len() > self.max_output_lines {
+            let remove_count = self.output_lines.len() - self.max_output_lines;
             self.output_lines.drain(0..remove_count);
+            self.dropped_lines += remove_count;
+            let first_kept = self.dropped_lines;
+            self.full_lines.retain(|&abs, _| abs >= first_kept);
             
             // Adjust scroll position if needed (but preserve SCROLL_TO_BOTTOM sentinel)
             if self.output_scroll != SCROLL_TO_BOTTOM {
@@ -162,6 +459,102 @@ impl DashboardState {
         }
     }
     
+    /// Remove all output lines along with any stored full-length text
+    pub fn clear_output(&mut self) {
+        self.output_lines.clear();
+        self.full_lines.clear();
+        self.dropped_lines = 0;
+        if let Some(command) = &self.last_command {
+            self.command_scroll.insert(command.clone(), 0);
+        }
+    }
+    
+    /// Full content of the output line at `index`, including text truncated for display
+    pub fn full_output_line(&self, index: usize) -> Option<&str> {
+        self.full_lines.get(&(self.dropped_lines + index))
+            .map(String::as_str)
+            .or_else(|| self.output_lines.get(index).map(String::as_str))
+    }
+    
+    /// Output line the copy-line action targets: the newest shown line while following
+    /// the output, otherwise the line at the top of the scrolled view
+    pub fn copy_target_line(&self) -> Option<usize> { //>
+        let shown = self.visible_output_indices();
+        if self.output_scroll == SCROLL_TO_BOTTOM {
+            return shown.last().copied();
+        }
+        shown.get(self.output_scroll.min(shown.len().saturating_sub(1))).copied()
+    } //<
+    
+    /// Open the filter input, pre-filled with the current pattern
+    pub fn open_filter_input(&mut self) {
+        let pattern = self.filter.as_ref().map(|re| re.as_str().to_string()).unwrap_or_default();
+        self.filter_input = Some(Input::new(pattern));
+        self.filter_error = None;
+    }
+    
+    /// Close the filter input without changing the active filter
+    pub fn close_filter_input(&mut self) {
+        self.filter_input = None;
+        self.filter_error = None;
+    }
+    
+    /// Compile the typed pattern and apply it; an empty pattern removes the filter
+    ///
+    /// An invalid regex keeps the input open with the parser's message in `filter_error`.
+    pub fn apply_filter_input(&mut self) { //>
+        let Some(input) = &self.filter_input else { return };
+        let pattern = input.value().trim();
+        if pattern.is_empty() {
+            self.filter = None;
+            self.filter_enabled = false;
+        } else {
+            match Regex::new(pattern) {
+                Ok(re) => {
+                    self.filter = Some(re);
+                    self.filter_enabled = true;
+                }
+                Err(e) => {
+                    // The last line of a regex error is the one-line reason ("error: unclosed group")
+                    let message = e.to_string();
+                    let reason = message.lines().last().unwrap_or_default().trim_start_matches("error: ");
+                    self.filter_error = Some(format!("Invalid pattern: {}", reason));
+                    return;
+                }
+            }
+        }
+        self.close_filter_input();
+        self.output_scroll = SCROLL_TO_BOTTOM;
+    } //<
+    
+    /// Turn the configured filter on or off; false if there is no filter to toggle
+    pub fn toggle_filter(&mut self) -> bool {
+        if self.filter.is_none() {
+            return false;
+        }
+        self.filter_enabled = !self.filter_enabled;
+        self.output_scroll = SCROLL_TO_BOTTOM;
+        true
+    }
+    
+    /// The filter applied to the output panel, if any
+    pub fn active_filter(&self) -> Option<&Regex> {
+        self.filter.as_ref().filter(|_| self.filter_enabled)
+    }
+    
+    /// Indices of the output lines the panel shows under the active filter
+    pub fn visible_output_indices(&self) -> Vec<usize> { //>
+        match self.active_filter() {
+            None => (0..self.output_lines.len()).collect(),
+            Some(re) => self.output_lines
+                .iter()
+                .enumerate()
+                .filter(|(_, line)| re.is_match(line) != self.filter_inverted)
+                .map(|(index, _)| index)
+                .collect(),
+        }
+    } //<
+    
     /// Queue an update to be applied in batch (for future use)
     #[allow(dead_code)]
     pub fn queue_update(&mut self, update: DashboardUpdate) {
@@ -202,6 +595,11 @@ impl DashboardState {
         self.status_text = intern_string(text);
     }
     
+    /// Reset the status text to the configured default
+    pub fn clear_status_text(&mut self) {
+        self.status_text = self.default_status_text.clone();
+    }
+    
     /// Set progress stage using string interning
     pub fn set_progress_stage(&mut self, stage: &str) {
         use crate::string_intern::intern_string;
@@ -214,6 +612,24 @@ impl DashboardState {
         self.current_file = intern_string(file);
     }
     
+    /// Start a new phase of a chained command, restarting the progress bar from zero
+    pub fn begin_phase(&mut self, phase: &str) {
+        use crate::string_intern::intern_string;
+        self.progress_phase = intern_string(phase);
+        self.progress_percent = 0.0;
+        self.progress_tracker = None;
+        self.set_progress_stage(phase);
+        self.set_current_file("");
+    }
+    
+    /// Cache summary for the completion status, e.g. "Compiled 3 files, reused 42"
+    pub fn compile_cache_summary(&self) -> Option<String> {
+        if self.files_compiled_fresh == 0 && self.files_reused == 0 {
+            return None;
+        }
+        Some(format!("Compiled {} files, reused {}", self.files_compiled_fresh, self.files_reused))
+    }
+    
     /// Initialize progress tracking for a new operation
     pub fn start_progress_tracking(&mut self, total_items: Option<usize>, historical_data: Option<crate::progress_tracker::HistoricalData>) {
         let mut tracker = ProgressTracker::new(total_items);
@@ -260,3 +676,209 @@ impl DashboardState {
         self.set_progress_stage(stage_name);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_state_recovers_from_poisoned_mutex() {
+        let dashboard = Arc::new(Mutex::new(DashboardState::new()));
+        let poisoner = dashboard.clone();
+        let _ = std::thread::spawn(move || {
+            let _guard = poisoner.lock().unwrap();
+            panic!("reader thread panic");
+        }).join();
+        assert!(dashboard.is_poisoned());
+
+        let mut state = lock_state(&dashboard);
+        state.set_status_text("still alive");
+        assert_eq!(&*state.status_text, "still alive");
+    }
+
+    #[test]
+    fn test_command_restricted_to_esp_idf_hidden_for_arduino() {
+        let mut state = DashboardState::new();
+        state.all_commands.insert(1, "Erase Flash".to_string());
+        state.command_environments.insert("Erase Flash".to_string(), vec!["esp-idf".to_string()]);
+
+        state.apply_environment("arduino");
+        assert!(!state.commands.iter().any(|c| c == "Erase Flash"));
+        assert_eq!(state.commands.len(), state.all_commands.len() - 1);
+
+        state.apply_environment("esp-idf");
+        assert_eq!(state.commands[1], "Erase Flash");
+    }
+
+    #[test]
+    fn test_scroll_offset_restored_when_reselecting_command() {
+        let mut state = DashboardState::new();
+        state.last_command = Some("Build".to_string());
+        for i in 0..50 {
+            state.add_output_line(format!("line {}", i));
+        }
+        state.output_scroll = 12;
+        state.remember_scroll();
+
+        let build = state.commands.iter().position(|c| c == "Build").unwrap();
+        state.select_command(0);
+        state.output_scroll = 40;
+        state.select_command(build);
+        assert_eq!(state.output_scroll, 12);
+        assert!(!state.auto_scroll_enabled);
+
+        // A fresh run starts the command back at the top
+        state.clear_output();
+        assert_eq!(state.command_scroll.get("Build"), Some(&0));
+    }
+
+    #[test]
+    fn test_begin_phase_resets_progress_between_compile_and_upload() {
+        let mut state = DashboardState::new();
+        state.begin_phase("Compiling");
+        state.start_progress_tracking(Some(10), None);
+        state.progress_percent = 100.0;
+        state.set_progress_stage("Complete");
+
+        state.begin_phase("Flashing");
+        assert_eq!(state.progress_percent, 0.0);
+        assert_eq!(&*state.progress_phase, "Flashing");
+        assert!(state.progress_tracker.is_none());
+    }
+
+    #[test]
+    fn test_long_line_truncated_for_display_but_retrievable() {
+        let mut state = DashboardState::new();
+        state.max_line_width = 16;
+        let blob = "A".repeat(100);
+        state.add_output_line("short".to_string());
+        state.add_output_line(blob.clone());
+
+        assert_eq!(state.output_lines[1], format!("{}…", "A".repeat(16)));
+        assert_eq!(state.full_output_line(1), Some(blob.as_str()));
+        assert_eq!(state.full_output_line(0), Some("short"));
+
+        // Full text follows its line as old lines are dropped
+        for i in 0..state.max_output_lines - 1 {
+            state.add_output_line(format!("line {}", i));
+        }
+        assert_eq!(state.full_output_line(0), Some(blob.as_str()));
+        state.add_output_line("one more".to_string());
+        assert!(state.full_lines.is_empty());
+    }
+
+    #[test]
+    fn test_zero_line_width_is_clamped() {
+        let mut state = DashboardState::new();
+        state.set_max_line_width(0);
+        assert_eq!(state.max_line_width, MIN_MAX_LINE_WIDTH);
+
+        let line = "Compiling sketch/main.cpp with a long list of flags";
+        state.add_output_line(line.to_string());
+        let expected: String = line.chars().take(MIN_MAX_LINE_WIDTH).collect();
+        assert_eq!(state.output_lines[0], format!("{}…", expected));
+        assert_eq!(state.full_output_line(0), Some(line));
+
+        state.set_max_line_width(200);
+        state.add_output_line(line.to_string());
+        assert_eq!(state.output_lines[1], line);
+    }
+
+    #[test]
+    fn test_output_cap_drains_oldest_and_keeps_scroll_on_same_line() {
+        let mut state = DashboardState::new();
+        state.max_output_lines = 5;
+        for i in 0..5 {
+            state.add_output_line(format!("line {}", i));
+        }
+        state.output_scroll = 3;
+
+        state.add_output_line("line 5".to_string());
+        state.add_output_line("line 6".to_string());
+        assert_eq!(state.output_lines.len(), 5);
+        assert_eq!(state.output_lines[0], "line 2");
+        assert_eq!(state.output_scroll, 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_cancel_running_kills_attached_child() {
+        let child = std::process::Command::new("sleep").arg("30").spawn().unwrap();
+        let child: SharedChild = Arc::new(Mutex::new(child));
+        let mut state = DashboardState::new();
+        assert!(!state.cancel_running());
+
+        state.is_running = true;
+        state.attach_child(child.clone());
+        assert!(state.cancel_running());
+        assert!(!state.is_running);
+        assert_eq!(&*state.status_text, "Cancelled");
+
+        let status = crate::commands::process_handler::wait_shared(&child).unwrap();
+        assert!(!status.success());
+        assert!(state.detach_child());
+    }
+
+    #[test]
+    fn test_cancel_before_attach_kills_child_on_attach() {
+        let mut state = DashboardState::new();
+        state.is_running = true;
+        assert!(state.cancel_running());
+
+        let child = std::process::Command::new("sleep").arg("30").spawn().unwrap();
+        let child: SharedChild = Arc::new(Mutex::new(child));
+        state.attach_child(child.clone());
+        assert!(state.running_child.is_none());
+
+        let status = crate::commands::process_handler::wait_shared(&child).unwrap();
+        assert!(!status.success());
+    }
+
+    #[test]
+    fn test_report_elapsed_records_duration_and_queues_toast() {
+        let mut state = DashboardState::new();
+        state.command_started = Some(Instant::now() - Duration::from_millis(42_300));
+        state.report_elapsed("Compile", true);
+
+        let elapsed = state.last_elapsed.unwrap();
+        assert!(elapsed >= Duration::from_millis(42_300));
+        let notice = state.notice.take().unwrap();
+        assert!(notice.starts_with("Compile finished in 42."));
+        assert_eq!(state.output_lines.last(), Some(&notice));
+
+        state.report_elapsed("Compile", false);
+        assert!(state.error_notice.unwrap().starts_with("Compile failed after"));
+        assert_eq!(elapsed_message("Upload", true, Duration::from_millis(1300)), "Upload finished in 1.3s");
+    }
+
+    #[test]
+    fn test_output_filter_matches_inverts_and_rejects_bad_pattern() {
+        let mut state = DashboardState::new();
+        for line in ["Compiling sketch...", "xtensa-esp32-elf-g++ -c foo.cpp", "xtensa-esp32-elf-g++ -c bar.cpp", "Sketch uses 1234 bytes"] {
+            state.add_output_line(line.to_string());
+        }
+
+        state.open_filter_input();
+        state.filter_input = Some(Input::new("(xtensa".to_string()));
+        state.apply_filter_input();
+        assert!(state.filter_input.is_some());
+        assert!(state.filter_error.as_deref().unwrap().starts_with("Invalid pattern:"));
+        assert!(state.active_filter().is_none());
+
+        state.filter_input = Some(Input::new("^xtensa".to_string()));
+        state.apply_filter_input();
+        assert!(state.filter_input.is_none() && state.filter_error.is_none());
+        assert_eq!(state.visible_output_indices(), vec![1, 2]);
+
+        state.filter_inverted = true;
+        assert_eq!(state.visible_output_indices(), vec![0, 3]);
+        assert_eq!(state.output_lines.len(), 4);
+
+        // Reopening pre-fills the pattern; toggling off shows everything again
+        state.open_filter_input();
+        assert_eq!(state.filter_input.as_ref().unwrap().value(), "^xtensa");
+        state.close_filter_input();
+        assert!(state.toggle_filter());
+        assert_eq!(state.visible_output_indices().len(), 4);
+    }
+}
@@ -1,7 +1,7 @@
 // Batch dashboard state updates module
 // Reduces lock contention on Arc<Mutex<DashboardState>> by batching updates
 
-use crate::dashboard::DashboardState;
+use crate::dashboard::{DashboardState, lock_state};
 use std::sync::{Arc, Mutex};
 
 /// Batch of dashboard state updates (for future use)
@@ -77,7 +77,7 @@ impl DashboardUpdateBatch {
     /// Apply all batched updates to dashboard state (single lock acquisition, for future use)
     #[allow(dead_code)]
     pub fn apply(&self, dashboard: Arc<Mutex<DashboardState>>) {
-        let mut state = dashboard.lock().unwrap();
+        let mut state = lock_state(&dashboard);
         
         // Apply status text
         if let Some(ref text) = self.status_text {
@@ -1,7 +1,7 @@
 // Error formatting utility module
 // Standardizes error message formatting across the application
 
-use crate::dashboard::DashboardState;
+use crate::dashboard::{DashboardState, lock_state};
 use std::sync::{Arc, Mutex};
 
 /// Format an error message consistently (for future use)
@@ -36,7 +36,7 @@ pub fn report_error(
 ) {
     use crate::string_intern::intern_string;
     let formatted = format_error(message);
-    let mut state = dashboard.lock().unwrap();
+    let mut state = lock_state(&dashboard);
     state.status_text = intern_string(&formatted);
     state.add_output_line(formatted);
 }
@@ -48,7 +48,7 @@ pub fn report_warning(
     message: &str,
 ) {
     let formatted = format_warning(message);
-    let mut state = dashboard.lock().unwrap();
+    let mut state = lock_state(&dashboard);
     state.add_output_line(formatted);
 }
 
@@ -59,7 +59,7 @@ pub fn report_info(
     message: &str,
 ) {
     let formatted = format_info(message);
-    let mut state = dashboard.lock().unwrap();
+    let mut state = lock_state(&dashboard);
     state.add_output_line(formatted);
 }
 
@@ -71,7 +71,7 @@ pub fn report_success(
 ) {
     use crate::string_intern::intern_string;
     let formatted = format_success(message);
-    let mut state = dashboard.lock().unwrap();
+    let mut state = lock_state(&dashboard);
     state.status_text = intern_string(&formatted);
     state.add_output_line(formatted);
 }
@@ -85,7 +85,7 @@ pub fn report_error_with_context(
 ) {
     use crate::string_intern::intern_string;
     let formatted = format!("{} ({})", format_error(message), context);
-    let mut state = dashboard.lock().unwrap();
+    let mut state = lock_state(&dashboard);
     state.status_text = intern_string(&formatted);
     state.add_output_line(formatted);
 }
@@ -1,14 +1,16 @@
 // Event handling module
 // Handles all keyboard and mouse events
 
-use crate::dashboard::DashboardState;
+use crate::dashboard::{DashboardState, lock_state};
 use crate::process_manager::ProcessManager;
 use crate::command_helper::execute_command;
 use crate::constants::HWND_MAIN_CONTENT_BOX;
-use crate::field_editor::{FieldEditorState, SettingsFields};
-use crate::layout_manager::LayoutManager;
+use crate::field_editor::{FieldEditorState, SettingsField, SettingsFields};
+use crate::key_bindings::{KeyBindings, ACTION_CANCEL, ACTION_CLEAR_STATUS, ACTION_COPY_LINE, ACTION_COPY_STATUS, ACTION_EXPORT_OUTPUT, ACTION_FILTER_OUTPUT, ACTION_INVERT_FILTER, ACTION_TOGGLE_FILTER};
+use crate::clipboard::{copy_with_notice, ClipboardSink};
 use crate::settings_manager::SettingsManager;
 use crate::profile_state::ProfileState;
+use crate::tab_bar_behavior::TabBarBehavior;
 
 use crossterm::event::{KeyCode, KeyModifiers, MouseEventKind};
 use tui_input::{Input, InputRequest};
@@ -19,59 +21,116 @@ use std::sync::{Arc, Mutex};
 /// Handle dashboard keyboard events
 pub fn handle_dashboard_key_event( //>
     key_code: crossterm::event::KeyCode,
+    key_modifiers: KeyModifiers,
+    key_bindings: &KeyBindings,
     dashboard: &Arc<Mutex<DashboardState>>,
     settings_manager: &SettingsManager,
     process_manager: Arc<ProcessManager>,
+    clipboard: &mut dyn ClipboardSink,
 ) -> bool { //>
     // Returns true if event was handled, false otherwise
     match key_code {
-        crossterm::event::KeyCode::Esc => {
-            // Cancel running command if one is active
-            let is_running = {
-                let state = dashboard.lock().unwrap();
-                state.is_running
-            };
-            
-            if is_running {
+        _ if key_bindings.matches(ACTION_CANCEL, key_code, key_modifiers) => {
+            // Cancel running command if one is active (kills its process); kill_all also
+            // reaches registered processes the direct child kill misses
+            if lock_state(&dashboard).cancel_running() {
                 process_manager.kill_all();
-                let mut state = dashboard.lock().unwrap();
-                state.is_running = false;
-                state.set_status_text("Command cancelled");
-                state.add_output_line("Command cancelled by user".to_string());
             }
             true
         }
+        _ if key_bindings.matches(ACTION_CLEAR_STATUS, key_code, key_modifiers) => {
+            lock_state(&dashboard).clear_status_text();
+            true
+        }
+        _ if key_bindings.matches(ACTION_EXPORT_OUTPUT, key_code, key_modifiers) => {
+            let settings = settings_manager.get();
+            let mut state = lock_state(&dashboard);
+            match crate::output_export::export_output(&crate::output_export::logs_dir(), &state, &settings) {
+                Ok(path) => state.notice = Some(format!("Output exported to {}", path.display())),
+                Err(e) => state.error_notice = Some(format!("Failed to export output: {}", e)),
+            }
+            true
+        }
+        _ if key_bindings.matches(ACTION_COPY_STATUS, key_code, key_modifiers) => {
+            let mut state = lock_state(&dashboard);
+            let status = state.status_text.to_string();
+            copy_with_notice(&mut state, clipboard, status, "Status");
+            true
+        }
+        _ if key_bindings.matches(ACTION_COPY_LINE, key_code, key_modifiers) => {
+            // Copies the stored full text, not the truncated display line
+            let mut state = lock_state(&dashboard);
+            let line = state.copy_target_line()
+                .and_then(|index| state.full_output_line(index))
+                .map(str::to_string);
+            match line {
+                Some(line) => copy_with_notice(&mut state, clipboard, line, "Output line"),
+                None => state.notice = Some("No output to copy".to_string()),
+            }
+            true
+        }
+        _ if key_bindings.matches(ACTION_FILTER_OUTPUT, key_code, key_modifiers) => {
+            lock_state(&dashboard).open_filter_input();
+            true
+        }
+        _ if key_bindings.matches(ACTION_TOGGLE_FILTER, key_code, key_modifiers) => {
+            lock_state(&dashboard).toggle_filter();
+            true
+        }
+        _ if key_bindings.matches(ACTION_INVERT_FILTER, key_code, key_modifiers) => {
+            let mut state = lock_state(&dashboard);
+            state.filter_inverted = !state.filter_inverted;
+            state.output_scroll = crate::dashboard::SCROLL_TO_BOTTOM;
+            true
+        }
         crossterm::event::KeyCode::Up | crossterm::event::KeyCode::Char('k') => {
-            let mut state = dashboard.lock().unwrap();
+            let mut state = lock_state(&dashboard);
             if state.selected_command > 0 {
-                state.selected_command -= 1;
+                let index = state.selected_command - 1;
+                state.select_command(index);
             }
             true
         }
         crossterm::event::KeyCode::Down | crossterm::event::KeyCode::Char('j') => {
-            let mut state = dashboard.lock().unwrap();
+            let mut state = lock_state(&dashboard);
             if state.selected_command < state.commands.len().saturating_sub(1) {
-                state.selected_command += 1;
+                let index = state.selected_command + 1;
+                state.select_command(index);
             }
             true
         }
         crossterm::event::KeyCode::Enter => {
-            // Get command and latest settings
+            // Get command and latest settings (nothing to execute if no commands are configured)
             let command = {
-                let state = dashboard.lock().unwrap();
-                state.commands[state.selected_command].clone()
+                let state = lock_state(&dashboard);
+                match state.selected_command_name() {
+                    Some(command) => command.to_string(),
+                    None => return true,
+                }
             };
             
             // Reload settings from disk to ensure we have the absolute latest
             // (in case settings were updated via dropdown selection)
             let _ = settings_manager.reload();
+
+            // `sketch_directory` set to the sketch file itself: use its folder and take the name from the file
+            let sketch_directory = settings_manager.get().sketch_directory;
+            if let Some((dir, name)) = crate::path_utils::split_sketch_file(std::path::Path::new(&sketch_directory)) {
+                let dir = dir.to_string_lossy().to_string();
+                let _ = settings_manager.update(|settings| {
+                    settings.sketch_directory = dir.clone();
+                    settings.sketch_name = name.clone();
+                });
+                lock_state(&dashboard).notice =
+                    Some(format!("Sketch directory pointed at a file; using {} with sketch '{}'", dir, name));
+            }
             
             // Get latest settings from manager (always up-to-date)
             let settings = settings_manager.get();
             
             // Debug: Log settings being used for command
             {
-                let mut state = dashboard.lock().unwrap();
+                let mut state = lock_state(&dashboard);
                 state.add_output_line(format!("[DEBUG] Command: {}", command));
                 state.add_output_line(format!("[DEBUG] Sketch directory: '{}'", settings.sketch_directory));
                 state.add_output_line(format!("[DEBUG] Sketch name: '{}'", settings.sketch_name));
@@ -86,13 +145,38 @@ pub fn handle_dashboard_key_event( //>
     }
 }
 
+/// Handle a key while the dashboard output filter input is open
+///
+/// Enter applies the pattern, Esc closes the input, other keys edit it.
+/// Returns false when the input is not open so the key is handled normally.
+pub fn handle_filter_input_key(
+    key_code: KeyCode,
+    key_modifiers: KeyModifiers,
+    dashboard: &Arc<Mutex<DashboardState>>,
+) -> bool { //>
+    let mut state = lock_state(dashboard);
+    match key_code {
+        _ if state.filter_input.is_none() => return false,
+        KeyCode::Enter => state.apply_filter_input(),
+        KeyCode::Esc => state.close_filter_input(),
+        _ => {
+            if let Some(input) = state.filter_input.as_mut() {
+                handle_editing_input(key_code, key_modifiers, input);
+            }
+            state.filter_error = None;
+        }
+    }
+    true
+} //<
+
 /// Result of handling a field editor event
 #[derive(Debug)]
 pub enum FieldEditorEventResult { //>
     Continue,
-    Exit,
     Toast(Toast),
     StateChanged(FieldEditorState),
+    /// Switch state and explain why (e.g. manual port entry after enumeration failed)
+    StateChangedWithToast(FieldEditorState, Toast),
 } //<
 
 /// Result of handling a profile event
@@ -228,19 +312,7 @@ fn handle_editing_key_event(
     settings_fields: &SettingsFields,
 ) -> FieldEditorEventResult {
     match key_code {
-        KeyCode::Enter => {
-            // Confirm edit - use SettingsManager to update and save atomically
-            let value = input.value().to_string();
-            match settings_manager.update(|settings| {
-                settings_fields.set_value(settings, field_index, value);
-            }) {
-                Err(e) => FieldEditorEventResult::Toast(Toast::new(
-                    format!("Failed to save settings: {}", e),
-                    ToastType::Error,
-                )),
-                Ok(_) => FieldEditorEventResult::Toast(Toast::new("Settings saved".to_string(), ToastType::Success)),
-            }
-        }
+        KeyCode::Enter => commit_edit(field_index, input, settings_manager, settings_fields),
         KeyCode::Esc => {
             FieldEditorEventResult::StateChanged(FieldEditorState::Selected { field_index })
         }
@@ -248,6 +320,49 @@ fn handle_editing_key_event(
     }
 }
 
+/// Open the editor for a field: its dropdown, or the text input when there is nothing to pick from
+///
+/// Falls back to typing the value when port enumeration failed (with a toast saying why)
+/// or when the board list is still loading, empty or failed.
+pub fn open_field_editor(
+    field_index: usize,
+    settings: &crate::settings::Settings,
+    settings_fields: &SettingsFields,
+) -> FieldEditorEventResult { //>
+    let current_value = settings_fields.get_value(settings, field_index);
+    let text_editor = |value: String| {
+        let mut input = Input::new(value);
+        let _ = input.handle(InputRequest::GoToEnd);
+        FieldEditorState::Editing { field_index, input }
+    };
+    if !settings_fields.is_dropdown(field_index) {
+        return FieldEditorEventResult::StateChanged(text_editor(current_value));
+    }
+
+    let options = settings_fields.get_dropdown_options(field_index, settings);
+    if let Some(message) = crate::field_editor::port_enumeration_error(&options) {
+        // Ports could not be listed: say why and fall back to typing the port
+        return FieldEditorEventResult::StateChangedWithToast(
+            text_editor(current_value),
+            Toast::new(message.to_string(), ToastType::Error),
+        );
+    }
+    if SettingsField::from_index(field_index) == Some(SettingsField::BoardModel)
+        && options.iter().all(|opt| crate::field_editor::is_placeholder_option(opt))
+    {
+        return FieldEditorEventResult::StateChanged(text_editor(current_value));
+    }
+
+    let selected_index = options.iter()
+        .position(|opt| settings_fields.option_value(field_index, opt) == current_value)
+        .unwrap_or(0);
+    FieldEditorEventResult::StateChanged(FieldEditorState::Selecting {
+        field_index,
+        selected_index,
+        options,
+    })
+} //<
+
 /// Handle keyboard events when a field is selected
 fn handle_selected_key_event(
     key_code: KeyCode,
@@ -261,32 +376,7 @@ fn handle_selected_key_event(
 ) -> FieldEditorEventResult {
     let settings = settings_manager.get(); // Get current settings
     match key_code {
-        KeyCode::Char('q') | KeyCode::Char('Q') => FieldEditorEventResult::Exit,
-        KeyCode::Enter => {
-            // Check if field is a dropdown
-            if settings_fields.is_dropdown(field_index) {
-                // Open dropdown
-                let options = settings_fields.get_dropdown_options(field_index, &settings);
-                let current_value = settings_fields.get_value(&settings, field_index);
-                let selected_index = options.iter()
-                    .position(|opt| opt == &current_value)
-                    .unwrap_or(0);
-                FieldEditorEventResult::StateChanged(FieldEditorState::Selecting {
-                    field_index,
-                    selected_index,
-                    options,
-                })
-            } else {
-                // Start text editing
-                let current_value = settings_fields.get_value(&settings, field_index);
-                let mut input = Input::new(current_value);
-                let _ = input.handle(InputRequest::GoToEnd);
-                FieldEditorEventResult::StateChanged(FieldEditorState::Editing {
-                    field_index,
-                    input,
-                })
-            }
-        }
+        KeyCode::Enter => open_field_editor(field_index, &settings, settings_fields),
         KeyCode::Up | KeyCode::Char('k') => {
             profile_state.move_up();
             FieldEditorEventResult::Continue
@@ -302,13 +392,13 @@ fn handle_selected_key_event(
             })
         }
         KeyCode::Left | KeyCode::Char('h') => {
-            if tab_style != TabBarStyle::BoxStatic && tab_style != TabBarStyle::TextStatic {
+            if TabBarBehavior::for_style(tab_style).keyboard_navigable {
                 main_content_tab_bar.navigate_previous(registry);
             }
             FieldEditorEventResult::Continue
         }
         KeyCode::Right | KeyCode::Char('l') => {
-            if tab_style != TabBarStyle::BoxStatic && tab_style != TabBarStyle::TextStatic {
+            if TabBarBehavior::for_style(tab_style).keyboard_navigable {
                 main_content_tab_bar.navigate_next(registry);
             }
             FieldEditorEventResult::Continue
@@ -317,6 +407,51 @@ fn handle_selected_key_event(
     }
 }
 
+/// Commit the edited value: reject invalid values and keep the editor open,
+/// otherwise update and save atomically through the SettingsManager
+pub fn commit_edit(
+    field_index: usize,
+    input: &Input,
+    settings_manager: &SettingsManager,
+    settings_fields: &SettingsFields,
+) -> FieldEditorEventResult { //>
+    let value = input.value().to_string();
+    if let Err(message) = settings_fields.validate(field_index, &value) {
+        return FieldEditorEventResult::StateChangedWithToast(
+            FieldEditorState::Editing { field_index, input: input.clone() },
+            Toast::new(message, ToastType::Error),
+        );
+    }
+    match settings_manager.update(|settings| {
+        settings_fields.set_value(settings, field_index, value);
+    }) {
+        Err(e) => FieldEditorEventResult::Toast(Toast::new(
+            format!("Failed to save settings: {}", e),
+            ToastType::Error,
+        )),
+        Ok(_) => FieldEditorEventResult::Toast(Toast::new("Settings saved".to_string(), ToastType::Success)),
+    }
+} //<
+
+/// Commit an open edit once input has been idle for `autosave_secs` (0 disables)
+///
+/// Returns `None` when nothing is being edited or the threshold has not been reached.
+pub fn autosave_idle_edit(
+    editor_state: &FieldEditorState,
+    idle: std::time::Duration,
+    autosave_secs: u64,
+    settings_manager: &SettingsManager,
+    settings_fields: &SettingsFields,
+) -> Option<FieldEditorEventResult> { //>
+    let FieldEditorState::Editing { field_index, input } = editor_state else {
+        return None;
+    };
+    if autosave_secs == 0 || idle < std::time::Duration::from_secs(autosave_secs) {
+        return None;
+    }
+    Some(commit_edit(*field_index, input, settings_manager, settings_fields))
+} //<
+
 /// Handle keyboard events when selecting from a dropdown (for Enter/Esc only)
 fn handle_selecting_key_event(
     key_code: KeyCode,
@@ -328,12 +463,27 @@ fn handle_selecting_key_event(
 ) -> FieldEditorEventResult {
     match key_code {
         KeyCode::Enter => {
+            // Placeholder entries aren't values - fall back to manual entry instead
+            if options.get(selected_index).is_some_and(|opt| crate::field_editor::is_placeholder_option(opt)) {
+                let current_value = settings_fields.get_value(&settings_manager.get(), field_index);
+                let mut input = Input::new(current_value);
+                let _ = input.handle(InputRequest::GoToEnd);
+                return FieldEditorEventResult::StateChanged(FieldEditorState::Editing {
+                    field_index,
+                    input,
+                });
+            }
             // Confirm selection - use SettingsManager to update and save atomically
             if selected_index < options.len() {
-                let selected_value = options[selected_index].clone();
+                // Labelled entries (e.g. USB port descriptions) store only their value
+                let selected_value = settings_fields.option_value(field_index, &options[selected_index]).to_string();
                 // Update settings and save
                 match settings_manager.update(|settings| {
-                    settings_fields.set_value(settings, field_index, selected_value.clone());
+                    if SettingsField::from_index(field_index) == Some(SettingsField::BoardModel) {
+                        crate::field_editor::apply_board_model(settings, selected_value.clone(), &crate::board_catalog::loaded().unwrap_or_default());
+                    } else {
+                        settings_fields.set_value(settings, field_index, selected_value.clone());
+                    }
                 }) {
                     Err(e) => FieldEditorEventResult::Toast(Toast::new(
                         format!("Failed to save settings: {}", e),
@@ -348,6 +498,11 @@ fn handle_selecting_key_event(
                                 format!("Warning: Settings may not have saved correctly. Expected '{}', got '{}'", selected_value, saved_value),
                                 ToastType::Error,
                             ))
+                        } else if crate::field_editor::is_remembered_port(options, selected_index) {
+                            FieldEditorEventResult::Toast(Toast::new(
+                                format!("Saved port {} - it is not currently connected", selected_value),
+                                ToastType::Error,
+                            ))
                         } else {
                             FieldEditorEventResult::Toast(Toast::new("Settings saved".to_string(), ToastType::Success))
                         }
@@ -481,13 +636,16 @@ pub fn handle_dashboard_scroll(
             
             if is_over_output {
                 // Modify Arc directly to avoid overwriting state
-                if let Ok(mut state) = dashboard_arc.lock() {
+                {
+                    let mut state = lock_state(&dashboard_arc);
                     match mouse_event.kind {
                         MouseEventKind::ScrollUp => {
                             state.scroll_output_up(3);
+                            state.remember_scroll();
                         }
                         MouseEventKind::ScrollDown => {
                             state.scroll_output_down(3);
+                            state.remember_scroll();
                         }
                         _ => {}
                     }
@@ -498,68 +656,23 @@ pub fn handle_dashboard_scroll(
 }
 
 /// Handle mouse clicks on settings fields
+///
+/// Opens the field exactly like Enter does, including the manual-entry fallbacks.
 pub fn handle_settings_field_click(
     mouse_event: &crossterm::event::MouseEvent,
     settings_manager: &SettingsManager,
     settings_fields: &SettingsFields,
     registry: &RectRegistry,
     main_content_tab_bar: &TabBarManager,
-) -> Option<FieldEditorState> {
+) -> Option<FieldEditorEventResult> {
     let settings = settings_manager.get(); // Get current settings
     if let Some(active_tab_idx) = registry.get_active_tab(main_content_tab_bar.handle()) {
         if let Some(tab_bar_state) = registry.get_tab_bar_state(main_content_tab_bar.handle()) {
             if let Some(tab_config) = tab_bar_state.tab_configs.get(active_tab_idx) {
                 if tab_config.id == "settings" {
-                    // Use registry to check which field was clicked
-                    // Field HWNDs in order: Sketch Dir (0), Sketch Name (1), then fields 2-13
-                    let field_hwnds = [
-                        crate::constants::HWND_SETTINGS_FIELD_SKETCH_DIR,
-                        crate::constants::HWND_SETTINGS_FIELD_SKETCH_NAME,
-                        crate::constants::HWND_SETTINGS_FIELD_ENV,
-                        crate::constants::HWND_SETTINGS_FIELD_BOARD_MODEL,
-                        crate::constants::HWND_SETTINGS_FIELD_FQBN,
-                        crate::constants::HWND_SETTINGS_FIELD_PORT,
-                        crate::constants::HWND_SETTINGS_FIELD_BAUDRATE,
-                        crate::constants::HWND_SETTINGS_FIELD_MQTT_HOST,
-                        crate::constants::HWND_SETTINGS_FIELD_MQTT_PORT,
-                        crate::constants::HWND_SETTINGS_FIELD_MQTT_USERNAME,
-                        crate::constants::HWND_SETTINGS_FIELD_MQTT_PASSWORD,
-                        crate::constants::HWND_SETTINGS_FIELD_MQTT_TOPIC_COMMAND,
-                        crate::constants::HWND_SETTINGS_FIELD_MQTT_TOPIC_STATE,
-                        crate::constants::HWND_SETTINGS_FIELD_MQTT_TOPIC_STATUS,
-                    ];
-                    
-                    // Check each field to see if click is within its bounds
-                    for (field_index, hwnd) in field_hwnds.iter().enumerate() {
-                        if let Some(field_box) = get_box_by_name(registry, hwnd) {
-                            if let Some(field_rect) = field_box.metrics(registry) {
-                                let rect: Rect = field_rect.into();
-                                if mouse_event.column >= rect.x && mouse_event.column < rect.x + rect.width &&
-                                   mouse_event.row >= rect.y && mouse_event.row < rect.y + rect.height {
-                                    // Click is within this field
-                                    if settings_fields.is_dropdown(field_index) {
-                                        let options = settings_fields.get_dropdown_options(field_index, &settings);
-                                        let current_value = settings_fields.get_value(&settings, field_index);
-                                        let selected_index = options.iter()
-                                            .position(|opt| opt == &current_value)
-                                            .unwrap_or(0);
-                                        return Some(FieldEditorState::Selecting {
-                                            field_index,
-                                            selected_index,
-                                            options,
-                                        });
-                                    } else {
-                                        let current_value = settings_fields.get_value(&settings, field_index);
-                                        let mut input = Input::new(current_value);
-                                        let _ = input.handle(InputRequest::GoToEnd);
-                                        return Some(FieldEditorState::Editing {
-                                            field_index,
-                                            input,
-                                        });
-                                    }
-                                }
-                            }
-                        }
+                    // Hit-test against the same rects the fields were drawn in
+                    if let Some(field_index) = crate::layout_utils::settings_field_at(registry, mouse_event.column, mouse_event.row) {
+                        return Some(open_field_editor(field_index, &settings, settings_fields));
                     }
                 }
             }
@@ -579,9 +692,186 @@ pub fn handle_tab_click(
     if let Some((ref tab_bar, _handle)) = current_tab_bar {
         let clicked_tab: Option<usize> = tab_bar.get_tab_at(mouse_event.column, mouse_event.row, Some(registry));
         if let Some(clicked_tab_idx) = clicked_tab {
-            if tab_style != TabBarStyle::BoxStatic && tab_style != TabBarStyle::TextStatic {
+            if TabBarBehavior::for_style(tab_style).click_switches_tabs {
                 main_content_tab_bar.set_active(registry, clicked_tab_idx);
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Clipboard that records copies, or fails like a headless session when `unavailable`
+    #[derive(Default)]
+    struct RecordingClipboard {
+        copied: Vec<String>,
+        unavailable: bool,
+    }
+
+    impl ClipboardSink for RecordingClipboard {
+        fn set_text(&mut self, text: String) -> Result<(), String> {
+            if self.unavailable {
+                return Err("no display".to_string());
+            }
+            self.copied.push(text);
+            Ok(())
+        }
+    }
+
+    /// Settings manager backed by a scratch directory, so tests never touch the user's config
+    fn temp_settings(name: &str) -> SettingsManager {
+        let dir = std::env::temp_dir().join(format!("dev_console_{}", name));
+        let _ = std::fs::remove_dir_all(&dir);
+        SettingsManager::load_with_fallback(dir.join("settings.yaml"), dir.join("fallback.yaml"))
+    }
+
+    #[test]
+    fn test_copy_status_reports_success_and_missing_clipboard() {
+        let dashboard = Arc::new(Mutex::new(DashboardState::new()));
+        lock_state(&dashboard).set_status_text("Compile failed: exit code 1");
+        let settings = temp_settings("copy_status");
+        let press_y = |clipboard: &mut RecordingClipboard| handle_dashboard_key_event(
+            KeyCode::Char('y'),
+            KeyModifiers::empty(),
+            &KeyBindings::from_config(&[]),
+            &dashboard,
+            &settings,
+            Arc::new(ProcessManager::new()),
+            clipboard,
+        );
+
+        let mut clipboard = RecordingClipboard::default();
+        assert!(press_y(&mut clipboard));
+        assert_eq!(clipboard.copied, vec!["Compile failed: exit code 1".to_string()]);
+        assert_eq!(lock_state(&dashboard).notice.take().as_deref(), Some("Status copied to clipboard"));
+
+        let mut headless = RecordingClipboard { unavailable: true, ..Default::default() };
+        assert!(press_y(&mut headless));
+        let state = lock_state(&dashboard);
+        assert!(state.notice.is_none());
+        assert_eq!(state.error_notice.as_deref(), Some("Clipboard unavailable: no display"));
+    }
+
+    #[test]
+    fn test_copy_line_copies_full_text_of_truncated_line() {
+        let dashboard = Arc::new(Mutex::new(DashboardState::new()));
+        let blob = "QUJD".repeat(50);
+        {
+            let mut state = lock_state(&dashboard);
+            state.max_line_width = 16;
+            state.add_output_line("first".to_string());
+            state.add_output_line(blob.clone());
+        }
+        let settings = temp_settings("copy_line");
+        let press_c = |clipboard: &mut RecordingClipboard| handle_dashboard_key_event(
+            KeyCode::Char('c'),
+            KeyModifiers::empty(),
+            &KeyBindings::from_config(&[]),
+            &dashboard,
+            &settings,
+            Arc::new(ProcessManager::new()),
+            clipboard,
+        );
+
+        // Following the output: the newest line, in full
+        let mut clipboard = RecordingClipboard::default();
+        assert!(press_c(&mut clipboard));
+        assert_eq!(clipboard.copied, vec![blob.clone()]);
+        assert_eq!(lock_state(&dashboard).notice.take().as_deref(), Some("Output line copied to clipboard"));
+
+        // Scrolled up: the line at the top of the view
+        lock_state(&dashboard).output_scroll = 0;
+        assert!(press_c(&mut clipboard));
+        assert_eq!(clipboard.copied.last().map(String::as_str), Some("first"));
+    }
+
+    #[test]
+    fn test_execute_with_empty_command_list_is_noop() {
+        let dashboard = Arc::new(Mutex::new(DashboardState::new()));
+        lock_state(&dashboard).commands.clear();
+        let process_manager = Arc::new(ProcessManager::new());
+
+        let handled = handle_dashboard_key_event(
+            KeyCode::Enter,
+            KeyModifiers::empty(),
+            &KeyBindings::from_config(&[]),
+            &dashboard,
+            &temp_settings("empty_command_list"),
+            process_manager,
+            &mut RecordingClipboard::default(),
+        );
+
+        assert!(handled);
+        let state = lock_state(&dashboard);
+        assert!(!state.is_running);
+        assert!(state.output_lines.is_empty());
+        assert!(state.last_command.is_none());
+    }
+
+    #[test]
+    fn test_clear_status_restores_configured_default() {
+        let dashboard = Arc::new(Mutex::new(DashboardState::new()));
+        {
+            let mut state = lock_state(&dashboard);
+            state.default_status_text = Arc::from("Idle");
+            state.set_status_text("Error: Port COM3 not found");
+        }
+
+        let handled = handle_dashboard_key_event(
+            KeyCode::Char('x'),
+            KeyModifiers::empty(),
+            &KeyBindings::from_config(&[]),
+            &dashboard,
+            &temp_settings("clear_status"),
+            Arc::new(ProcessManager::new()),
+            &mut RecordingClipboard::default(),
+        );
+
+        assert!(handled);
+        assert_eq!(&*lock_state(&dashboard).status_text, "Idle");
+    }
+
+    #[test]
+    fn test_board_model_without_catalog_opens_text_editor() {
+        // No catalog has been loaded in tests, so there are no boards to pick from
+        let fields = SettingsFields::new();
+        let mut settings = crate::settings::Settings::default();
+        settings.board_model = "ESP32S3 Dev Module".to_string();
+
+        match open_field_editor(SettingsField::BoardModel as usize, &settings, &fields) {
+            FieldEditorEventResult::StateChanged(FieldEditorState::Editing { field_index, input }) => {
+                assert_eq!(field_index, SettingsField::BoardModel as usize);
+                assert_eq!(input.value(), "ESP32S3 Dev Module");
+            }
+            other => panic!("expected the text editor, got {:?}", other),
+        }
+        assert!(matches!(
+            open_field_editor(SettingsField::Environment as usize, &settings, &fields),
+            FieldEditorEventResult::StateChanged(FieldEditorState::Selecting { .. })
+        ));
+    }
+
+    #[test]
+    fn test_idle_edit_is_committed_past_threshold() {
+        let dir = std::env::temp_dir().join("dev_console_edit_autosave");
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = dir.join("settings.yaml");
+        let manager = SettingsManager::load_with_fallback(path.clone(), dir.join("fallback.yaml"));
+        let fields = SettingsFields::new();
+        let editing = FieldEditorState::Editing { field_index: 1, input: Input::new("blink".to_string()) };
+        let idle = |secs| std::time::Duration::from_secs(secs);
+
+        // Disabled, or not idle long enough: nothing happens
+        assert!(autosave_idle_edit(&editing, idle(600), 0, &manager, &fields).is_none());
+        assert!(autosave_idle_edit(&editing, idle(29), 30, &manager, &fields).is_none());
+        assert_ne!(manager.get().sketch_name, "blink");
+
+        let result = autosave_idle_edit(&editing, idle(31), 30, &manager, &fields);
+        assert!(matches!(result, Some(FieldEditorEventResult::Toast(_))));
+        assert_eq!(manager.get().sketch_name, "blink");
+        assert!(path.exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
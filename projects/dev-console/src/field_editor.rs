@@ -1,11 +1,112 @@
 // Field editor state and settings fields module
 
+use crate::board_catalog::BoardCatalog;
 use crate::settings::Settings;
-use serialport::available_ports;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serialport::{available_ports, SerialPortInfo, SerialPortType};
 use tui_input::Input;
 use std::fs;
 use std::path::PathBuf;
 
+lazy_static! {
+    /// `vendor:arch:board` with optional `:key=value,...` board options
+    static ref FQBN_PATTERN: Regex = Regex::new(r"^[\w.-]+:[\w.-]+:[\w.-]+(:\S+)?$").unwrap();
+}
+
+/// Baud rates offered by the Baud Rate dropdown (74880 is the ESP8266 boot ROM rate)
+pub const STANDARD_BAUDRATES: [u32; 9] = [9600, 19200, 38400, 57600, 74880, 115200, 230400, 460800, 921600];
+
+/// Non-selectable dropdown entry shown when no serial ports are detected
+pub const NO_PORTS_PLACEHOLDER: &str = "No ports detected — connect a board";
+
+/// Non-selectable dropdown entry shown when the board list command yields nothing
+pub const NO_BOARDS_PLACEHOLDER: &str = "No boards listed — enter a name manually";
+
+/// Non-selectable header above remembered ports that are not currently attached
+pub const PREVIOUSLY_USED_HEADER: &str = "── Previously used ──";
+
+/// Start of the non-selectable entry shown when serial port enumeration itself fails
+pub const PORT_ENUMERATION_FAILED: &str = "Port enumeration failed";
+
+/// Check if a dropdown option is a placeholder rather than a real value
+pub fn is_placeholder_option(option: &str) -> bool {
+    option == NO_PORTS_PLACEHOLDER
+        || option == NO_BOARDS_PLACEHOLDER
+        || option == PREVIOUSLY_USED_HEADER
+        || option.starts_with(PORT_ENUMERATION_FAILED)
+}
+
+/// User-facing explanation of a failed port enumeration (permissions, missing udev rules)
+pub fn port_enumeration_message(reason: &str) -> String {
+    format!("{}: {} — check permissions", PORT_ENUMERATION_FAILED, reason)
+}
+
+/// The enumeration failure message, if the port options carry one
+pub fn port_enumeration_error(options: &[String]) -> Option<&str> {
+    options.first().map(String::as_str).filter(|o| o.starts_with(PORT_ENUMERATION_FAILED))
+}
+
+/// Port dropdown options from an enumeration result
+///
+/// A failed enumeration shows its reason in place of the detected ports instead of an empty list.
+pub fn port_options_from(enumerated: Result<Vec<String>, String>, remembered: Vec<String>) -> Vec<String> { //>
+    match enumerated {
+        Ok(ports) => port_options(ports, remembered),
+        Err(reason) => port_options(vec![port_enumeration_message(&reason)], remembered),
+    }
+} //<
+
+/// Build the port dropdown options from enumerated port names
+///
+/// Remembered ports that are absent are listed after `PREVIOUSLY_USED_HEADER`.
+pub fn port_options(ports: Vec<String>, remembered: Vec<String>) -> Vec<String> { //>
+    let mut options = if ports.is_empty() {
+        vec![NO_PORTS_PLACEHOLDER.to_string()]
+    } else {
+        ports
+    };
+    if !remembered.is_empty() {
+        options.push(PREVIOUSLY_USED_HEADER.to_string());
+        options.extend(remembered);
+    }
+    options
+} //<
+
+/// Separator between a port's device path and its USB description in the Port dropdown
+pub const PORT_DESCRIPTION_SEPARATOR: &str = " — ";
+
+/// Port dropdown label: the device path, plus product name and VID:PID for USB adapters
+///
+/// e.g. `COM9 — Silicon Labs CP210x (10C4:EA60)`; identical adapters stay distinguishable by path.
+pub fn port_label(info: &SerialPortInfo) -> String { //>
+    match &info.port_type {
+        SerialPortType::UsbPort(usb) => {
+            let description = usb.product.as_deref()
+                .or(usb.manufacturer.as_deref())
+                .unwrap_or("USB serial");
+            format!(
+                "{}{}{} ({:04X}:{:04X})",
+                info.port_name, PORT_DESCRIPTION_SEPARATOR, description, usb.vid, usb.pid
+            )
+        }
+        _ => info.port_name.clone(),
+    }
+} //<
+
+/// Check if the option at `index` is a remembered port that is not currently attached
+pub fn is_remembered_port(options: &[String], index: usize) -> bool {
+    options.iter().position(|o| o == PREVIOUSLY_USED_HEADER).is_some_and(|header| index > header)
+}
+
+/// Apply a Board Model choice, filling in the FQBN when the catalog knows the board
+pub fn apply_board_model(settings: &mut Settings, name: String, catalog: &BoardCatalog) {
+    if let Some(fqbn) = catalog.fqbn_for(&name) {
+        settings.fqbn = fqbn.to_string();
+    }
+    settings.board_model = name;
+}
+
 /// Settings field editor state
 #[derive(Debug, Clone)]
 pub enum FieldEditorState {
@@ -142,6 +243,45 @@ impl SettingsField {
         }
     }
     
+    /// Key of this field in settings.yaml
+    pub fn yaml_key(&self) -> &'static str {
+        match self {
+            SettingsField::SketchDirectory => "sketch_directory",
+            SettingsField::SketchName => "sketch_name",
+            SettingsField::Environment => "env",
+            SettingsField::BoardModel => "board_model",
+            SettingsField::FQBN => "fqbn",
+            SettingsField::Port => "port",
+            SettingsField::Baudrate => "baudrate",
+            SettingsField::MqttHost => "mqtt_host",
+            SettingsField::MqttPort => "mqtt_port",
+            SettingsField::MqttUsername => "mqtt_username",
+            SettingsField::MqttPassword => "mqtt_password",
+            SettingsField::MqttTopicCommand => "mqtt_topic_command",
+            SettingsField::MqttTopicState => "mqtt_topic_state",
+            SettingsField::MqttTopicStatus => "mqtt_topic_status",
+        }
+    }
+    
+    /// Check if the field holds a number rather than text
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, SettingsField::Baudrate | SettingsField::MqttPort)
+    }
+    
+    /// Check if the field may be left out of settings.yaml
+    pub fn is_optional(&self) -> bool {
+        matches!(
+            self,
+            SettingsField::MqttHost
+                | SettingsField::MqttPort
+                | SettingsField::MqttUsername
+                | SettingsField::MqttPassword
+                | SettingsField::MqttTopicCommand
+                | SettingsField::MqttTopicState
+                | SettingsField::MqttTopicStatus
+        )
+    }
+    
     /// Get value from settings
     pub fn get_value(&self, settings: &Settings) -> String {
         match self {
@@ -190,9 +330,39 @@ impl SettingsField {
         }
     }
     
+    /// Check a typed value before it is committed; the error is shown to the user
+    pub fn validate(&self, value: &str) -> Result<(), String> { //>
+        let value = value.trim();
+        match self {
+            SettingsField::FQBN if !FQBN_PATTERN.is_match(value) => Err(format!(
+                "Invalid FQBN '{}': expected vendor:arch:board[:options]",
+                value
+            )),
+            SettingsField::Baudrate => match value.parse::<u32>() {
+                Ok(b) if b > 0 => Ok(()),
+                _ => Err(format!("Invalid baud rate '{}': expected a positive number", value)),
+            },
+            SettingsField::MqttPort if value.parse::<u16>().is_err() => {
+                Err(format!("Invalid MQTT port '{}': expected 0-65535", value))
+            }
+            _ => Ok(()),
+        }
+    } //<
+    
     /// Check if field is a dropdown
     pub fn is_dropdown(&self) -> bool {
-        matches!(self, SettingsField::Environment | SettingsField::Port | SettingsField::SketchName)
+        matches!(
+            self,
+            SettingsField::Environment | SettingsField::BoardModel | SettingsField::Port | SettingsField::Baudrate | SettingsField::SketchName
+        )
+    }
+    
+    /// The value a dropdown option stands for (a Port label's device path, otherwise the option)
+    pub fn option_value<'a>(&self, option: &'a str) -> &'a str {
+        match self {
+            SettingsField::Port => option.split(PORT_DESCRIPTION_SEPARATOR).next().unwrap_or(option),
+            _ => option,
+        }
     }
     
     /// Get dropdown options for a field
@@ -201,20 +371,25 @@ impl SettingsField {
             SettingsField::Environment => {
                 vec!["arduino".to_string(), "esp-idf".to_string()]
             }
-            SettingsField::Port => {
-                // Port dropdown - detect available COM ports
-                match available_ports() {
-                    Ok(ports) => {
-                        ports.into_iter()
-                            .map(|p| p.port_name)
-                            .collect()
-                    }
-                    Err(_) => {
-                        // Fallback to common ports if detection fails
-                        vec!["COM1".to_string(), "COM3".to_string(), "COM5".to_string(), "COM7".to_string(), "COM9".to_string()]
-                    }
+            SettingsField::BoardModel => {
+                // Friendly board names from the background-loaded board list (placeholder while
+                // loading or if the tool listed none)
+                let names = crate::board_catalog::loaded().map(|catalog| catalog.names()).unwrap_or_default();
+                if names.is_empty() {
+                    vec![NO_BOARDS_PLACEHOLDER.to_string()]
+                } else {
+                    names
                 }
             }
+            SettingsField::Port => {
+                // Port dropdown - detect available ports (placeholder if none are attached),
+                // followed by previously seen ports that are currently absent
+                let ports = available_ports().map_err(|e| e.to_string());
+                let detected: Vec<String> = ports.iter().flatten().map(|p| p.port_name.clone()).collect();
+                let remembered = crate::port_memory::remember_ports(&crate::port_memory::seen_ports_path(), &detected);
+                port_options_from(ports.map(|ports| ports.iter().map(port_label).collect()), remembered)
+            }
+            SettingsField::Baudrate => STANDARD_BAUDRATES.iter().map(|b| b.to_string()).collect(),
             SettingsField::SketchName => {
                 // Sketch Name dropdown - scan sketch directory for .ino files
                 if settings.sketch_directory.is_empty() {
@@ -289,6 +464,21 @@ impl SettingsField {
 /// Settings fields definition and management (backward compatibility wrapper)
 pub struct SettingsFields;
 
+/// Replace the open Port dropdown's options with a fresh scan
+///
+/// The highlighted port stays highlighted (by name) when it is still listed;
+/// otherwise the highlight keeps its position, clamped to the new list.
+pub fn refresh_port_options(options: &mut Vec<String>, selected_index: &mut usize, fresh: Vec<String>) { //>
+    if *options == fresh {
+        return;
+    }
+    let highlighted = options.get(*selected_index).cloned();
+    *selected_index = highlighted
+        .and_then(|name| fresh.iter().position(|opt| *opt == name))
+        .unwrap_or_else(|| (*selected_index).min(fresh.len().saturating_sub(1)));
+    *options = fresh;
+} //<
+
 impl SettingsFields {
     /// Create a new settings fields instance
     pub fn new() -> Self {
@@ -309,6 +499,13 @@ impl SettingsFields {
         }
     }
     
+    /// Validate a typed value for a field (by index for backward compatibility)
+    pub fn validate(&self, index: usize, value: &str) -> Result<(), String> {
+        SettingsField::from_index(index)
+            .map(|field| field.validate(value))
+            .unwrap_or(Ok(()))
+    }
+    
     /// Get the number of fields
     pub fn count(&self) -> usize {
         SettingsField::all().len()
@@ -328,6 +525,13 @@ impl SettingsFields {
             .unwrap_or(false)
     }
     
+    /// The value a dropdown option stands for (by index for backward compatibility)
+    pub fn option_value<'a>(&self, index: usize, option: &'a str) -> &'a str {
+        SettingsField::from_index(index)
+            .map(|field| field.option_value(option))
+            .unwrap_or(option)
+    }
+    
     /// Get dropdown options for a field
     pub fn get_dropdown_options(&self, index: usize, settings: &Settings) -> Vec<String> {
         SettingsField::from_index(index)
@@ -335,3 +539,124 @@ impl SettingsFields {
             .unwrap_or_default()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_rejects_malformed_fqbn_and_baudrate() {
+        assert!(SettingsField::FQBN.validate("esp32:esp32:esp32s3").is_ok());
+        assert!(SettingsField::FQBN.validate("esp32:esp32:esp32s3:CDCOnBoot=cdc,PSRAM=opi").is_ok());
+        assert!(SettingsField::FQBN.validate("esp32:esp32").is_err());
+        assert!(SettingsField::FQBN.validate("esp32 esp32 esp32s3").is_err());
+
+        assert!(SettingsField::Baudrate.validate("115200").is_ok());
+        assert!(SettingsField::Baudrate.validate("fast").is_err());
+        assert!(SettingsField::Baudrate.validate("0").is_err());
+    }
+
+    #[test]
+    fn test_usb_port_label_keeps_device_path_as_value() {
+        let info = SerialPortInfo {
+            port_name: "COM9".to_string(),
+            port_type: SerialPortType::UsbPort(serialport::UsbPortInfo {
+                vid: 0x10C4,
+                pid: 0xEA60,
+                serial_number: None,
+                manufacturer: Some("Silicon Labs".to_string()),
+                product: Some("Silicon Labs CP210x".to_string()),
+            }),
+        };
+        let label = port_label(&info);
+        assert_eq!(label, "COM9 — Silicon Labs CP210x (10C4:EA60)");
+        assert_eq!(SettingsField::Port.option_value(&label), "COM9");
+        assert_eq!(SettingsField::Port.option_value("/dev/ttyS0"), "/dev/ttyS0");
+        assert_eq!(SettingsField::BoardModel.option_value(&label), label);
+    }
+
+    #[test]
+    fn test_port_refresh_keeps_highlighted_port() {
+        let mut options = vec!["/dev/ttyUSB0".to_string(), "/dev/ttyUSB1".to_string()];
+        let mut selected_index = 1;
+
+        // A board plugged in ahead of the highlighted port: highlight follows the name
+        let fresh = vec!["/dev/ttyACM0".to_string(), "/dev/ttyUSB0".to_string(), "/dev/ttyUSB1".to_string()];
+        refresh_port_options(&mut options, &mut selected_index, fresh.clone());
+        assert_eq!(options, fresh);
+        assert_eq!(selected_index, 2);
+
+        // Highlighted port unplugged: index stays put, clamped to the shorter list
+        refresh_port_options(&mut options, &mut selected_index, vec!["/dev/ttyACM0".to_string()]);
+        assert_eq!(selected_index, 0);
+    }
+
+    #[test]
+    fn test_baudrate_dropdown_round_trips_selection() {
+        let fields = SettingsFields::new();
+        let mut settings = Settings::default();
+        assert!(fields.is_dropdown(6));
+
+        let options = fields.get_dropdown_options(6, &settings);
+        assert_eq!(options.len(), STANDARD_BAUDRATES.len());
+        assert_eq!(options.first().map(String::as_str), Some("9600"));
+        assert_eq!(options.last().map(String::as_str), Some("921600"));
+
+        fields.set_value(&mut settings, 6, "460800".to_string());
+        assert_eq!(settings.baudrate, 460800);
+    }
+
+    #[test]
+    fn test_empty_port_enumeration_yields_placeholder() {
+        let options = port_options(Vec::new(), Vec::new());
+        assert_eq!(options, vec![NO_PORTS_PLACEHOLDER.to_string()]);
+        assert!(is_placeholder_option(&options[0]));
+        assert!(!options.iter().any(|o| o == "COM1"));
+
+        let options = port_options(vec!["/dev/ttyUSB0".to_string()], Vec::new());
+        assert_eq!(options, vec!["/dev/ttyUSB0".to_string()]);
+    }
+
+    #[test]
+    fn test_port_enumeration_error_is_described_not_replaced() {
+        let options = port_options_from(Err("Permission denied (os error 13)".to_string()), Vec::new());
+        assert_eq!(
+            options,
+            vec!["Port enumeration failed: Permission denied (os error 13) — check permissions".to_string()]
+        );
+        assert!(is_placeholder_option(&options[0]));
+        assert_eq!(port_enumeration_error(&options), Some(options[0].as_str()));
+        assert!(!options.iter().any(|o| o == "COM1" || o == NO_PORTS_PLACEHOLDER));
+
+        let options = port_options_from(Ok(vec!["/dev/ttyUSB0".to_string()]), Vec::new());
+        assert_eq!(port_enumeration_error(&options), None);
+    }
+
+    #[test]
+    fn test_absent_remembered_port_listed_under_header() {
+        let options = port_options(vec!["/dev/ttyUSB0".to_string()], vec!["/dev/ttyACM1".to_string()]);
+        assert_eq!(options, vec!["/dev/ttyUSB0", PREVIOUSLY_USED_HEADER, "/dev/ttyACM1"]);
+        assert!(!is_remembered_port(&options, 0));
+        assert!(is_placeholder_option(&options[1]));
+        assert!(is_remembered_port(&options, 2));
+    }
+
+    #[test]
+    fn test_selecting_board_name_sets_fqbn() {
+        let catalog = BoardCatalog::parse(
+            "Board Name            FQBN\n\
+             ESP32-S3 Dev Module   esp32:esp32:esp32s3\n\
+             XIAO_ESP32C3          esp32:esp32:XIAO_ESP32C3\n",
+        );
+        let mut settings = Settings::default();
+
+        apply_board_model(&mut settings, "XIAO_ESP32C3".to_string(), &catalog);
+        assert_eq!(settings.board_model, "XIAO_ESP32C3");
+        assert_eq!(settings.fqbn, "esp32:esp32:XIAO_ESP32C3");
+
+        // Unknown names keep the existing FQBN
+        apply_board_model(&mut settings, "custom-board".to_string(), &catalog);
+        assert_eq!(settings.board_model, "custom-board");
+        assert_eq!(settings.fqbn, "esp32:esp32:XIAO_ESP32C3");
+    }
+}
@@ -0,0 +1,130 @@
+// OSC 8 terminal hyperlinks
+// Splits tool output into plain text and link text with its target URL, and opens links on click
+
+use regex::Regex;
+use lazy_static::lazy_static;
+use std::process::{Command, Stdio};
+
+lazy_static! {
+    // ESC ] 8 ; params ; URI terminated by BEL or ESC \ (an empty URI closes the link)
+    static ref OSC8_RE: Regex = Regex::new(r"\x1B\]8;[^;\x07\x1B]*;([^\x07\x1B]*)(?:\x07|\x1B\\)").unwrap();
+}
+
+/// A run of output text, with the URL it links to if it sits inside an OSC 8 link
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkSegment {
+    pub text: String,
+    pub url: Option<String>,
+}
+
+/// True if the line contains an OSC 8 hyperlink sequence
+pub fn has_hyperlinks(line: &str) -> bool {
+    line.contains("\x1B]8;")
+}
+
+/// Split a line at its OSC 8 sequences; other escape codes are left in the text
+pub fn split_hyperlinks(line: &str) -> Vec<LinkSegment> { //>
+    let mut segments = Vec::new();
+    let mut current_url: Option<String> = None;
+    let mut last_end = 0;
+
+    for cap in OSC8_RE.captures_iter(line) {
+        let full_match = cap.get(0).unwrap();
+        if full_match.start() > last_end {
+            segments.push(LinkSegment {
+                text: line[last_end..full_match.start()].to_string(),
+                url: current_url.clone(),
+            });
+        }
+        let uri = cap.get(1).map(|m| m.as_str()).unwrap_or("");
+        current_url = (!uri.is_empty()).then(|| uri.to_string());
+        last_end = full_match.end();
+    }
+
+    if last_end < line.len() {
+        segments.push(LinkSegment {
+            text: line[last_end..].to_string(),
+            url: current_url,
+        });
+    }
+    segments
+} //<
+
+/// True for an `http(s)://` URL made only of RFC 3986 characters
+///
+/// Link targets come from tool and serial output, so anything else (other
+/// schemes, whitespace, quotes, `^`, `|`, `<`, `>`, `\`) is refused.
+pub fn is_safe_url(url: &str) -> bool { //>
+    let rest = url.strip_prefix("https://").or_else(|| url.strip_prefix("http://"));
+    match rest {
+        Some(rest) => !rest.is_empty() && rest.chars().all(|c| {
+            c.is_ascii_alphanumeric() || "-._~:/?#[]@!$&'()*+,;=%".contains(c)
+        }),
+        None => false,
+    }
+} //<
+
+/// Open a URL in the default browser without blocking; unsafe URLs and failures are ignored
+pub fn open_url(url: &str) { //>
+    if !is_safe_url(url) {
+        return;
+    }
+    // No shell is involved on any platform, so the URL is never parsed as a command line
+    let mut cmd = if cfg!(windows) {
+        let mut cmd = Command::new("rundll32");
+        cmd.args(["url.dll,FileProtocolHandler", url]);
+        cmd
+    } else if cfg!(target_os = "macos") {
+        let mut cmd = Command::new("open");
+        cmd.arg(url);
+        cmd
+    } else {
+        let mut cmd = Command::new("xdg-open");
+        cmd.arg(url);
+        cmd
+    };
+    cmd.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null());
+    let _ = cmd.spawn();
+} //<
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_osc8_parsed_into_link_text_and_url() {
+        let line = "see \x1B]8;;https://docs.espressif.com/esptool\x1B\\esptool docs\x1B]8;;\x1B\\ for details";
+        assert!(has_hyperlinks(line));
+
+        let segments = split_hyperlinks(line);
+        assert_eq!(segments, vec![
+            LinkSegment { text: "see ".to_string(), url: None },
+            LinkSegment {
+                text: "esptool docs".to_string(),
+                url: Some("https://docs.espressif.com/esptool".to_string()),
+            },
+            LinkSegment { text: " for details".to_string(), url: None },
+        ]);
+
+        // BEL-terminated form with link parameters
+        let bel = split_hyperlinks("\x1B]8;id=1;https://example.com\x07here\x1B]8;;\x07");
+        assert_eq!(bel, vec![LinkSegment {
+            text: "here".to_string(),
+            url: Some("https://example.com".to_string()),
+        }]);
+    }
+
+    #[test]
+    fn test_only_plain_http_urls_are_safe_to_open() {
+        assert!(is_safe_url("https://docs.espressif.com/esptool?lang=en&v=4#flash"));
+        assert!(is_safe_url("http://192.168.4.1/update"));
+
+        assert!(!is_safe_url("file:///etc/passwd"));
+        assert!(!is_safe_url("javascript:alert(1)"));
+        assert!(!is_safe_url("https://"));
+        assert!(!is_safe_url("https://example.com/a|calc"));
+        assert!(!is_safe_url("https://example.com/^&calc"));
+        assert!(!is_safe_url("https://example.com/\" calc"));
+        assert!(!is_safe_url("https://example.com/ x"));
+    }
+}
@@ -0,0 +1,150 @@
+// Configurable key bindings
+// Maps semantic actions (quit, cancel) to one or more physical keys from config.yaml
+
+use crate::config::BindingConfigYaml;
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::collections::HashMap;
+
+/// Action name for leaving the application
+pub const ACTION_QUIT: &str = "quit";
+
+/// Action name for cancelling the running command
+pub const ACTION_CANCEL: &str = "cancel";
+
+/// Action name for toggling terminal mouse capture
+pub const ACTION_TOGGLE_MOUSE: &str = "toggle_mouse";
+
+/// Action name for resetting the dashboard status text to its default
+pub const ACTION_CLEAR_STATUS: &str = "clear_status";
+
+/// Action name for typing a regex that filters the dashboard output
+pub const ACTION_FILTER_OUTPUT: &str = "filter_output";
+
+/// Action name for switching the output filter on and off
+pub const ACTION_TOGGLE_FILTER: &str = "toggle_filter";
+
+/// Action name for showing the lines that do not match the output filter
+pub const ACTION_INVERT_FILTER: &str = "invert_filter";
+
+/// Action name for exporting the dashboard output and settings to a JSON file
+pub const ACTION_EXPORT_OUTPUT: &str = "export_output";
+
+/// Action name for copying the dashboard status text to the system clipboard
+pub const ACTION_COPY_STATUS: &str = "copy_status";
+
+/// Action name for copying the full text of an output line (newest, or top of the view when scrolled up)
+pub const ACTION_COPY_LINE: &str = "copy_line";
+
+/// Keys used for actions the config does not bind
+const DEFAULT_BINDINGS: &[(&str, &[&str])] = &[
+    (ACTION_QUIT, &["q"]),
+    (ACTION_CANCEL, &["esc"]),
+    // Not ctrl+m: terminals send it as Enter (CR)
+    (ACTION_TOGGLE_MOUSE, &["ctrl+t"]),
+    (ACTION_CLEAR_STATUS, &["x"]),
+    (ACTION_FILTER_OUTPUT, &["/"]),
+    (ACTION_TOGGLE_FILTER, &["f"]),
+    (ACTION_INVERT_FILTER, &["!"]),
+    (ACTION_EXPORT_OUTPUT, &["w"]),
+    (ACTION_COPY_STATUS, &["y"]),
+    (ACTION_COPY_LINE, &["c"]),
+];
+
+/// Action -> physical keys lookup built from the application bindings
+#[derive(Debug, Clone)]
+pub struct KeyBindings {
+    actions: HashMap<String, Vec<String>>,
+}
+
+impl KeyBindings {
+    /// Build from config bindings, keeping defaults for actions the config leaves unbound
+    pub fn from_config(bindings: &[BindingConfigYaml]) -> Self { //>
+        let mut actions: HashMap<String, Vec<String>> = DEFAULT_BINDINGS.iter()
+            .map(|(action, keys)| (action.to_string(), keys.iter().map(|k| k.to_string()).collect()))
+            .collect();
+
+        for binding in bindings {
+            if let Some(action) = &binding.action {
+                if !binding.keys.is_empty() {
+                    actions.insert(action.to_lowercase(), binding.keys.clone());
+                }
+            }
+        }
+
+        Self { actions }
+    } //<
+
+    /// True if the key event triggers the given action
+    pub fn matches(&self, action: &str, code: KeyCode, modifiers: KeyModifiers) -> bool {
+        self.actions.get(action)
+            .map(|keys| keys.iter().any(|k| key_matches(code, modifiers, k)))
+            .unwrap_or(false)
+    }
+}
+
+/// Check a key event against a binding string such as "q", "esc" or "ctrl+c"
+pub fn key_matches(code: KeyCode, modifiers: KeyModifiers, binding_key: &str) -> bool { //>
+    let binding_lower = binding_key.to_lowercase();
+    let inner = binding_lower.trim_matches(|c| c == '[' || c == ']');
+    let mut req_mods = KeyModifiers::empty();
+    let mut target = "";
+
+    for part in inner.split('+') {
+        match part {
+            "alt" => req_mods.insert(KeyModifiers::ALT),
+            "ctrl" | "control" => req_mods.insert(KeyModifiers::CONTROL),
+            "shift" => req_mods.insert(KeyModifiers::SHIFT),
+            k => target = k,
+        }
+    }
+
+    // Shift is implied by the character itself for letters, so only Ctrl/Alt must match exactly
+    let significant = KeyModifiers::CONTROL | KeyModifiers::ALT;
+    if (modifiers & significant) != (req_mods & significant) {
+        return false;
+    }
+
+    match target {
+        "enter" | "return" => code == KeyCode::Enter,
+        "esc" | "escape" => code == KeyCode::Esc,
+        "up" => code == KeyCode::Up,
+        "down" => code == KeyCode::Down,
+        "left" => code == KeyCode::Left,
+        "right" => code == KeyCode::Right,
+        "tab" => code == KeyCode::Tab,
+        "backspace" => code == KeyCode::Backspace,
+        _ => {
+            let mut chars = target.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => matches!(code, KeyCode::Char(k) if k.eq_ignore_ascii_case(&c)),
+                _ => false,
+            }
+        }
+    }
+} //<
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quit_bound_to_multiple_keys() {
+        let bindings = KeyBindings::from_config(&[BindingConfigYaml {
+            key: "[q/Ctrl+C]".to_string(),
+            description: "Quit".to_string(),
+            action: Some("quit".to_string()),
+            keys: vec!["q".to_string(), "ctrl+c".to_string()],
+        }]);
+
+        assert!(bindings.matches(ACTION_QUIT, KeyCode::Char('q'), KeyModifiers::empty()));
+        assert!(bindings.matches(ACTION_QUIT, KeyCode::Char('c'), KeyModifiers::CONTROL));
+        assert!(!bindings.matches(ACTION_QUIT, KeyCode::Char('c'), KeyModifiers::empty()));
+        assert!(!bindings.matches(ACTION_QUIT, KeyCode::Char('q'), KeyModifiers::CONTROL));
+
+        // Unbound actions keep their defaults
+        assert!(bindings.matches(ACTION_CANCEL, KeyCode::Esc, KeyModifiers::empty()));
+        assert!(bindings.matches(ACTION_COPY_STATUS, KeyCode::Char('y'), KeyModifiers::empty()));
+        assert!(bindings.matches(ACTION_TOGGLE_MOUSE, KeyCode::Char('t'), KeyModifiers::CONTROL));
+        assert!(!bindings.matches(ACTION_TOGGLE_MOUSE, KeyCode::Enter, KeyModifiers::empty()));
+    }
+}
@@ -1,19 +1,31 @@
 // Layout calculation utilities
 
-use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use ratatui::widgets::Block;
+use ratatui::layout::{Position, Rect};
 use crate::constants::*;
+use tui_components::{RectRegistry, get_box_by_name};
+
+/// Tab content area inside the main content box's border
+pub fn tab_content_area(content_rect: Rect) -> Rect {
+    Rect {
+        x: content_rect.x.saturating_add(1),
+        y: content_rect.y.saturating_add(1),
+        width: content_rect.width.saturating_sub(2),
+        height: content_rect.height.saturating_sub(2),
+    }
+}
 
 /// Calculate centered content area within a given rect
-#[allow(dead_code)]
-pub fn calculate_centered_content_area(area: Rect) -> Option<Rect> {
+///
+/// `None` when `area` is smaller than `min_cols` x `min_rows`; the settings form is then
+/// replaced by the size warning, so this is the single "large enough" check.
+pub fn calculate_centered_content_area(area: Rect, min_cols: u16, min_rows: u16) -> Option<Rect> { //>
     // Check if terminal is large enough
-    if area.width < MIN_WIDTH_PIXELS || area.height < MIN_HEIGHT_PIXELS {
+    if area.width < min_cols || area.height < min_rows {
         return None;
     }
     
-    let content_width = (area.width * CONTENT_WIDTH_PERCENT / 100).max(MIN_WIDTH_PIXELS).min(area.width);
-    let content_height = (area.height * CONTENT_HEIGHT_PERCENT / 100).max(MIN_HEIGHT_PIXELS).min(area.height);
+    let content_width = (area.width * CONTENT_WIDTH_PERCENT / 100).max(min_cols).min(area.width);
+    let content_height = (area.height * CONTENT_HEIGHT_PERCENT / 100).max(min_rows).min(area.height);
     let content_x = area.x + (area.width.saturating_sub(content_width)) / 2;
     let content_y = area.y + (area.height.saturating_sub(content_height)) / 2;
     
@@ -23,134 +35,125 @@ pub fn calculate_centered_content_area(area: Rect) -> Option<Rect> {
         width: content_width.min(area.width.saturating_sub(content_x.saturating_sub(area.x))),
         height: content_height.min(area.height.saturating_sub(content_y.saturating_sub(area.y))),
     })
+} //<
+
+/// Field HWNDs in settings field order: Sketch Dir (0), Sketch Name (1), then fields 2-13
+pub const SETTINGS_FIELD_HWNDS: [&str; 14] = [
+    HWND_SETTINGS_FIELD_SKETCH_DIR,
+    HWND_SETTINGS_FIELD_SKETCH_NAME,
+    HWND_SETTINGS_FIELD_ENV,
+    HWND_SETTINGS_FIELD_BOARD_MODEL,
+    HWND_SETTINGS_FIELD_FQBN,
+    HWND_SETTINGS_FIELD_PORT,
+    HWND_SETTINGS_FIELD_BAUDRATE,
+    HWND_SETTINGS_FIELD_MQTT_HOST,
+    HWND_SETTINGS_FIELD_MQTT_PORT,
+    HWND_SETTINGS_FIELD_MQTT_USERNAME,
+    HWND_SETTINGS_FIELD_MQTT_PASSWORD,
+    HWND_SETTINGS_FIELD_MQTT_TOPIC_COMMAND,
+    HWND_SETTINGS_FIELD_MQTT_TOPIC_STATE,
+    HWND_SETTINGS_FIELD_MQTT_TOPIC_STATUS,
+];
+
+/// Screen rect of a settings field, as registered by the last settings render
+///
+/// The single source for dropdown placement, cursor positioning and click
+/// hit-testing, so none of them can drift from where the field is drawn.
+pub fn settings_field_rect(registry: &RectRegistry, field_index: usize) -> Option<Rect> {
+    let hwnd = SETTINGS_FIELD_HWNDS.get(field_index)?;
+    get_box_by_name(registry, hwnd)?.metrics(registry).map(Into::into)
 }
 
-/// Calculate field area for dropdown positioning
-#[allow(dead_code)]
-pub fn calculate_field_area(
-    content_area: Rect,
-    field_index: usize,
-) -> Option<Rect> {
-    // Split into top section (Sketch Directory, Sketch Name) and bottom section (Device/Connection)
-    let main_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(6), // Top section: 2 boxes (3 lines each)
-            Constraint::Min(0),   // Bottom section: Device/Connection
-        ])
-        .split(content_area);
-    
-    if field_index < 2 {
-        // Top full-width fields
-        let top_chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(3), // Sketch Directory
-                Constraint::Length(3), // Sketch Name
-            ])
-            .split(main_chunks[0]);
-        Some(top_chunks[field_index])
-    } else if field_index < 5 {
-        // Device column (left)
-        let bottom_chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Percentage(50), // Device column
-                Constraint::Percentage(50), // Connection column
-            ])
-            .split(main_chunks[1]);
-        
-        // Device section: Environment (2), Board Model (3), FQBN (4)
-        let section_inner = Block::default().borders(ratatui::widgets::Borders::ALL).inner(bottom_chunks[0]);
-        let field_offset = (field_index - 2) as u16 * (FIELD_HEIGHT + FIELD_SPACING);
-        Some(Rect {
-            x: section_inner.x + 1,
-            y: section_inner.y + 1 + field_offset,
-            width: section_inner.width.saturating_sub(2),
-            height: FIELD_HEIGHT,
-        })
-    } else {
-        // Connection column (right)
-        let bottom_chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Percentage(50), // Device column
-                Constraint::Percentage(50), // Connection column
-            ])
-            .split(main_chunks[1]);
-        
-        // Connection section: Port (5), Baudrate (6)
-        let section_inner = Block::default().borders(ratatui::widgets::Borders::ALL).inner(bottom_chunks[1]);
-        let field_offset = (field_index - 5) as u16 * (FIELD_HEIGHT + FIELD_SPACING);
-        Some(Rect {
-            x: section_inner.x + 1,
-            y: section_inner.y + 1 + field_offset,
-            width: section_inner.width.saturating_sub(2),
-            height: FIELD_HEIGHT,
-        })
-    }
+/// Index of the settings field under a screen position
+pub fn settings_field_at(registry: &RectRegistry, column: u16, row: u16) -> Option<usize> {
+    (0..SETTINGS_FIELD_HWNDS.len()).find(|&field_index| {
+        settings_field_rect(registry, field_index).is_some_and(|rect| rect.contains(Position { x: column, y: row }))
+    })
 }
 
-/// Calculate dropdown area position
-#[allow(dead_code)]
-pub fn calculate_dropdown_area(
-    field_area: Rect,
-    options_count: usize,
-    frame_height: u16,
-) -> Rect {
-    let dropdown_height = (options_count + 2).min(10) as u16;
-    let dropdown_area = Rect {
-        x: field_area.x,
-        y: field_area.y + field_area.height,
-        width: field_area.width,
-        height: dropdown_height,
-    };
-    
-    // Make sure dropdown fits in the frame
-    if dropdown_area.y + dropdown_area.height > frame_height {
-        // If doesn't fit below, show above
-        Rect {
-            x: dropdown_area.x,
-            y: field_area.y.saturating_sub(dropdown_area.height),
-            width: dropdown_area.width,
-            height: dropdown_height,
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field_editor::{FieldEditorState, SettingsFields};
+    use crate::profile_state::ProfileState;
+    use crate::settings::Settings;
+    use crate::theme::Theme;
+    use ratatui::{backend::TestBackend, Terminal};
+    use tui_components::DimmingContext;
+
+    #[test]
+    fn test_settings_field_rects_match_rendered_layout() {
+        let area = Rect::new(0, 0, 160, 48);
+        let mut registry = RectRegistry::new();
+        let mut terminal = Terminal::new(TestBackend::new(area.width, area.height)).unwrap();
+        let render = |registry: &mut RectRegistry, terminal: &mut Terminal<TestBackend>| {
+            terminal
+                .draw(|f| {
+                    crate::render::render_settings(
+                        f,
+                        area,
+                        &Settings::default(),
+                        &SettingsFields::new(),
+                        &FieldEditorState::new_selected(0),
+                        &ProfileState::new(),
+                        registry,
+                        &DimmingContext::new(false),
+                        &Theme::default(),
+                    );
+                })
+                .unwrap();
+        };
+
+        render(&mut registry, &mut terminal);
+        let rects: Vec<Rect> = (0..SETTINGS_FIELD_HWNDS.len())
+            .map(|i| settings_field_rect(&registry, i).expect("every field is registered"))
+            .collect();
+
+        for (i, rect) in rects.iter().enumerate() {
+            assert!(area.contains(rect.as_position()), "field {} outside the content area", i);
+            assert_eq!(rect.height, FIELD_HEIGHT);
+            // Clicking the middle of a field hits that field and no other
+            let (column, row) = (rect.x + rect.width / 2, rect.y + 1);
+            assert_eq!(settings_field_at(&registry, column, row), Some(i));
+        }
+
+        // A second frame re-registers the same rects rather than drifting
+        render(&mut registry, &mut terminal);
+        for (i, rect) in rects.iter().enumerate() {
+            assert_eq!(settings_field_rect(&registry, i), Some(*rect));
         }
-    } else {
-        dropdown_area
     }
-}
 
-/// Calculate cursor position for editing fields
-#[allow(dead_code)]
-pub fn calculate_cursor_position(
-    content_area: Rect,
-    field_index: usize,
-    cursor_pos: usize,
-    scroll_offset: usize,
-    _inner_width: usize,
-) -> Option<(u16, u16)> {
-    let content_x = content_area.x;
-    let content_y = content_area.y;
-    let content_width = content_area.width;
-    
-    let cursor_pos_in_view = cursor_pos.saturating_sub(scroll_offset);
-    
-    let (cursor_x, cursor_y) = if field_index < 2 {
-        // Top section: Sketch Directory (0) or Sketch Name (1)
-        let y_offset = if field_index == 0 { 0 } else { 3 };
-        (content_x + 1 + cursor_pos_in_view as u16, content_y + y_offset + 1)
-    } else if field_index < 5 {
-        // Device section: Environment (2), Board Model (3), FQBN (4)
-        let section_y = content_y + 6; // After top section
-        let field_offset = ((field_index - 2) * 4) as u16; // 3 lines per field + 1 spacing
-        (content_x + 1 + 1 + cursor_pos_in_view as u16, section_y + 1 + 1 + field_offset + 1)
-    } else {
-        // Connection section: Port (5), Baudrate (6)
-        let section_y = content_y + 6; // After top section
-        let section_x = content_x + content_width / 2; // Right column
-        let field_offset = ((field_index - 5) * 4) as u16; // 3 lines per field + 1 spacing
-        (section_x + 1 + 1 + cursor_pos_in_view as u16, section_y + 1 + 1 + field_offset + 1)
-    };
-    
-    Some((cursor_x, cursor_y))
+    #[test]
+    fn test_form_and_size_check_agree_on_configured_minimum() {
+        let settings = Settings { min_cols: 100, min_rows: 30, ..Settings::default() };
+        let mut registry = RectRegistry::new();
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        let mut form_shown = |area: Rect| {
+            let mut shown = false;
+            terminal
+                .draw(|f| {
+                    shown = crate::render::render_settings(
+                        f,
+                        area,
+                        &settings,
+                        &SettingsFields::new(),
+                        &FieldEditorState::new_selected(0),
+                        &ProfileState::new(),
+                        &mut registry,
+                        &DimmingContext::new(false),
+                        &Theme::default(),
+                    );
+                })
+                .unwrap();
+            shown
+        };
+
+        for area in [Rect::new(0, 0, 99, 40), Rect::new(0, 0, 100, 29), Rect::new(0, 0, 100, 30), Rect::new(0, 0, 120, 40)] {
+            let fits = calculate_centered_content_area(area, settings.min_cols, settings.min_rows).is_some();
+            assert_eq!(form_shown(area), fits, "{:?}", area);
+        }
+        assert!(calculate_centered_content_area(Rect::new(0, 0, 100, 30), 100, 30).is_some());
+        assert_eq!(tab_content_area(Rect::new(0, 0, 102, 32)), Rect::new(1, 1, 100, 30));
+    }
 }
@@ -10,6 +10,8 @@ mod profile_state;
 mod field_editor;
 mod dashboard;
 mod dashboard_batch;
+mod output_export;
+mod clipboard;
 mod config;
 mod config_validation;
 mod error_format;
@@ -22,18 +24,26 @@ mod process_manager;
 mod constants;
 mod path_utils;
 mod layout_utils;
+mod tab_bar_behavior;
 mod app_state;
-mod layout_cache;
-mod layout_manager;
 mod event_handler;
+mod key_bindings;
+mod mouse_capture;
 mod ui_coordinator;
 mod progress_tracker;
 mod progress_history;
+mod profile_diff;
+mod hyperlink;
+mod update_throttle;
+mod board_catalog;
+mod sketch_project;
+mod settings_schema;
+mod port_memory;
+mod theme;
 
 //--------------------------------------------------------<<
 // IMPORTS ------------------>> 
 
-use crate::layout_manager::LayoutManager;
 use ratatui::{
     backend::CrosstermBackend,
     Terminal,
@@ -60,6 +70,7 @@ use config_validation::load_and_validate_config;
 use constants::*;
 use event_handler::{
     handle_dashboard_key_event,
+    handle_filter_input_key,
     handle_dashboard_scroll,
     handle_field_editor_key_event,
     handle_profile_key_event,
@@ -71,7 +82,12 @@ use event_handler::{
     ProfileEventResult,
 };
 use ui_coordinator::{render_ui, handle_cursor_positioning};
+use key_bindings::{KeyBindings, ACTION_QUIT, ACTION_TOGGLE_MOUSE};
+use mouse_capture::MouseCapture;
 use field_editor::FieldEditorState;
+use profile_diff::{diff_settings, render_profile_diff, PendingProfileLoad};
+use sketch_project::SketchProject;
+use settings::Settings;
 
 //--------------------------------------------------------<<
 
@@ -81,8 +97,16 @@ use field_editor::FieldEditorState;
 // └──────────────────────────────────────────────────────────────────────────────────────────────────────────────────┘
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // `--settings-schema` prints the settings.yaml JSON schema for editors/scripts and exits
+    if std::env::args().any(|arg| arg == settings_schema::SCHEMA_FLAG) {
+        println!("{}", serde_json::to_string_pretty(&settings_schema::settings_schema(&board_catalog::cached()))?);
+        return Ok(());
+    }
+    
     let popup: Option<Popup> = None;
     let mut toasts: Vec<Toast> = Vec::new();
+    let mut pending_profile_load: Option<PendingProfileLoad> = None;
+    let mut pending_sketch_project: Option<SketchProject> = None;
     let mut registry = RectRegistry::new();
     
     // Initialize application state
@@ -90,6 +114,28 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     
     // Load and validate configuration from YAML file (with error recovery)
     let app_config = load_and_validate_config(None)?;
+    {
+        let mut dashboard_state = dashboard::lock_state(&app_state.dashboard);
+        dashboard_state.output_placeholder =
+            dashboard::OutputPlaceholder::from_config(&app_config.application.output_placeholder);
+        dashboard_state.set_max_line_width(app_config.application.max_line_width);
+        dashboard_state.dim_commands_while_running = app_config.application.dim_commands_while_running;
+        dashboard_state.progress_updates_per_sec = app_config.application.progress_updates_per_sec;
+        dashboard_state.compile_backend = app_config.application.compile_backend;
+        dashboard_state.command_environments = app_config.application.command_environments.clone();
+        if let Some(command) = &app_config.application.board_list_command {
+            board_catalog::set_board_list_command(command);
+        }
+        board_catalog::start_loading();
+        if let Some(idle_text) = &app_config.application.status_bar.idle_text {
+            dashboard_state.default_status_text = string_intern::intern_string(idle_text);
+            dashboard_state.clear_status_text();
+        }
+    }
+    app_state.theme = theme::Theme::from_config(&app_config.application.theme);
+    
+    let key_bindings = KeyBindings::from_config(&app_config.application.bindings);
+    let mut mouse_capture = MouseCapture::new(true);
     
     // Setup terminal
     enable_raw_mode()?;
@@ -140,7 +186,31 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     
     let main_content_box_handle_name = HWND_MAIN_CONTENT_BOX;
     let mut original_anchor_metrics: Option<Rect> = None;
-    let mut layout_manager = LayoutManager::new();
+    
+    if app_state.settings.using_fallback() {
+        toasts.push(Toast::new(
+            format!(
+                "Config directory is not writable - settings are saved to {}",
+                app_state.settings.path().display()
+            ),
+            ToastType::Error,
+        ));
+    }
+    
+    // Offer the sketch's sketch.yaml defaults when they differ from the loaded settings
+    {
+        let current_settings = app_state.settings.get();
+        let sketch_dir = std::path::PathBuf::from(&current_settings.sketch_directory);
+        if let Some(project) = SketchProject::load(&sketch_dir) {
+            if let Some(changes) = project.describe_changes(&current_settings) {
+                toasts.push(Toast::new(
+                    format!("sketch.yaml suggests {} - [y] apply, any other key ignores", changes),
+                    ToastType::Success,
+                ));
+                pending_sketch_project = Some(project);
+            }
+        }
+    }
     
     // Find active profile on startup
     {
@@ -160,6 +230,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
+    let mut last_port_scan = std::time::Instant::now();
+    let mut last_input = std::time::Instant::now();
+
     // ┌────────────────────────────────────────────────────────────────────────────────────────────────┐
     // │                                           MAIN LOOP                                            │
     // └────────────────────────────────────────────────────────────────────────────────────────────────┘ 
@@ -172,12 +245,70 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 .unwrap_or(true)
         });
 
+        {
+            let mut dashboard_state = dashboard::lock_state(&app_state.dashboard);
+            if let Some(notice) = dashboard_state.notice.take() {
+                toasts.push(Toast::new(notice, ToastType::Success));
+            }
+            if let Some(notice) = dashboard_state.error_notice.take() {
+                toasts.push(Toast::new(notice, ToastType::Error));
+            }
+        }
+
+        // Hide commands that don't apply to the selected environment (no-op unless env changed),
+        // and pick up the output line cap from the current settings (a loaded profile may change it)
+        {
+            let settings = app_state.settings.get();
+            let mut dashboard_state = dashboard::lock_state(&app_state.dashboard);
+            dashboard_state.apply_environment(&settings.env);
+            dashboard_state.max_output_lines = settings.max_output_lines.max(1);
+        }
+
+        // Re-scan serial ports while the Port dropdown is open so newly plugged boards appear
+        if let FieldEditorState::Selecting { field_index, ref mut selected_index, ref mut options } = app_state.field_editor_state {
+            if field_editor::SettingsField::from_index(field_index) == Some(field_editor::SettingsField::Port)
+                && last_port_scan.elapsed() >= std::time::Duration::from_millis(PORT_RESCAN_INTERVAL_MS)
+            {
+                last_port_scan = std::time::Instant::now();
+                let fresh = app_state.settings_fields.get_dropdown_options(field_index, &app_state.settings.get());
+                field_editor::refresh_port_options(options, selected_index, fresh);
+            }
+        }
+
+        // Commit an edit left open without input for `edit_autosave_secs`
+        if let Some(result) = event_handler::autosave_idle_edit(
+            &app_state.field_editor_state,
+            last_input.elapsed(),
+            app_config.application.edit_autosave_secs,
+            &app_state.settings,
+            &app_state.settings_fields,
+        ) {
+            last_input = std::time::Instant::now();
+            match result {
+                FieldEditorEventResult::Toast(toast) => {
+                    toasts.push(toast);
+                    if let FieldEditorState::Editing { field_index, .. } = app_state.field_editor_state {
+                        app_state.field_editor_state = FieldEditorState::Selected { field_index };
+                    }
+                }
+                FieldEditorEventResult::StateChangedWithToast(new_state, toast) => {
+                    // Invalid value: keep editing, retry after the next idle period
+                    app_state.field_editor_state = new_state;
+                    toasts.push(toast);
+                }
+                _ => {}
+            }
+        }
+
         terminal.draw(|f| {
             let area = f.area();
             
             // Create dimming context based on popup state or dropdown selection
             let is_selecting = matches!(app_state.field_editor_state, FieldEditorState::Selecting { .. } | FieldEditorState::ProfileSelecting { .. });
-            let dimming = DimmingContext::new(popup.is_some() || is_selecting);
+            let dimming = DimmingContext::new(popup.is_some() || is_selecting || pending_profile_load.is_some());
+            
+            // Links are re-recorded by the dashboard render; drop them when another tab is shown
+            dashboard::lock_state(&app_state.dashboard).output_links.clear();
             
             // Render UI using coordinator
             render_ui(
@@ -188,7 +319,6 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 &mut registry,
                 main_content_box_handle_name,
                 &mut original_anchor_metrics,
-                &mut layout_manager,
                 &main_content_tab_bar,
                 tab_style,
                 &app_state.settings,
@@ -196,6 +326,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 &app_state.field_editor_state,
                 &app_state.profile_state,
                 &app_state.dashboard,
+                &app_state.theme,
                 &popup,
                 &toasts,
                 &mut current_tab_bar,
@@ -205,12 +336,17 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             // Handle cursor positioning for editing fields
             handle_cursor_positioning(
                 f,
+                &app_state.settings.get(),
                 &app_state.field_editor_state,
                 &registry,
                 &main_content_tab_bar,
                 main_content_box_handle_name,
-                &mut layout_manager,
             );
+            
+            // Profile load confirmation sits above everything else
+            if let Some(pending) = &pending_profile_load {
+                render_profile_diff(f, area, pending);
+            }
         })?;
         
         // ┌──────────────────────────────────────────────────────────────────────────────────────────────┐
@@ -219,6 +355,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         
         match crossterm::event::poll(std::time::Duration::from_millis(50)) {
             Ok(true) => {
+                last_input = std::time::Instant::now();
                 match event::read()? {
                     Event::Key(key) => {
                         if key.kind != KeyEventKind::Press {
@@ -233,6 +370,49 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                         let has_popup = popup.is_some();
                         let is_modal = is_editing || is_selecting || has_popup;
                         
+                        // Pending profile load: Enter applies the diff, Esc aborts, everything else is swallowed
+                        if let Some(pending) = pending_profile_load.take() {
+                            match key.code {
+                                KeyCode::Enter => toasts.push(apply_profile(&app_state, &pending.profile_name, pending.settings)),
+                                KeyCode::Esc => {}
+                                _ => pending_profile_load = Some(pending),
+                            }
+                            continue;
+                        }
+                        
+                        // sketch.yaml offer: y applies its defaults, any other key dismisses it and is handled normally
+                        if let Some(project) = pending_sketch_project.take() {
+                            if !is_modal && key.code == KeyCode::Char('y') {
+                                let updated = project.apply_to(&app_state.settings.get());
+                                toasts.push(match app_state.settings.update(|settings| *settings = updated.clone()) {
+                                    Ok(_) => Toast::new("Applied sketch.yaml defaults".to_string(), ToastType::Success),
+                                    Err(e) => Toast::new(format!("Failed to save settings: {}", e), ToastType::Error),
+                                });
+                                continue;
+                            }
+                        }
+                        
+                        // Toggle mouse capture so output can be selected with the terminal
+                        if key_bindings.matches(ACTION_TOGGLE_MOUSE, key.code, key.modifiers) {
+                            mouse_capture.toggle();
+                            mouse_capture.apply(terminal.backend_mut())?;
+                            toasts.push(Toast::new(
+                                mouse_capture.status_message().to_string(),
+                                ToastType::Success,
+                            ));
+                            continue;
+                        }
+                        
+                        // Dashboard output filter input takes every key while it is open
+                        if !is_modal && handle_filter_input_key(key.code, key.modifiers, &app_state.dashboard) {
+                            continue;
+                        }
+                        
+                        // Quit on any configured quit key (text entry and popups keep their keys)
+                        if !is_modal && key_bindings.matches(ACTION_QUIT, key.code, key.modifiers) {
+                            break;
+                        }
+                        
                         // Handle dashboard navigation (only if not in a modal state)
                         if !is_modal {
                             if let Some(active_tab_idx) = registry.get_active_tab(main_content_tab_bar.handle()) {
@@ -242,9 +422,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                                             // SettingsManager always has latest values - no reload needed
                                             if handle_dashboard_key_event(
                                                 key.code,
+                                                key.modifiers,
+                                                &key_bindings,
                                                 &app_state.dashboard,
                                                 &app_state.settings,
                                                 app_state.process_manager.clone(),
+                                                &mut app_state.clipboard,
                                             ) {
                                                 continue;
                                             }
@@ -287,38 +470,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                                     continue;
                                 }
                                 ProfileEventResult::LoadProfile(profile_name) => {
-                                    match crate::profile_manager::load_profile(&profile_name) {
-                                        Ok(loaded_settings) => {
-                                            // Update all settings fields
-                                            match app_state.settings.update(|settings| {
-                                                *settings = loaded_settings.clone();
-                                            }) {
-                                                Ok(_) => {
-                                                    toasts.push(Toast::new(
-                                                        format!("Profile '{}' loaded", profile_name),
-                                                        ToastType::Success,
-                                                    ));
-                                                    // Set active profile name
-                                                    {
-                                                        let mut active_name = app_state.profile_state.active_profile_name.lock().unwrap();
-                                                        *active_name = Some(profile_name.clone());
-                                                    }
-                                                }
-                                                Err(e) => {
-                                                    toasts.push(Toast::new(
-                                                        format!("Failed to save loaded profile: {}", e),
-                                                        ToastType::Error,
-                                                    ));
-                                                }
-                                            }
-                                        }
-                                        Err(e) => {
-                                            toasts.push(Toast::new(
-                                                format!("Failed to load profile: {}", e),
-                                                ToastType::Error,
-                                            ));
-                                        }
-                                    }
+                                    request_profile_load(&app_state, &profile_name, &mut pending_profile_load, &mut toasts);
                                     continue;
                                 }
                                 ProfileEventResult::RefreshProfiles => {
@@ -358,6 +510,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                                             app_state.field_editor_state = FieldEditorState::Selected { field_index };
                                         }
                                     }
+                                    FieldEditorEventResult::StateChangedWithToast(new_state, toast) => {
+                                        // Validation failed: stay in the editor so the value can be fixed
+                                        app_state.field_editor_state = new_state;
+                                        toasts.push(toast);
+                                    }
                                     _ => {}
                                 }
                             }
@@ -389,38 +546,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 if let FieldEditorState::ProfileSelecting { selected_index, options } = &app_state.field_editor_state {
                                     if key.code == KeyCode::Enter && *selected_index < options.len() {
                                         let profile_name = options[*selected_index].clone();
-                                        // Load the selected profile logic (copied from above for now, or I should refactor)
-                                        match crate::profile_manager::load_profile(&profile_name) {
-                                            Ok(loaded_settings) => {
-                                                match app_state.settings.update(|settings| {
-                                                    *settings = loaded_settings.clone();
-                                                }) {
-                                                    Ok(_) => {
-                                                        toasts.push(Toast::new(
-                                                            format!("Profile '{}' loaded", profile_name),
-                                                            ToastType::Success,
-                                                        ));
-                                                        // Set active profile name
-                                                        {
-                                                            let mut active_name = app_state.profile_state.active_profile_name.lock().unwrap();
-                                                            *active_name = Some(profile_name.clone());
-                                                        }
-                                                    }
-                                                    Err(e) => {
-                                                        toasts.push(Toast::new(
-                                                            format!("Failed to save loaded profile: {}", e),
-                                                            ToastType::Error,
-                                                        ));
-                                                    }
-                                                }
-                                            }
-                                            Err(e) => {
-                                                toasts.push(Toast::new(
-                                                    format!("Failed to load profile: {}", e),
-                                                    ToastType::Error,
-                                                ));
-                                            }
-                                        }
+                                        request_profile_load(&app_state, &profile_name, &mut pending_profile_load, &mut toasts);
                                     }
                                 }
                                 // Back to selected state (in this case, just keep current field editor state which is likely Selected(0) or whatever it was)
@@ -444,15 +570,16 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                                             tab_style,
                                         );
                                         match result {
-                                            FieldEditorEventResult::Exit => {
-                                                break;
-                                            }
                                             FieldEditorEventResult::StateChanged(new_state) => {
                                                 app_state.field_editor_state = new_state;
                                             }
                                             FieldEditorEventResult::Toast(toast) => {
                                                 toasts.push(toast);
                                             }
+                                            FieldEditorEventResult::StateChangedWithToast(new_state, toast) => {
+                                                app_state.field_editor_state = new_state;
+                                                toasts.push(toast);
+                                            }
                                             _ => {}
                                         }
                                     }
@@ -483,6 +610,16 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                         }
                         
                         if mouse_event.kind == MouseEventKind::Down(crossterm::event::MouseButton::Left) {
+                            // Open OSC 8 links clicked in the output panel
+                            let clicked_url = dashboard::lock_state(&app_state.dashboard)
+                                .link_at(mouse_event.column, mouse_event.row)
+                                .map(str::to_string);
+                            if let Some(url) = clicked_url {
+                                hyperlink::open_url(&url);
+                                toasts.push(Toast::new(format!("Opening {}", url), ToastType::Success));
+                                continue;
+                            }
+                            
                             // Handle mouse clicks on tabs
                             handle_tab_click(
                                 &mouse_event,
@@ -493,14 +630,21 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                             );
                             
                             // Handle mouse clicks on settings fields
-                            if let Some(new_state) = handle_settings_field_click(
+                            match handle_settings_field_click(
                                 &mouse_event,
                                 &app_state.settings,
                                 &app_state.settings_fields,
                                 &registry,
                                 &main_content_tab_bar,
                             ) {
-                                app_state.field_editor_state = new_state;
+                                Some(FieldEditorEventResult::StateChanged(new_state)) => {
+                                    app_state.field_editor_state = new_state;
+                                }
+                                Some(FieldEditorEventResult::StateChangedWithToast(new_state, toast)) => {
+                                    app_state.field_editor_state = new_state;
+                                    toasts.push(toast);
+                                }
+                                _ => {}
                             }
                             
                             // Handle mouse clicks on profile selector
@@ -564,3 +708,47 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     
     Ok(())
 }
+
+
+/// Load a profile and either apply it directly (nothing changes) or queue it behind the diff popup
+fn request_profile_load(
+    app_state: &AppState,
+    profile_name: &str,
+    pending: &mut Option<PendingProfileLoad>,
+    toasts: &mut Vec<Toast>,
+) { //>
+    match crate::profile_manager::load_profile(profile_name) {
+        Ok(loaded_settings) => {
+            let rows = diff_settings(&app_state.settings.get(), &loaded_settings);
+            if rows.is_empty() {
+                toasts.push(apply_profile(app_state, profile_name, loaded_settings));
+            } else {
+                *pending = Some(PendingProfileLoad {
+                    profile_name: profile_name.to_string(),
+                    settings: loaded_settings,
+                    rows,
+                });
+            }
+        }
+        Err(e) => {
+            toasts.push(Toast::new(
+                format!("Failed to load profile: {}", e),
+                ToastType::Error,
+            ));
+        }
+    }
+} //<
+
+/// Replace the current settings with a loaded profile and mark it active
+fn apply_profile(app_state: &AppState, profile_name: &str, loaded_settings: Settings) -> Toast { //>
+    match app_state.settings.update(|settings| {
+        *settings = loaded_settings.clone();
+    }) {
+        Ok(_) => {
+            let mut active_name = app_state.profile_state.active_profile_name.lock().unwrap();
+            *active_name = Some(profile_name.to_string());
+            Toast::new(format!("Profile '{}' loaded", profile_name), ToastType::Success)
+        }
+        Err(e) => Toast::new(format!("Failed to save loaded profile: {}", e), ToastType::Error),
+    }
+} //<
@@ -0,0 +1,62 @@
+// Runtime mouse capture toggle
+// Lets the user hand the mouse back to the terminal for native text selection
+
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::execute;
+use std::io::{self, Write};
+
+/// Tracks whether crossterm mouse capture is currently enabled
+#[derive(Debug, Clone, Copy)]
+pub struct MouseCapture {
+    captured: bool,
+}
+
+impl MouseCapture {
+    /// Start in the given state (the terminal setup enables capture)
+    pub fn new(captured: bool) -> Self {
+        Self { captured }
+    }
+
+    /// True while the application receives mouse events
+    pub fn is_captured(&self) -> bool {
+        self.captured
+    }
+
+    /// Flip the captured flag and return the new state
+    pub fn toggle(&mut self) -> bool {
+        self.captured = !self.captured;
+        self.captured
+    }
+
+    /// Send the matching enable/disable sequence to the terminal
+    pub fn apply<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        if self.captured {
+            execute!(out, EnableMouseCapture)
+        } else {
+            execute!(out, DisableMouseCapture)
+        }
+    }
+
+    /// Toast text describing the current state
+    pub fn status_message(&self) -> &'static str {
+        if self.captured {
+            "Mouse capture on"
+        } else {
+            "Mouse capture off — select text with the terminal"
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toggle_flips_captured_state() {
+        let mut capture = MouseCapture::new(true);
+        assert!(!capture.toggle());
+        assert!(!capture.is_captured());
+        assert!(capture.toggle());
+        assert!(capture.is_captured());
+    }
+}
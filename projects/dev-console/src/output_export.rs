@@ -0,0 +1,97 @@
+// Dashboard output export
+// Writes the output buffer, status and resolved settings to a timestamped JSON file for bug reports
+
+use crate::dashboard::DashboardState;
+use crate::settings::Settings;
+use serde::Serialize;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Contents of an exported output file
+#[derive(Debug, Serialize)]
+pub struct OutputExport<'a> {
+    /// Unix time of the export, in milliseconds
+    pub exported_at_ms: u128,
+    pub last_command: Option<&'a str>,
+    pub status_text: &'a str,
+    pub progress_stage: &'a str,
+    pub output_lines: &'a [String],
+    /// Settings with secrets replaced by `REDACTED`
+    pub settings: Settings,
+}
+
+/// Replacement for secrets in exported files
+pub const REDACTED: &str = "<redacted>";
+
+/// Copy of the settings with the MQTT password replaced by `REDACTED`
+pub fn redact_settings(settings: &Settings) -> Settings {
+    let mut redacted = settings.clone();
+    if redacted.mqtt_password.as_deref().is_some_and(|p| !p.is_empty()) {
+        redacted.mqtt_password = Some(REDACTED.to_string());
+    }
+    redacted
+}
+
+/// Folder exports are written to: `<config dir>/dev-console/logs`
+pub fn logs_dir() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("dev-console")
+        .join("logs")
+}
+
+/// Write the dashboard state and redacted settings to `dir/output-<unix ms>.json`, creating `dir` if needed
+pub fn export_output(dir: &Path, state: &DashboardState, settings: &Settings) -> io::Result<PathBuf> { //>
+    let exported_at_ms = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis()).unwrap_or(0);
+    let export = OutputExport {
+        exported_at_ms,
+        last_command: state.last_command.as_deref(),
+        status_text: &state.status_text,
+        progress_stage: &state.progress_stage,
+        output_lines: &state.output_lines,
+        settings: redact_settings(settings),
+    };
+    let contents = serde_json::to_string_pretty(&export).map_err(io::Error::other)?;
+
+    fs::create_dir_all(dir)?;
+    let path = dir.join(format!("output-{}.json", exported_at_ms));
+    fs::write(&path, contents)?;
+    Ok(path)
+} //<
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_creates_missing_dir_and_writes_json() {
+        let dir = std::env::temp_dir().join("dev-console-export-test").join("logs");
+        let _ = fs::remove_dir_all(&dir);
+        let mut state = DashboardState::new();
+        state.add_output_line("Compiling sketch...".to_string());
+        state.set_status_text("Compile failed");
+        state.set_progress_stage("Linking");
+
+        let path = export_output(&dir, &state, &Settings::default()).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json["status_text"], "Compile failed");
+        assert_eq!(json["progress_stage"], "Linking");
+        assert_eq!(json["output_lines"][0], "Compiling sketch...");
+        assert_eq!(json["settings"]["fqbn"], "esp32:esp32:esp32s3");
+
+        // Secrets never reach the file
+        let settings = Settings { mqtt_password: Some("hunter2".to_string()), ..Settings::default() };
+        let path = export_output(&dir, &state, &settings).unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(!contents.contains("hunter2"));
+        let json: serde_json::Value = serde_json::from_str(&contents).unwrap();
+        assert_eq!(json["settings"]["mqtt_password"], REDACTED);
+
+        // A file where the folder should be surfaces the io error
+        let blocked = dir.join(path.file_name().unwrap());
+        assert!(export_output(&blocked, &state, &Settings::default()).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
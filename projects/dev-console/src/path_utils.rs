@@ -79,3 +79,33 @@ pub fn find_arduino_cli(env: &str, project_root: &Path) -> PathBuf {
 pub fn get_library_path(project_root: &Path, board_model: &str) -> PathBuf {
     project_root.join("lib").join(board_model)
 }
+
+/// Split a `sketch_directory` that names the sketch file itself into (folder, sketch name)
+///
+/// Returns `None` when the path is a directory or does not exist.
+pub fn split_sketch_file(path: &Path) -> Option<(PathBuf, String)> { //>
+    if !path.is_file() {
+        return None;
+    }
+    let dir = path.parent()?.to_path_buf();
+    let name = path.file_stem()?.to_string_lossy().to_string();
+    Some((dir, name))
+} //<
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sketch_file_split_into_directory_and_name() {
+        let dir = std::env::temp_dir().join("dev_console_sketch_file").join("blink");
+        std::fs::create_dir_all(&dir).unwrap();
+        let sketch = dir.join("blink.ino");
+        std::fs::write(&sketch, "void setup() {}\nvoid loop() {}\n").unwrap();
+
+        assert_eq!(split_sketch_file(&sketch), Some((dir.clone(), "blink".to_string())));
+        assert_eq!(split_sketch_file(&dir), None);
+        assert_eq!(split_sketch_file(&dir.join("missing.ino")), None);
+        let _ = std::fs::remove_dir_all(dir.parent().unwrap());
+    }
+}
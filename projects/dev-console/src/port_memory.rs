@@ -0,0 +1,88 @@
+// Remembered serial ports
+// Ports seen by the Port dropdown, persisted so absent boards can still be pre-selected
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Ports seen by previous enumerations, oldest first
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SeenPorts {
+    #[serde(default)]
+    pub ports: Vec<String>,
+}
+
+/// Default location, next to `settings.yaml`
+pub fn seen_ports_path() -> PathBuf { //>
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("dev-console")
+        .join("seen_ports.yaml")
+} //<
+
+impl SeenPorts {
+    /// Load from `path`; a missing or unreadable file yields an empty list
+    pub fn load_from(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| serde_yaml::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    /// Save to `path`, creating the directory if needed
+    pub fn save_to(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> { //>
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_yaml::to_string(self)?)?;
+        Ok(())
+    } //<
+
+    /// Add newly detected ports; true if the list changed
+    pub fn remember(&mut self, detected: &[String]) -> bool { //>
+        let before = self.ports.len();
+        for port in detected {
+            if !self.ports.contains(port) {
+                self.ports.push(port.clone());
+            }
+        }
+        self.ports.len() != before
+    } //<
+
+    /// Remembered ports that are not in `detected`
+    pub fn absent_from(&self, detected: &[String]) -> Vec<String> {
+        self.ports.iter().filter(|p| !detected.contains(p)).cloned().collect()
+    }
+}
+
+/// Record `detected` in the file at `path` and return the remembered ports currently absent
+///
+/// Save failures are ignored; the dropdown still works without persistence.
+pub fn remember_ports(path: &Path, detected: &[String]) -> Vec<String> { //>
+    let mut seen = SeenPorts::load_from(path);
+    if seen.remember(detected) {
+        let _ = seen.save_to(path);
+    }
+    seen.absent_from(detected)
+} //<
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_absent_port_is_remembered_across_loads() {
+        let dir = std::env::temp_dir().join("dev_console_seen_ports");
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join("seen_ports.yaml");
+
+        let first = vec!["/dev/ttyUSB0".to_string(), "/dev/ttyACM1".to_string()];
+        assert!(remember_ports(&path, &first).is_empty());
+
+        // ttyACM1 unplugged: still offered from the persisted list
+        let absent = remember_ports(&path, &["/dev/ttyUSB0".to_string()]);
+        assert_eq!(absent, vec!["/dev/ttyACM1".to_string()]);
+        assert_eq!(SeenPorts::load_from(&path).ports, first);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
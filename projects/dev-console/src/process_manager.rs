@@ -41,6 +41,12 @@ impl ProcessManager {
             #[cfg(unix)]
             {
                 use std::process::Command;
+                // Children first (arduino-cli's toolchain processes), then the process itself
+                let _ = Command::new("pkill")
+                    .arg("-TERM")
+                    .arg("-P")
+                    .arg(pid.to_string())
+                    .output();
                 // On Unix, use kill command with TERM signal for graceful shutdown
                 let _ = Command::new("kill")
                     .arg("-TERM")
@@ -51,9 +57,9 @@ impl ProcessManager {
             #[cfg(windows)]
             {
                 use std::process::Command;
-                // On Windows, use taskkill with /F for force termination
+                // On Windows, use taskkill with /F for force termination and /T for the whole tree
                 let _ = Command::new("taskkill")
-                    .args(&["/F", "/PID", &pid.to_string()])
+                    .args(&["/F", "/T", "/PID", &pid.to_string()])
                     .output();
             }
         }
@@ -99,6 +105,12 @@ impl ProcessManager {
             #[cfg(unix)]
             {
                 use std::process::Command;
+                // Children first (arduino-cli's toolchain processes), then the process itself
+                let _ = Command::new("pkill")
+                    .arg("-TERM")
+                    .arg("-P")
+                    .arg(pid.to_string())
+                    .output();
                 // On Unix, use kill command with TERM signal for graceful shutdown
                 let _ = Command::new("kill")
                     .arg("-TERM")
@@ -109,9 +121,9 @@ impl ProcessManager {
             #[cfg(windows)]
             {
                 use std::process::Command;
-                // On Windows, use taskkill with /F for force termination
+                // On Windows, use taskkill with /F for force termination and /T for the whole tree
                 let _ = Command::new("taskkill")
-                    .args(&["/F", "/PID", &pid.to_string()])
+                    .args(&["/F", "/T", "/PID", &pid.to_string()])
                     .output();
             }
         }
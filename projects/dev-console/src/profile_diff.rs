@@ -0,0 +1,129 @@
+// Profile load diff
+// Before/after view of the settings a profile load would change, confirmed before applying
+
+use crate::field_editor::SettingsField;
+use crate::settings::Settings;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table},
+    Frame,
+};
+
+/// One changed setting with its current and incoming value
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffRow {
+    pub label: &'static str,
+    pub old: String,
+    pub new: String,
+}
+
+/// Fields that differ between the current settings and a profile, sorted by label
+pub fn diff_settings(current: &Settings, incoming: &Settings) -> Vec<DiffRow> {
+    let mut rows: Vec<DiffRow> = SettingsField::all()
+        .into_iter()
+        .filter_map(|field| {
+            let old = field.get_value(current);
+            let new = field.get_value(incoming);
+            (old != new).then(|| DiffRow { label: field.label(), old, new })
+        })
+        .collect();
+    rows.sort_by(|a, b| a.label.cmp(b.label));
+    rows
+}
+
+/// A profile load waiting for the user to confirm the diff
+#[derive(Debug, Clone)]
+pub struct PendingProfileLoad {
+    pub profile_name: String,
+    pub settings: Settings,
+    pub rows: Vec<DiffRow>,
+}
+
+/// Render the confirmation popup: label, old value (dimmed) and new value (highlighted)
+pub fn render_profile_diff(f: &mut Frame, area: Rect, pending: &PendingProfileLoad) { //>
+    let accent = Color::Rgb(255, 215, 0);
+    let label_width = pending.rows.iter().map(|r| r.label.len()).max().unwrap_or(0) as u16;
+
+    // Rows + header + footer + borders
+    let height = (pending.rows.len() as u16 + 5).min(area.height);
+    let width = (area.width * 4 / 5).max(40).min(area.width);
+    let popup_area = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    };
+
+    let block = Block::default()
+        .title(Span::styled(format!(" Load profile '{}'? ", pending.profile_name), Style::default().fg(accent)))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(accent));
+    let inner = block.inner(popup_area);
+    f.render_widget(Clear, popup_area);
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let header = Row::new(vec!["Field", "Current", "Profile"])
+        .style(Style::default().fg(Color::Gray).add_modifier(Modifier::UNDERLINED));
+    let rows = pending.rows.iter().map(|row| {
+        Row::new(vec![
+            Cell::from(row.label),
+            Cell::from(Span::styled(format!("- {}", row.old), Style::default().fg(Color::DarkGray))),
+            Cell::from(Span::styled(
+                format!("+ {}", row.new),
+                Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+            )),
+        ])
+    });
+    let table = Table::new(
+        rows,
+        [Constraint::Length(label_width + 1), Constraint::Percentage(50), Constraint::Percentage(50)],
+    )
+    .header(header)
+    .column_spacing(2);
+    f.render_widget(table, chunks[0]);
+
+    let footer = Line::from(vec![
+        Span::styled("[Enter]", Style::default().fg(accent)),
+        Span::raw(" Apply  "),
+        Span::styled("[Esc]", Style::default().fg(accent)),
+        Span::raw(" Cancel"),
+    ]);
+    f.render_widget(Paragraph::new(footer), chunks[1]);
+} //<
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_rows_pair_old_and_new_in_label_order() {
+        let current = Settings::default();
+        let mut incoming = current.clone();
+        incoming.port = "COM7".to_string();
+        incoming.fqbn = "esp32:esp32:esp32c3".to_string();
+        incoming.baudrate = 921600;
+
+        let rows = diff_settings(&current, &incoming);
+
+        let labels: Vec<&str> = rows.iter().map(|r| r.label).collect();
+        let mut sorted = labels.clone();
+        sorted.sort();
+        assert_eq!(labels, sorted);
+        assert_eq!(labels, vec!["Baudrate", "FQBN", "Port"]);
+
+        for row in &rows {
+            let field = SettingsField::all().into_iter().find(|f| f.label() == row.label).unwrap();
+            assert_eq!(row.old, field.get_value(&current));
+            assert_eq!(row.new, field.get_value(&incoming));
+        }
+
+        assert!(diff_settings(&current, &current).is_empty());
+    }
+}
@@ -104,24 +104,119 @@ impl ProgressHistory {
         self.history.get(&key)
     }
     
-    /// Get average total time for a file (for future use)
-    #[allow(dead_code)]
-    pub fn get_average_total_time(&self, file_path: &PathBuf) -> Option<std::time::Duration> {
-        self.get_historical_data(file_path)
-            .and_then(|hist| {
-                if hist.total_averages.is_empty() {
-                    None
-                } else {
-                    let avg: f64 = hist.total_averages.iter().sum::<f64>() / hist.total_averages.len() as f64;
-                    Some(std::time::Duration::from_secs_f64(avg))
-                }
-            })
+}
+
+/// Most recent build durations kept per sketch
+const MAX_RECORDED_DURATIONS: usize = 20;
+
+/// Past successful build durations per sketch name; drives the "~38s remaining" estimate
+pub struct BuildDurations {
+    data_file: PathBuf,
+    durations: HashMap<String, Vec<f64>>, // Key: sketch name, values in seconds
+}
+
+impl BuildDurations {
+    /// Load recorded durations, starting empty when the file is missing or unreadable
+    pub fn load(data_file: PathBuf) -> Self {
+        let durations = fs::read_to_string(&data_file).ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+        Self { data_file, durations }
+    }
+    
+    /// Save recorded durations to file
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(parent) = self.data_file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.data_file, serde_json::to_string_pretty(&self.durations)?)?;
+        Ok(())
+    }
+    
+    /// Record one successful build of `sketch_name`
+    pub fn record(&mut self, sketch_name: &str, total_time: std::time::Duration) {
+        let times = self.durations.entry(sketch_name.to_string()).or_default();
+        times.push(total_time.as_secs_f64());
+        if times.len() > MAX_RECORDED_DURATIONS {
+            times.remove(0);
+        }
     }
+    
+    /// Median of the recorded durations for `sketch_name`, or `None` before its first build
+    ///
+    /// The median rather than the mean, so one unusually slow or clean build doesn't skew the ETA.
+    pub fn median(&self, sketch_name: &str) -> Option<std::time::Duration> { //>
+        let mut times = self.durations.get(sketch_name)?.clone();
+        if times.is_empty() {
+            return None;
+        }
+        times.sort_by(|a, b| a.total_cmp(b));
+        let mid = times.len() / 2;
+        let median = if times.len() % 2 == 0 { (times[mid - 1] + times[mid]) / 2.0 } else { times[mid] };
+        Some(std::time::Duration::from_secs_f64(median))
+    } //<
 }
 
+/// Weight of the newest estimate when smoothing the displayed ETA between updates
+pub const ETA_SMOOTHING: f64 = 0.3;
+
+/// Seconds left, blending the history (median total minus elapsed) with the current run's rate
+///
+/// Early on the history dominates; as `percent` grows the projection from this run's own
+/// pace takes over, so a slower or faster build than usual is still tracked.
+pub fn estimate_remaining(expected_secs: f64, elapsed_secs: f64, percent: f64) -> f64 { //>
+    let from_history = (expected_secs - elapsed_secs).max(0.0);
+    if percent <= 0.0 {
+        return from_history;
+    }
+    let fraction = (percent / 100.0).min(1.0);
+    let from_rate = elapsed_secs * (1.0 - fraction) / fraction;
+    (1.0 - fraction) * from_history + fraction * from_rate
+} //<
+
 /// Normalize path for consistent key generation
 fn normalize_path(path: &PathBuf) -> String {
     path.to_string_lossy().to_string()
         .replace('\\', "/")
         .to_lowercase()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_median_duration_feeds_blended_estimate() {
+        let file = std::env::temp_dir().join("dev-console-build-durations.json");
+        let _ = fs::remove_file(&file);
+        let mut durations = BuildDurations::load(file.clone());
+        assert_eq!(durations.median("blink"), None);
+        for secs in [40, 80] {
+            durations.record("blink", std::time::Duration::from_secs(secs));
+        }
+        let expected = durations.median("blink").unwrap().as_secs_f64();
+        assert_eq!(expected, 60.0);
+
+        // At the start only history counts; at 50% history and pace are weighted equally
+        assert_eq!(estimate_remaining(expected, 0.0, 0.0), 60.0);
+        assert_eq!(estimate_remaining(expected, 20.0, 50.0), 30.0);
+        assert_eq!(estimate_remaining(expected, 90.0, 100.0), 0.0);
+
+        // Durations are keyed by sketch name and survive a reload
+        durations.save().unwrap();
+        let reloaded = BuildDurations::load(file.clone());
+        assert_eq!(reloaded.median("blink"), Some(std::time::Duration::from_secs(60)));
+        assert_eq!(reloaded.median("sensor"), None);
+        let _ = fs::remove_file(&file);
+    }
+
+    #[test]
+    fn test_slow_outlier_does_not_skew_median() {
+        let mut durations = BuildDurations::load(std::env::temp_dir().join("dev-console-durations-outlier.json"));
+        for secs in [30, 32, 31, 300, 29] {
+            durations.record("blink", std::time::Duration::from_secs(secs));
+        }
+        // The mean would be 84.4s
+        assert_eq!(durations.median("blink"), Some(std::time::Duration::from_secs(31)));
+    }
+}
@@ -249,7 +249,7 @@ impl ProgressTracker {
 }
 
 /// Format duration as human-readable string
-fn format_duration(duration: Duration) -> String {
+pub fn format_duration(duration: Duration) -> String {
     let total_secs = duration.as_secs();
     let hours = total_secs / 3600;
     let minutes = (total_secs % 3600) / 60;
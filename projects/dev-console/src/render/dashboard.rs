@@ -1,15 +1,64 @@
 // Dashboard panel rendering
 
-use crate::dashboard::{DashboardState, SCROLL_TO_BOTTOM};
+use crate::dashboard::{DashboardState, OutputLink, OutputPlaceholder, SCROLL_TO_BOTTOM};
+use crate::hyperlink::{has_hyperlinks, split_hyperlinks};
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
 };
 use tui_components::DimmingContext;
 
+/// Shown in the command list when the configuration leaves it empty
+const NO_COMMANDS_PLACEHOLDER: &str = "No commands configured";
+
+/// Dimming for the command list: also dimmed while a command runs, if enabled
+fn commands_dimming(dimming: &DimmingContext, dashboard_state: &DashboardState) -> DimmingContext {
+    let busy = dashboard_state.dim_commands_while_running && dashboard_state.is_running;
+    DimmingContext::new(dimming.modal_visible || busy)
+}
+
+/// Build the lines shown in the output panel while no command has run
+fn placeholder_lines(placeholder: &OutputPlaceholder) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(Span::styled(
+        placeholder.text.clone(),
+        Style::default().fg(placeholder.color),
+    ))];
+    if let Some(hint) = &placeholder.hint {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            hint.clone(),
+            Style::default().fg(placeholder.color).add_modifier(Modifier::ITALIC),
+        )));
+    }
+    lines
+}
+
+/// Output panel title naming the last command and the active profile
+fn output_title(last_command: Option<&str>, profile: Option<&str>) -> String {
+    match (last_command, profile) {
+        (Some(command), Some(profile)) => format!(" Output — {} @ {} ", command, profile),
+        (Some(command), None) => format!(" Output — {} ", command),
+        (None, Some(profile)) => format!(" Output @ {} ", profile),
+        (None, None) => " Output ".to_string(),
+    }
+}
+
+/// Title suffix describing the active output filter, e.g. "[filter: !^xtensa]"
+fn filter_label(dashboard_state: &DashboardState) -> Option<String> {
+    let filter = dashboard_state.active_filter()?;
+    let negation = if dashboard_state.filter_inverted { "!" } else { "" };
+    Some(format!("[filter: {}{}] ", negation, filter.as_str()))
+}
+
+/// Progress line with the history-based estimate, e.g. "Compiling: 47% — ~38s remaining"
+fn eta_status_line(stage_label: &str, percent: f64, remaining_secs: f64) -> String {
+    let remaining = crate::progress_tracker::format_duration(std::time::Duration::from_secs_f64(remaining_secs.max(0.0)));
+    format!("{}: {:.0}% — ~{} remaining", stage_label, percent, remaining)
+}
+
 /// Parse a line with ANSI color codes and convert to ratatui Line
 fn parse_ansi_line(line: &str) -> Line<'static> {
     // Simple ANSI code parser - preserves color codes
@@ -23,6 +72,34 @@ fn parse_ansi_line(line: &str) -> Line<'static> {
     }
 }
 
+/// Parse an output line, styling OSC 8 link text and returning each link's column range and URL
+fn parse_output_line(line: &str) -> (Line<'static>, Vec<(u16, u16, String)>) { //>
+    if !has_hyperlinks(line) {
+        return (parse_ansi_line(line), Vec::new());
+    }
+
+    let link_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::UNDERLINED);
+    let mut spans = Vec::new();
+    let mut links = Vec::new();
+    let mut column: u16 = 0;
+
+    for segment in split_hyperlinks(line) {
+        let start = column;
+        for span in parse_ansi_line(&segment.text).spans {
+            column = column.saturating_add(span.content.chars().count() as u16);
+            if segment.url.is_some() {
+                spans.push(Span::styled(span.content, span.style.patch(link_style)));
+            } else {
+                spans.push(span);
+            }
+        }
+        if let Some(url) = segment.url {
+            links.push((start, column, url));
+        }
+    }
+    (Line::from(spans), links)
+} //<
+
 /// Parse ANSI escape sequences and convert to ratatui Spans
 fn parse_ansi_to_spans(text: &str) -> Line<'static> {
     use regex::Regex;
@@ -181,7 +258,7 @@ pub fn render_dashboard(
     f: &mut Frame,
     area: Rect,
     dashboard_state: &mut DashboardState,
-    _profile_state: &crate::profile_state::ProfileState,
+    profile_state: &crate::profile_state::ProfileState,
     _registry: &mut tui_components::RectRegistry,
     dimming: &DimmingContext,
 ) {
@@ -195,7 +272,7 @@ pub fn render_dashboard(
         .iter()
         .map(|cmd| cmd.len())
         .max()
-        .unwrap_or(10);
+        .unwrap_or(NO_COMMANDS_PLACEHOLDER.len());
     let commands_box_width = ((max_command_width + 4) as u16).min(area.width);
     
     // Split into two columns
@@ -208,30 +285,43 @@ pub fn render_dashboard(
         .split(area);
     
     // Column 1: Command list
-    let command_items: Vec<ListItem> = dashboard_state.commands
-        .iter()
-        .enumerate()
-        .map(|(idx, cmd)| {
-            let style = if idx == dashboard_state.selected_command {
-                Style::default()
-                    .fg(dimming.dim_color(Color::Cyan))
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default()
-                    .fg(dimming.text_color(false))
-            };
-            ListItem::new(Line::from(Span::styled(cmd.clone(), style)))
-        })
-        .collect();
+    let command_dimming = commands_dimming(dimming, dashboard_state);
+    let command_items: Vec<ListItem> = if dashboard_state.commands.is_empty() {
+        vec![ListItem::new(Line::from(Span::styled(
+            NO_COMMANDS_PLACEHOLDER,
+            Style::default()
+                .fg(command_dimming.dim_color(Color::DarkGray))
+                .add_modifier(Modifier::ITALIC),
+        )))]
+    } else {
+        dashboard_state.commands
+            .iter()
+            .enumerate()
+            .map(|(idx, cmd)| {
+                let style = if idx == dashboard_state.selected_command {
+                    Style::default()
+                        .fg(command_dimming.dim_color(Color::Cyan))
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                        .fg(command_dimming.text_color(false))
+                };
+                ListItem::new(Line::from(Span::styled(cmd.clone(), style)))
+            })
+            .collect()
+    };
     
     let command_list = List::new(command_items)
         .block(Block::default()
             .borders(Borders::ALL)
-            .title(Span::styled(" Commands ", Style::default().fg(dimming.text_color(true))))
-            .border_style(Style::default().fg(dimming.border_color(false)))
+            .title(Span::styled(" Commands ", Style::default().fg(command_dimming.text_color(true))))
+            .border_style(Style::default().fg(command_dimming.border_color(false)))
             .padding(ratatui::widgets::Padding::new(1, 1, 0, 0)));
     
-    f.render_widget(command_list, columns[0]);
+    // Stateful render keeps the selected command scrolled into view in short panes
+    let mut command_list_state = ListState::default()
+        .with_selected((!dashboard_state.commands.is_empty()).then_some(dashboard_state.selected_command));
+    f.render_stateful_widget(command_list, columns[0], &mut command_list_state);
     
     // Column 2: Split into status bar and output
     let column2_chunks = Layout::default()
@@ -258,17 +348,21 @@ pub fn render_dashboard(
         // ... (progress calculation) ...
         let (line1, line2, line3, line4) = if let Some(ref tracker) = dashboard_state.progress_tracker {
             // ... (tracker logic) ...
-            let elapsed = tracker.format_elapsed();
-            let eta = tracker.format_estimated_remaining()
-                .map(|r| format!(" | ETA: {}", r))
-                .unwrap_or_default();
-            
-            let line1 = format!("{}: {:.1}% | Elapsed: {}{}", 
-                tracker.current_stage_name(), 
-                tracker.progress_percent, 
-                elapsed,
-                eta
-            );
+            let stage_label = if dashboard_state.progress_phase.is_empty() {
+                tracker.current_stage_name().to_string()
+            } else {
+                format!("{} › {}", dashboard_state.progress_phase.as_ref(), tracker.current_stage_name())
+            };
+            // History-based ETA once this sketch has build history, otherwise the tracker's own estimate
+            let line1 = match dashboard_state.eta_secs {
+                Some(remaining) => eta_status_line(&stage_label, tracker.progress_percent, remaining),
+                None => {
+                    let eta = tracker.format_estimated_remaining()
+                        .map(|r| format!(" | ETA: {}", r))
+                        .unwrap_or_default();
+                    format!("{}: {:.1}% | Elapsed: {}{}", stage_label, tracker.progress_percent, tracker.format_elapsed(), eta)
+                }
+            };
             
             let percent_text = format!("{:.1}%", tracker.progress_percent);
             let percent_text_width = percent_text.len();
@@ -296,7 +390,11 @@ pub fn render_dashboard(
             (line1, line2, line3, line4)
         } else {
             // Fallback ...
-            let line1 = format!("{}: {:.1}%", dashboard_state.progress_stage.as_ref(), dashboard_state.progress_percent);
+            let line1 = if dashboard_state.progress_phase.is_empty() || dashboard_state.progress_phase == dashboard_state.progress_stage {
+                format!("{}: {:.1}%", dashboard_state.progress_stage.as_ref(), dashboard_state.progress_percent)
+            } else {
+                format!("{} › {}: {:.1}%", dashboard_state.progress_phase.as_ref(), dashboard_state.progress_stage.as_ref(), dashboard_state.progress_percent)
+            };
             let percent_text = format!("{:.1}%", dashboard_state.progress_percent);
             let percent_text_width = percent_text.len();
             let progress_width = (status_inner.width as usize).saturating_sub(percent_text_width + 4).max(10);
@@ -348,7 +446,15 @@ pub fn render_dashboard(
         // Render the main status block border first
         f.render_widget(status_block.clone(), column2_chunks[0]);
         
-        let status_para = Paragraph::new(dashboard_state.status_text.as_ref())
+        // Second line: how long the last run took
+        let mut status_lines = vec![Line::from(dashboard_state.status_text.as_ref())];
+        if let (false, Some(elapsed)) = (dashboard_state.is_running, dashboard_state.last_elapsed) {
+            status_lines.push(Line::from(Span::styled(
+                format!("Took {:.1}s", elapsed.as_secs_f64()),
+                Style::default().fg(dimming.dim_color(Color::DarkGray)),
+            )));
+        }
+        let status_para = Paragraph::new(status_lines)
             .style(Style::default().fg(dimming.dim_color(Color::White)));
         
         f.render_widget(status_para, status_inner);
@@ -356,16 +462,30 @@ pub fn render_dashboard(
     
     // Output box with scrolling
     let output_area = column2_chunks[1];
+    let active_profile = profile_state.active_profile_name.lock().unwrap().clone();
+    let mut title = output_title(dashboard_state.last_command.as_deref(), active_profile.as_deref());
+    if let Some(label) = filter_label(dashboard_state) {
+        title.push_str(&label);
+    }
     let output_block = Block::default()
         .borders(Borders::ALL)
-        .title(Span::styled(" Output ", Style::default().fg(dimming.text_color(true))))
+        .title(Span::styled(title, Style::default().fg(dimming.text_color(true))))
         .border_style(Style::default().fg(dimming.border_color(false)))
         .padding(ratatui::widgets::Padding::new(1, 1, 0, 0));
     let output_inner = output_block.inner(output_area);
     
-    // Calculate visible lines
+    // The open filter input takes the bottom row of the panel
+    let (output_inner, filter_row) = if dashboard_state.filter_input.is_some() && output_inner.height > 1 {
+        let row = Rect { y: output_inner.bottom() - 1, height: 1, ..output_inner };
+        (Rect { height: output_inner.height - 1, ..output_inner }, Some(row))
+    } else {
+        (output_inner, None)
+    };
+    
+    // Calculate visible lines (only those passing the output filter; the buffer is untouched)
+    let shown = dashboard_state.visible_output_indices();
     let visible_height = output_inner.height as usize;
-    let total_lines = dashboard_state.output_lines.len();
+    let total_lines = shown.len();
     
     // Calculate maximum scroll position (0-based index of first visible line when at bottom)
     // If total_lines <= visible_height, max_scroll is 0 (no scrolling needed)
@@ -388,22 +508,6 @@ pub fn render_dashboard(
     let start_line = dashboard_state.output_scroll;
     let end_line = (start_line + visible_height).min(total_lines);
     
-    // Parse ANSI color codes and convert to ratatui Spans
-    let visible_lines: Vec<Line> = if dashboard_state.output_lines.is_empty() {
-        vec![Line::from(Span::styled(
-            "No output yet. Select a command to run.",
-            Style::default().fg(Color::Rgb(128, 128, 128)),
-        ))]
-    } else {
-        dashboard_state.output_lines[start_line..end_line]
-            .iter()
-            .map(|line| {
-                // Parse ANSI codes in the line and convert to Spans
-                parse_ansi_line(line)
-            })
-            .collect()
-    };
-    
     // Render the block (borders and title) to the full area
     f.render_widget(output_block.clone(), output_area);
     
@@ -422,12 +526,51 @@ pub fn render_dashboard(
         output_inner
     };
     
+    // Parse ANSI color codes and convert to ratatui Spans, recording link positions for clicks
+    dashboard_state.output_links.clear();
+    let visible_lines: Vec<Line> = if dashboard_state.output_lines.is_empty() {
+        placeholder_lines(&dashboard_state.output_placeholder)
+    } else if shown.is_empty() {
+        vec![Line::from(Span::styled(
+            "No output lines match the filter",
+            Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC),
+        ))]
+    } else {
+        let mut lines = Vec::with_capacity(end_line - start_line);
+        for (row, &index) in shown[start_line..end_line].iter().enumerate() {
+            let (parsed, links) = parse_output_line(&dashboard_state.output_lines[index]);
+            for (start_col, end_col, url) in links {
+                dashboard_state.output_links.push(OutputLink {
+                    row: content_area.y + row as u16,
+                    start_col: content_area.x + start_col,
+                    end_col: content_area.x + end_col,
+                    url,
+                });
+            }
+            lines.push(parsed);
+        }
+        lines
+    };
+    
     // Render content without block (block already rendered above)
     let output_para = Paragraph::new(visible_lines)
         .style(Style::default().fg(Color::White));
     
     f.render_widget(output_para, content_area);
     
+    // Filter input line, with the reason an invalid pattern was rejected
+    if let (Some(row), Some(input)) = (filter_row, &dashboard_state.filter_input) {
+        let mut spans = vec![
+            Span::styled("/", Style::default().fg(Color::Cyan)),
+            Span::raw(input.value().to_string()),
+        ];
+        if let Some(error) = &dashboard_state.filter_error {
+            spans.push(Span::styled(format!("  {}", error), Style::default().fg(Color::Red)));
+        }
+        f.render_widget(Paragraph::new(Line::from(spans)), row);
+        f.set_cursor_position((row.x + 1 + input.visual_cursor() as u16, row.y));
+    }
+    
     // Render scrollbar if there are more lines than visible
     if total_lines > visible_height {
         // Position scrollbar on the right edge of the inner content area
@@ -474,4 +617,85 @@ pub fn render_dashboard(
         
         f.render_stateful_widget(scrollbar, scrollbar_area, &mut scrollbar_state);
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::OutputPlaceholderConfigYaml;
+
+    #[test]
+    fn test_configured_placeholder_rendered_when_output_empty() {
+        let mut state = DashboardState::new();
+        state.output_placeholder = OutputPlaceholder::from_config(&OutputPlaceholderConfigYaml {
+            text: "Nothing here yet".to_string(),
+            color: Some("yellow".to_string()),
+            hint: Some("[Up/Down] select  [Enter] run".to_string()),
+        });
+        assert!(state.output_lines.is_empty());
+
+        let lines = placeholder_lines(&state.output_placeholder);
+        assert_eq!(lines[0].spans[0].content, "Nothing here yet");
+        assert_eq!(lines[0].spans[0].style.fg, Some(Color::Yellow));
+        assert_eq!(lines.last().unwrap().spans[0].content, "[Up/Down] select  [Enter] run");
+
+        // Empty config keeps the original text and color
+        let default_lines = placeholder_lines(&OutputPlaceholder::default());
+        assert_eq!(default_lines.len(), 1);
+        assert_eq!(default_lines[0].spans[0].content, "No output yet. Select a command to run.");
+        assert_eq!(default_lines[0].spans[0].style.fg, Some(Color::Rgb(128, 128, 128)));
+    }
+
+    #[test]
+    fn test_output_title_names_last_command_and_profile() {
+        assert_eq!(output_title(Some("Upload"), Some("esp32-s3-dev")), " Output — Upload @ esp32-s3-dev ");
+        assert_eq!(output_title(Some("Compile"), None), " Output — Compile ");
+        assert_eq!(output_title(None, None), " Output ");
+    }
+
+    #[test]
+    fn test_filter_label_shows_pattern_and_inversion() {
+        let mut state = DashboardState::new();
+        assert_eq!(filter_label(&state), None);
+        state.filter = Some(regex::Regex::new("^xtensa").unwrap());
+        state.filter_enabled = true;
+        assert_eq!(filter_label(&state).as_deref(), Some("[filter: ^xtensa] "));
+        state.filter_inverted = true;
+        assert_eq!(filter_label(&state).as_deref(), Some("[filter: !^xtensa] "));
+        state.filter_enabled = false;
+        assert_eq!(filter_label(&state), None);
+    }
+
+    #[test]
+    fn test_eta_status_line_format() {
+        assert_eq!(eta_status_line("Compiling", 47.2, 38.0), "Compiling: 47% — ~38s remaining");
+
+        // No history: update_eta leaves the estimate unset so the line falls back to the tracker
+        let mut state = DashboardState::new();
+        state.progress_percent = 50.0;
+        state.update_eta(std::time::Duration::from_secs(20));
+        assert_eq!(state.eta_secs, None);
+        state.expected_secs = Some(60.0);
+        state.update_eta(std::time::Duration::from_secs(20));
+        assert_eq!(state.eta_secs, Some(30.0));
+    }
+
+    #[test]
+    fn test_command_region_dimmed_while_running() {
+        let no_modal = DimmingContext::new(false);
+        let mut state = DashboardState::new();
+        state.dim_commands_while_running = true;
+
+        assert!(!commands_dimming(&no_modal, &state).modal_visible);
+        state.is_running = true;
+        assert!(commands_dimming(&no_modal, &state).modal_visible);
+        state.is_running = false;
+        assert!(!commands_dimming(&no_modal, &state).modal_visible);
+
+        // Disabled option leaves the list alone while running
+        state.dim_commands_while_running = false;
+        state.is_running = true;
+        assert!(!commands_dimming(&no_modal, &state).modal_visible);
+        assert!(commands_dimming(&DimmingContext::new(true), &state).modal_visible);
+    }
+}
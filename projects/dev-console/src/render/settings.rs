@@ -4,6 +4,8 @@ use crate::settings::Settings;
 use crate::field_editor::{FieldEditorState, SettingsFields};
 use crate::profile_state::ProfileState;
 use crate::constants::*;
+use crate::layout_utils::{calculate_centered_content_area, SETTINGS_FIELD_HWNDS};
+use crate::theme::Theme;
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
@@ -23,6 +25,9 @@ fn register_or_update(registry: &mut RectRegistry, hwnd_name: &str, rect: Rect)
 }
 
 /// Render settings panel
+///
+/// Returns false when the area is below `settings.min_cols` x `settings.min_rows` and the
+/// size warning was drawn instead of the form.
 pub fn render_settings(
     f: &mut Frame,
     area: Rect,
@@ -32,24 +37,22 @@ pub fn render_settings(
     profile_state: &ProfileState,
     registry: &mut RectRegistry,
     dimming: &DimmingContext,
-) {
+    theme: &Theme,
+) -> bool {
     // Check if terminal is too small (minimum size requirements)
-    let min_width_pixels = MIN_WIDTH_PIXELS;
-    let min_height_pixels = MIN_HEIGHT_PIXELS;
-    
-    if area.width < min_width_pixels || area.height < min_height_pixels {
+    let Some(content_area) = calculate_centered_content_area(area, settings.min_cols, settings.min_rows) else {
         // Terminal is too small - show warning message
         let warning_text = vec![
             Line::from(""),
             Line::from(Span::styled(
                 "⚠ Terminal Too Small",
-                Style::default().fg(Color::Rgb(255, 215, 0)).add_modifier(Modifier::BOLD),
+                Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
             )),
             Line::from(""),
-            Line::from(format!("Minimum size required: {}x{}", min_width_pixels, min_height_pixels)),
+            Line::from(format!("Minimum size required: {}x{}", settings.min_cols, settings.min_rows)),
             Line::from(format!("Current size: {}x{}", area.width, area.height)),
             Line::from(""),
-            Line::from("Please resize your terminal to at least 80 columns by 21 rows."),
+            Line::from(format!("Please resize your terminal to at least {} columns by {} rows.", settings.min_cols, settings.min_rows)),
             Line::from(""),
             Line::from(Span::styled(
                 "The form will appear automatically when the terminal is large enough.",
@@ -64,7 +67,7 @@ pub fn render_settings(
         
         let warning_block = Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Rgb(255, 215, 0)))
+            .border_style(Style::default().fg(theme.accent))
             .title("Warning");
         
         let warning_para = Paragraph::new(warning_text)
@@ -85,27 +88,12 @@ pub fn render_settings(
         };
         
         f.render_widget(warning_para, warning_area);
-        return;
-    }
-    
-    // Calculate content size: 50% of available space, but at least 80 pixels wide and 25 pixels tall
-    let content_width = (area.width * CONTENT_WIDTH_PERCENT / 100).max(min_width_pixels).min(area.width);
-    let content_height = (area.height * CONTENT_HEIGHT_PERCENT / 100).max(min_height_pixels).min(area.height);
-    // Center the content (no blank lines above/below)
-    let content_x = area.x + (area.width.saturating_sub(content_width)) / 2;
-    let content_y = area.y + (area.height.saturating_sub(content_height)) / 2;
-    
-    // Ensure content area doesn't exceed bounds
-    let content_area = Rect {
-        x: content_x.min(area.x + area.width),
-        y: content_y.min(area.y + area.height),
-        width: content_width.min(area.width.saturating_sub(content_x.saturating_sub(area.x))),
-        height: content_height.min(area.height.saturating_sub(content_y.saturating_sub(area.y))),
+        return false;
     };
     
     // Ensure content_area is valid before splitting
     if content_area.width == 0 || content_area.height == 0 {
-        return;
+        return false;
     }
     
     // Calculate profile box width based on longest profile name + 2 spaces
@@ -163,11 +151,11 @@ pub fn render_settings(
         
         
         
-    register_or_update(registry, HWND_SETTINGS_FIELD_SKETCH_DIR, sketch_chunks[0]);
-    render_full_width_field(f, sketch_chunks[0], settings, fields, editor_state, 0, "Sketch Directory", dimming);
+    register_or_update(registry, SETTINGS_FIELD_HWNDS[0], sketch_chunks[0]);
+    render_full_width_field(f, sketch_chunks[0], settings, fields, editor_state, 0, "Sketch Directory", dimming, theme);
     
-    register_or_update(registry, HWND_SETTINGS_FIELD_SKETCH_NAME, sketch_chunks[1]);
-    render_full_width_field(f, sketch_chunks[1], settings, fields, editor_state, 1, "Sketch Name", dimming);
+    register_or_update(registry, SETTINGS_FIELD_HWNDS[1], sketch_chunks[1]);
+    render_full_width_field(f, sketch_chunks[1], settings, fields, editor_state, 1, "Sketch Name", dimming, theme);
     
     // Bottom section: 3 columns - Device | Connection | MQTT (2 sub-columns)
     let bottom_columns = Layout::default()
@@ -180,11 +168,11 @@ pub fn render_settings(
         .split(config_chunks[1]);
     
     // Device section: Environment, Board Model, FQBN - FULL HEIGHT
-    render_section(f, bottom_columns[0], settings, fields, editor_state, "Device", &[2, 3, 4], None, registry, dimming);
+    render_section(f, bottom_columns[0], settings, fields, editor_state, "Device", &[2, 3, 4], None, registry, dimming, theme);
     register_or_update(registry, HWND_SETTINGS_SECTION_DEVICE, bottom_columns[0]);
     
     // Connection section: Port, Baud Rate - FULL HEIGHT
-    render_section(f, bottom_columns[1], settings, fields, editor_state, "Connection", &[5, 6], None, registry, dimming);
+    render_section(f, bottom_columns[1], settings, fields, editor_state, "Connection", &[5, 6], None, registry, dimming, theme);
     register_or_update(registry, HWND_SETTINGS_SECTION_CONNECTION, bottom_columns[1]);
 
     // MQTT section: 2 sub-columns (Credentials | Topics)
@@ -197,17 +185,18 @@ pub fn render_settings(
         .split(bottom_columns[2]);
     
     // MQTT Credentials column - FULL HEIGHT
-    render_section(f, mqtt_columns[0], settings, fields, editor_state, "MQTT", &[7, 8, 9, 10], None, registry, dimming);
+    render_section(f, mqtt_columns[0], settings, fields, editor_state, "MQTT", &[7, 8, 9, 10], None, registry, dimming, theme);
     
     // MQTT Topics column - FULL HEIGHT
-    render_section(f, mqtt_columns[1], settings, fields, editor_state, "Topics", &[11, 12, 13], None, registry, dimming);
+    render_section(f, mqtt_columns[1], settings, fields, editor_state, "Topics", &[11, 12, 13], None, registry, dimming, theme);
     
     // Register combined MQTT section (full height)
     register_or_update(registry, HWND_SETTINGS_SECTION_MQTT, bottom_columns[2]);
     
     // Render profile box - FULL HEIGHT on the left (top-aligned, like dashboard commands)
     register_or_update(registry, HWND_PROFILE_BOX, profile_area);
-    render_profile_box(f, profile_area, profile_state, registry, dimming);
+    render_profile_box(f, profile_area, profile_state, registry, dimming, theme);
+    true
 }
 
 /// Render a full-width field (for Sketch Directory and Sketch Name)
@@ -220,6 +209,7 @@ fn render_full_width_field(
     field_index: usize,
     title: &str,
     dimming: &DimmingContext,
+    theme: &Theme,
 ) {
     // Ensure area is valid
     if area.width == 0 || area.height == 0 {
@@ -255,11 +245,11 @@ fn render_full_width_field(
     let border_color = if dimming.modal_visible {
         hex_color(0x222222)
     } else if is_editing {
-        Color::Cyan // Cyan when editing
+        theme.border_editing
     } else if is_selected {
-        Color::White // White when selected but not editing
+        theme.border_selected
     } else {
-        Color::Rgb(102, 102, 102) // Gray when inactive
+        theme.border_default
     };
     
     // Title color: white for text
@@ -300,6 +290,7 @@ fn render_section(
     target_height: Option<u16>,
     registry: &mut RectRegistry,
     dimming: &DimmingContext,
+    theme: &Theme,
 ) -> u16 {
     // Ensure area is valid
     if area.width == 0 || area.height == 0 {
@@ -307,7 +298,7 @@ fn render_section(
     }
 
     // Border color: #666666 (RGB 102, 102, 102) for box characters
-    let border_color = if dimming.modal_visible { hex_color(0x222222) } else { Color::Rgb(102, 102, 102) };
+    let border_color = if dimming.modal_visible { hex_color(0x222222) } else { theme.border_default };
     
     // Section title style
     let section_title_style = Style::default().fg(if dimming.modal_visible { hex_color(0x444444) } else { Color::Cyan });
@@ -331,21 +322,6 @@ fn render_section(
     
     let mut y_offset = 0; // Start at top of inner area
     
-    // Map field indices to HWND constants
-    let field_hwnds = [
-        HWND_SETTINGS_FIELD_ENV,
-        HWND_SETTINGS_FIELD_BOARD_MODEL,
-        HWND_SETTINGS_FIELD_FQBN,
-        HWND_SETTINGS_FIELD_PORT,
-        HWND_SETTINGS_FIELD_BAUDRATE,
-        HWND_SETTINGS_FIELD_MQTT_HOST,
-        HWND_SETTINGS_FIELD_MQTT_PORT,
-        HWND_SETTINGS_FIELD_MQTT_USERNAME,
-        HWND_SETTINGS_FIELD_MQTT_PASSWORD,
-        HWND_SETTINGS_FIELD_MQTT_TOPIC_COMMAND,
-        HWND_SETTINGS_FIELD_MQTT_TOPIC_STATE,
-        HWND_SETTINGS_FIELD_MQTT_TOPIC_STATUS,
-    ];
     
     for &field_index in field_indices {
         if field_index >= fields.count() {
@@ -359,15 +335,12 @@ fn render_section(
             height: field_height as u16,
         };
         
-        // Register field with HWND (field_index 2-13 map to hwnds 0-11)
-        if field_index >= 2 && field_index <= 13 {
-            let hwnd_index = field_index - 2;
-            if hwnd_index < field_hwnds.len() {
-                register_or_update(registry, field_hwnds[hwnd_index], field_area);
-            }
+        // Register field with HWND so overlays, cursor and clicks find it via settings_field_rect
+        if let Some(hwnd) = SETTINGS_FIELD_HWNDS.get(field_index) {
+            register_or_update(registry, hwnd, field_area);
         }
         
-        render_nested_field(f, field_area, settings, fields, editor_state, field_index, dimming);
+        render_nested_field(f, field_area, settings, fields, editor_state, field_index, dimming, theme);
         y_offset += field_height as u16 + spacing as u16; // Add spacing between fields
     }
     
@@ -387,6 +360,7 @@ fn render_nested_field(
     editor_state: &FieldEditorState,
     field_index: usize,
     dimming: &DimmingContext,
+    theme: &Theme,
 ) {
     // Ensure area is valid
     if area.width == 0 || area.height == 0 {
@@ -437,9 +411,9 @@ fn render_nested_field(
     let highlight_color = if dimming.modal_visible {
         hex_color(0x222222)
     } else if is_editing {
-        Color::Cyan
+        theme.border_editing
     } else if is_selected {
-        Color::White
+        theme.border_selected
     } else {
         Color::White // Match title bar style (bright when undimmed)
     };
@@ -467,6 +441,7 @@ fn render_profile_box(
     profile_state: &ProfileState,
     registry: &mut RectRegistry,
     dimming: &DimmingContext,
+    theme: &Theme,
 ) {
     // Ensure area is valid
     if area.width == 0 || area.height == 0 {
@@ -482,7 +457,7 @@ fn render_profile_box(
     let border_color = if dimming.modal_visible {
         hex_color(0x222222)
     } else if *is_active {
-        Color::White          // White when focused
+        theme.border_selected // White when focused
         // .add_modifier(Modifier::BOLD)
     } else {
         hex_color(0x777777)  // Brighter grey when unfocused (match title bar style)
@@ -527,7 +502,7 @@ fn render_profile_box(
         )
         .highlight_style(
             Style::default()
-                .fg(Color::Rgb(255, 215, 0)) // Gold for selected
+                .fg(theme.accent) // Gold for selected
                 .add_modifier(Modifier::BOLD)
         )
         .highlight_symbol("> ");
@@ -540,3 +515,34 @@ fn render_profile_box(
     
     f.render_stateful_widget(list, list_area, &mut list_state);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ThemeConfigYaml;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn test_theme_override_changes_selected_field_border() {
+        let theme = Theme::from_config(&ThemeConfigYaml {
+            border_selected: Some("magenta".to_string()),
+            ..Default::default()
+        });
+        let settings = Settings::default();
+        let fields = SettingsFields::new();
+        let editor_state = FieldEditorState::Selected { field_index: 4 };
+        let dimming = DimmingContext::new(false);
+        let mut terminal = Terminal::new(TestBackend::new(30, 3)).unwrap();
+
+        terminal
+            .draw(|f| render_nested_field(f, f.area(), &settings, &fields, &editor_state, 4, &dimming, &theme))
+            .unwrap();
+        assert_eq!(terminal.backend().buffer()[(0, 0)].fg, Color::Magenta);
+
+        let editor_state = FieldEditorState::Selected { field_index: 5 };
+        terminal
+            .draw(|f| render_nested_field(f, f.area(), &settings, &fields, &editor_state, 4, &dimming, &theme))
+            .unwrap();
+        assert_eq!(terminal.backend().buffer()[(0, 0)].fg, Color::White);
+    }
+}
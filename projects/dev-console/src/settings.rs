@@ -3,7 +3,8 @@
 
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use crate::constants::{DEFAULT_MAX_OUTPUT_LINES, MIN_HEIGHT_PIXELS, MIN_WIDTH_PIXELS};
 
 //--------------------------------------------------------<<
 
@@ -17,6 +18,15 @@ pub struct Settings { //>
     pub port: String,
     pub baudrate: u32,
     pub create_log: bool,
+    /// Dashboard output lines kept before the oldest are dropped
+    #[serde(default = "default_max_output_lines")]
+    pub max_output_lines: usize,
+    /// Narrowest settings tab (columns) the form is drawn in; below it the size warning is shown
+    #[serde(default = "default_min_cols")]
+    pub min_cols: u16,
+    /// Shortest settings tab (rows) the form is drawn in; below it the size warning is shown
+    #[serde(default = "default_min_rows")]
+    pub min_rows: u16,
     #[serde(default)]
     pub mqtt_host: Option<String>,
     #[serde(default)]
@@ -44,6 +54,9 @@ impl Default for Settings { //>
             port: "COM9".to_string(),
             baudrate: 115200,
             create_log: false,
+            max_output_lines: DEFAULT_MAX_OUTPUT_LINES,
+            min_cols: MIN_WIDTH_PIXELS,
+            min_rows: MIN_HEIGHT_PIXELS,
             mqtt_host: None,
             mqtt_port: None,
             mqtt_username: Some("mqtt".to_string()),
@@ -56,6 +69,18 @@ impl Default for Settings { //>
 } //<
 
 
+fn default_max_output_lines() -> usize {
+    DEFAULT_MAX_OUTPUT_LINES
+}
+
+fn default_min_cols() -> u16 {
+    MIN_WIDTH_PIXELS
+}
+
+fn default_min_rows() -> u16 {
+    MIN_HEIGHT_PIXELS
+}
+
 pub fn get_settings_path() -> PathBuf { //>
     dirs::config_dir()
         .unwrap_or_else(|| PathBuf::from("."))
@@ -63,12 +88,37 @@ pub fn get_settings_path() -> PathBuf { //>
         .join("settings.yaml")
 } //<
 
+/// Working-directory settings file used when the config directory cannot be written
+pub const FALLBACK_SETTINGS_FILE: &str = "settings.yaml";
+
+/// Check that `dir` can be created and written to by writing and removing a marker file
+pub fn is_dir_writable(dir: &Path) -> bool { //>
+    if fs::create_dir_all(dir).is_err() {
+        return false;
+    }
+    let marker = dir.join(".dev-console-write-test");
+    let writable = fs::write(&marker, b"").is_ok();
+    let _ = fs::remove_file(&marker);
+    writable
+} //<
+
+/// Pick where settings are saved: `primary` if its directory is writable, otherwise `fallback`
+///
+/// Returns the chosen path and whether the fallback was used.
+pub fn resolve_settings_path(primary: PathBuf, fallback: PathBuf) -> (PathBuf, bool) { //>
+    let primary_dir = primary.parent().unwrap_or_else(|| Path::new("."));
+    if is_dir_writable(primary_dir) {
+        (primary, false)
+    } else {
+        (fallback, true)
+    }
+} //<
+
 
 impl Settings {
 
-    pub fn load() -> Self {
-        let path = get_settings_path();
-        if let Ok(contents) = fs::read_to_string(&path) {
+    pub fn load_from(path: &Path) -> Self {
+        if let Ok(contents) = fs::read_to_string(path) {
             if let Ok(settings) = serde_yaml::from_str::<Settings>(&contents) {
                 return settings;
             }
@@ -76,16 +126,15 @@ impl Settings {
         Self::default()
     }
     
-    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let path = get_settings_path();
+    pub fn save_to(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)?;
         }
         let contents = serde_yaml::to_string(self)?;
-        fs::write(&path, contents)?;
+        fs::write(path, contents)?;
         // Ensure file is flushed to disk
         use std::io::Write;
-        if let Ok(mut file) = std::fs::OpenOptions::new().write(true).open(&path) {
+        if let Ok(mut file) = std::fs::OpenOptions::new().write(true).open(path) {
             let _ = file.flush();
         }
         Ok(())
@@ -1,7 +1,7 @@
 // Settings Manager - Centralized settings management
 // Single source of truth for settings loading, saving, and updates
 
-use crate::settings::{Settings, get_settings_path};
+use crate::settings::{Settings, get_settings_path, resolve_settings_path, FALLBACK_SETTINGS_FILE};
 use std::sync::{Arc, Mutex};
 use std::path::PathBuf;
 
@@ -10,19 +10,38 @@ use std::path::PathBuf;
 pub struct SettingsManager {
     settings: Arc<Mutex<Settings>>,
     settings_path: PathBuf,
+    using_fallback: bool,
 }
 
 impl SettingsManager {
     /// Create a new settings manager by loading from disk
     pub fn load() -> Self {
-        let settings = Settings::load();
-        let settings_path = get_settings_path();
+        Self::load_with_fallback(get_settings_path(), PathBuf::from(FALLBACK_SETTINGS_FILE))
+    }
+    
+    /// Load from `primary`, switching to `fallback` when the primary directory is unwritable
+    ///
+    /// Until the fallback file exists, settings are still read from the primary path so
+    /// existing values carry over on the first save.
+    pub fn load_with_fallback(primary: PathBuf, fallback: PathBuf) -> Self {
+        let (settings_path, using_fallback) = resolve_settings_path(primary.clone(), fallback);
+        let settings = if using_fallback && !settings_path.exists() {
+            Settings::load_from(&primary)
+        } else {
+            Settings::load_from(&settings_path)
+        };
         Self {
             settings: Arc::new(Mutex::new(settings)),
             settings_path,
+            using_fallback,
         }
     }
     
+    /// True when settings are saved to the working-directory fallback
+    pub fn using_fallback(&self) -> bool {
+        self.using_fallback
+    }
+    
     /// Get a clone of the current settings
     /// Use this when you need to pass settings to a thread or function
     pub fn get(&self) -> Settings {
@@ -45,7 +64,7 @@ impl SettingsManager {
         let mut settings = self.settings.lock().unwrap();
         f(&mut settings);
         // Save to disk and ensure it's flushed
-        settings.save()?;
+        settings.save_to(&self.settings_path)?;
         // Verify the update was applied to the in-memory copy
         // (settings is already updated, we just need to ensure save succeeded)
         Ok(())
@@ -66,20 +85,19 @@ impl SettingsManager {
     #[allow(dead_code)]
     pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
         let settings = self.settings.lock().unwrap();
-        settings.save()?;
+        settings.save_to(&self.settings_path)?;
         Ok(())
     }
     
     /// Reload settings from disk (useful after external changes)
     pub fn reload(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let new_settings = Settings::load();
+        let new_settings = Settings::load_from(&self.settings_path);
         let mut settings = self.settings.lock().unwrap();
         *settings = new_settings;
         Ok(())
     }
     
-    /// Get the settings path (for debugging/logging)
-    #[allow(dead_code)]
+    /// Get the path settings are saved to
     pub fn path(&self) -> &PathBuf {
         &self.settings_path
     }
@@ -90,6 +108,35 @@ impl Clone for SettingsManager {
         Self {
             settings: Arc::clone(&self.settings),
             settings_path: self.settings_path.clone(),
+            using_fallback: self.using_fallback,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unwritable_config_dir_saves_to_fallback() {
+        let base = std::env::temp_dir().join("dev_console_settings_fallback");
+        let _ = std::fs::remove_dir_all(&base);
+        std::fs::create_dir_all(&base).unwrap();
+
+        // A regular file where the config directory should be makes it impossible to create
+        let blocker = base.join("config");
+        std::fs::write(&blocker, b"").unwrap();
+        let primary = blocker.join("dev-console").join("settings.yaml");
+        let fallback = base.join("settings.yaml");
+
+        let manager = SettingsManager::load_with_fallback(primary.clone(), fallback.clone());
+        assert!(manager.using_fallback());
+        assert_eq!(manager.path(), &fallback);
+
+        manager.update(|settings| settings.baudrate = 9600).unwrap();
+        assert!(!primary.exists());
+        assert_eq!(Settings::load_from(&fallback).baudrate, 9600);
+
+        let _ = std::fs::remove_dir_all(&base);
+    }
+}
@@ -0,0 +1,82 @@
+// Settings schema
+// JSON schema for settings.yaml, generated from the same field metadata as the settings form
+
+use crate::board_catalog::BoardCatalog;
+use crate::field_editor::SettingsField;
+use crate::settings::Settings;
+use serde_json::{json, Map, Value};
+
+/// Command-line flag that prints the schema and exits
+pub const SCHEMA_FLAG: &str = "--settings-schema";
+
+/// Build the schema; board names from `catalog` become the Board Model enum when known
+pub fn settings_schema(catalog: &BoardCatalog) -> Value { //>
+    let defaults = Settings::default();
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+
+    for field in SettingsField::all() {
+        let mut property = Map::new();
+        property.insert("title".to_string(), json!(field.label()));
+        property.insert("type".to_string(), json!(if field.is_numeric() { "integer" } else { "string" }));
+
+        let options = match field {
+            SettingsField::Environment => field.get_dropdown_options(&defaults),
+            SettingsField::BoardModel => catalog.names(),
+            _ => Vec::new(),
+        };
+        if !options.is_empty() {
+            property.insert("enum".to_string(), json!(options));
+        }
+
+        let default = field.get_value(&defaults);
+        if !default.is_empty() {
+            let default = match default.parse::<u64>() {
+                Ok(number) if field.is_numeric() => json!(number),
+                _ => json!(default),
+            };
+            property.insert("default".to_string(), default);
+        }
+
+        if !field.is_optional() {
+            required.push(field.yaml_key());
+        }
+        properties.insert(field.yaml_key().to_string(), Value::Object(property));
+    }
+
+    // Not shown in the form, but still part of settings.yaml
+    properties.insert("create_log".to_string(), json!({ "title": "Create Log", "type": "boolean", "default": defaults.create_log }));
+    required.push("create_log");
+    properties.insert(
+        "max_output_lines".to_string(),
+        json!({ "title": "Max Output Lines", "type": "integer", "minimum": 1, "default": defaults.max_output_lines }),
+    );
+    properties.insert("min_cols".to_string(), json!({ "title": "Minimum Columns", "type": "integer", "minimum": 1, "default": defaults.min_cols }));
+    properties.insert("min_rows".to_string(), json!({ "title": "Minimum Rows", "type": "integer", "minimum": 1, "default": defaults.min_rows }));
+
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "dev-console settings.yaml",
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    })
+} //<
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_describes_baudrate_and_environment() {
+        let schema = settings_schema(&BoardCatalog::default());
+        let properties = &schema["properties"];
+
+        assert_eq!(properties["baudrate"]["type"], "integer");
+        assert_eq!(properties["baudrate"]["default"], 115200);
+        assert_eq!(properties["env"]["enum"], json!(["arduino", "esp-idf"]));
+        assert!(properties["board_model"].get("enum").is_none());
+        assert!(schema["required"].as_array().unwrap().contains(&json!("baudrate")));
+        assert!(!schema["required"].as_array().unwrap().contains(&json!("mqtt_host")));
+    }
+}
@@ -0,0 +1,83 @@
+// arduino-cli sketch project file
+// Reads `sketch.yaml` defaults so settings can be matched to the project on load
+
+use crate::settings::Settings;
+use serde::Deserialize;
+use std::path::Path;
+
+/// Project file name arduino-cli looks for in the sketch directory
+pub const SKETCH_PROJECT_FILE: &str = "sketch.yaml";
+
+/// Board defaults declared by a sketch's `sketch.yaml`
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct SketchProject {
+    #[serde(default)]
+    pub default_fqbn: Option<String>,
+    #[serde(default)]
+    pub default_port: Option<String>,
+}
+
+impl SketchProject {
+    /// Parse the file contents; other keys (profiles, libraries) are ignored
+    pub fn parse(contents: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(contents)
+    }
+
+    /// Load `sketch.yaml` from the sketch directory, if present and readable
+    pub fn load(sketch_dir: &Path) -> Option<Self> {
+        let contents = std::fs::read_to_string(sketch_dir.join(SKETCH_PROJECT_FILE)).ok()?;
+        Self::parse(&contents).ok()
+    }
+
+    /// Settings with the project defaults applied over `settings`
+    pub fn apply_to(&self, settings: &Settings) -> Settings {
+        let mut updated = settings.clone();
+        if let Some(fqbn) = &self.default_fqbn {
+            updated.fqbn = fqbn.clone();
+        }
+        if let Some(port) = &self.default_port {
+            updated.port = port.clone();
+        }
+        updated
+    }
+
+    /// Human-readable summary of the defaults that differ from `settings`, or `None` if they match
+    pub fn describe_changes(&self, settings: &Settings) -> Option<String> { //>
+        let mut changes = Vec::new();
+        if let Some(fqbn) = self.default_fqbn.as_ref().filter(|f| **f != settings.fqbn) {
+            changes.push(format!("FQBN {}", fqbn));
+        }
+        if let Some(port) = self.default_port.as_ref().filter(|p| **p != settings.port) {
+            changes.push(format!("port {}", port));
+        }
+        (!changes.is_empty()).then(|| changes.join(", "))
+    } //<
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_extracts_default_fqbn_and_port() {
+        let project = SketchProject::parse(
+            "profiles:\n  \
+               s3:\n    \
+                 fqbn: esp32:esp32:esp32s3\n\
+             default_fqbn: esp32:esp32:esp32s3:CDCOnBoot=cdc\n\
+             default_port: /dev/ttyACM0\n",
+        )
+        .unwrap();
+        assert_eq!(project.default_fqbn.as_deref(), Some("esp32:esp32:esp32s3:CDCOnBoot=cdc"));
+        assert_eq!(project.default_port.as_deref(), Some("/dev/ttyACM0"));
+
+        let settings = Settings::default();
+        assert_eq!(
+            project.describe_changes(&settings).as_deref(),
+            Some("FQBN esp32:esp32:esp32s3:CDCOnBoot=cdc, port /dev/ttyACM0")
+        );
+        let applied = project.apply_to(&settings);
+        assert_eq!(applied.port, "/dev/ttyACM0");
+        assert!(project.describe_changes(&applied).is_none());
+    }
+}
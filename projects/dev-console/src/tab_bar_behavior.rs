@@ -0,0 +1,45 @@
+// Tab bar interaction policy module
+// Single source of truth for how each tab bar style responds to keyboard and mouse input
+
+use tui_components::TabBarStyle;
+
+/// Interaction capabilities derived from a tab bar style
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TabBarBehavior {
+    /// Arrow keys / h / l move between tabs
+    pub keyboard_navigable: bool,
+    /// Clicking a tab makes it active
+    pub click_switches_tabs: bool,
+}
+
+impl TabBarBehavior {
+    /// Get the behavior for a tab bar style
+    /// Static styles (BoxStatic, TextStatic) are display-only and ignore both keys and clicks
+    pub fn for_style(style: TabBarStyle) -> Self {
+        let is_static = matches!(style, TabBarStyle::BoxStatic | TabBarStyle::TextStatic);
+        Self {
+            keyboard_navigable: !is_static,
+            click_switches_tabs: !is_static,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_static_styles_ignore_keys_and_clicks() {
+        let box_static = TabBarBehavior::for_style(TabBarStyle::BoxStatic);
+        assert!(!box_static.keyboard_navigable);
+        assert!(!box_static.click_switches_tabs);
+
+        let text_static = TabBarBehavior::for_style(TabBarStyle::TextStatic);
+        assert!(!text_static.keyboard_navigable);
+        assert!(!text_static.click_switches_tabs);
+
+        let tab = TabBarBehavior::for_style(TabBarStyle::Tab);
+        assert!(tab.keyboard_navigable);
+        assert!(tab.click_switches_tabs);
+    }
+}
@@ -0,0 +1,63 @@
+// Theme
+// Named color roles for borders and highlights, overridable from `application.theme`
+
+use crate::config::ThemeConfigYaml;
+use ratatui::style::Color;
+use std::str::FromStr;
+
+/// Colors shared by the settings and overlay renderers
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    /// Border of an inactive field or section
+    pub border_default: Color,
+    /// Border of the selected field
+    pub border_selected: Color,
+    /// Border of the field being edited
+    pub border_editing: Color,
+    /// Highlights: selected dropdown/profile entries, warnings
+    pub accent: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            border_default: Color::Rgb(102, 102, 102),
+            border_selected: Color::White,
+            border_editing: Color::Cyan,
+            accent: Color::Rgb(255, 215, 0),
+        }
+    }
+}
+
+impl Theme {
+    /// Default theme with configured overrides; unknown color names keep the default
+    pub fn from_config(config: &ThemeConfigYaml) -> Self { //>
+        let defaults = Self::default();
+        let pick = |value: &Option<String>, fallback: Color| {
+            value.as_deref().and_then(|c| Color::from_str(c).ok()).unwrap_or(fallback)
+        };
+        Self {
+            border_default: pick(&config.border_default, defaults.border_default),
+            border_selected: pick(&config.border_selected, defaults.border_selected),
+            border_editing: pick(&config.border_editing, defaults.border_editing),
+            accent: pick(&config.accent, defaults.accent),
+        }
+    } //<
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overrides_replace_only_configured_roles() {
+        let theme = Theme::from_config(&ThemeConfigYaml {
+            border_selected: Some("#ff00ff".to_string()),
+            accent: Some("not-a-color".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(theme.border_selected, Color::Rgb(255, 0, 255));
+        assert_eq!(theme.accent, Theme::default().accent);
+        assert_eq!(theme.border_default, Theme::default().border_default);
+    }
+}
@@ -4,14 +4,15 @@
 use crate::render::{render_content, render_settings, render_dashboard, render_settings2_standalone};
 use crate::field_editor::{FieldEditorState, SettingsFields};
 use crate::dashboard::DashboardState;
-use crate::layout_manager::LayoutManager;
+use crate::layout_utils::{calculate_centered_content_area, settings_field_rect, tab_content_area};
 use crate::profile_state::ProfileState;
+use crate::theme::Theme;
 
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, List, ListItem},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
     Frame,
 };
 use tui_components::{
@@ -34,7 +35,6 @@ pub fn render_ui(
     registry: &mut RectRegistry,
     main_content_box_handle_name: &str,
     original_anchor_metrics: &mut Option<Rect>,
-    layout_manager: &mut LayoutManager,
     main_content_tab_bar: &TabBarManager,
     tab_style: TabBarStyle,
     settings_manager: &crate::settings_manager::SettingsManager,
@@ -42,6 +42,7 @@ pub fn render_ui(
     field_editor_state: &FieldEditorState,
     profile_state: &ProfileState,
     dashboard_arc: &Arc<Mutex<DashboardState>>,
+    theme: &Theme,
     popup: &Option<Popup>,
     toasts: &Vec<Toast>,
     current_tab_bar: &mut Option<(TabBar, RectHandle)>,
@@ -106,6 +107,9 @@ pub fn render_ui(
     // Prepare tab bar
     let tab_bar_result = main_content_tab_bar.prepare(registry, Some(tab_style));
     
+    // Whether the settings form (and so its field rects) is on screen this frame
+    let mut form_visible = false;
+    
     // Render content block
     let render_area = if let Some(box_manager) = get_box_by_name(registry, main_content_box_handle_name) {
         box_manager.prepare(registry).unwrap_or(content_area)
@@ -121,16 +125,11 @@ pub fn render_ui(
         if let Some(tab_bar_state) = registry.get_tab_bar_state(main_content_tab_bar.handle()) {
             if let Some(tab_config) = tab_bar_state.tab_configs.get(active_tab_idx) {
                 // Create nested area for tab content (x+1, y+1, width-2, height-2 to account for borders)
-                let nested_area = Rect {
-                    x: render_area.x.saturating_add(1),
-                    y: render_area.y.saturating_add(1),
-                    width: render_area.width.saturating_sub(2),
-                    height: render_area.height.saturating_sub(2),
-                };
+                let nested_area = tab_content_area(render_area);
                 
                 if tab_config.id == "settings" {
                     let settings = settings_manager.get(); // Get current settings
-                    render_settings(f, nested_area, &settings, settings_fields, field_editor_state, profile_state, registry, dimming);
+                    form_visible = render_settings(f, nested_area, &settings, settings_fields, field_editor_state, profile_state, registry, dimming, theme);
                 } else if tab_config.id == "settings2" {
                     let settings = settings_manager.get(); // Get current settings
                     render_settings2_standalone(f, nested_area, &settings, settings_fields, field_editor_state, profile_state);
@@ -138,9 +137,8 @@ pub fn render_ui(
                 
                 } else if tab_config.id == "dashboard" {
                     // Render dashboard directly from Arc to avoid cloning
-                    if let Ok(mut state) = dashboard_arc.lock() {
-                        render_dashboard(f, nested_area, &mut *state, profile_state, registry, dimming);
-                    }
+                    let mut state = crate::dashboard::lock_state(&dashboard_arc);
+                    render_dashboard(f, nested_area, &mut *state, profile_state, registry, dimming);
                 }
             }
         }
@@ -189,6 +187,8 @@ pub fn render_ui(
         area,
         field_editor_state,
         registry,
+        theme,
+        form_visible,
     );
     
     // Render popup
@@ -201,52 +201,35 @@ pub fn render_ui(
 }
 
 /// Render dropdown overlay when selecting a field
+///
+/// Field dropdowns are skipped while the size warning hides the form, since the
+/// registered field rects are then stale.
 fn render_dropdown_overlay(
     f: &mut Frame,
     area: Rect,
     field_editor_state: &FieldEditorState,
     registry: &RectRegistry,
+    theme: &Theme,
+    form_visible: bool,
 ) {
     // Render dropdown overlay if selecting
     match field_editor_state {
-        FieldEditorState::Selecting { field_index, selected_index, options } => {
-            // Use registry to get the field's registered rectangle
-            let field_hwnds = [
-                crate::constants::HWND_SETTINGS_FIELD_SKETCH_DIR,
-                crate::constants::HWND_SETTINGS_FIELD_SKETCH_NAME,
-                crate::constants::HWND_SETTINGS_FIELD_ENV,
-                crate::constants::HWND_SETTINGS_FIELD_BOARD_MODEL,
-                crate::constants::HWND_SETTINGS_FIELD_FQBN,
-                crate::constants::HWND_SETTINGS_FIELD_PORT,
-                crate::constants::HWND_SETTINGS_FIELD_BAUDRATE,
-                crate::constants::HWND_SETTINGS_FIELD_MQTT_HOST,
-                crate::constants::HWND_SETTINGS_FIELD_MQTT_PORT,
-                crate::constants::HWND_SETTINGS_FIELD_MQTT_USERNAME,
-                crate::constants::HWND_SETTINGS_FIELD_MQTT_PASSWORD,
-                crate::constants::HWND_SETTINGS_FIELD_MQTT_TOPIC_COMMAND,
-                crate::constants::HWND_SETTINGS_FIELD_MQTT_TOPIC_STATE,
-                crate::constants::HWND_SETTINGS_FIELD_MQTT_TOPIC_STATUS,
-            ];
+        FieldEditorState::Selecting { field_index, selected_index, options } if form_visible => {
             
             // Get field label
             let field_label = crate::field_editor::SettingsField::from_index(*field_index)
                 .map(|f| f.label())
                 .unwrap_or("");
             
-            if let Some(hwnd) = field_hwnds.get(*field_index) {
-                if let Some(field_box) = get_box_by_name(registry, hwnd) {
-                    if let Some(field_rect) = field_box.metrics(registry) {
-                        let field_area: Rect = field_rect.into();
-                        render_dropdown(f, area, field_area, options, *selected_index, field_label);
-                    }
-                }
+            if let Some(field_area) = settings_field_rect(registry, *field_index) {
+                render_dropdown(f, area, field_area, options, *selected_index, field_label, theme);
             }
         }
         FieldEditorState::ProfileSelecting { selected_index, options } => {
             if let Some(box_manager) = get_box_by_name(registry, crate::constants::HWND_PROFILE_SELECTOR) {
                 if let Some(rect) = box_manager.metrics(registry) {
                     let field_area: Rect = rect.into();
-                    render_dropdown(f, area, field_area, options, *selected_index, "Profiles");
+                    render_dropdown(f, area, field_area, options, *selected_index, "Profiles", theme);
                 }
             }
         }
@@ -262,6 +245,7 @@ fn render_dropdown(
     options: &[String],
     selected_index: usize,
     field_label: &str,
+    theme: &Theme,
 ) {
     // Calculate dropdown position - 3 lines up from the bottom of the field
     let dropdown_height = (options.len() + 2).min(10) as u16; // +2 for top and bottom borders
@@ -272,12 +256,12 @@ fn render_dropdown(
         height: dropdown_height,
     };
     
-    // Make sure dropdown fits in the frame
-    let adjusted_dropdown_area = if dropdown_area.y + dropdown_area.height > area.height {
+    // Make sure dropdown fits in the frame (fields low in a column, e.g. Baud Rate, open upwards)
+    let adjusted_dropdown_area = if dropdown_area.bottom() > area.bottom() {
         // If doesn't fit below, adjust position
         Rect {
             x: dropdown_area.x,
-            y: area.height.saturating_sub(dropdown_area.height),
+            y: area.bottom().saturating_sub(dropdown_area.height).max(area.y),
             width: dropdown_area.width,
             height: dropdown_height,
         }
@@ -285,40 +269,61 @@ fn render_dropdown(
         dropdown_area
     };
     
-    // Render dropdown
+    // Render dropdown (long entries such as USB port descriptions are cut to the box width)
+    let text_width = adjusted_dropdown_area.width.saturating_sub(2) as usize;
     let mut items = Vec::new();
     for (i, option) in options.iter().enumerate() {
-        let style = if i == selected_index {
+        let style = if crate::field_editor::is_placeholder_option(option) {
+            Style::default()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::ITALIC)
+        } else if crate::field_editor::is_remembered_port(options, i) {
+            // Previously used but not attached - grayed, still selectable
+            let style = Style::default().fg(Color::DarkGray);
+            if i == selected_index { style.add_modifier(Modifier::REVERSED) } else { style }
+        } else if i == selected_index {
             Style::default()
-                .fg(Color::Rgb(255, 215, 0))
+                .fg(theme.accent)
                 .add_modifier(Modifier::BOLD | Modifier::REVERSED)
         } else {
             Style::default()
                 .fg(Color::White)
         };
         items.push(ListItem::new(Line::from(vec![
-            Span::styled(option.clone(), style),
+            Span::styled(truncate_to_width(option, text_width), style),
         ])));
     }
     
     // Create list with all borders and field label as title
     let list = List::new(items)
         .block(Block::default()
-            .title(Span::styled(format!(" {} ", field_label), Style::default().fg(Color::Rgb(255, 215, 0))))
+            .title(Span::styled(format!(" {} ", field_label), Style::default().fg(theme.accent)))
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Rgb(255, 215, 0))));
+            .border_style(Style::default().fg(theme.accent)));
+    // Stateful so lists longer than the box (e.g. baud rates) scroll to the highlighted entry
+    let mut list_state = ListState::default().with_selected(Some(selected_index));
     f.render_widget(Clear, adjusted_dropdown_area);
-    f.render_widget(list, adjusted_dropdown_area);
+    f.render_stateful_widget(list, adjusted_dropdown_area, &mut list_state);
+}
+
+/// Cut `text` to `width` characters, marking the cut with an ellipsis
+fn truncate_to_width(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(width.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
 }
 
 /// Handle cursor positioning for editing fields
 pub fn handle_cursor_positioning(
     f: &mut Frame,
+    settings: &crate::settings::Settings,
     field_editor_state: &FieldEditorState,
     registry: &RectRegistry,
     main_content_tab_bar: &TabBarManager,
     main_content_box_handle_name: &str,
-    layout_manager: &mut LayoutManager,
 ) {
     if let FieldEditorState::Editing { field_index, ref input } = field_editor_state {
         if let Some(active_tab_idx) = registry.get_active_tab(main_content_tab_bar.handle()) {
@@ -329,94 +334,24 @@ pub fn handle_cursor_positioning(
                             if let Some(content_rect) = box_manager.metrics(registry) {
                                 let content_rect: Rect = content_rect.into();
                                 
-                                // Check if terminal is large enough - don't position cursor if warning is shown
-                                let min_width_pixels = crate::constants::MIN_WIDTH_PIXELS;
-                                let min_height_pixels = crate::constants::MIN_HEIGHT_PIXELS;
-                                
-                                // Only position cursor if terminal is large enough
-                                if content_rect.width >= min_width_pixels && content_rect.height >= min_height_pixels {
-                                    // Get field position from HWND registry
-                                    let field_hwnds = [
-                                        crate::constants::HWND_SETTINGS_FIELD_SKETCH_DIR,
-                                        crate::constants::HWND_SETTINGS_FIELD_SKETCH_NAME,
-                                        crate::constants::HWND_SETTINGS_FIELD_ENV,
-                                        crate::constants::HWND_SETTINGS_FIELD_BOARD_MODEL,
-                                        crate::constants::HWND_SETTINGS_FIELD_FQBN,
-                                        crate::constants::HWND_SETTINGS_FIELD_PORT,
-                                        crate::constants::HWND_SETTINGS_FIELD_BAUDRATE,
-                                        crate::constants::HWND_SETTINGS_FIELD_MQTT_HOST,
-                                        
-                                        
-                                        
-                                        crate::constants::HWND_SETTINGS_FIELD_MQTT_PORT,
-                                        crate::constants::HWND_SETTINGS_FIELD_MQTT_USERNAME,
-                                        crate::constants::HWND_SETTINGS_FIELD_MQTT_PASSWORD,
-                                        crate::constants::HWND_SETTINGS_FIELD_MQTT_TOPIC_COMMAND,
-                                        crate::constants::HWND_SETTINGS_FIELD_MQTT_TOPIC_STATE,
-                                        crate::constants::HWND_SETTINGS_FIELD_MQTT_TOPIC_STATUS,
-                                        
-                                        
-                                        
-                                        
-                                        
-                                        
-                                    ];
-                                    
-                                    if let Some(field_hwnd) = field_hwnds.get(*field_index) {
-                                        if let Some(field_box) = get_box_by_name(registry, field_hwnd) {
-                                            if let Some(field_rect) = field_box.metrics(registry) {
-                                                let field_rect: Rect = field_rect.into();
-                                                // Get inner area for text (accounting for borders and padding)
-                                                let inner_area = Block::default()
-                                                    .borders(Borders::ALL)
-                                                    .padding(ratatui::widgets::Padding { left: 1, right: 1, top: 0, bottom: 0 })
-                                                    .inner(field_rect);
-                                                let text_width = inner_area.width as usize;
-                                                
-                                                // Calculate scroll offset and visual cursor position
-                                                let scroll_offset = input.visual_scroll(text_width);
-                                                let visual_cursor = input.visual_cursor();
-                                                let cursor_pos_in_view = visual_cursor.saturating_sub(scroll_offset);
-                                                
-                                                // Calculate cursor position relative to field's inner area
-                                                let cursor_x = inner_area.x + cursor_pos_in_view as u16;
-                                                let cursor_y = inner_area.y;
-                                                
-                                                f.set_cursor_position((cursor_x, cursor_y));
-                                                return; // Successfully positioned cursor using HWND
-                                            }
-                                        }
-                                    }
-                                    
-                                    // Fallback to old method if HWND lookup fails
-                                    // Use LayoutManager for cached content area calculation
-                                    if let Some(content_area) = layout_manager.get_content_area(content_rect) {
-                                        // Get inner area width for scroll calculation
-                                        let inner_width = if *field_index < 2 {
-                                            // Top fields use full width minus borders
-                                            (content_area.width.saturating_sub(2)) as usize
-                                        } else if *field_index < 5 {
-                                            // Device section fields
-                                            ((content_area.width / 2).saturating_sub(4)) as usize
-                                        } else {
-                                            // Connection section fields
-                                            ((content_area.width / 2).saturating_sub(4)) as usize
-                                        };
+                                // Don't position the cursor while the warning is shown (same check as render_settings)
+                                let tab_area = tab_content_area(content_rect);
+                                if calculate_centered_content_area(tab_area, settings.min_cols, settings.min_rows).is_some() {
+                                    if let Some(field_rect) = settings_field_rect(registry, *field_index) {
+                                        // Get inner area for text (accounting for borders and padding)
+                                        let inner_area = Block::default()
+                                            .borders(Borders::ALL)
+                                            .padding(ratatui::widgets::Padding { left: 1, right: 1, top: 0, bottom: 0 })
+                                            .inner(field_rect);
+                                        let text_width = inner_area.width as usize;
                                         
                                         // Calculate scroll offset and visual cursor position
-                                        let scroll_offset = input.visual_scroll(inner_width);
+                                        let scroll_offset = input.visual_scroll(text_width);
                                         let visual_cursor = input.visual_cursor();
+                                        let cursor_pos_in_view = visual_cursor.saturating_sub(scroll_offset);
                                         
-                                        // Use layout_utils to calculate cursor position
-                                        if let Some((cursor_x, cursor_y)) = crate::layout_utils::calculate_cursor_position(
-                                            content_area,
-                                            *field_index,
-                                            visual_cursor as usize,
-                                            scroll_offset,
-                                            inner_width,
-                                        ) {
-                                            f.set_cursor_position((cursor_x, cursor_y));
-                                        }
+                                        // Calculate cursor position relative to field's inner area
+                                        f.set_cursor_position((inner_area.x + cursor_pos_in_view as u16, inner_area.y));
                                     }
                                 }
                             }
@@ -0,0 +1,95 @@
+// Dashboard update throttle
+// Coalesces progress/state updates so parse rate is decoupled from redraw rate
+
+use std::time::{Duration, Instant};
+
+/// Rate limiter for dashboard state updates
+///
+/// Requests inside the window are deferred, not lost: callers keep accumulating
+/// state, the first request after the window closes applies it all in one update,
+/// and whatever is still pending when the stream ends is flushed by the caller.
+#[derive(Debug, Clone)]
+pub struct UpdateThrottle {
+    min_interval: Duration,
+    last_flush: Option<Instant>,
+    pending: bool,
+}
+
+impl UpdateThrottle {
+    /// Allow at most `updates_per_sec` flushes per second (0 disables throttling)
+    pub fn new(updates_per_sec: u32) -> Self {
+        let min_interval = if updates_per_sec == 0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs(1) / updates_per_sec
+        };
+        Self { min_interval, last_flush: None, pending: false }
+    }
+
+    /// Request an update at `now`; true if the caller should apply it
+    ///
+    /// The update stays pending until `flushed` is called, so a caller that could not
+    /// apply it (e.g. the dashboard lock was busy) is let through again on the next request.
+    pub fn request(&mut self, now: Instant) -> bool { //>
+        self.pending = true;
+        match self.last_flush {
+            Some(last) => now.saturating_duration_since(last) >= self.min_interval,
+            None => true,
+        }
+    } //<
+
+    /// Record that the pending update was applied at `now`, starting a new window
+    pub fn flushed(&mut self, now: Instant) {
+        self.last_flush = Some(now);
+        self.pending = false;
+    }
+
+    /// True if an update was requested but not applied yet (flush it when the stream ends)
+    pub fn has_pending(&self) -> bool {
+        self.pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_burst_within_window_coalesces_to_single_update() {
+        let mut throttle = UpdateThrottle::new(30);
+        let start = Instant::now();
+
+        let mut applied = 0;
+        for i in 0..500 {
+            let now = start + Duration::from_micros(i * 50);
+            if throttle.request(now) {
+                throttle.flushed(now);
+                applied += 1;
+            }
+        }
+        assert_eq!(applied, 1);
+
+        // First request after the window applies the coalesced state
+        assert!(throttle.request(start + Duration::from_millis(40)));
+        throttle.flushed(start + Duration::from_millis(40));
+        assert!(!throttle.request(start + Duration::from_millis(41)));
+    }
+
+    #[test]
+    fn test_unapplied_and_trailing_updates_stay_pending() {
+        let mut throttle = UpdateThrottle::new(30);
+        let start = Instant::now();
+
+        // Due but not applied (lock busy): the next request is let through instead of waiting a window
+        assert!(throttle.request(start));
+        assert!(throttle.request(start + Duration::from_millis(1)));
+        throttle.flushed(start + Duration::from_millis(1));
+        assert!(!throttle.has_pending());
+
+        // The last update of a burst is deferred, and still pending when the stream ends
+        assert!(!throttle.request(start + Duration::from_millis(2)));
+        assert!(throttle.has_pending());
+        throttle.flushed(start + Duration::from_millis(3));
+        assert!(!throttle.has_pending());
+    }
+}
@@ -0,0 +1,127 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use crate::config::{LogConfig, LogLevel};
+
+/// Default diagnostic log location, relative to the working directory.
+pub const DEFAULT_APP_LOG_PATH: &str = ".dev-console/app.log";
+
+/// Number of files kept on disk: the live log plus its rotated predecessors.
+const KEEP_FILES: usize = 3;
+
+/// The application's own diagnostic log, separate from build output.
+///>
+/// Records config load results, errors and background thread spawns so a
+/// misbehaving session can be inspected afterwards. When appending would grow
+/// the file past `max_bytes`, `app.log` becomes `app.log.1`, `app.log.1`
+/// becomes `app.log.2`, and the oldest file is dropped. Write failures are
+/// ignored; diagnostics must never take the UI down.
+///<
+#[derive(Debug, Clone)]
+pub struct AppLog {
+    level: LogLevel,
+    path: PathBuf,
+    max_bytes: u64,
+}
+
+impl AppLog {
+    pub fn new(config: &LogConfig) -> Self {
+        let path = config.path.as_deref().unwrap_or(DEFAULT_APP_LOG_PATH);
+        Self { level: config.level, path: PathBuf::from(path), max_bytes: config.max_bytes }
+    }
+
+    /// File the log is written to.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// A log that never writes.
+    #[cfg(test)]
+    pub fn disabled() -> Self {
+        Self { level: LogLevel::Off, path: PathBuf::new(), max_bytes: 0 }
+    }
+
+    pub fn error(&self, message: &str) {
+        self.write(LogLevel::Error, message);
+    }
+
+    pub fn info(&self, message: &str) {
+        self.write(LogLevel::Info, message);
+    }
+
+    pub fn debug(&self, message: &str) {
+        self.write(LogLevel::Debug, message);
+    }
+
+    fn write(&self, level: LogLevel, message: &str) {
+        if level == LogLevel::Off || level > self.level {
+            return;
+        }
+        let line = format!("{} [{}] {}\n", crate::app::ui_state::unix_now(), level.label(), message);
+
+        if let Some(parent) = self.path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        let size = fs::metadata(&self.path).map(|m| m.len()).unwrap_or(0);
+        if size > 0 && size + line.len() as u64 > self.max_bytes {
+            self.rotate();
+        }
+        if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(&self.path) {
+            let _ = file.write_all(line.as_bytes());
+        }
+    }
+
+    /// Shifts `app.log.N` to `app.log.N+1`, dropping files beyond `KEEP_FILES`.
+    fn rotate(&self) {
+        let _ = fs::remove_file(rotated_path(&self.path, KEEP_FILES - 1));
+        for n in (1..KEEP_FILES - 1).rev() {
+            let _ = fs::rename(rotated_path(&self.path, n), rotated_path(&self.path, n + 1));
+        }
+        let _ = fs::rename(&self.path, rotated_path(&self.path, 1));
+    }
+}
+
+/// `app.log` -> `app.log.N`
+fn rotated_path(path: &Path, n: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", n));
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotates_when_size_cap_exceeded() {
+        let dir = std::env::temp_dir().join("dc2_app_log_rotation");
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join("app.log");
+        let log = AppLog::new(&LogConfig {
+            level: LogLevel::Info,
+            path: Some(path.to_string_lossy().into_owned()),
+            max_bytes: 64,
+        });
+
+        log.info("first entry, well under the cap");
+        assert!(path.exists());
+        assert!(!rotated_path(&path, 1).exists());
+
+        log.info("second entry pushes the file past the cap");
+        assert!(rotated_path(&path, 1).exists());
+        assert!(fs::read_to_string(&path).unwrap().contains("second entry"));
+        assert!(fs::read_to_string(rotated_path(&path, 1)).unwrap().contains("first entry"));
+
+        // Only the live file and two rotated ones are kept
+        for i in 0..5 {
+            log.info(&format!("entry {} padded out to exceed the cap", i));
+        }
+        assert!(rotated_path(&path, 2).exists());
+        assert!(!rotated_path(&path, 3).exists());
+
+        // Filtered by level
+        log.debug("too verbose");
+        assert!(!fs::read_to_string(&path).unwrap().contains("too verbose"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
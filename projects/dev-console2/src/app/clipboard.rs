@@ -0,0 +1,32 @@
+use arboard::Clipboard;
+
+/// Destination for copied text; the system clipboard in the app, a recorder in tests.
+pub trait ClipboardSink {
+    fn set_text(&mut self, text: String) -> Result<(), String>;
+}
+
+impl std::fmt::Debug for dyn ClipboardSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ClipboardSink")
+    }
+}
+
+/// The system clipboard, opened on first use.
+///>
+/// On X11/Wayland copied text is only served while the handle is alive, so it
+/// is kept for the lifetime of the app instead of dropped after each copy.
+///<
+#[derive(Default)]
+pub struct SystemClipboard {
+    clipboard: Option<Clipboard>,
+}
+
+impl ClipboardSink for SystemClipboard {
+    fn set_text(&mut self, text: String) -> Result<(), String> {
+        if self.clipboard.is_none() { //>
+            self.clipboard = Some(Clipboard::new().map_err(|e| e.to_string())?);
+        } //<
+        let clipboard = self.clipboard.as_mut().expect("clipboard opened above");
+        clipboard.set_text(text).map_err(|e| e.to_string())
+    }
+}
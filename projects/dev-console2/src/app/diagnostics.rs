@@ -0,0 +1,71 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::commands::BuildRecord;
+use crate::config::ProfileConfig;
+
+/// Default folder diagnostic bundles are written under, relative to the working directory.
+pub const DEFAULT_DIAGNOSTICS_DIR: &str = ".dev-console/diagnostics";
+
+/// Replacement for secrets in exported files.
+pub const REDACTED: &str = "<redacted>";
+
+/// Everything a diagnostic bundle is built from.
+#[derive(Debug, Default)]
+pub struct SessionSnapshot<'a> {
+    pub profiles: Option<&'a ProfileConfig>,
+    pub output: &'a [String],
+    pub app_log: Option<&'a Path>,
+    pub history: Vec<BuildRecord>,
+}
+
+/// Copy of the profiles with every MQTT password replaced by `REDACTED`.
+pub fn redact_profiles(config: &ProfileConfig) -> ProfileConfig {
+    let mut redacted = config.clone();
+    for mqtt in &mut redacted.mqtt {
+        if !mqtt.password.is_empty() {
+            mqtt.password = REDACTED.to_string();
+        }
+    }
+    redacted
+}
+
+/// Replaces the user's home directory with `~` so exported paths don't reveal the account name.
+pub fn redact_paths(text: &str, home: Option<&str>) -> String {
+    match home.map(|h| h.trim_end_matches(['/', '\\'])).filter(|h| !h.is_empty()) {
+        Some(home) => text.replace(home, "~"),
+        None => text.to_string(),
+    }
+}
+
+/// The home directory `redact_paths` strips by default.
+pub fn home_dir() -> Option<String> {
+    std::env::var("HOME").or_else(|_| std::env::var("USERPROFILE")).ok()
+}
+
+/// Writes a session bundle into a new `session-<unix time>` folder under `dir`.
+///>
+/// The folder holds `settings.yaml` (profiles, MQTT passwords redacted),
+/// `output.log` (the output buffer), `app.log` (the diagnostic log, when one
+/// exists) and `history.json` (the given build records). The home directory
+/// is replaced with `~` in every file. Returns the folder that was written.
+///<
+pub fn export_bundle(dir: &Path, snapshot: &SessionSnapshot, home: Option<&str>) -> std::io::Result<PathBuf> {
+    let bundle = dir.join(format!("session-{}", crate::app::ui_state::unix_now()));
+    fs::create_dir_all(&bundle)?;
+
+    let settings = match snapshot.profiles {
+        Some(config) => serde_saphyr::to_string(&redact_profiles(config))
+            .map_err(|e| std::io::Error::other(format!("Failed to serialize settings: {}", e)))?,
+        None => "# No profiles were loaded\n".to_string(),
+    };
+    fs::write(bundle.join("settings.yaml"), redact_paths(&settings, home))?;
+    fs::write(bundle.join("output.log"), redact_paths(&snapshot.output.join("\n"), home))?;
+
+    if let Some(log) = snapshot.app_log.and_then(|path| fs::read_to_string(path).ok()) {
+        fs::write(bundle.join("app.log"), redact_paths(&log, home))?;
+    }
+
+    let history = serde_json::to_string_pretty(&snapshot.history).map_err(std::io::Error::other)?;
+    fs::write(bundle.join("history.json"), redact_paths(&history, home))?;
+    Ok(bundle)
+}
@@ -1,8 +1,10 @@
-use crate::app::{App, TaskState, MonitorType, Action};
-use arboard::Clipboard;
+use crate::app::{App, TaskState, MonitorType, Action, Operation};
 use std::sync::atomic::Ordering;
 use std::sync::mpsc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
+
+/// How long an upload waits for a paused serial monitor to close the port.
+const PORT_RELEASE_TIMEOUT: Duration = Duration::from_secs(2);
 
 /// Semantic action implementations (The 'How' of application logic).
 ///>
@@ -13,7 +15,34 @@ use std::time::Instant;
 ///< 
 impl App {
     /// Terminates the application loop.
+    ///>
+    /// While a monitor or build is active the user is asked to confirm first;
+    /// the answer is handled by `confirm_quit` or cleared with `n`/`Esc`.
+    ///<
     pub fn exec_quit(&mut self) {
+        let prompt = match self.task_state {
+            TaskState::Monitoring { .. } => "Monitor active — quit and disconnect? [y/n]",
+            TaskState::Running { .. } => "Build running — quit and cancel it? [y/n]",
+            TaskState::Idle => {
+                self.running = false;
+                return;
+            }
+        };
+        self.quit_pending = true;
+        self.status_text = prompt.to_string();
+    }
+
+    /// Stops the active task and terminates the application loop.
+    pub fn confirm_quit(&mut self) {
+        self.quit_pending = false;
+        if let Some(tx) = self.serial_tx.take() {
+            let _ = tx.send(crate::commands::SerialCommand::Stop);
+        }
+        if let Some(tx) = self.mqtt_tx.take() {
+            let _ = tx.send(crate::commands::MqttCommand::Stop);
+        }
+        self.cancel_signal.store(true, Ordering::SeqCst);
+        self.monitor_cancel.store(true, Ordering::SeqCst);
         self.running = false;
     }
 
@@ -201,14 +230,26 @@ impl App {
     /// Attempts to cancel any active background tasks or exit input mode.
     pub fn exec_cancel(&mut self) {
         if self.modal.is_some() {
-            self.modal = None;
+            self.close_file_browser();
+            return;
+        }
+        if self.trend_modal.is_some() {
+            self.close_trend_modal();
+            return;
+        }
+        if self.map_report_modal.is_some() {
+            self.close_map_report();
             return;
         }
 
-        let is_active = matches!(self.task_state, TaskState::Running { .. }) || matches!(self.task_state, TaskState::Monitoring { .. });
-        
-        if is_active {
-            self.cancel_signal.store(true, Ordering::SeqCst);
+        // A monitor paused for an upload survives cancelling the upload and resumes afterwards
+        let signal = match self.task_state {
+            TaskState::Running { .. } => Some(&self.cancel_signal),
+            TaskState::Monitoring { .. } => Some(&self.monitor_cancel),
+            _ => None,
+        };
+        if let Some(signal) = signal {
+            signal.store(true, Ordering::SeqCst);
             self.log("system", "Cancellation signal sent...");
         }
 
@@ -228,12 +269,76 @@ impl App {
         self.start_process(true);
     }
 
+    /// Compiles every profile in `ProfileConfig` one after another.
+    ///>
+    /// Profiles whose device or connection cannot be resolved are skipped with
+    /// a warning. The batch keeps going past failed builds and finishes with a
+    /// pass/fail summary toast.
+    ///<
+    pub fn exec_compile_all(&mut self) {
+        let Some(profile_config) = &self.profile_config else {
+            self.report_error("No profiles loaded; nothing to compile.");
+            return;
+        };
+        let manager = crate::commands::HistoryManager::load(&self.history_path());
+        let mut profiles = Vec::new();
+        let mut skipped = Vec::new();
+        for sketch in &profile_config.sketches {
+            match crate::config::resolve_profile_settings(profile_config, &sketch.id) {
+                Ok(settings) => profiles.push(crate::commands::ProfileBuild {
+                    id: sketch.id.clone(),
+                    settings,
+                    stats: crate::commands::ProgressPredictor::with_stats(manager.get_stats(&sketch.id)).get_stats(),
+                }),
+                Err(_) => skipped.push(sketch.id.clone()),
+            }
+        }
+        if profiles.is_empty() {
+            self.report_error("No profile has a valid device and connection; nothing to compile.");
+            return;
+        }
+
+        let now = Instant::now();
+        self.running_operation = Operation::Compile;
+        self.task_state = TaskState::Running {
+            percentage: 0.0,
+            visual_percentage: 0.0,
+            last_percentage: 0.0,
+            stage: "Initializing Compile...".to_string(),
+            start_time: now,
+            last_updated: now,
+            smoothed_eta: None,
+        };
+        self.clear_output();
+        self.apply_command_autoscroll(self.config.application.autoscroll.compile);
+        for id in skipped {
+            self.log("warn", &format!("Skipping profile '{}': device or connection not found.", id));
+        }
+        self.batch_run = Some(crate::app::BatchRun {
+            expected_secs: profiles.iter().map(|p| p.stats.averages.values().sum()).collect(),
+            current: None,
+            profile_started: now,
+        });
+
+        let tx = self.command_tx.clone();
+        let cancel_signal = self.cancel_signal.clone();
+        cancel_signal.store(false, Ordering::SeqCst);
+        self.app_log.debug(&format!("Spawning compile-all thread for {} profiles", profiles.len()));
+        std::thread::spawn(move || {
+            crate::commands::run_compile_all(profiles, cancel_signal, move |update| {
+                let _ = tx.send(update);
+            });
+        });
+    }
+
     /// Core logic for spawning background build/upload threads.
     ///>
     /// This method resets progress, trains the time predictor with latest history,
     /// and dispatches the long-running task to a background thread.
     ///< 
     fn start_process(&mut self, is_upload: bool) {
+        let serial_monitoring = matches!(self.task_state, TaskState::Monitoring { monitor_type: MonitorType::Serial, .. });
+        self.running_operation = if is_upload { Operation::Upload } else { Operation::Compile };
         let now = Instant::now();
         self.task_state = TaskState::Running {
             percentage: 0.0,
@@ -244,8 +349,10 @@ impl App {
             last_updated: now,
             smoothed_eta: None,
         };
-        self.output_lines.clear();
-        self.output_cached_lines.clear();
+        self.clear_output();
+        let autoscroll = &self.config.application.autoscroll;
+        let follow = if is_upload { autoscroll.upload } else { autoscroll.compile };
+        self.apply_command_autoscroll(follow);
         let tx = self.command_tx.clone();
         let cancel_signal = self.cancel_signal.clone();
         cancel_signal.store(false, Ordering::SeqCst);
@@ -255,15 +362,45 @@ impl App {
         
         match self.get_settings_from_profile() {
             Ok(settings) => {
+                let mut port_released = None;
+                if is_upload && serial_monitoring && self.config.application.pause_monitor_during_upload {
+                    if let Some(serial_tx) = &self.serial_tx {
+                        let (released_tx, released_rx) = mpsc::channel();
+                        self.monitor_paused_for_upload = serial_tx.send(crate::commands::SerialCommand::Pause(released_tx)).is_ok();
+                        port_released = self.monitor_paused_for_upload.then_some(released_rx);
+                    }
+                }
+                let hooks = self.config.application.hooks.clone();
+                let runner = self.command_runner.clone();
+                self.app_log.debug(&format!("Spawning {} thread", if is_upload { "upload" } else { "compile" }));
                 std::thread::spawn(move || {
-                    let callback = move |update| {
+                    let callback = move |update: crate::commands::ProgressUpdate| {
                         if tx.send(update).is_err() { return; }
                     };
-                    if is_upload {
-                        crate::commands::run_upload(&settings, stats, cancel_signal, callback);
-                    } else {
-                        crate::commands::run_compile(&settings, stats, cancel_signal, callback);
+                    // The upload must not race the monitor for the port
+                    if let Some(released) = port_released {
+                        if released.recv_timeout(PORT_RELEASE_TIMEOUT).is_err() {
+                            callback(crate::commands::ProgressUpdate::OutputLine(
+                                "⚠ Serial monitor did not release the port; uploading anyway.".to_string(),
+                            ));
+                        }
                     }
+                    let sketch_dir = std::path::PathBuf::from(&settings.sketch_directory);
+                    let fs = crate::commands::traits::RealFileSystem;
+                    crate::commands::run_with_hooks(
+                        runner.as_ref(),
+                        &hooks,
+                        &sketch_dir,
+                        cancel_signal.clone(),
+                        callback,
+                        |callback| {
+                            if is_upload {
+                                crate::commands::upload::run_upload_with_runner(runner.as_ref(), &fs, &settings, stats, cancel_signal, callback);
+                            } else {
+                                crate::commands::compile::run_compile_with_runners(runner.as_ref(), &fs, &settings, stats, cancel_signal, callback);
+                            }
+                        },
+                    );
                 });
             },
             Err(e) => {
@@ -273,16 +410,43 @@ impl App {
         }
     }
 
+    /// Reconnects a serial monitor that was paused for an upload.
+    ///>
+    /// Returns true if a paused monitor was resumed, in which case the task
+    /// state is restored to `Monitoring`.
+    ///<
+    pub(crate) fn resume_monitor_after_upload(&mut self) -> bool {
+        if !std::mem::take(&mut self.monitor_paused_for_upload) {
+            return false;
+        }
+        let Some(serial_tx) = &self.serial_tx else { return false; };
+        if serial_tx.send(crate::commands::SerialCommand::Resume).is_err() {
+            return false;
+        }
+        self.task_state = TaskState::Monitoring { monitor_type: MonitorType::Serial, start_time: Instant::now() };
+        self.log("system", "Resuming serial monitor...");
+        true
+    }
+
     /// Opens a serial port and begins monitoring hardware output.
     pub fn exec_monitor_serial(&mut self) {
+        self.clear_output();
+        self.start_serial_monitor();
+    }
+
+    /// Spawns the serial monitor thread without touching existing output.
+    ///>
+    /// Shared by the explicit monitor command and the post-upload chain, which
+    /// keeps the upload log visible above the monitor stream.
+    ///<
+    pub(crate) fn start_serial_monitor(&mut self) {
         let now = Instant::now();
         self.task_state = TaskState::Monitoring {
             monitor_type: MonitorType::Serial,
             start_time: now,
         };
-        self.output_lines.clear();
-        self.output_cached_lines.clear();
         self.log("action", "Starting Serial Monitor...");
+        self.apply_command_autoscroll(self.config.application.autoscroll.monitor);
         
         // Activate Input Field Automatically
         self.input_active = true;
@@ -291,16 +455,24 @@ impl App {
         let tx = self.command_tx.clone();
         let (serial_tx, serial_rx) = mpsc::channel();
         self.serial_tx = Some(serial_tx);
-        let cancel_signal = self.cancel_signal.clone();
+        let cancel_signal = self.monitor_cancel.clone();
         cancel_signal.store(false, Ordering::SeqCst);
+        let provider = self.serial_provider.clone();
+        let options = crate::commands::SerialOptions {
+            normalize_line_endings: self.config.application.normalize_serial_line_endings,
+            validate_baud: self.config.application.validate_serial_baud,
+        };
 
         match self.get_settings_from_profile() {
             Ok(settings) => {
+                self.app_log.debug(&format!("Spawning serial monitor thread on {}", settings.port));
                 std::thread::spawn(move || {
                     let callback = move |update| {
                         if tx.send(update).is_err() { return; }
                     };
-                    crate::commands::run_serial_monitor(settings.port, settings.baudrate, cancel_signal, serial_rx, callback);
+                    crate::commands::serial_v2::run_serial_monitor_with_provider(
+                        provider.as_ref(), settings.port, settings.baudrate, options, cancel_signal, serial_rx, callback,
+                    );
                 });
             },
             Err(e) => {
@@ -318,9 +490,9 @@ impl App {
             monitor_type: MonitorType::Mqtt,
             start_time: now,
         };
-        self.output_lines.clear();
-        self.output_cached_lines.clear();
+        self.clear_output();
         self.log("action", "Starting MQTT Monitor...");
+        self.apply_command_autoscroll(self.config.application.autoscroll.monitor);
         
         // Activate Input Field Automatically
         self.input_active = true;
@@ -329,7 +501,7 @@ impl App {
         let tx = self.command_tx.clone();
         let (mqtt_tx, mqtt_rx) = mpsc::channel();
         self.mqtt_tx = Some(mqtt_tx);
-        let cancel_signal = self.cancel_signal.clone();
+        let cancel_signal = self.monitor_cancel.clone();
         cancel_signal.store(false, Ordering::SeqCst);
 
         match self.get_settings_from_profile() {
@@ -345,6 +517,7 @@ impl App {
                     let username = if m.username.is_empty() { None } else { Some(m.username.clone()) };
                     let password = if m.password.is_empty() { None } else { Some(m.password.clone()) };
                     
+                    self.app_log.debug(&format!("Spawning MQTT monitor thread for {}:{}", host, port));
                     std::thread::spawn(move || {
                         let callback = move |update| {
                             if tx.send(update).is_err() { return; }
@@ -365,6 +538,61 @@ impl App {
         }
     }
 
+    /// Asks for confirmation before erasing the whole flash of an ESP board.
+    ///>
+    /// Only Espressif FQBNs are accepted. The answer is handled by
+    /// `confirm_erase_flash` or cleared with `n`/`Esc`.
+    ///<
+    pub fn exec_erase_flash(&mut self) {
+        if !matches!(self.task_state, TaskState::Idle) {
+            self.status_text = "Stop the running task before erasing flash.".to_string();
+            return;
+        }
+        match self.get_settings_from_profile() {
+            Ok(settings) if !crate::commands::is_esp_board(&settings.fqbn) => {
+                self.report_error(format!("Erase Flash is only available for ESP boards (FQBN {}).", settings.fqbn));
+            }
+            Ok(settings) => {
+                self.erase_pending = true;
+                self.status_text = format!("This erases all firmware and data on {}. Continue? [y/n]", settings.port);
+            }
+            Err(e) => self.report_error(e),
+        }
+    }
+
+    /// Runs the confirmed flash erase in the background, streaming its output.
+    pub fn confirm_erase_flash(&mut self) {
+        self.erase_pending = false;
+        let settings = match self.get_settings_from_profile() {
+            Ok(settings) => settings,
+            Err(e) => {
+                self.report_error(e);
+                return;
+            }
+        };
+        let now = Instant::now();
+        self.running_operation = Operation::EraseFlash;
+        self.task_state = TaskState::Running {
+            percentage: 0.0,
+            visual_percentage: 0.0,
+            last_percentage: 0.0,
+            stage: "Erasing Flash...".to_string(),
+            start_time: now,
+            last_updated: now,
+            smoothed_eta: None,
+        };
+        self.clear_output();
+        let tx = self.command_tx.clone();
+        let cancel_signal = self.cancel_signal.clone();
+        cancel_signal.store(false, Ordering::SeqCst);
+        self.app_log.debug("Spawning erase thread");
+        std::thread::spawn(move || {
+            crate::commands::run_erase_flash(&settings, cancel_signal, move |update| {
+                let _ = tx.send(update);
+            });
+        });
+    }
+
     pub fn exec_clean(&mut self) {
         self.push_line("Cleaning project...".to_string());
         // Implement actual clean logic here
@@ -392,11 +620,7 @@ impl App {
         if !self.tabs.is_empty() {
             let current = self.tabs.iter().position(|t| t.active).unwrap_or(0);
             let next = (current + 1) % self.tabs.len();
-            for (i, tab) in self.tabs.iter_mut().enumerate() {
-                tab.active = i == next;
-            }
-            // Recalculate layout for the new tab
-            self.layout = self.calculate_layout(self.view_area);
+            self.activate_tab(next);
         }
     }
 
@@ -404,11 +628,7 @@ impl App {
         if !self.tabs.is_empty() {
             let current = self.tabs.iter().position(|t| t.active).unwrap_or(0);
             let prev = if current > 0 { current - 1 } else { self.tabs.len() - 1 };
-            for (i, tab) in self.tabs.iter_mut().enumerate() {
-                tab.active = i == prev;
-            }
-            // Recalculate layout for the new tab
-            self.layout = self.calculate_layout(self.view_area);
+            self.activate_tab(prev);
         }
     }
 
@@ -419,7 +639,7 @@ impl App {
 
     pub fn exec_scroll_line_down(&mut self) {
         let viewport_h = self.layout.output.height.saturating_sub(2) as usize;
-        let max_scroll = self.output_lines.len().saturating_sub(viewport_h) as u16;
+        let max_scroll = self.visible_output_len().saturating_sub(viewport_h) as u16;
         if self.output_scroll < max_scroll {
             self.output_scroll = self.output_scroll.saturating_add(1);
             if self.output_scroll >= max_scroll {
@@ -440,7 +660,7 @@ impl App {
 
     pub fn exec_scroll_page_down(&mut self) {
         let viewport_h = self.layout.output.height.saturating_sub(2);
-        let max_scroll = self.output_lines.len().saturating_sub(viewport_h as usize) as u16;
+        let max_scroll = self.visible_output_len().saturating_sub(viewport_h as usize) as u16;
         let next = self.output_scroll.saturating_add(viewport_h);
         
         if next >= max_scroll {
@@ -462,6 +682,14 @@ impl App {
         self.sync_autoscroll();
     }
 
+    /// Sets autoscroll to the configured default for a command that is starting.
+    fn apply_command_autoscroll(&mut self, enabled: bool) {
+        self.output_autoscroll = enabled;
+        if enabled {
+            self.sync_autoscroll();
+        }
+    }
+
     pub fn exec_toggle_autoscroll(&mut self) {
         self.output_autoscroll = !self.output_autoscroll;
         if self.output_autoscroll {
@@ -469,10 +697,65 @@ impl App {
         }
     }
 
+    /// Switches between quiet (toolchain invocations hidden) and verbose output.
+    pub fn exec_toggle_quiet(&mut self) {
+        self.output_quiet = !self.output_quiet;
+        let viewport_h = self.layout.output.height.saturating_sub(2) as usize;
+        let max_scroll = self.visible_output_len().saturating_sub(viewport_h) as u16;
+        self.output_scroll = self.output_scroll.min(max_scroll);
+        self.sync_autoscroll();
+        self.toast_manager.info(if self.output_quiet { "Quiet output" } else { "Verbose output" });
+    }
+
+    /// Opens the input line to edit the output filter, prefilled with the current one.
+    pub fn exec_filter_output(&mut self) {
+        self.input = tui_input::Input::new(self.output_filter.clone().unwrap_or_default());
+        self.input_active = true;
+        self.filter_editing = true;
+    }
+
+    /// Applies the edited filter; an empty value removes it.
+    pub fn exec_apply_output_filter(&mut self) {
+        let value = self.input.value().trim().to_string();
+        self.input.reset();
+        self.filter_editing = false;
+        if value.is_empty() {
+            self.exec_clear_output_filter();
+            return;
+        }
+        self.toast_manager.info(&format!("Filtering output by \"{}\"", value));
+        self.output_filter = Some(value);
+        self.output_scroll = 0;
+        self.sync_autoscroll();
+    }
+
+    pub fn exec_clear_output_filter(&mut self) {
+        if self.output_filter.take().is_some() {
+            self.toast_manager.info("Output filter cleared");
+            self.sync_autoscroll();
+        }
+    }
+
+    /// Maximizes the output pane over the whole content area, or restores the split.
+    ///>
+    /// The scroll offset is kept so the reader stays on the same lines; with
+    /// autoscroll on it is re-pinned to the bottom of the resized pane.
+    ///<
+    pub fn exec_toggle_output_maximized(&mut self) {
+        self.output_maximized = !self.output_maximized;
+        self.layout = self.calculate_layout(self.view_area);
+        self.sync_autoscroll();
+    }
+
+    /// Toggles the elapsed-time prefix on newly received output lines.
+    pub fn exec_toggle_timestamps(&mut self) {
+        self.output_timestamps = !self.output_timestamps;
+        self.toast_manager.info(if self.output_timestamps { "Timestamps on" } else { "Timestamps off" });
+    }
+
     pub fn exec_copy_status(&mut self) {
-        match Clipboard::new() {
-            Ok(mut clipboard) => {
-                let _ = clipboard.set_text(self.status_text.clone());
+        match self.clipboard.set_text(self.status_text.clone()) {
+            Ok(()) => {
                 self.toast_manager.success("Status copied.");
             }
             Err(_) => {
@@ -481,19 +764,24 @@ impl App {
         }
     }
 
+    /// Resets a lingering status message to the configured default text.
+    pub fn exec_clear_status(&mut self) {
+        self.status_text = self.config.application.status_bar.text();
+    }
+
     pub fn exec_copy_output(&mut self, full: bool) {
+        let visible_lines = self.visible_output_lines();
         let content = if full {
-            self.output_lines.join("\n")
+            visible_lines.iter().map(|l| l.as_str()).collect::<Vec<_>>().join("\n")
         } else {
-            let start = self.output_scroll as usize;
+            let start = (self.output_scroll as usize).min(visible_lines.len());
             let height = self.layout.output.height.saturating_sub(2) as usize;
-            let end = (start + height).min(self.output_lines.len());
-            self.output_lines[start..end].join("\n")
+            let end = (start + height).min(visible_lines.len());
+            visible_lines[start..end].iter().map(|l| l.as_str()).collect::<Vec<_>>().join("\n")
         };
 
-        match Clipboard::new() {
-            Ok(mut clipboard) => {
-                let _ = clipboard.set_text(content);
+        match self.clipboard.set_text(content) {
+            Ok(()) => {
                 let msg = if full { "Full output copied." } else { "Visible lines copied." };
                 self.toast_manager.success(msg);
             }
@@ -503,6 +791,43 @@ impl App {
         }
     }
 
+    /// Copies a single output line (Alt+Click) and briefly highlights it.
+    pub fn copy_output_line(&mut self, index: usize) {
+        // Copy the rendered text, without ANSI color codes
+        let Some(line) = self.output_cached_lines.get(index) else { return };
+        let text: String = line.spans.iter().map(|span| span.content.as_ref()).collect();
+        self.line_copy_flash = Some((Instant::now(), index));
+        self.should_redraw = true;
+
+        match self.clipboard.set_text(text) {
+            Ok(()) => {
+                self.toast_manager.success("Line copied.");
+            }
+            Err(_) => {
+                self.toast_manager.error("Clipboard failed.");
+            }
+        }
+    }
+
+    /// Copies the error block of the last failure (or the last command's output).
+    pub fn exec_copy_last_error(&mut self) {
+        let lines: Vec<&str> = self.output_lines.iter().map(|l| l.as_str()).collect();
+        let block = crate::commands::extract_error_block(&lines);
+        if block.is_empty() {
+            self.toast_manager.info("No output to copy.");
+            return;
+        }
+
+        match self.clipboard.set_text(block.join("\n")) {
+            Ok(()) => {
+                self.toast_manager.success(&format!("Copied {} error line(s).", block.len()));
+            }
+            Err(_) => {
+                self.toast_manager.error("Clipboard failed.");
+            }
+        }
+    }
+
     pub fn exec_send_command(&mut self) {
         if self.input.value().is_empty() { return; }
         
@@ -545,6 +870,7 @@ impl App {
         self.focus = match self.focus {
             crate::app::Focus::Sidebar => crate::app::Focus::Content,
             crate::app::Focus::Content => crate::app::Focus::Sidebar,
+            crate::app::Focus::Modal => crate::app::Focus::Modal,
         };
     }
 
@@ -565,6 +891,7 @@ impl App {
                 connection: config.connections.first().map(|c| c.id.clone()).unwrap_or_default(),
                 device: config.devices.first().map(|d| d.id.clone()).unwrap_or_default(),
                 mqtt: config.mqtt.first().map(|m| m.id.clone()).unwrap_or_default(),
+                post_flash_serial: None,
             };
 
             config.sketches.push(new_sketch);
@@ -583,23 +910,14 @@ impl App {
             // Trigger File Picker for Sketch Path
             use crate::widgets::file_browser::FileBrowser;
             use crate::widgets::popup::Popup;
-            use std::path::{Path, PathBuf};
 
-            let current_path = if let (Some(config), Some(profile_id)) = (&self.profile_config, self.get_current_sketch_id()) {
-                config.sketches.iter().find(|s| s.id == profile_id).map(|s| s.path.clone()).unwrap_or_else(|| ".".to_string())
-            } else {
-                ".".to_string()
-            };
-
-            let mut start_dir = PathBuf::from(current_path);
-            if start_dir.is_file() {
-                start_dir = start_dir.parent().map(|p| p.to_path_buf()).unwrap_or_else(|| PathBuf::from("."));
-            } else if !start_dir.exists() {
-                start_dir = PathBuf::from(".");
-            }
+            let start_dir = self.last_browser_dir.clone()
+                .filter(|dir| dir.is_dir())
+                .unwrap_or_else(|| self.default_browser_dir());
 
             let browser = FileBrowser::new(start_dir);
             self.modal = Some(Popup::new(browser, "SELECT SKETCH".to_string()));
+            self.push_modal();
             self.log("action", "Opening file picker...");
         }
     }
@@ -699,4 +1017,182 @@ impl App {
             }
         }
     }
+
+    /// Opens a popup charting recent build durations for the current sketch.
+    pub fn exec_show_build_trend(&mut self) {
+        let sketch_id = self.get_current_sketch_id().unwrap_or_else(|| "default".to_string());
+        let manager = crate::commands::HistoryManager::load(&self.history_path());
+        let mut durations = manager.build_durations(&sketch_id);
+        let keep = durations.len().saturating_sub(20);
+        durations.drain(..keep);
+
+        let trend = crate::widgets::build_trend::BuildTrend::new(sketch_id, durations);
+        self.trend_modal = Some(crate::widgets::popup::Popup::new(trend, "BUILD TIME TREND".to_string()).with_size(60, 30));
+        self.push_modal();
+    }
+
+    /// Opens the last build's linker map in a scrollable popup.
+    ///>
+    /// The map is looked up in the profile's `build` folder; a toast explains
+    /// when the build did not produce one.
+    ///<
+    pub fn exec_show_map_report(&mut self) {
+        let settings = match self.get_settings_from_profile() {
+            Ok(settings) => settings,
+            Err(e) => return self.report_error(e),
+        };
+        let build_dir = std::path::Path::new(&settings.sketch_directory).join("build");
+        let Some(path) = crate::commands::map_report::find_map_file(&build_dir, &settings.sketch_name) else {
+            self.toast_manager.warning(&format!("No map file was produced in {}", build_dir.display()));
+            return;
+        };
+        match crate::commands::map_report::load_map_lines(&path) {
+            Ok(lines) => {
+                let title = format!("MAP · {}", path.file_name().unwrap_or_default().to_string_lossy());
+                let viewer = crate::widgets::text_viewer::TextViewer::new(lines);
+                self.map_report_modal = Some(crate::widgets::popup::Popup::new(viewer, title).with_size(80, 80));
+                self.push_modal();
+            }
+            Err(e) => self.report_error(format!("Failed to read {}: {}", path.display(), e)),
+        }
+    }
+
+    /// Writes settings, output, app log and this sketch's build history to a diagnostics folder.
+    ///>
+    /// MQTT passwords and the home directory are redacted so the folder can be
+    /// attached to a bug report as-is; a toast shows where it was written.
+    ///<
+    pub fn exec_export_diagnostics(&mut self) {
+        let sketch_id = self.get_current_sketch_id().unwrap_or_else(|| "default".to_string());
+        let history = crate::commands::HistoryManager::load(&self.history_path()).builds
+            .into_iter()
+            .filter(|record| record.sketch_id == sketch_id)
+            .collect();
+        let snapshot = crate::app::diagnostics::SessionSnapshot {
+            profiles: self.profile_config.as_ref(),
+            output: &self.output_lines,
+            app_log: Some(self.app_log.path()),
+            history,
+        };
+        let dir = std::path::Path::new(crate::app::diagnostics::DEFAULT_DIAGNOSTICS_DIR);
+        match crate::app::diagnostics::export_bundle(dir, &snapshot, crate::app::diagnostics::home_dir().as_deref()) {
+            Ok(path) => self.toast_manager.success(&format!("Diagnostics exported to {}", path.display())),
+            Err(e) => self.report_error(format!("Failed to export diagnostics: {}", e)),
+        }
+    }
+
+    /// Re-reads build-config.yaml, widget-config.yaml and config.yaml from disk.
+    pub fn exec_reload_config(&mut self) {
+        self.apply_config_reload(
+            crate::config::load_config(),
+            crate::config::load_widget_config(),
+            crate::config::load_profile_config(),
+        );
+    }
+
+    /// Applies freshly loaded configuration, keeping the previous value for any piece that failed.
+    ///>
+    /// The UI config rebuilds the tab bar map, tab names, theme and diagnostic
+    /// log while keeping the active tab; profiles keep the selected profile
+    /// when its id still exists. A toast lists what reloaded and what failed.
+    ///<
+    pub(crate) fn apply_config_reload(
+        &mut self,
+        config: color_eyre::Result<crate::config::Config>,
+        widget_config: color_eyre::Result<crate::widgets::toast::ToastConfig>,
+        profile_config: color_eyre::Result<crate::config::ProfileConfig>,
+    ) {
+        let mut reloaded = Vec::new();
+        let mut failed = Vec::new();
+
+        match config {
+            Ok(config) => {
+                let active_tab_id = self.tabs.iter().find(|t| t.active).map(|t| t.id.clone());
+                self.tab_bar_map = config.tab_bars.iter().map(|tb| (tb.id.clone(), tb.clone())).collect();
+                if let Some(main_tabs) = config.tab_bars.iter().find(|t| t.id == "MainContentTabBar") {
+                    let keep_active = main_tabs.tabs.iter().any(|t| Some(&t.id) == active_tab_id.as_ref());
+                    self.tabs = main_tabs.tabs.iter().map(|t| crate::widgets::tab_bar::TabBarItem {
+                        id: t.id.clone(),
+                        name: t.name.clone(),
+                        active: if keep_active {
+                            Some(&t.id) == active_tab_id.as_ref()
+                        } else {
+                            t.default == Some("active".to_string())
+                        },
+                    }).collect();
+                }
+                self.theme = crate::app::theme::Theme::new(&config.theme);
+                self.app_log = crate::app::app_log::AppLog::new(&config.application.log);
+                self.config = config;
+                reloaded.push("build-config.yaml");
+            }
+            Err(e) => failed.push(format!("build-config.yaml ({})", e)),
+        }
+
+        match widget_config {
+            Ok(widget_config) => {
+                self.toast_manager.config = widget_config;
+                reloaded.push("widget-config.yaml");
+            }
+            Err(e) => failed.push(format!("widget-config.yaml ({})", e)),
+        }
+
+        match profile_config {
+            Ok(profile_config) => {
+                let selected_id = self.get_current_sketch_id();
+                self.profile_ids = profile_config.sketches.iter().map(|s| s.id.clone()).collect();
+                self.selected_profile_index = selected_id
+                    .and_then(|id| self.profile_ids.iter().position(|p| *p == id))
+                    .unwrap_or(0);
+                self.profile_config = Some(profile_config);
+                reloaded.push("config.yaml");
+            }
+            Err(e) => failed.push(format!("config.yaml ({})", e)),
+        }
+
+        self.layout = self.calculate_layout(self.view_area);
+        self.should_redraw = true;
+
+        if failed.is_empty() {
+            self.app_log.info(&format!("Reloaded {}", reloaded.join(", ")));
+            self.toast_manager.success(&format!("Reloaded {}", reloaded.join(", ")));
+        } else {
+            let reloaded = if reloaded.is_empty() { "nothing".to_string() } else { reloaded.join(", ") };
+            let msg = format!("Reloaded {}; kept previous {}", reloaded, failed.join(", "));
+            self.app_log.error(&msg);
+            self.toast_manager.error(&msg);
+        }
+    }
+
+    /// Loads the profile of the selected History row and returns to the dashboard.
+    pub fn exec_history_open(&mut self) {
+        let Some(profile_id) = self.history_view.selected().map(|record| record.sketch_id.clone()) else {
+            return;
+        };
+        match self.select_profile(&profile_id) {
+            Ok(()) => {
+                if let Some(dashboard) = self.tabs.iter().position(|tab| tab.id == "dashboard") {
+                    self.activate_tab(dashboard);
+                }
+                self.toast_manager.success(&format!("Loaded profile {}", profile_id));
+            }
+            Err(e) => self.report_error(e),
+        }
+    }
+
+    /// Opens the quick-switcher over all profile ids.
+    pub fn exec_open_profile_switcher(&mut self) {
+        let switcher = crate::widgets::profile_switcher::ProfileSwitcher::new(self.profile_ids.clone());
+        self.profile_switcher = Some(crate::widgets::popup::Popup::new(switcher, "SWITCH PROFILE".to_string()).with_size(50, 50));
+        self.push_modal();
+    }
+
+    /// Closes the quick-switcher and loads the profile picked in it.
+    pub(crate) fn confirm_profile_switch(&mut self, profile_id: &str) {
+        self.close_profile_switcher();
+        match self.select_profile(profile_id) {
+            Ok(()) => self.toast_manager.success(&format!("Loaded profile {}", profile_id)),
+            Err(e) => self.report_error(e),
+        }
+    }
 }
@@ -0,0 +1,25 @@
+use std::fs;
+use std::path::Path;
+
+/// Default location of the last build's output, relative to the working directory.
+pub const DEFAULT_LAST_OUTPUT_PATH: &str = ".dev-console/last_output.log";
+
+/// Line shown above output restored from a previous session.
+pub const RESTORED_HEADER: &str = "⬒ Output of the previous run (restored; cleared by the next command).";
+
+/// Writes a finished build's output, one buffer line per file line.
+pub fn save(path: &Path, lines: &[String]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() { //>
+        fs::create_dir_all(parent)?;
+    } //<
+    fs::write(path, lines.join("\n"))
+}
+
+/// Reads output saved by `save`; a missing, unreadable or empty file yields `None`.
+pub fn load(path: &Path) -> Option<Vec<String>> {
+    let content = fs::read_to_string(path).ok()?;
+    if content.is_empty() {
+        return None;
+    }
+    Some(content.lines().map(str::to_string).collect())
+}
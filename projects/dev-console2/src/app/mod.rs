@@ -5,6 +5,11 @@ mod system;
 mod view;
 mod ansi;
 pub mod theme;
+pub mod ui_state;
+pub mod app_log;
+pub mod last_output;
+pub mod diagnostics;
+pub mod clipboard;
 
 use crate::app::theme::Theme;
 
@@ -14,6 +19,7 @@ use crate::widgets::smooth_scrollbar::{ScrollBar, ScrollBarInteraction, ScrollCo
 use crate::widgets::toast::{ToastManager};
 use crate::widgets::popup::Popup;
 use crate::widgets::file_browser::FileBrowser;
+use crate::widgets::build_trend::BuildTrend;
 use crate::widgets::WidgetOutcome;
 use ratatui::{
     layout::{Constraint, Layout, Rect, Position},
@@ -52,10 +58,17 @@ pub enum Action {
     #[strum(serialize = "scroll_bottom", serialize = "scroll_output_to_bottom")]
     ScrollOutputToBottom,
     ToggleAutoscroll,
+    ToggleQuiet,
+    ToggleTimestamps,
+    ToggleOutputMaximized,
     ToggleInput,
+    FilterOutput,
+    ClearOutputFilter,
     CopyStatus,
+    ClearStatus,
     CopyOutputVisible,
     CopyOutputFull,
+    CopyLastError,
     #[strum(serialize = "Compile")]
     Compile,
     #[strum(serialize = "Upload")]
@@ -66,6 +79,10 @@ pub enum Action {
     MonitorMqtt,
     #[strum(serialize = "Clean")]
     Clean,
+    #[strum(serialize = "Erase Flash")]
+    EraseFlash,
+    #[strum(serialize = "All Profiles")]
+    CompileAll,
     CommandsUp,
     CommandsDown,
     SettingsUp,
@@ -77,6 +94,15 @@ pub enum Action {
     ProfileClone,
     ProfileDelete,
     ProfileSave,
+    ShowBuildTrend,
+    ShowMapReport,
+    ExportDiagnostics,
+    HistoryUp,
+    HistoryDown,
+    HistorySort,
+    HistoryOpen,
+    OpenProfileSwitcher,
+    ReloadConfig,
     Cancel,
 }
 
@@ -111,6 +137,36 @@ pub enum DispatchMode {
 pub enum Focus {
     Sidebar,
     Content,
+    /// An overlay (file browser, trend chart, profile switcher) owns all input.
+    Modal,
+}
+
+/// The kind of task a `TaskState::Running` state belongs to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operation {
+    Compile,
+    Upload,
+    EraseFlash,
+}
+
+impl Operation {
+    /// Command name recorded in build history.
+    pub fn command(self) -> &'static str {
+        match self {
+            Operation::Compile => "Compile",
+            Operation::Upload => "Upload",
+            Operation::EraseFlash => "Erase Flash",
+        }
+    }
+
+    /// Name used in completion toasts and notifications.
+    pub fn label(self) -> &'static str {
+        match self {
+            Operation::Compile => "Build",
+            Operation::Upload => "Upload",
+            Operation::EraseFlash => "Erase Flash",
+        }
+    }
 }
 
 /// Represents the current state of a background task or monitoring process.
@@ -136,7 +192,44 @@ pub enum TaskState {
     },
 }
 
-const MAX_OUTPUT_LINES: usize = 2000;
+/// An "All Profiles" compile in progress.
+///>
+/// Per-sketch updates are attributed to `current` so build history and the
+/// predictor stay per sketch, while the progress bar and ETA cover the batch.
+///<
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchRun {
+    /// Expected build time of each queued profile, from its history.
+    pub expected_secs: Vec<f64>,
+    /// Position and sketch id of the profile compiling now.
+    pub current: Option<(usize, String)>,
+    /// When the current profile started compiling.
+    pub profile_started: Instant,
+}
+
+/// Lowest scrollback cap accepted from `application.max_output_lines`.
+const MIN_OUTPUT_LINES: usize = 100;
+
+/// Accepted range for `application.progress_smoothing` (fraction of the gap closed per tick).
+const PROGRESS_SMOOTHING_RANGE: (f64, f64) = (0.02, 1.0);
+
+/// Formats the relative timestamp prepended to output lines, e.g. `[+1.23s] `.
+pub fn timestamp_prefix(elapsed: std::time::Duration) -> String {
+    format!("[+{:.2}s] ", elapsed.as_secs_f64())
+}
+
+/// Layout variant selected from the terminal size.
+///>
+/// `Compact` applies between `compact_min_*` and `min_*`, stacking the
+/// dashboard panels in a single column. Below the compact minimum the
+/// "too small" warning is shown instead.
+///<
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LayoutMode {
+    Full,
+    Compact,
+    TooSmall,
+}
 
 /// Spatial coordinates for primary UI regions.
 ///>
@@ -154,6 +247,7 @@ pub struct AppLayout {
     pub status: Rect,
     pub output: Rect,
     pub settings: Option<SettingsLayout>,
+    pub history: Option<Rect>,
 }
 
 /// Layout specifically for the Settings/Profiles tab.
@@ -190,6 +284,16 @@ pub struct App {
     icon_focused: bool,
     output_lines: Vec<String>,
     output_cached_lines: Vec<ratatui::text::Line<'static>>,
+    output_verbose_flags: Vec<bool>,
+    pub output_quiet: bool,
+    /// Case-insensitive text the output pane is filtered by; kept for the session across tabs and commands.
+    pub output_filter: Option<String>,
+    /// Set while the input line is editing `output_filter` rather than sending to a monitor.
+    pub filter_editing: bool,
+    /// Prefix new output lines with the time elapsed since the task started.
+    pub output_timestamps: bool,
+    /// Output pane fills the whole content area; commands, profile and status are hidden.
+    output_maximized: bool,
     output_scroll: u16,
     output_scroll_interaction: ScrollBarInteraction,
     output_autoscroll: bool,
@@ -203,6 +307,15 @@ pub struct App {
     selected_profile_index: usize,
     profile_ids: Vec<String>,
     cancel_signal: Arc<AtomicBool>,
+    /// Stops the serial/MQTT monitor; separate from `cancel_signal` so starting or
+    /// cancelling an upload leaves a paused monitor alive.
+    monitor_cancel: Arc<AtomicBool>,
+    /// Spawns compile/upload and hook processes (mocked in tests).
+    pub command_runner: Arc<dyn crate::commands::traits::CommandRunner>,
+    /// Opens the serial monitor's port (mocked in tests).
+    pub serial_provider: Arc<dyn crate::commands::traits::SerialProvider>,
+    /// Receives copied text (a recorder in tests).
+    pub clipboard: Box<dyn clipboard::ClipboardSink>,
     view_area: Rect,
     layout: AppLayout,
     pub theme: Theme,
@@ -212,8 +325,46 @@ pub struct App {
     pub should_redraw: bool,
     pub dispatch_mode: DispatchMode,
     pub focus: Focus,
+    /// Focus underneath each open overlay, restored as they close.
+    pub focus_stack: Vec<Focus>,
 
     pub modal: Option<Popup<FileBrowser>>,
+    pub trend_modal: Option<Popup<BuildTrend>>,
+    /// Linker map of the last build, opened with `show_map_report`.
+    pub map_report_modal: Option<Popup<crate::widgets::text_viewer::TextViewer>>,
+    pub profile_switcher: Option<Popup<crate::widgets::profile_switcher::ProfileSwitcher>>,
+    /// Directory the file browser was last closed in, reused on the next open.
+    pub last_browser_dir: Option<std::path::PathBuf>,
+    pub ui_state: ui_state::UiState,
+    pub ui_state_path: std::path::PathBuf,
+    /// Where the last build's output is kept when `application.persist_last_output` is set.
+    pub last_output_path: std::path::PathBuf,
+    /// Diagnostic log for the application itself.
+    pub app_log: app_log::AppLog,
+    /// Rows of the History tab, reloaded each time the tab is opened.
+    pub history_view: crate::widgets::build_history::BuildHistory,
+    /// Set while the serial monitor is paused so an upload can use the port.
+    pub monitor_paused_for_upload: bool,
+    /// Set while a quit request waits for the user to confirm stopping an active task.
+    pub quit_pending: bool,
+    /// Set while an Erase Flash request waits for the user to confirm the destructive erase.
+    pub erase_pending: bool,
+    /// Set while an "All Profiles" compile runs.
+    pub batch_run: Option<BatchRun>,
+    /// What the current (or last) running task is, so a failure is labelled correctly.
+    pub running_operation: Operation,
+    /// Set when a completion bell should be written to the terminal after the next draw.
+    pub bell_pending: bool,
+    /// Start time and tint of an active completion flash.
+    pub completion_flash: Option<(Instant, ratatui::style::Color)>,
+    /// Source file most recently named in the running build's output.
+    pub current_build_file: Option<String>,
+    /// Start time and buffer index of an output line copied with Alt+Click.
+    pub line_copy_flash: Option<(Instant, usize)>,
+    /// Animation ticks elapsed in the current tab transition, if one is playing.
+    pub tab_transition_frame: Option<u8>,
+    /// Profile's post-flash serial line and the time it becomes due.
+    pub pending_serial_send: Option<(String, Instant)>,
 
     // Input state
     pub input: tui_input::Input,
@@ -231,6 +382,8 @@ impl App {
     ///<
     pub fn new() -> Result<Self> {
         let config = crate::config::load_config()?;
+        let app_log = app_log::AppLog::new(&config.application.log);
+        app_log.info("Loaded build-config.yaml");
         
         let mut tab_bar_map: HashMap<String, crate::config::TabBarConfig> = HashMap::new();
         for tb in config.tab_bars.iter() {
@@ -251,6 +404,7 @@ impl App {
             .and_then(|t| t.tabs.iter().find(|tab| tab.id == "autoscroll"))
             .map(|tab| tab.default == Some("active".to_string()))
             .unwrap_or(true);
+        let output_timestamps = config.application.output_timestamps;
 
         let commands = vec![
             "Compile".to_string(),
@@ -258,25 +412,27 @@ impl App {
             "Monitor-Serial".to_string(),
             "Monitor-MQTT".to_string(),
             "Clean".to_string(),
-            "All".to_string(),
+            "Erase Flash".to_string(),
+            "All Profiles".to_string(),
         ];
 
         let (command_tx, command_rx) = mpsc::channel();
 
-        let mut profile_config: Option<ProfileConfig> = None;
         let mut profile_ids: Vec<String> = Vec::new();
         let mut initial_output = Vec::new();
         
         let toast_config = crate::config::load_widget_config()?;
-        let toast_manager = ToastManager::new(toast_config);
+        let mut toast_manager = ToastManager::new(toast_config);
 
-        let initial_status = match crate::config::load_profile_config() {
+        let profile_load = crate::config::load_profile_config();
+        let initial_status = match &profile_load {
             Ok(config) => {
                 profile_ids = config.sketches.iter().map(|s| s.id.clone()).collect();
-                profile_config = Some(config);
+                app_log.info(&format!("Loaded {} profiles from config.yaml", profile_ids.len()));
                 format!("{} profiles loaded.", profile_ids.len())
             },
             Err(e) => {
+                app_log.error(&format!("Failed to load config.yaml: {}", e));
                 let msg = "[Error] Failed to load config.yaml".to_string();
                 for line in format!("{}", e).lines() {
                     initial_output.push(line.to_string());
@@ -284,10 +440,12 @@ impl App {
                 msg
             }
         };
+        announce_profile_load(&mut toast_manager, profile_load.is_ok(), &initial_status, config.application.quiet_startup);
+        let profile_config: Option<ProfileConfig> = profile_load.ok();
 
         let app_theme = Theme::new(&config.theme);
 
-        Ok(Self {
+        let mut app = Self {
             running: true,
             tabs,
             config,
@@ -309,6 +467,12 @@ impl App {
             icon_focused: false,
             output_lines: initial_output.clone(),
             output_cached_lines: initial_output.iter().map(|l| crate::app::ansi::parse_ansi_line(l)).collect(),
+            output_verbose_flags: initial_output.iter().map(|l| crate::commands::is_verbose_line(l)).collect(),
+            output_quiet: false,
+            output_filter: None,
+            filter_editing: false,
+            output_timestamps,
+            output_maximized: false,
             output_scroll: 0,
             output_scroll_interaction: ScrollBarInteraction::new(),
             output_autoscroll,
@@ -322,6 +486,10 @@ impl App {
             selected_profile_index: 0,
             profile_ids,
             cancel_signal: Arc::new(AtomicBool::new(false)),
+            monitor_cancel: Arc::new(AtomicBool::new(false)),
+            command_runner: Arc::new(crate::commands::traits::RealCommandRunner),
+            serial_provider: Arc::new(crate::commands::traits::RealSerialProvider),
+            clipboard: Box::new(clipboard::SystemClipboard::default()),
             view_area: Rect::default(),
             layout: AppLayout {
                 title: Rect::default(),
@@ -333,6 +501,7 @@ impl App {
                 status: Rect::default(),
                 output: Rect::default(),
                 settings: None,
+                history: None,
             },
             theme: app_theme,
             predictor: crate::commands::ProgressPredictor::new(),
@@ -341,12 +510,35 @@ impl App {
             should_redraw: true,
             dispatch_mode: DispatchMode::OnSelect,
             focus: Focus::Sidebar,
+            focus_stack: Vec::new(),
             modal: None,
+            trend_modal: None,
+            map_report_modal: None,
+            profile_switcher: None,
+            last_browser_dir: None,
+            monitor_paused_for_upload: false,
+            quit_pending: false,
+            erase_pending: false,
+            batch_run: None,
+            running_operation: Operation::Compile,
+            bell_pending: false,
+            completion_flash: None,
+            line_copy_flash: None,
+            tab_transition_frame: None,
+            current_build_file: None,
+            pending_serial_send: None,
+            ui_state: ui_state::UiState::load(std::path::Path::new(ui_state::DEFAULT_UI_STATE_PATH)),
+            ui_state_path: std::path::PathBuf::from(ui_state::DEFAULT_UI_STATE_PATH),
+            last_output_path: std::path::PathBuf::from(last_output::DEFAULT_LAST_OUTPUT_PATH),
+            app_log,
+            history_view: Default::default(),
             input: tui_input::Input::default(),
             input_active: false,
             serial_tx: None,
             mqtt_tx: None,
-        })
+        };
+        app.restore_last_output();
+        Ok(app)
     }
 
     /// Recalculates the geometry of all UI regions based on available area.
@@ -355,16 +547,8 @@ impl App {
     /// Output, etc.) while respecting widget-specific height requirements 
     /// defined in the configuration.
     ///< 
-    pub fn calculate_layout(&self, area: Rect) -> AppLayout {
-        let vertical_layout = Layout::vertical([
-            Constraint::Length(1),
-            Constraint::Min(0),
-            Constraint::Length(1),
-            Constraint::Length(2),
-        ]);
-
-        let [title, main, bindings, status_bar] = vertical_layout.areas(area);
-
+    /// Area inside the main tab bar and its border where the active tab draws.
+    pub fn tab_content_area(&self, main: Rect) -> Rect {
         let mut inner_main = main;
         for id in &["MainContentTabBar"] {
             if let Some(tab_config) = self.config.tab_bars.iter().find(|t| t.id == *id) {
@@ -377,7 +561,19 @@ impl App {
                 }
             }
         }
-        inner_main = Block::bordered().inner(inner_main);
+        Block::bordered().inner(inner_main)
+    }
+
+    pub fn calculate_layout(&self, area: Rect) -> AppLayout {
+        let vertical_layout = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(1),
+            Constraint::Length(2),
+        ]);
+
+        let [title, main, bindings, status_bar] = vertical_layout.areas(area);
+        let inner_main = self.tab_content_area(main);
 
         let active_tab_id = self.tabs.iter()
             .find(|t| t.active)
@@ -396,6 +592,55 @@ impl App {
                 status: Rect::default(),
                 output: Rect::default(),
                 settings: Some(settings),
+                history: None,
+            }
+        } else if active_tab_id == "history" {
+            AppLayout {
+                title,
+                main,
+                bindings,
+                status_bar,
+                profile: Rect::default(),
+                commands: Rect::default(),
+                status: Rect::default(),
+                output: Rect::default(),
+                settings: None,
+                history: Some(inner_main),
+            }
+        } else if self.output_maximized {
+            AppLayout {
+                title,
+                main,
+                bindings,
+                status_bar,
+                profile: Rect::default(),
+                commands: Rect::default(),
+                status: Rect::default(),
+                output: inner_main,
+                settings: None,
+                history: None,
+            }
+        } else if self.layout_mode(area) == LayoutMode::Compact {
+            // Single column: commands, status and output stacked, profile hidden
+            let command_rows = (self.commands.len() as u16).saturating_add(2);
+            let [commands, status, output] = Layout::vertical([
+                Constraint::Length(command_rows),
+                Constraint::Length(4),
+                Constraint::Min(0),
+            ])
+            .areas(inner_main);
+
+            AppLayout {
+                title,
+                main,
+                bindings,
+                status_bar,
+                profile: Rect::default(),
+                commands,
+                status,
+                output,
+                settings: None,
+                history: None,
             }
         } else {
             let [left_col, right_col] = Layout::horizontal([
@@ -426,6 +671,7 @@ impl App {
                 status,
                 output,
                 settings: None,
+                history: None,
             }
         }
     }
@@ -551,7 +797,9 @@ impl App {
                         .map(|t| t.id.as_str())
                         .unwrap_or("");
                     
-                    if active_tab_id == "profiles" {
+                    if self.filter_editing {
+                        self.exec_apply_output_filter();
+                    } else if active_tab_id == "profiles" {
                         self.exec_settings_finish_edit();
                     } else {
                         self.exec_send_command();
@@ -562,6 +810,7 @@ impl App {
                 KeyCode::Esc => {
                     if self.input_active {
                         self.input_active = false;
+                        self.filter_editing = false;
                         self.input.reset();
                     } else {
                         self.dispatch_command(Action::Cancel);
@@ -576,7 +825,43 @@ impl App {
             return;
         }
 
+        // 0. Pending quit confirmation swallows every key until answered
+        if self.quit_pending {
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => self.confirm_quit(),
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    self.quit_pending = false;
+                    self.status_text = "Quit canceled.".to_string();
+                }
+                _ => {}
+            }
+            return;
+        }
+
+        // 0. Pending erase confirmation, likewise answered before anything else
+        if self.erase_pending {
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => self.confirm_erase_flash(),
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    self.erase_pending = false;
+                    self.status_text = "Erase canceled.".to_string();
+                }
+                _ => {}
+            }
+            return;
+        }
+
         // 0. Modal Handling (Priority)
+        if self.modal.is_some() && key.code == KeyCode::Char('r') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            // Forget the remembered directory and jump back to the default start
+            self.last_browser_dir = None;
+            let default_dir = self.default_browser_dir();
+            if let Some(modal) = &mut self.modal {
+                modal.content = crate::widgets::file_browser::FileBrowser::new(default_dir);
+            }
+            self.should_redraw = true;
+            return;
+        }
         if let Some(modal) = &mut self.modal {
             match modal.handle_key(key) {
                 WidgetOutcome::Consumed | WidgetOutcome::Changed(_) => {
@@ -602,17 +887,45 @@ impl App {
                         let msg = format!("Confirmed path: {:?}", path);
                         self.log("info", &msg);
                     }
-                    self.modal = None;
+                    self.close_file_browser();
                     return;
                 }
                 WidgetOutcome::Canceled => {
-                    self.modal = None;
+                    self.close_file_browser();
                     return;
                 }
                 WidgetOutcome::None => {}
             }
         }
 
+        if let Some(trend_modal) = &mut self.trend_modal {
+            if trend_modal.handle_key(key) == WidgetOutcome::Canceled {
+                self.close_trend_modal();
+            }
+            return;
+        }
+
+        if let Some(map_report) = &mut self.map_report_modal {
+            if map_report.handle_key(key) == WidgetOutcome::Canceled {
+                self.close_map_report();
+            }
+            return;
+        }
+
+        if let Some(switcher) = &mut self.profile_switcher {
+            match switcher.handle_key(key) {
+                WidgetOutcome::Confirmed(profile_id) => self.confirm_profile_switch(&profile_id),
+                WidgetOutcome::Canceled => self.close_profile_switcher(),
+                _ => {}
+            }
+            return;
+        }
+
+        // Nothing underneath an open overlay sees input
+        if self.focus == Focus::Modal {
+            return;
+        }
+
         let active_tab_id = self.tabs.iter()
             .find(|t| t.active)
             .map(|t| t.id.as_str())
@@ -661,6 +974,12 @@ impl App {
             return;
         }
 
+        // 2.5 Alt+letter tab accelerators
+        if let Some(tab_idx) = self.tab_accelerator_hit(key) {
+            self.activate_tab(tab_idx);
+            return;
+        }
+
         // 3. Tab Bar Navigation Bindings
         if let Some(tab_bar) = self.tab_bar_map.get("MainContentTabBar") {
             for key_str in &tab_bar.navigation.left {
@@ -736,12 +1055,12 @@ impl App {
                             }
                         }
                     }
-                    self.modal = None;
+                    self.close_file_browser();
                     self.should_redraw = true;
                     return;
                 }
                 WidgetOutcome::Canceled => {
-                    self.modal = None;
+                    self.close_file_browser();
                     self.should_redraw = true;
                     return;
                 }
@@ -753,6 +1072,32 @@ impl App {
             }
         }
 
+        if let Some(trend_modal) = &mut self.trend_modal {
+            if trend_modal.handle_mouse(mouse_event, self.view_area) == WidgetOutcome::Canceled {
+                self.close_trend_modal();
+                self.should_redraw = true;
+            }
+            return;
+        }
+
+        if let Some(map_report) = &mut self.map_report_modal {
+            if map_report.handle_mouse(mouse_event, self.view_area) == WidgetOutcome::Canceled {
+                self.close_map_report();
+            }
+            self.should_redraw = true;
+            return;
+        }
+
+        if let Some(switcher) = &mut self.profile_switcher {
+            match switcher.handle_mouse(mouse_event, self.view_area) {
+                WidgetOutcome::Confirmed(profile_id) => self.confirm_profile_switch(&profile_id),
+                WidgetOutcome::Canceled => self.close_profile_switcher(),
+                _ => {}
+            }
+            self.should_redraw = true;
+            return;
+        }
+
         // RAW LOGGING (Ignore Move noise)
         if mouse_event.kind != event::MouseEventKind::Moved {
             let mods_str = self.get_modifiers_display(mouse_event.modifiers);
@@ -767,17 +1112,24 @@ impl App {
         if let Some(_) = self.tab_bar_map.get("MainContentTabBar") {
             if let Some((tab_bar, horiz, vert, off_x, off_y)) = TabBarWidget::from_config(&self.config, &self.tabs, "MainContentTabBar") {
                 if let Some(tab_idx) = tab_bar.handle_mouse_event(layout.main, horiz, vert, off_x, off_y, mouse_event) {
-                    for (i, tab) in self.tabs.iter_mut().enumerate() {
-                        tab.active = i == tab_idx;
-                    }
-                    // Recalculate layout for the new tab
-                    self.layout = self.calculate_layout(self.view_area);
-                    self.should_redraw = true;
+                    self.activate_tab(tab_idx);
                     return; 
                 }
             }
         }
 
+        // 1.05 Tab Binding Buttons
+        if let Some(bindings_config) = self.active_tab_bindings().filter(|b| b.buttons) {
+            let buttons = crate::widgets::button_bar::Button::from_bindings(bindings_config);
+            let widget = crate::widgets::button_bar::ButtonBarWidget::new(&buttons);
+            if let Some(idx) = widget.handle_mouse_event(layout.bindings, mouse_event) {
+                if let Some(action) = Action::from_str(&buttons[idx].action) {
+                    self.dispatch_command(action);
+                }
+                return;
+            }
+        }
+
         // 1.1 Settings Tab Hit Detection
         let active_tab_id = self.tabs.iter()
             .find(|t| t.active)
@@ -874,11 +1226,14 @@ impl App {
         }
 
         // 1.2 Output Panel "Auto" Toggle
-        let output_static_tabs = vec![TabBarItem { id: "autoscroll".to_string(), name: "Auto".to_string(), active: self.output_autoscroll }];
+        let output_static_tabs = self.output_static_tabs();
         if let Some(_) = self.tab_bar_map.get("OutputPanelStaticOptions") {
             if let Some((tab_bar, horiz, vert, off_x, off_y)) = TabBarWidget::from_config(&self.config, &output_static_tabs, "OutputPanelStaticOptions") {
-                if tab_bar.handle_mouse_event(layout.output, horiz, vert, off_x, off_y, mouse_event).is_some() {
-                    self.dispatch_command(Action::ToggleAutoscroll);
+                if let Some(idx) = tab_bar.handle_mouse_event(layout.output, horiz, vert, off_x, off_y, mouse_event) {
+                    match output_static_tabs[idx].id.as_str() {
+                        "quiet" => self.dispatch_command(Action::ToggleQuiet),
+                        _ => self.dispatch_command(Action::ToggleAutoscroll),
+                    }
                     return;
                 }
             }
@@ -921,6 +1276,14 @@ impl App {
                 if layout.status.contains(mouse_pos) && mouse_event.modifiers.contains(KeyModifiers::CONTROL) {
                     self.dispatch_command(Action::CopyStatus);
                     return;
+                } else if layout.status.contains(mouse_pos) && matches!(self.task_state, TaskState::Idle) {
+                    self.dispatch_command(Action::ClearStatus);
+                    return;
+                } else if layout.output.contains(mouse_pos) && mouse_event.modifiers.contains(KeyModifiers::ALT) {
+                    if let Some(index) = self.output_line_at(mouse_event.row) {
+                        self.copy_output_line(index);
+                        return;
+                    }
                 } else if layout.output.contains(mouse_pos) && mouse_event.modifiers.contains(KeyModifiers::CONTROL) {
                     let is_full = mouse_event.modifiers.contains(KeyModifiers::SHIFT);
                     if is_full {
@@ -937,7 +1300,7 @@ impl App {
         // 4. Scrollbar interaction
         let inner_output = Block::bordered().inner(layout.output);
         let scrollbar = ScrollBar::vertical(ScrollLengths {
-            content_len: self.output_lines.len() + 1,
+            content_len: self.visible_output_len() + 1,
             viewport_len: inner_output.height as usize,
         }).offset(self.output_scroll as usize);
         
@@ -990,6 +1353,9 @@ impl App {
             "tab" => matches!(key.code, KeyCode::Tab),
             "delete" | "del" => matches!(key.code, KeyCode::Delete),
             _ => {
+                if let Some(n) = target.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    return key.code == KeyCode::F(n);
+                }
                 if target.len() == 1 {
                     let c = target.chars().next().unwrap();
                     matches!(key.code, KeyCode::Char(key_c) if key_c.to_ascii_lowercase() == c.to_ascii_lowercase())
@@ -1045,10 +1411,17 @@ impl App {
             Action::ScrollOutputToTop => self.exec_scroll_top(),
             Action::ScrollOutputToBottom => self.exec_scroll_bottom(),
             Action::ToggleAutoscroll => self.exec_toggle_autoscroll(),
+            Action::ToggleQuiet => self.exec_toggle_quiet(),
+            Action::FilterOutput => self.exec_filter_output(),
+            Action::ClearOutputFilter => self.exec_clear_output_filter(),
+            Action::ToggleTimestamps => self.exec_toggle_timestamps(),
+            Action::ToggleOutputMaximized => self.exec_toggle_output_maximized(),
             Action::ToggleInput => self.exec_toggle_input(),
             Action::CopyStatus => self.exec_copy_status(),
+            Action::ClearStatus => self.exec_clear_status(),
             Action::CopyOutputVisible => self.exec_copy_output(false),
             Action::CopyOutputFull => self.exec_copy_output(true),
+            Action::CopyLastError => self.exec_copy_last_error(),
             Action::CommandsUp => self.exec_commands_up(),
             Action::CommandsDown => self.exec_commands_down(),
             Action::SettingsUp => self.exec_settings_up(),
@@ -1059,30 +1432,194 @@ impl App {
             Action::ProfileClone => self.exec_profile_clone(),
             Action::ProfileDelete => self.exec_profile_delete(),
             Action::ProfileSave => self.exec_profile_save(),
+            Action::HistoryUp => self.history_view.select_prev(),
+            Action::HistoryDown => self.history_view.select_next(),
+            Action::HistorySort => self.history_view.cycle_sort(),
+            Action::HistoryOpen => self.exec_history_open(),
+            Action::OpenProfileSwitcher => self.exec_open_profile_switcher(),
+            Action::ReloadConfig => self.exec_reload_config(),
+            Action::ShowBuildTrend => self.exec_show_build_trend(),
+            Action::ShowMapReport => self.exec_show_map_report(),
+            Action::ExportDiagnostics => self.exec_export_diagnostics(),
             Action::Cancel => self.exec_cancel(),
             Action::Compile => self.exec_compile(),
             Action::Upload => self.exec_upload(),
             Action::MonitorSerial => self.exec_monitor_serial(),
             Action::MonitorMqtt => self.exec_monitor_mqtt(),
             Action::Clean => self.exec_clean(),
+            Action::EraseFlash => self.exec_erase_flash(),
+            Action::CompileAll => self.exec_compile_all(),
         }
     }
     
+    /// Prepends the elapsed-time prefix when timestamps are enabled and a task is active.
+    fn stamp_line(&self, line: String) -> String {
+        if !self.output_timestamps {
+            return line;
+        }
+        match &self.task_state {
+            TaskState::Running { start_time, .. } | TaskState::Monitoring { start_time, .. } => {
+                format!("{}{}", timestamp_prefix(start_time.elapsed()), line)
+            }
+            TaskState::Idle => line,
+        }
+    }
+
     /// Internal helper for adding lines to the output buffer.
     fn push_line(&mut self, line: String) {
+        let line = self.stamp_line(line);
+        self.push_stamped_line(line);
+    }
+
+    /// Appends a line that already carries its timestamp prefix (if any).
+    fn push_stamped_line(&mut self, line: String) {
         let cached = crate::app::ansi::parse_ansi_line(&line);
+        self.output_verbose_flags.push(crate::commands::is_verbose_line(&line));
         self.output_lines.push(line);
         self.output_cached_lines.push(cached);
 
-        if self.output_lines.len() > MAX_OUTPUT_LINES {
-            let to_remove = self.output_lines.len() - MAX_OUTPUT_LINES;
+        let max_lines = self.max_output_lines();
+        if self.output_lines.len() > max_lines {
+            let to_remove = self.output_lines.len() - max_lines;
             self.output_lines.drain(0..to_remove);
             self.output_cached_lines.drain(0..to_remove);
+            self.output_verbose_flags.drain(0..to_remove);
         }
         self.should_redraw = true;
         self.sync_autoscroll();
     }
 
+    /// Replaces the most recent output line in place, or appends if empty.
+    fn replace_last_line(&mut self, line: String) {
+        let line = self.stamp_line(line);
+        if self.output_lines.is_empty() {
+            self.push_stamped_line(line);
+            return;
+        }
+        let last = self.output_lines.len() - 1;
+        self.output_cached_lines[last] = crate::app::ansi::parse_ansi_line(&line);
+        self.output_verbose_flags[last] = crate::commands::is_verbose_line(&line);
+        self.output_lines[last] = line;
+        self.should_redraw = true;
+        self.sync_autoscroll();
+    }
+
+    /// Shows the previous session's build output when `application.persist_last_output` is set.
+    ///>
+    /// Only applies while the buffer holds nothing else (e.g. no config load
+    /// errors); the restored lines stay until the next command clears them.
+    ///<
+    pub fn restore_last_output(&mut self) {
+        if !self.config.application.persist_last_output || !self.output_lines.is_empty() {
+            return;
+        }
+        if let Some(lines) = last_output::load(&self.last_output_path) {
+            self.push_line(last_output::RESTORED_HEADER.to_string());
+            for line in lines {
+                self.push_line(line);
+            }
+        }
+    }
+
+    /// Saves the finished build's output for the next session, if enabled.
+    pub(crate) fn persist_last_output(&self) {
+        if !self.config.application.persist_last_output {
+            return;
+        }
+        if let Err(e) = last_output::save(&self.last_output_path, &self.output_lines) {
+            self.app_log.error(&format!("Failed to save last output: {}", e));
+        }
+    }
+
+    /// Empties the output buffer ahead of a new task.
+    fn clear_output(&mut self) {
+        self.output_lines.clear();
+        self.output_cached_lines.clear();
+        self.output_verbose_flags.clear();
+        self.line_copy_flash = None;
+        self.current_build_file = None;
+    }
+
+    /// Buffer index of the output line drawn at screen row `row`, if any.
+    ///>
+    /// Output lines never wrap, so a row maps to one visible line offset by the
+    /// scroll position; quiet mode and the filter are accounted for by walking
+    /// the visible lines. Rows covered by the send-command box resolve to `None`.
+    ///<
+    pub fn output_line_at(&self, row: u16) -> Option<usize> {
+        let inner = Block::bordered().inner(self.layout.output);
+        let text_height = if self.input_active { inner.height.saturating_sub(3) } else { inner.height };
+        if row < inner.y || row >= inner.y + text_height {
+            return None;
+        }
+        let visible_index = (row - inner.y) as usize + self.output_scroll as usize;
+        (0..self.output_lines.len())
+            .filter(|i| self.is_output_line_visible(*i))
+            .nth(visible_index)
+    }
+
+    /// Position of buffer line `index` among the currently visible lines.
+    fn visible_output_position(&self, index: usize) -> Option<usize> {
+        self.is_output_line_visible(index)
+            .then(|| (0..index).filter(|i| self.is_output_line_visible(*i)).count())
+    }
+
+    /// Returns true if the output line at `index` is shown at the current verbosity and filter.
+    fn is_output_line_visible(&self, index: usize) -> bool {
+        let verbosity_ok = !self.output_quiet || !self.output_verbose_flags.get(index).copied().unwrap_or(false);
+        let filter_ok = match &self.output_filter {
+            // Match the rendered text so ANSI color codes never split a word
+            Some(filter) => self.output_cached_lines.get(index).is_some_and(|line| {
+                let text: String = line.spans.iter().map(|span| span.content.as_ref()).collect();
+                text.to_lowercase().contains(&filter.to_lowercase())
+            }),
+            None => true,
+        };
+        verbosity_ok && filter_ok
+    }
+
+    /// True when quiet mode or a filter hides some output lines.
+    pub fn output_is_filtered(&self) -> bool {
+        self.output_quiet || self.output_filter.is_some()
+    }
+
+    /// Number of output lines shown at the current verbosity and filter.
+    pub fn visible_output_len(&self) -> usize {
+        if self.output_is_filtered() {
+            (0..self.output_lines.len()).filter(|i| self.is_output_line_visible(*i)).count()
+        } else {
+            self.output_lines.len()
+        }
+    }
+
+    /// Raw output lines shown at the current verbosity and filter.
+    pub fn visible_output_lines(&self) -> Vec<&String> {
+        self.output_lines.iter().enumerate()
+            .filter(|(i, _)| self.is_output_line_visible(*i))
+            .map(|(_, line)| line)
+            .collect()
+    }
+
+    /// Parsed output lines shown at the current verbosity and filter.
+    fn visible_cached_lines(&self) -> Vec<ratatui::text::Line<'static>> {
+        self.output_cached_lines.iter().enumerate()
+            .filter(|(i, _)| self.is_output_line_visible(*i))
+            .map(|(_, line)| line.clone())
+            .collect()
+    }
+
+    /// Returns the configured scrollback cap, clamped to a sensible floor.
+    pub fn max_output_lines(&self) -> usize {
+        self.config.application.max_output_lines.max(MIN_OUTPUT_LINES)
+    }
+
+    /// Returns the configured progress lerp factor per tick, clamped to a sane range.
+    pub fn progress_smoothing(&self) -> f64 {
+        let (min, max) = PROGRESS_SMOOTHING_RANGE;
+        let factor = self.config.application.progress_smoothing;
+        if factor.is_finite() { factor.clamp(min, max) } else { max }
+    }
+
     /// Adds a themed message to the application log.
     pub fn log(&mut self, kind: &str, message: &str) {
         let formatted = self.theme.format_message(kind, message);
@@ -1093,15 +1630,41 @@ impl App {
     pub fn sync_autoscroll(&mut self) {
         if self.output_autoscroll {
             let layout = self.calculate_layout(self.view_area);
-            let total_count = self.output_lines.len() + 1;
+            let total_count = self.visible_output_len() + 1;
             let visible_height = layout.output.height.saturating_sub(2) as usize;
             self.output_scroll = total_count.saturating_sub(visible_height) as u16;
         }
     }
 
+    /// Bindings configured for the active main tab, if any.
+    fn active_tab_bindings(&self) -> Option<&crate::config::BindingsConfig> {
+        let active_tab = self.tabs.iter().find(|tab| tab.active)?;
+        self.tab_bar_map.get("MainContentTabBar")?.tab_bindings.get(&active_tab.id)
+    }
+
+    /// Builds the output panel's static toggle tabs from current state.
+    fn output_static_tabs(&self) -> Vec<TabBarItem> {
+        vec![
+            TabBarItem { id: "autoscroll".to_string(), name: "Auto".to_string(), active: self.output_autoscroll },
+            TabBarItem { id: "quiet".to_string(), name: "Quiet".to_string(), active: self.output_quiet },
+        ]
+    }
+
     /// Updates internal flag if terminal dimensions fall below minimums.
     pub fn check_terminal_size(&mut self, area: Rect) {
-        self.terminal_too_small = area.width < self.config.application.min_width || area.height < self.config.application.min_height;
+        self.terminal_too_small = self.layout_mode(area) == LayoutMode::TooSmall;
+    }
+
+    /// Picks the layout variant for a terminal of the given size.
+    pub fn layout_mode(&self, area: Rect) -> LayoutMode {
+        let app = &self.config.application;
+        if area.width >= app.min_width && area.height >= app.min_height {
+            LayoutMode::Full
+        } else if area.width >= app.compact_min_width.min(app.min_width) && area.height >= app.compact_min_height.min(app.min_height) {
+            LayoutMode::Compact
+        } else {
+            LayoutMode::TooSmall
+        }
     }
 
     /// Returns the ID of the currently selected sketch profile.
@@ -1109,10 +1672,100 @@ impl App {
         self.profile_ids.get(self.selected_profile_index).cloned()
     }
 
+    /// Main tab whose accelerator matches an Alt+letter key, if accelerators are enabled.
+    fn tab_accelerator_hit(&self, key: event::KeyEvent) -> Option<usize> {
+        let enabled = self.tab_bar_map.get("MainContentTabBar").is_some_and(|t| t.accelerators);
+        match key.code {
+            KeyCode::Char(c) if enabled && key.modifiers.contains(KeyModifiers::ALT) => {
+                crate::widgets::tab_bar::accelerator_index(&self.tabs, c)
+            }
+            _ => None,
+        }
+    }
+
+    /// Makes the tab at `tab_idx` active and lays out its view.
+    pub(crate) fn activate_tab(&mut self, tab_idx: usize) {
+        for (i, tab) in self.tabs.iter_mut().enumerate() {
+            tab.active = i == tab_idx;
+        }
+        if self.tabs.get(tab_idx).is_some_and(|tab| tab.id == "history") {
+            let manager = crate::commands::HistoryManager::load(&self.history_path());
+            self.history_view = crate::widgets::build_history::BuildHistory::new(&manager.builds);
+        }
+        if self.config.application.tab_transition != crate::config::TabTransition::Off {
+            self.tab_transition_frame = Some(0);
+        }
+        self.layout = self.calculate_layout(self.view_area);
+        self.should_redraw = true;
+    }
+
+    /// Gives all input to a newly opened overlay, remembering what had focus beneath it.
+    pub(crate) fn push_modal(&mut self) {
+        self.focus_stack.push(self.focus);
+        self.focus = Focus::Modal;
+    }
+
+    /// Returns input to whatever had focus before the topmost overlay opened.
+    pub(crate) fn pop_modal(&mut self) {
+        if let Some(previous) = self.focus_stack.pop() {
+            self.focus = previous;
+        }
+    }
+
+    /// Closes the file browser, remembering the directory it was showing.
+    fn close_file_browser(&mut self) {
+        if let Some(modal) = self.modal.take() {
+            self.last_browser_dir = Some(modal.content.current_dir);
+            self.pop_modal();
+        }
+    }
+
+    fn close_trend_modal(&mut self) {
+        if self.trend_modal.take().is_some() {
+            self.pop_modal();
+        }
+    }
+
+    fn close_map_report(&mut self) {
+        if self.map_report_modal.take().is_some() {
+            self.pop_modal();
+        }
+    }
+
+    fn close_profile_switcher(&mut self) {
+        if self.profile_switcher.take().is_some() {
+            self.pop_modal();
+        }
+    }
+
+    /// Start directory for the file browser when nothing is remembered.
+    fn default_browser_dir(&self) -> std::path::PathBuf {
+        let sketch_path = match (&self.profile_config, self.get_current_sketch_id()) {
+            (Some(config), Some(profile_id)) => config.sketches.iter().find(|s| s.id == profile_id).map(|s| s.path.clone()),
+            _ => None,
+        };
+        crate::widgets::file_browser::resolve_start_dir(sketch_path.as_deref(), self.config.application.sketch_root.as_deref())
+    }
+
+    /// Selects the sketch profile with the given id, as if picked in the UI.
+    pub fn select_profile(&mut self, profile_id: &str) -> Result<()> {
+        match self.profile_ids.iter().position(|id| id == profile_id) {
+            Some(index) => {
+                self.selected_profile_index = index;
+                Ok(())
+            }
+            None => Err(crate::config::unknown_profile_error(profile_id, &self.profile_ids)),
+        }
+    }
+
+    /// Returns the configured location of the build history file.
+    pub fn history_path(&self) -> std::path::PathBuf {
+        crate::commands::HistoryManager::resolve_path(self.config.application.history_path.as_deref())
+    }
+
     /// Loads history and creates a predictor with optimized weights.
     fn train_predictor(&self) -> crate::commands::ProgressPredictor {
-        let history_path = std::path::Path::new(".dev-console/progress_history.json");
-        let manager = crate::commands::HistoryManager::load(history_path);
+        let manager = crate::commands::HistoryManager::load(&self.history_path());
         let stats = self.get_current_sketch_id()
             .and_then(|id| manager.get_stats(&id));
         
@@ -1132,6 +1785,7 @@ impl App {
     /// Unified error reporting pipeline for status bars, logs, and toasts.
     pub fn report_error(&mut self, e: impl std::fmt::Display) {
         let msg = format!("{}", e);
+        self.app_log.error(&msg);
         self.status_text = format!("[Error] {}", msg);
         self.log("error", &msg);
         self.toast_manager.error(&msg);
@@ -1152,6 +1806,20 @@ impl App {
     }
 }
 
+/// Queues the startup toast for the profile load.
+///>
+/// With `quiet` (`application.quiet_startup`, on by default) the
+/// informational "profiles loaded" toast is skipped and the result only goes
+/// to the status line and log; load errors are always shown.
+///<
+pub(crate) fn announce_profile_load(toasts: &mut ToastManager, loaded: bool, status: &str, quiet: bool) {
+    if !loaded {
+        toasts.error(status);
+    } else if !quiet {
+        toasts.info(status);
+    }
+}
+
 /// Events that drive application state transitions.
 #[derive(PartialEq, Debug, Clone)]
 pub enum Message {
@@ -1,5 +1,43 @@
 use crate::app::{App, Message, TaskState};
 use crate::commands::ProgressUpdate;
+use crate::commands::predictor::CompileStage;
+
+/// How long the completion flash tints the screen.
+const COMPLETION_FLASH: std::time::Duration = std::time::Duration::from_millis(150);
+
+/// How long an Alt+Clicked output line stays highlighted.
+const LINE_COPY_FLASH: std::time::Duration = std::time::Duration::from_millis(300);
+
+/// Animation ticks a tab transition lasts.
+const TAB_TRANSITION_FRAMES: u8 = 4;
+
+/// Settling time between reconnecting after a flash and sending the post-flash line.
+const POST_FLASH_SEND_DELAY: std::time::Duration = std::time::Duration::from_millis(1500);
+
+/// Frame duration the smoothing factor is defined against (the event poll interval).
+const SMOOTHING_TICK_SECS: f64 = 0.05;
+
+/// Moves `current` toward `target` by `factor` of the gap per reference tick.
+///>
+/// The factor is rescaled by `dt` so the bar catches up at the same speed
+/// regardless of how often frames are actually drawn.
+///<
+pub(crate) fn smooth_toward(current: f64, target: f64, factor: f64, dt: f64) -> f64 {
+    let step = 1.0 - (1.0 - factor).powf(dt / SMOOTHING_TICK_SECS);
+    current + (target - current) * step
+}
+
+/// Predictor stage for a `ProgressUpdate::Stage` name, if it is one the predictor tracks.
+fn predictor_stage(name: &str) -> Option<CompileStage> {
+    match name {
+        "Initializing" => Some(CompileStage::Initializing),
+        "DetectingLibraries" => Some(CompileStage::DetectingLibraries),
+        "Compiling" => Some(CompileStage::Compiling),
+        "Linking" => Some(CompileStage::Linking),
+        "Generating" => Some(CompileStage::Generating),
+        _ => None,
+    }
+}
 
 /// Event translation and ingestion for background processes.
 ///>
@@ -30,8 +68,15 @@ impl App {
     ///<
     pub fn exec_system_update(&mut self, update: ProgressUpdate) {
         self.should_redraw = true;
+        let Some(update) = self.exec_batch_update(update) else { return; };
         match update {
             ProgressUpdate::OutputLine(line) => {
+                if matches!(self.task_state, TaskState::Running { .. }) {
+                    if let Some(file) = crate::commands::extract_current_file(&line) {
+                        let name = std::path::Path::new(&file).file_name().map(|n| n.to_string_lossy().into_owned());
+                        self.current_build_file = name.or(Some(file));
+                    }
+                }
                 if let Some(first_char) = line.chars().next() {
                     let char_len = first_char.len_utf8();
                     match first_char {
@@ -53,6 +98,10 @@ impl App {
                     self.log("board", &line);
                 }
             }
+            ProgressUpdate::ReplaceLine(line) => {
+                let formatted = self.theme.format_message("board", &line);
+                self.replace_last_line(formatted);
+            }
             ProgressUpdate::Percentage(p) => {
                 let remaining = self.predictor.predict_remaining(p);
                 
@@ -65,15 +114,7 @@ impl App {
                 }
             }
             ProgressUpdate::Stage(s) => {
-                let predictor_stage = match s.as_str() {
-                    "Initializing" => Some(crate::commands::predictor::CompileStage::Initializing),
-                    "DetectingLibraries" => Some(crate::commands::predictor::CompileStage::DetectingLibraries),
-                    "Compiling" => Some(crate::commands::predictor::CompileStage::Compiling),
-                    "Linking" => Some(crate::commands::predictor::CompileStage::Linking),
-                    "Generating" => Some(crate::commands::predictor::CompileStage::Generating),
-                    _ => None,
-                };
-                if let Some(stage) = predictor_stage {
+                if let Some(stage) = predictor_stage(&s) {
                     self.predictor.enter_stage(stage);
                 }
 
@@ -82,24 +123,268 @@ impl App {
                 }
             }
             ProgressUpdate::CompletedWithMetrics { stage_times } => {
+                let elapsed = self.running_elapsed();
                 let sketch_id = self.get_current_sketch_id().unwrap_or_else(|| "default".to_string());
-                let history_path = std::path::Path::new(".dev-console/progress_history.json");
+                let was_upload = stage_times.keys().any(|s| s.rank() >= CompileStage::Resetting.rank());
+                if was_upload {
+                    self.ui_state.record_flash(&sketch_id, crate::app::ui_state::unix_now());
+                    let _ = self.ui_state.save(&self.ui_state_path);
+                }
+
+                let history_path = self.history_path();
                 
-                let mut manager = crate::commands::HistoryManager::load(history_path);
+                let mut manager = crate::commands::HistoryManager::load(&history_path);
                 manager.record_run(&sketch_id, stage_times);
-                let _ = manager.save(history_path);
+                manager.record_build(crate::commands::BuildRecord {
+                    sketch_id: sketch_id.clone(),
+                    command: if was_upload { "Upload" } else { "Compile" }.to_string(),
+                    duration_secs: elapsed.map(|d| d.as_secs_f64()).unwrap_or_default(),
+                    success: true,
+                    finished_at: crate::app::ui_state::unix_now(),
+                });
+                let _ = manager.save(&history_path);
+                self.persist_last_output();
 
                 self.task_state = TaskState::Idle;
                 self.status_text = "Command completed successfully.".to_string();
                 self.log("system", "Command completed successfully (Metrics saved).");
+                self.notify_completion(true, if was_upload { "Upload" } else { "Build" }, elapsed);
+
+                let resumed = self.resume_monitor_after_upload();
+                if was_upload && !resumed && self.config.application.auto_monitor_after_upload {
+                    self.start_serial_monitor();
+                }
+                if was_upload && matches!(self.task_state, TaskState::Monitoring { .. }) {
+                    self.queue_post_flash_serial();
+                }
+            }
+            ProgressUpdate::Completed(message) => {
+                let elapsed = self.running_elapsed();
+                self.task_state = TaskState::Idle;
+                self.log("system", &message);
+                self.status_text = message;
+                self.notify_completion(true, "Erase Flash", elapsed);
+            }
+            ProgressUpdate::ProfileStarted { index, total, id } => {
+                self.log("system", &format!("[{}/{}] {}", index + 1, total, id));
+            }
+            ProgressUpdate::BatchCompleted { succeeded, failed } => {
+                self.batch_run = None;
+                let elapsed = self.running_elapsed();
+                let summary = format!("{} ok, {} failed", succeeded, failed);
+                self.task_state = TaskState::Idle;
+                self.log("system", &format!("All Profiles compile finished: {}.", summary));
+                self.status_text = format!("All Profiles: {}.", summary);
+                if failed == 0 {
+                    self.toast_manager.success(&summary);
+                } else {
+                    self.toast_manager.warning(&summary);
+                }
+                self.persist_last_output();
+                self.signal_completion(failed == 0, "All Profiles", elapsed);
             }
             ProgressUpdate::Failed(e) => {
+                let elapsed = self.running_elapsed();
+                let operation = self.running_operation;
+                if let Some(elapsed) = elapsed {
+                    let history_path = self.history_path();
+                    let mut manager = crate::commands::HistoryManager::load(&history_path);
+                    manager.record_build(crate::commands::BuildRecord {
+                        sketch_id: self.get_current_sketch_id().unwrap_or_else(|| "default".to_string()),
+                        command: operation.command().to_string(),
+                        duration_secs: elapsed.as_secs_f64(),
+                        success: false,
+                        finished_at: crate::app::ui_state::unix_now(),
+                    });
+                    let _ = manager.save(&history_path);
+                }
                 self.task_state = TaskState::Idle;
                 self.report_error(e);
+                if elapsed.is_some() {
+                    self.persist_last_output();
+                }
+                if let Some(elapsed) = elapsed {
+                    self.notify_completion(false, operation.label(), Some(elapsed));
+                }
+                self.resume_monitor_after_upload();
+            }
+        }
+    }
+
+    /// Handles the per-sketch updates of an "All Profiles" compile.
+    ///>
+    /// Stages and percentages feed the predictor raw, for the sketch being
+    /// built, while the bar shows batch progress and the ETA adds the expected
+    /// time of the profiles still queued. Each sketch's completion or failure
+    /// is recorded in its own history instead of ending the task. Returns the
+    /// update when it still needs the regular handling.
+    ///<
+    fn exec_batch_update(&mut self, update: ProgressUpdate) -> Option<ProgressUpdate> {
+        let Some(batch) = &mut self.batch_run else { return Some(update); };
+        let total = batch.expected_secs.len();
+        match update {
+            ProgressUpdate::ProfileStarted { index, ref id, .. } => {
+                batch.current = Some((index, id.clone()));
+                batch.profile_started = std::time::Instant::now();
+                let stats = crate::commands::HistoryManager::load(&self.history_path()).get_stats(id);
+                self.predictor = crate::commands::ProgressPredictor::with_stats(stats);
+                Some(update)
+            }
+            ProgressUpdate::Percentage(p) => {
+                let index = batch.current.as_ref().map(|(i, _)| *i).unwrap_or_default();
+                let queued: f64 = batch.expected_secs.iter().skip(index + 1).sum();
+                let remaining = self.predictor.predict_remaining(p).map(|rem| rem.as_secs_f64() + queued);
+                if let TaskState::Running { percentage, smoothed_eta, last_updated, .. } = &mut self.task_state {
+                    *percentage = crate::commands::compile_all::batch_percentage(index, p, total);
+                    *last_updated = std::time::Instant::now();
+                    if remaining.is_some() {
+                        *smoothed_eta = remaining;
+                    }
+                }
+                None
+            }
+            ProgressUpdate::Stage(s) => {
+                if let Some(stage) = predictor_stage(&s) {
+                    self.predictor.enter_stage(stage);
+                }
+                let index = batch.current.as_ref().map(|(i, _)| *i).unwrap_or_default();
+                if let TaskState::Running { stage, .. } = &mut self.task_state {
+                    *stage = format!("[{}/{}] {}", index + 1, total, s);
+                }
+                None
+            }
+            ProgressUpdate::CompletedWithMetrics { stage_times } => {
+                let (sketch_id, duration) = Self::batch_profile(batch);
+                let history_path = self.history_path();
+                let mut manager = crate::commands::HistoryManager::load(&history_path);
+                manager.record_run(&sketch_id, stage_times);
+                manager.record_build(crate::commands::BuildRecord {
+                    sketch_id: sketch_id.clone(),
+                    command: "Compile".to_string(),
+                    duration_secs: duration,
+                    success: true,
+                    finished_at: crate::app::ui_state::unix_now(),
+                });
+                let _ = manager.save(&history_path);
+                self.log("system", &format!("{} compiled in {:.1}s.", sketch_id, duration));
+                None
+            }
+            ProgressUpdate::Failed(e) => {
+                let (sketch_id, duration) = Self::batch_profile(batch);
+                let history_path = self.history_path();
+                let mut manager = crate::commands::HistoryManager::load(&history_path);
+                manager.record_build(crate::commands::BuildRecord {
+                    sketch_id: sketch_id.clone(),
+                    command: "Compile".to_string(),
+                    duration_secs: duration,
+                    success: false,
+                    finished_at: crate::app::ui_state::unix_now(),
+                });
+                let _ = manager.save(&history_path);
+                self.log("error", &format!("{}: {}", sketch_id, e));
+                None
             }
+            other => Some(other),
         }
     }
 
+    /// Sketch id and elapsed seconds of the profile a batch is compiling.
+    fn batch_profile(batch: &crate::app::BatchRun) -> (String, f64) {
+        let sketch_id = batch.current.as_ref().map(|(_, id)| id.clone()).unwrap_or_else(|| "default".to_string());
+        (sketch_id, batch.profile_started.elapsed().as_secs_f64())
+    }
+
+    /// Schedules the profile's `post_flash_serial` line once the monitor has settled.
+    fn queue_post_flash_serial(&mut self) {
+        let sketch_id = self.get_current_sketch_id();
+        let command = self.profile_config.as_ref()
+            .and_then(|config| config.sketches.iter().find(|s| Some(&s.id) == sketch_id.as_ref()))
+            .and_then(|sketch| sketch.post_flash_serial.clone());
+        self.pending_serial_send = command.map(|cmd| (cmd, std::time::Instant::now() + POST_FLASH_SEND_DELAY));
+    }
+
+    /// Writes a due post-flash line to the serial monitor, dropping it if the monitor stopped.
+    fn flush_pending_serial_send(&mut self, now: std::time::Instant) {
+        let Some((_, due)) = &self.pending_serial_send else { return; };
+        if !matches!(self.task_state, TaskState::Monitoring { .. }) {
+            self.pending_serial_send = None;
+            return;
+        }
+        if now < *due {
+            return;
+        }
+        if let (Some((command, _)), Some(serial_tx)) = (self.pending_serial_send.take(), &self.serial_tx) {
+            let _ = serial_tx.send(crate::commands::SerialCommand::SendData(command.clone()));
+            self.log("system", &format!("Sent post-flash command: {}", command));
+        }
+    }
+
+    /// Time since the running build started, or `None` when no build is active.
+    fn running_elapsed(&self) -> Option<std::time::Duration> {
+        match &self.task_state {
+            TaskState::Running { start_time, .. } => Some(start_time.elapsed()),
+            _ => None,
+        }
+    }
+
+    /// Announces a finished build according to `application.notify_on_complete`.
+    ///>
+    /// The bell is only flagged here; the main loop writes it after drawing so
+    /// it never interleaves with a frame. Desktop notifications are posted
+    /// independently when `application.desktop_notifications` is set.
+    ///<
+    fn notify_completion(&mut self, success: bool, operation: &str, elapsed: Option<std::time::Duration>) {
+        // Failures already raise an error toast through `report_error`
+        if success && self.config.application.notify_on_complete != crate::config::CompletionNotify::Off {
+            self.toast_manager.success(&format!("{} finished", operation));
+        }
+        self.signal_completion(success, operation, elapsed);
+    }
+
+    /// Posts the desktop notification, bell and flash for a finished task, without a toast.
+    fn signal_completion(&mut self, success: bool, operation: &str, elapsed: Option<std::time::Duration>) {
+        if self.config.application.desktop_notifications {
+            let sketch_id = self.get_current_sketch_id().unwrap_or_else(|| "default".to_string());
+            crate::commands::DesktopNotification::completion(operation, &sketch_id, success, elapsed.unwrap_or_default()).post();
+        }
+
+        let mode = self.config.application.notify_on_complete;
+        if mode == crate::config::CompletionNotify::Off {
+            return;
+        }
+        if mode.rings_bell() {
+            self.bell_pending = true;
+        }
+        if mode.flashes() {
+            let tint = if success { ratatui::style::Color::Green } else { ratatui::style::Color::Red };
+            self.completion_flash = Some((std::time::Instant::now(), tint));
+        }
+    }
+
+    /// Returns and clears the pending completion bell.
+    pub fn take_bell(&mut self) -> bool {
+        std::mem::take(&mut self.bell_pending)
+    }
+
+    /// Tint of the completion flash, if it is still running.
+    pub fn active_flash(&self) -> Option<ratatui::style::Color> {
+        self.completion_flash
+            .filter(|(started, _)| started.elapsed() < COMPLETION_FLASH)
+            .map(|(_, tint)| tint)
+    }
+
+    /// Buffer index of the output line still highlighted after a copy.
+    pub fn active_line_flash(&self) -> Option<usize> {
+        self.line_copy_flash
+            .filter(|(started, _)| started.elapsed() < LINE_COPY_FLASH)
+            .map(|(_, index)| index)
+    }
+
+    /// Fraction (0.0..1.0) of the tab transition played so far, if one is running.
+    pub fn tab_transition_progress(&self) -> Option<f32> {
+        self.tab_transition_frame.map(|frame| frame as f32 / TAB_TRANSITION_FRAMES as f32)
+    }
+
     /// Advances animations based on elapsed time.
     ///>
     /// This is called on every loop iteration to ensure that visual elements 
@@ -110,15 +395,29 @@ impl App {
         let dt = now.duration_since(self.last_frame_time).as_secs_f64();
         self.last_frame_time = now;
 
+        if self.completion_flash.is_some() && self.active_flash().is_none() {
+            self.completion_flash = None;
+            self.should_redraw = true;
+        }
+        if self.line_copy_flash.is_some() && self.active_line_flash().is_none() {
+            self.line_copy_flash = None;
+            self.should_redraw = true;
+        }
+        if let Some(frame) = self.tab_transition_frame {
+            let next = frame + 1;
+            self.tab_transition_frame = (next < TAB_TRANSITION_FRAMES).then_some(next);
+            self.should_redraw = true;
+        }
+
+        self.flush_pending_serial_send(now);
+
+        let factor = self.progress_smoothing();
         if let TaskState::Running { percentage, visual_percentage, .. } = &mut self.task_state {
             let target = *percentage;
             let current = *visual_percentage;
             
             if (target - current).abs() > 0.01 {
-                // Exponential decay: visual = visual + (target - visual) * (1 - exp(-speed * dt))
-                let speed = 5.0;
-                let factor = 1.0 - (-speed * dt).exp();
-                *visual_percentage = current + (target - current) * factor;
+                *visual_percentage = smooth_toward(current, target, factor, dt);
                 
                 // Snap if very close
                 if (target - *visual_percentage).abs() < 0.05 {
@@ -130,6 +429,9 @@ impl App {
 
     /// Returns true if any visual elements are still transitioning.
     pub fn is_animating(&self) -> bool {
+        if self.completion_flash.is_some() || self.line_copy_flash.is_some() || self.tab_transition_frame.is_some() {
+            return true;
+        }
         if let TaskState::Running { percentage, visual_percentage, .. } = &self.task_state {
             return (percentage - visual_percentage).abs() > 0.01;
         }
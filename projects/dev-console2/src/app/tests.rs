@@ -24,6 +24,47 @@ fn buffer_content(buffer: &Buffer) -> String {
     result
 }
 
+/// Command runner whose spawns fail without starting a process, reporting each command line to `spawned`.
+fn offline_runner(spawned: Option<mpsc::Sender<String>>) -> crate::commands::traits::MockCommandRunner {
+    let mut runner = crate::commands::traits::MockCommandRunner::new();
+    runner.expect_spawn().returning(move |command| {
+        if let Some(spawned) = &spawned {
+            let line = std::iter::once(command.get_program())
+                .chain(command.get_args())
+                .map(|part| part.to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+                .join(" ");
+            let _ = spawned.send(line);
+        }
+        Err(std::io::Error::new(std::io::ErrorKind::NotFound, "offline test runner"))
+    });
+    runner
+}
+
+/// Serial provider with no ports (monitor threads end at once), reporting each `port@baud` to `opened`.
+fn offline_serial(opened: Option<mpsc::Sender<String>>) -> crate::commands::traits::MockSerialProvider {
+    let mut provider = crate::commands::traits::MockSerialProvider::new();
+    provider.expect_open().returning(move |port, baud| {
+        if let Some(opened) = &opened {
+            let _ = opened.send(format!("{}@{}", port, baud));
+        }
+        Err(serialport::Error::new(serialport::ErrorKind::NoDevice, "offline test provider"))
+    });
+    provider
+}
+
+/// Clipboard that never touches the system clipboard, reporting each copied text to its sender.
+struct RecordingClipboard(Option<mpsc::Sender<String>>);
+
+impl crate::app::clipboard::ClipboardSink for RecordingClipboard {
+    fn set_text(&mut self, text: String) -> Result<(), String> {
+        if let Some(copied) = &self.0 {
+            let _ = copied.send(text);
+        }
+        Ok(())
+    }
+}
+
 /// Helper to create a minimal App state for unit testing
 fn create_test_app() -> App {
     let (tx, rx) = mpsc::channel();
@@ -93,6 +134,12 @@ fn create_test_app() -> App {
         icon_focused: false,
         output_lines: Vec::new(),
         output_cached_lines: Vec::new(),
+        output_verbose_flags: Vec::new(),
+        output_quiet: false,
+        output_filter: None,
+        filter_editing: false,
+        output_timestamps: false,
+        output_maximized: false,
         output_scroll: 0,
         output_scroll_interaction: crate::widgets::smooth_scrollbar::ScrollBarInteraction::default(),
         output_autoscroll: true,
@@ -126,12 +173,17 @@ fn create_test_app() -> App {
                 connection: "c1".to_string(),
                 device: "d1".to_string(),
                 mqtt: "m1".to_string(),
+                post_flash_serial: None,
             }],
         }),
         profile_config_path: "test_config.yaml".to_string(),
         selected_profile_index: 0,
         profile_ids: vec!["p1".to_string()],
         cancel_signal: Arc::new(AtomicBool::new(false)),
+        monitor_cancel: Arc::new(AtomicBool::new(false)),
+        command_runner: Arc::new(offline_runner(None)),
+        serial_provider: Arc::new(offline_serial(None)),
+        clipboard: Box::new(RecordingClipboard(None)),
         view_area: Rect::new(0, 0, 100, 50),
         layout: crate::app::AppLayout {
             settings: None,
@@ -144,7 +196,28 @@ fn create_test_app() -> App {
         should_redraw: false,
         dispatch_mode: DispatchMode::OnSelect,
         focus: Focus::Sidebar,
+        focus_stack: Vec::new(),
         modal: None,
+        trend_modal: None,
+        map_report_modal: None,
+        profile_switcher: None,
+        last_browser_dir: None,
+        monitor_paused_for_upload: false,
+        quit_pending: false,
+        erase_pending: false,
+        batch_run: None,
+        running_operation: crate::app::Operation::Compile,
+        bell_pending: false,
+        completion_flash: None,
+        line_copy_flash: None,
+        tab_transition_frame: None,
+        current_build_file: None,
+        pending_serial_send: None,
+        ui_state: crate::app::ui_state::UiState::default(),
+        last_output_path: std::env::temp_dir().join("dc2_test_last_output.log"),
+        ui_state_path: std::env::temp_dir().join("dc2_test_ui_state.json"),
+        app_log: crate::app::app_log::AppLog::disabled(),
+        history_view: Default::default(),
         input: tui_input::Input::default(),
         input_active: false,
         serial_tx: None,
@@ -170,6 +243,32 @@ fn press(code: KeyCode, mods: KeyModifiers) -> KeyEvent {
 mod dashboard {
     use super::*;
 
+    #[test]
+    fn test_reload_applies_config_and_keeps_active_tab() {
+        let mut app = create_test_app();
+        app.activate_tab(1);
+        let toast_duration = app.toast_manager.config.duration_seconds;
+
+        let mut config = app.config.clone();
+        config.application.min_width = 120;
+        config.tab_bars[0].tabs = vec![
+            crate::config::TabConfig { id: "dashboard".to_string(), name: "Dashboard".to_string(), default: Some("active".to_string()) },
+            crate::config::TabConfig { id: "profiles".to_string(), name: "Device Profiles".to_string(), default: None },
+        ];
+        let profiles = app.profile_config.clone().unwrap();
+
+        app.apply_config_reload(Ok(config), Err(color_eyre::eyre::eyre!("bad indent")), Ok(profiles));
+
+        assert_eq!(app.config.application.min_width, 120);
+        assert_eq!(app.tabs[1].name, "Device Profiles");
+        assert!(app.tabs[1].active && !app.tabs[0].active);
+        assert_eq!(app.get_current_sketch_id().as_deref(), Some("p1"));
+        // The failed widget config keeps its previous value
+        assert_eq!(app.toast_manager.config.duration_seconds, toast_duration);
+        let toast = &app.toast_manager.toasts.last().unwrap().message;
+        assert!(toast.contains("kept previous widget-config.yaml (bad indent)"));
+    }
+
     #[test]
     fn test_initialization() {
         let app = create_test_app();
@@ -208,6 +307,594 @@ mod dashboard {
         app.update(Message::SystemUpdate(ProgressUpdate::OutputLine(msg.to_string())));
         assert!(app.output_lines.iter().any(|l| l.contains(msg)));
     }
+
+    #[test]
+    fn test_auto_monitor_after_successful_upload() {
+        let upload_done = || ProgressUpdate::CompletedWithMetrics {
+            stage_times: [(crate::commands::predictor::CompileStage::Uploading, 3.0)].into_iter().collect(),
+        };
+        let opened_port = |opened: &mpsc::Receiver<String>| opened.recv_timeout(std::time::Duration::from_secs(5)).ok();
+
+        let mut app = create_test_app();
+        let (opened_tx, opened) = mpsc::channel();
+        app.serial_provider = Arc::new(offline_serial(Some(opened_tx)));
+        app.config.application.history_path = Some(std::env::temp_dir().join("dc2_auto_monitor_history.json").to_string_lossy().into_owned());
+        app.update(Message::SystemUpdate(upload_done()));
+        assert!(matches!(app.task_state, TaskState::Idle));
+
+        app.config.application.auto_monitor_after_upload = true;
+        app.update(Message::SystemUpdate(upload_done()));
+        assert!(matches!(app.task_state, TaskState::Monitoring { monitor_type: MonitorType::Serial, .. }));
+        assert!(app.output_lines.iter().any(|l| l.contains("Command completed successfully")));
+        // Only the chained monitor opened the profile's port
+        assert_eq!(opened_port(&opened).as_deref(), Some("COM1@115200"));
+        assert!(opened.try_recv().is_err());
+
+        // A failed upload never chains into the monitor
+        app.task_state = TaskState::Idle;
+        app.update(Message::SystemUpdate(ProgressUpdate::Failed("Upload failed".to_string())));
+        assert!(matches!(app.task_state, TaskState::Idle));
+        assert!(opened.recv_timeout(std::time::Duration::from_millis(100)).is_err());
+    }
+
+    #[test]
+    fn test_post_flash_serial_sent_once_after_auto_monitor() {
+        let mut app = create_test_app();
+        let (opened_tx, opened) = mpsc::channel();
+        app.serial_provider = Arc::new(offline_serial(Some(opened_tx)));
+        app.config.application.history_path = Some(std::env::temp_dir().join("dc2_post_flash_history.json").to_string_lossy().into_owned());
+        app.config.application.auto_monitor_after_upload = true;
+        app.profile_config.as_mut().unwrap().sketches[0].post_flash_serial = Some("cfg on".to_string());
+
+        app.update(Message::SystemUpdate(ProgressUpdate::CompletedWithMetrics {
+            stage_times: [(crate::commands::predictor::CompileStage::Uploading, 3.0)].into_iter().collect(),
+        }));
+        assert!(matches!(app.task_state, TaskState::Monitoring { monitor_type: MonitorType::Serial, .. }));
+        assert_eq!(app.pending_serial_send.as_ref().map(|(cmd, _)| cmd.as_str()), Some("cfg on"));
+        assert!(opened.recv_timeout(std::time::Duration::from_secs(5)).is_ok());
+
+        // Not written before the settle delay
+        let (tx, rx) = mpsc::channel();
+        app.serial_tx = Some(tx);
+        app.tick();
+        assert!(rx.try_recv().is_err());
+
+        app.pending_serial_send.as_mut().unwrap().1 = Instant::now();
+        app.tick();
+        app.tick();
+        assert!(matches!(rx.try_recv(), Ok(crate::commands::SerialCommand::SendData(cmd)) if cmd == "cfg on"));
+        assert!(rx.try_recv().is_err());
+        assert!(app.pending_serial_send.is_none());
+    }
+
+    #[test]
+    fn test_replace_line_overwrites_last_output_line() {
+        let mut app = create_test_app();
+        app.update(Message::SystemUpdate(ProgressUpdate::OutputLine("Writing at 0x1000 (10 %)".to_string())));
+        let count = app.output_lines.len();
+        app.update(Message::SystemUpdate(ProgressUpdate::ReplaceLine("Writing at 0x8000 (50 %)".to_string())));
+        app.update(Message::SystemUpdate(ProgressUpdate::ReplaceLine("Writing at 0xf000 (100 %)".to_string())));
+
+        assert_eq!(app.output_lines.len(), count);
+        assert!(app.output_lines.last().unwrap().contains("(100 %)"));
+        assert!(!app.output_lines.iter().any(|l| l.contains("(10 %)") || l.contains("(50 %)")));
+    }
+
+    #[test]
+    fn test_clear_status_restores_configured_default() {
+        let mut app = create_test_app();
+        app.config.application.status_bar.default_text = "Status: Idle".to_string();
+        app.status_text = "[Error] Port COM3 not found".to_string();
+
+        app.dispatch_command(Action::ClearStatus);
+        assert_eq!(app.status_text, "Status: Idle");
+
+        // Plain click on the status panel clears too
+        app.status_text = "[Error] Upload failed".to_string();
+        let status = app.layout.status;
+        app.update(Message::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: status.x + 1,
+            row: status.y + 1,
+            modifiers: KeyModifiers::empty(),
+        }));
+        assert_eq!(app.status_text, "Status: Idle");
+    }
+
+    #[test]
+    fn test_quit_while_monitoring_requires_confirmation() {
+        let mut app = create_test_app();
+        let (tx, rx) = mpsc::channel();
+        app.serial_tx = Some(tx);
+        app.task_state = TaskState::Monitoring { monitor_type: MonitorType::Serial, start_time: Instant::now() };
+
+        app.exec_quit();
+        assert!(app.running);
+        assert!(app.quit_pending);
+        assert!(app.status_text.contains("quit and disconnect?"));
+
+        // Declining keeps the monitor alive
+        app.update(Message::Key(press(KeyCode::Char('n'), KeyModifiers::empty())));
+        assert!(app.running);
+        assert!(!app.quit_pending);
+        assert!(rx.try_recv().is_err());
+
+        app.exec_quit();
+        app.update(Message::Key(press(KeyCode::Char('y'), KeyModifiers::empty())));
+        assert!(!app.running);
+        assert!(matches!(rx.try_recv(), Ok(crate::commands::SerialCommand::Stop)));
+    }
+
+    #[test]
+    fn test_erase_flash_requires_confirmation() {
+        let mut app = create_test_app();
+        app.dispatch_command(Action::EraseFlash);
+        assert!(app.erase_pending);
+        assert!(matches!(app.task_state, TaskState::Idle));
+        assert_eq!(app.status_text, "This erases all firmware and data on COM1. Continue? [y/n]");
+
+        // Any other key leaves the prompt up; declining never starts the erase
+        app.update(Message::Key(press(KeyCode::Char('x'), KeyModifiers::empty())));
+        assert!(app.erase_pending);
+        app.update(Message::Key(press(KeyCode::Char('n'), KeyModifiers::empty())));
+        assert!(!app.erase_pending);
+        assert!(matches!(app.task_state, TaskState::Idle));
+
+        // Non-ESP boards are refused outright
+        app.profile_config.as_mut().unwrap().devices[0].fbqn = "arduino:avr:uno".to_string();
+        app.dispatch_command(Action::EraseFlash);
+        assert!(!app.erase_pending);
+        assert!(app.status_text.starts_with("[Error] Erase Flash is only available for ESP boards"));
+    }
+
+    #[test]
+    fn test_quiet_startup_suppresses_info_toast_but_not_errors() {
+        use crate::app::announce_profile_load;
+        use crate::widgets::toast::{ToastConfig, ToastLevel, ToastManager};
+        let mut toasts = ToastManager::new(ToastConfig::default());
+        announce_profile_load(&mut toasts, true, "3 profiles loaded.", false);
+        assert_eq!(toasts.toasts.last().map(|t| t.level), Some(ToastLevel::Info));
+
+        let mut toasts = ToastManager::new(ToastConfig::default());
+        announce_profile_load(&mut toasts, true, "3 profiles loaded.", true);
+        assert!(toasts.toasts.is_empty());
+
+        announce_profile_load(&mut toasts, false, "[Error] Failed to load config.yaml", true);
+        assert_eq!(toasts.toasts.last().map(|t| t.level), Some(ToastLevel::Error));
+
+        // Quiet unless the config opts back in
+        let unset: crate::config::ApplicationConfig = serde_saphyr::from_str("title: Dev Console").unwrap();
+        assert!(unset.quiet_startup);
+    }
+
+    #[test]
+    fn test_completion_notification_toasts_when_enabled() {
+        use crate::widgets::toast::ToastLevel;
+        let compile_done = || ProgressUpdate::CompletedWithMetrics {
+            stage_times: [(crate::commands::predictor::CompileStage::Compiling, 3.0)].into_iter().collect(),
+        };
+
+        let mut app = create_test_app();
+        app.config.application.history_path = Some(std::env::temp_dir().join("dc2_notify_history.json").to_string_lossy().into_owned());
+        app.update(Message::SystemUpdate(compile_done()));
+        assert!(app.toast_manager.toasts.is_empty());
+
+        app.config.application.notify_on_complete = crate::config::CompletionNotify::Both;
+        app.update(Message::SystemUpdate(compile_done()));
+        assert_eq!(app.toast_manager.toasts.last().map(|t| t.level), Some(ToastLevel::Success));
+        assert!(app.take_bell());
+        assert!(!app.take_bell());
+        assert_eq!(app.active_flash(), Some(Color::Green));
+
+        let now = Instant::now();
+        app.task_state = TaskState::Running {
+            percentage: 40.0, visual_percentage: 40.0, last_percentage: 40.0, stage: "Compiling".to_string(),
+            start_time: now, last_updated: now, smoothed_eta: None,
+        };
+        app.update(Message::SystemUpdate(ProgressUpdate::Failed("Compilation failed".to_string())));
+        assert_eq!(app.toast_manager.toasts.last().map(|t| t.level), Some(ToastLevel::Error));
+        assert_eq!(app.active_flash(), Some(Color::Red));
+    }
+
+    #[test]
+    fn test_failed_upload_is_labelled_as_upload() {
+        let mut app = create_test_app();
+        app.exec_upload();
+        assert_eq!(app.running_operation, crate::app::Operation::Upload);
+        assert_eq!(app.running_operation.label(), "Upload");
+
+        app.task_state = TaskState::Idle;
+        app.exec_compile();
+        assert_eq!(app.running_operation.label(), "Build");
+    }
+
+    #[test]
+    fn test_failed_upload_is_recorded_as_upload() {
+        let mut app = create_test_app();
+        app.config.application.history_path = Some(std::env::temp_dir().join("dc2_failed_upload_history.json").to_string_lossy().into_owned());
+        let _ = std::fs::remove_file(app.history_path());
+        app.exec_upload();
+
+        app.update(Message::SystemUpdate(ProgressUpdate::Failed("Upload failed".to_string())));
+        let history = crate::commands::HistoryManager::load(&app.history_path());
+        assert_eq!(history.builds.last().map(|b| b.command.as_str()), Some("Upload"));
+        assert!(!history.builds.last().unwrap().success);
+    }
+
+    #[test]
+    fn test_compile_all_tracks_each_sketch() {
+        let mut app = create_test_app();
+        app.config.application.history_path = Some(std::env::temp_dir().join("dc2_compile_all_history.json").to_string_lossy().into_owned());
+        let _ = std::fs::remove_file(app.history_path());
+        let now = Instant::now();
+        app.task_state = TaskState::Running {
+            percentage: 0.0, visual_percentage: 0.0, last_percentage: 0.0, stage: "Initializing Compile...".to_string(),
+            start_time: now, last_updated: now, smoothed_eta: None,
+        };
+        app.batch_run = Some(crate::app::BatchRun { expected_secs: vec![30.0, 60.0], current: None, profile_started: now });
+
+        app.update(Message::SystemUpdate(ProgressUpdate::ProfileStarted { index: 1, total: 2, id: "beta".to_string() }));
+        app.update(Message::SystemUpdate(ProgressUpdate::Stage("Linking".to_string())));
+        app.update(Message::SystemUpdate(ProgressUpdate::Percentage(50.0)));
+        let TaskState::Running { percentage, stage, .. } = &app.task_state else { panic!("batch must keep running") };
+        assert_eq!((*percentage, stage.as_str()), (75.0, "[2/2] Linking"));
+        assert_eq!(app.predictor.current_stage(), crate::commands::predictor::CompileStage::Linking);
+
+        app.update(Message::SystemUpdate(ProgressUpdate::CompletedWithMetrics {
+            stage_times: [(crate::commands::predictor::CompileStage::Linking, 4.0)].into_iter().collect(),
+        }));
+        assert!(matches!(app.task_state, TaskState::Running { .. }));
+        let manager = crate::commands::HistoryManager::load(&app.history_path());
+        assert!(manager.get_stats("beta").is_some());
+        let _ = std::fs::remove_file(app.history_path());
+    }
+
+    #[test]
+    fn test_compile_all_summary_toast() {
+        use crate::widgets::toast::ToastLevel;
+        let mut app = create_test_app();
+        let now = Instant::now();
+        app.task_state = TaskState::Running {
+            percentage: 80.0, visual_percentage: 80.0, last_percentage: 80.0, stage: "[5/5] Linking".to_string(),
+            start_time: now, last_updated: now, smoothed_eta: None,
+        };
+        app.update(Message::SystemUpdate(ProgressUpdate::BatchCompleted { succeeded: 4, failed: 1 }));
+        assert!(matches!(app.task_state, TaskState::Idle));
+        assert_eq!(app.status_text, "All Profiles: 4 ok, 1 failed.");
+        let toast = app.toast_manager.toasts.last().unwrap();
+        assert_eq!((toast.message.as_str(), toast.level), ("4 ok, 1 failed", ToastLevel::Warning));
+
+        app.update(Message::SystemUpdate(ProgressUpdate::BatchCompleted { succeeded: 5, failed: 0 }));
+        assert_eq!(app.toast_manager.toasts.last().map(|t| t.level), Some(ToastLevel::Success));
+    }
+
+    #[test]
+    fn test_compact_layout_between_thresholds() {
+        use crate::app::LayoutMode;
+        let mut app = create_test_app();
+        app.config.application.min_width = 80;
+        app.config.application.min_height = 21;
+        app.config.application.compact_min_width = 40;
+        app.config.application.compact_min_height = 16;
+
+        assert_eq!(app.layout_mode(Rect::new(0, 0, 100, 30)), LayoutMode::Full);
+        assert_eq!(app.layout_mode(Rect::new(0, 0, 30, 30)), LayoutMode::TooSmall);
+
+        let area = Rect::new(0, 0, 60, 20);
+        assert_eq!(app.layout_mode(area), LayoutMode::Compact);
+        app.check_terminal_size(area);
+        assert!(!app.terminal_too_small);
+
+        let layout = app.calculate_layout(area);
+        assert_eq!(layout.commands.x, layout.output.x);
+        assert!(layout.commands.bottom() <= layout.status.y);
+        assert!(layout.status.bottom() <= layout.output.y);
+        assert_eq!(layout.profile, Rect::default());
+    }
+
+    #[test]
+    fn test_maximized_output_fills_content_area_and_restores_split() {
+        let mut app = create_test_app();
+        app.output_autoscroll = false;
+        app.output_scroll = 3;
+        let split = app.layout;
+
+        app.dispatch_command(Action::ToggleOutputMaximized);
+        // Full bordered content area: left edge of the commands column, bottom of the output
+        assert_eq!(app.layout.output.x, split.profile.x);
+        assert_eq!(app.layout.output.right(), split.output.right());
+        assert_eq!(app.layout.output.bottom(), split.output.bottom());
+        assert!(app.layout.output.y <= split.profile.y);
+        assert_eq!(app.layout.commands, Rect::default());
+        assert_eq!(app.layout.status, Rect::default());
+        assert_eq!(app.output_scroll, 3);
+
+        app.dispatch_command(Action::ToggleOutputMaximized);
+        assert_eq!(app.layout.output, split.output);
+        assert_eq!(app.layout.commands, split.commands);
+        assert_eq!(app.layout.status, split.status);
+        assert_eq!(app.output_scroll, 3);
+    }
+
+    #[test]
+    fn test_output_timestamp_reflects_elapsed_time() {
+        assert_eq!(crate::app::timestamp_prefix(std::time::Duration::from_millis(1230)), "[+1.23s] ");
+
+        let mut app = create_test_app();
+        let start = Instant::now() - std::time::Duration::from_millis(1230);
+        app.task_state = TaskState::Monitoring { monitor_type: MonitorType::Serial, start_time: start };
+        app.update(Message::SystemUpdate(ProgressUpdate::OutputLine("plain".to_string())));
+        assert!(!app.output_lines.last().unwrap().contains("[+"));
+
+        app.exec_toggle_timestamps();
+        assert!(app.output_timestamps);
+        app.update(Message::SystemUpdate(ProgressUpdate::OutputLine("stamped".to_string())));
+        let last = app.output_lines.last().unwrap();
+        assert!(last.starts_with("[+1.2"), "unexpected prefix: {}", last);
+        assert!(last.contains("stamped"));
+    }
+
+    #[test]
+    fn test_replaced_line_on_empty_buffer_is_stamped_once() {
+        let mut app = create_test_app();
+        let start = Instant::now() - std::time::Duration::from_millis(1230);
+        app.task_state = TaskState::Monitoring { monitor_type: MonitorType::Serial, start_time: start };
+        app.exec_toggle_timestamps();
+        assert!(app.output_lines.is_empty());
+
+        app.update(Message::SystemUpdate(ProgressUpdate::ReplaceLine("progress 10%".to_string())));
+        assert_eq!(app.output_lines.len(), 1);
+        assert_eq!(app.output_lines[0].matches("[+").count(), 1, "stamped twice: {}", app.output_lines[0]);
+
+        app.update(Message::SystemUpdate(ProgressUpdate::ReplaceLine("progress 20%".to_string())));
+        assert_eq!(app.output_lines.len(), 1);
+        assert_eq!(app.output_lines[0].matches("[+").count(), 1, "stamped twice: {}", app.output_lines[0]);
+        assert!(app.output_lines[0].contains("progress 20%"));
+    }
+
+    #[test]
+    fn test_monitor_paused_during_upload_and_resumed_after() {
+        use crate::commands::SerialCommand;
+        let mut app = create_test_app();
+        app.config.application.history_path = Some(std::env::temp_dir().join("dc2_pause_monitor_history.json").to_string_lossy().into_owned());
+        app.config.application.pause_monitor_during_upload = true;
+        // A pre-build hook is the first thing the upload thread spawns
+        app.config.application.hooks.pre_build = vec!["prepare-board".to_string()];
+        let (spawned_tx, spawned_rx) = mpsc::channel();
+        app.command_runner = Arc::new(offline_runner(Some(spawned_tx)));
+        let (tx, rx) = mpsc::channel();
+        app.serial_tx = Some(tx);
+        app.task_state = TaskState::Monitoring { monitor_type: MonitorType::Serial, start_time: Instant::now() };
+
+        app.exec_upload();
+        let Ok(SerialCommand::Pause(released)) = rx.try_recv() else { panic!("monitor was not paused") };
+        assert!(app.monitor_paused_for_upload);
+        assert!(matches!(app.task_state, TaskState::Running { .. }));
+
+        // Nothing runs until the monitor has released the port
+        assert!(spawned_rx.recv_timeout(std::time::Duration::from_millis(200)).is_err());
+        released.send(()).unwrap();
+        let spawned = spawned_rx.recv_timeout(std::time::Duration::from_secs(5)).unwrap();
+        assert!(spawned.ends_with("prepare-board"), "{}", spawned);
+
+        // Cancelling the upload leaves the paused monitor alive
+        app.exec_cancel();
+        assert!(app.cancel_signal.load(Ordering::SeqCst));
+        assert!(!app.monitor_cancel.load(Ordering::SeqCst));
+
+        app.update(Message::SystemUpdate(ProgressUpdate::CompletedWithMetrics {
+            stage_times: [(crate::commands::predictor::CompileStage::Uploading, 3.0)].into_iter().collect(),
+        }));
+        assert!(matches!(rx.try_recv(), Ok(SerialCommand::Resume)));
+        assert!(!app.monitor_paused_for_upload);
+        assert!(matches!(app.task_state, TaskState::Monitoring { monitor_type: MonitorType::Serial, .. }));
+    }
+
+    #[test]
+    fn test_autoscroll_default_applied_per_command_type() {
+        let mut app = create_test_app();
+        let (opened_tx, opened) = mpsc::channel();
+        app.serial_provider = Arc::new(offline_serial(Some(opened_tx)));
+        let (spawned_tx, spawned) = mpsc::channel();
+        app.command_runner = Arc::new(offline_runner(Some(spawned_tx)));
+        app.config.application.history_path = Some(std::env::temp_dir().join("dc2_autoscroll_history.json").to_string_lossy().into_owned());
+        app.config.application.autoscroll.monitor = false;
+
+        app.exec_monitor_serial();
+        assert!(!app.output_autoscroll);
+        assert!(opened.recv_timeout(std::time::Duration::from_secs(5)).is_ok());
+
+        app.task_state = TaskState::Idle;
+        app.exec_compile();
+        assert!(app.output_autoscroll);
+        // The test sketch does not exist, so the compile stops before spawning anything
+        assert!(spawned.recv_timeout(std::time::Duration::from_millis(100)).is_err());
+    }
+
+    #[test]
+    fn test_tab_binding_button_click_dispatches_action() {
+        let mut app = create_test_app();
+        let mut dashboard_bindings = crate::config::BindingsConfig { buttons: true, ..Default::default() };
+        dashboard_bindings.items.push(crate::config::BindingConfig {
+            key: "[v]".to_string(),
+            description: "Quiet".to_string(),
+            triggers: [("v".to_string(), "toggle_quiet".to_string())].into_iter().collect(),
+        });
+        app.tab_bar_map.get_mut("MainContentTabBar").unwrap()
+            .tab_bindings.insert("dashboard".to_string(), dashboard_bindings);
+        let area = app.layout.bindings;
+        assert!(!app.output_quiet);
+
+        app.dispatch_mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: area.x + 1,
+            row: area.y,
+            modifiers: KeyModifiers::empty(),
+        });
+        assert!(app.output_quiet);
+    }
+
+    #[test]
+    fn test_quiet_mode_hides_raw_toolchain_lines() {
+        let mut app = create_test_app();
+        let gcc_line = "/opt/xtensa-esp32s3-elf-g++ -MMD -c -Os -DF_CPU=240000000L -o build/main.cpp.o main.cpp";
+        app.update(Message::SystemUpdate(ProgressUpdate::OutputLine(gcc_line.to_string())));
+        app.update(Message::SystemUpdate(ProgressUpdate::OutputLine("Compiling main.cpp".to_string())));
+
+        assert_eq!(app.visible_output_len(), 2);
+
+        app.exec_toggle_quiet();
+        let visible = app.visible_output_lines();
+        assert_eq!(visible.len(), 1);
+        assert!(visible[0].contains("Compiling main.cpp"));
+        assert!(!visible.iter().any(|l| l.contains("-MMD")));
+
+        // Verbose again reveals the buffered line
+        app.exec_toggle_quiet();
+        assert_eq!(app.visible_output_len(), 2);
+    }
+
+    #[test]
+    fn test_persisted_output_restored_on_startup_when_enabled() {
+        let mut app = create_test_app();
+        app.last_output_path = std::env::temp_dir().join("dc2_persisted_last_output.log");
+        app.config.application.persist_last_output = true;
+        for line in ["Compiling main.cpp", "error: 'foo' was not declared"] {
+            app.update(Message::SystemUpdate(ProgressUpdate::OutputLine(line.to_string())));
+        }
+        app.persist_last_output();
+
+        // A fresh session with the flag off starts empty
+        let mut next = create_test_app();
+        next.last_output_path = app.last_output_path.clone();
+        next.restore_last_output();
+        assert!(next.output_lines.is_empty());
+
+        next.config.application.persist_last_output = true;
+        next.restore_last_output();
+        assert_eq!(next.output_lines[0], crate::app::last_output::RESTORED_HEADER);
+        assert_eq!(next.output_lines[1..], app.output_lines[..]);
+        assert!(next.output_lines[2].contains("'foo' was not declared"));
+        let _ = std::fs::remove_file(&app.last_output_path);
+    }
+
+    #[test]
+    fn test_alt_click_copies_the_clicked_output_line() {
+        let mut app = create_test_app();
+        let (copied_tx, copied_rx) = mpsc::channel();
+        app.clipboard = Box::new(RecordingClipboard(Some(copied_tx)));
+        app.output_autoscroll = false;
+        let gcc_line = "/opt/xtensa-esp32s3-elf-g++ -MMD -c -Os -o build/main.cpp.o main.cpp";
+        for line in ["line 0", gcc_line, "line 1", "line 2", "\x1b[31mline 3\x1b[0m", "line 4"] {
+            app.update(Message::SystemUpdate(ProgressUpdate::OutputLine(line.to_string())));
+        }
+        app.exec_toggle_quiet();
+        app.output_scroll = 1;
+
+        // Third visible row shows "line 3": scrolled past "line 0", with the gcc line hidden
+        let inner = ratatui::widgets::Block::bordered().inner(app.layout.output);
+        assert!(inner.height >= 3);
+        app.dispatch_mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: inner.x + 2,
+            row: inner.y + 2,
+            modifiers: KeyModifiers::ALT,
+        });
+        assert_eq!(app.output_line_at(inner.y + 2), Some(4));
+        assert_eq!(app.active_line_flash(), Some(4));
+        assert_eq!(copied_rx.try_recv().as_deref(), Ok("line 3"));
+        assert!(copied_rx.try_recv().is_err());
+        assert!(app.is_animating());
+    }
+
+    #[test]
+    fn test_output_filter_survives_tab_switch() {
+        let mut app = create_test_app();
+        for line in ["Compiling main.cpp", "warning: unused variable", "Linking firmware"] {
+            app.update(Message::SystemUpdate(ProgressUpdate::OutputLine(line.to_string())));
+        }
+
+        app.exec_filter_output();
+        for c in "WARN".chars() {
+            app.update(Message::Key(press(KeyCode::Char(c), KeyModifiers::empty())));
+        }
+        app.update(Message::Key(press(KeyCode::Enter, KeyModifiers::empty())));
+        assert_eq!(app.output_filter.as_deref(), Some("WARN"));
+        let visible = app.visible_output_lines();
+        assert_eq!(visible.len(), 1);
+        assert!(visible[0].contains("warning: unused variable"));
+
+        app.exec_next_tab();
+        app.exec_prev_tab();
+        assert_eq!(app.output_filter.as_deref(), Some("WARN"));
+        assert_eq!(app.visible_output_len(), 1);
+
+        app.exec_clear_output_filter();
+        assert_eq!(app.visible_output_len(), 3);
+    }
+
+    #[test]
+    fn test_progress_smoothing_advances_by_lerp_factor() {
+        use crate::app::system::smooth_toward;
+
+        // One reference tick closes exactly `factor` of the gap
+        assert!((smooth_toward(0.0, 100.0, 0.25, 0.05) - 25.0).abs() < 1e-9);
+        assert!((smooth_toward(25.0, 100.0, 0.25, 0.05) - 43.75).abs() < 1e-9);
+        // Two ticks' worth of time in one frame lands at the same place
+        assert!((smooth_toward(0.0, 100.0, 0.25, 0.10) - 43.75).abs() < 1e-9);
+        // A factor of 1.0 snaps straight to the target
+        assert_eq!(smooth_toward(10.0, 80.0, 1.0, 0.05), 80.0);
+
+        let mut app = create_test_app();
+        app.config.application.progress_smoothing = 5.0;
+        assert_eq!(app.progress_smoothing(), 1.0);
+        app.config.application.progress_smoothing = 0.0;
+        assert_eq!(app.progress_smoothing(), 0.02);
+    }
+
+    #[test]
+    fn test_output_trimming_honors_configured_cap() {
+        let mut app = create_test_app();
+        app.config.application.max_output_lines = 150;
+        for i in 0..200 {
+            app.log("board", &format!("line {}", i));
+        }
+        assert_eq!(app.output_lines.len(), 150);
+        assert_eq!(app.output_cached_lines.len(), 150);
+        assert!(app.output_lines[0].contains("line 50"));
+
+        // Values below the floor are clamped
+        app.config.application.max_output_lines = 10;
+        app.log("board", "one more");
+        assert_eq!(app.output_lines.len(), 100);
+    }
+
+    #[test]
+    fn test_configured_command_icon_prefixes_name() {
+        use crate::app::theme::{Theme, ThemeConfig};
+        use crate::widgets::selection_list::SelectionListWidget;
+
+        let config: ThemeConfig = serde_saphyr::from_str(
+            "command_icons:\n  Upload: { icon: \"⮉\", ascii: \"^\", style: \"green\" }\n  Compile: { icon: \"⚒\" }\n"
+        ).unwrap();
+        let commands = vec!["Compile".to_string(), "Upload".to_string(), "Clean".to_string()];
+        let row = |theme: &Theme, y: u16| {
+            let mut buf = Buffer::empty(Rect::new(0, 0, 20, 3));
+            let icons = commands.iter().map(|c| theme.command_icon(c)).collect();
+            ratatui::widgets::Widget::render(SelectionListWidget::new(&commands, 0, None).icons(icons), buf.area, &mut buf);
+            (0..20).map(|x| buf[(x, y)].symbol().to_string()).collect::<String>()
+        };
+
+        let theme = Theme::new(&config);
+        assert!(row(&theme, 1).starts_with("  ⮉ Upload"));
+        assert!(row(&theme, 2).starts_with("  Clean"));
+        assert_eq!(theme.command_icon("Upload").unwrap().1.fg, Some(Color::Green));
+
+        // ASCII mode swaps in the fallback and drops glyphs that have none
+        let theme = Theme::new(&ThemeConfig { ascii_icons: true, ..config });
+        assert!(row(&theme, 1).starts_with("  ^ Upload"));
+        assert!(row(&theme, 0).starts_with("  Compile"));
+    }
 }
 
 /// --------------------------------------------------------------------------- 
@@ -226,6 +913,35 @@ mod profiles {
         app
     }
 
+    #[test]
+    fn test_select_profile_by_id() {
+        let mut app = create_test_app();
+        app.profile_ids.push("p2".to_string());
+
+        app.select_profile("p2").unwrap();
+        assert_eq!(app.get_current_sketch_id().as_deref(), Some("p2"));
+
+        let err = app.select_profile("nope").unwrap_err().to_string();
+        assert!(err.contains("p1, p2"));
+        assert_eq!(app.selected_profile_index, 1);
+    }
+
+    #[test]
+    fn test_profile_switcher_filters_and_loads_selection() {
+        let mut app = create_test_app();
+        app.profile_ids.extend(["esp32-s3-led".to_string(), "c3-sensor".to_string()]);
+
+        app.exec_open_profile_switcher();
+        for c in "s3".chars() {
+            app.update(Message::Key(press(KeyCode::Char(c), KeyModifiers::empty())));
+        }
+        assert_eq!(app.profile_switcher.as_ref().unwrap().content.matches(), ["esp32-s3-led".to_string()]);
+
+        app.update(Message::Key(press(KeyCode::Enter, KeyModifiers::empty())));
+        assert!(app.profile_switcher.is_none());
+        assert_eq!(app.get_current_sketch_id().as_deref(), Some("esp32-s3-led"));
+    }
+
     #[test]
     fn test_category_navigation() {
         let mut app = setup_profiles_tab();
@@ -344,6 +1060,32 @@ mod profiles {
         assert!(!app.icon_focused);
         assert!(app.input_active); // Should trigger edit on click
     }
+
+    #[test]
+    fn test_export_diagnostics_redacts_password_and_home() {
+        use crate::app::diagnostics::{export_bundle, SessionSnapshot, REDACTED};
+
+        let mut app = create_test_app();
+        let config = app.profile_config.as_mut().unwrap();
+        config.mqtt[0].password = "hunter2".to_string();
+        config.sketches[0].path = "/home/alice/sketches/test.ino".to_string();
+        let output = vec!["Compiling /home/alice/sketches/test.ino".to_string(), "Done".to_string()];
+
+        let dir = std::env::temp_dir().join("dc2_test_diagnostics");
+        let _ = std::fs::remove_dir_all(&dir);
+        let snapshot = SessionSnapshot { profiles: app.profile_config.as_ref(), output: &output, ..Default::default() };
+        let bundle = export_bundle(&dir, &snapshot, Some("/home/alice/")).unwrap();
+
+        let settings = std::fs::read_to_string(bundle.join("settings.yaml")).unwrap();
+        assert!(!settings.contains("hunter2"));
+        assert!(settings.contains(REDACTED));
+        assert!(settings.contains("~/sketches/test.ino"));
+        let output = std::fs::read_to_string(bundle.join("output.log")).unwrap();
+        assert_eq!(output, "Compiling ~/sketches/test.ino\nDone");
+        assert!(bundle.join("history.json").is_file());
+        assert!(!bundle.join("app.log").exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
 
 /// --------------------------------------------------------------------------- 
@@ -361,6 +1103,59 @@ mod modals {
         app.modal = Some(Popup::new(browser, "MODAL".to_string()));
     }
 
+    #[test]
+    fn test_modal_captures_keys_and_restores_focus() {
+        let mut app = create_test_app();
+        app.profile_ids.push("esp32-s3-led".to_string());
+        app.focus = Focus::Content;
+
+        app.exec_open_profile_switcher();
+        assert_eq!(app.focus, Focus::Modal);
+
+        // Tab would toggle focus underneath; the switcher takes it instead
+        app.update(Message::Key(press(KeyCode::Tab, KeyModifiers::empty())));
+        app.update(Message::Key(press(KeyCode::Char('x'), KeyModifiers::empty())));
+        assert_eq!(app.focus, Focus::Modal);
+        assert_eq!(app.profile_switcher.as_ref().unwrap().content.matches().len(), 0);
+
+        // The first Esc clears the query, the second closes
+        app.update(Message::Key(press(KeyCode::Esc, KeyModifiers::empty())));
+        assert_eq!(app.profile_switcher.as_ref().unwrap().content.matches().len(), 2);
+        assert_eq!(app.focus, Focus::Modal);
+        app.update(Message::Key(press(KeyCode::Esc, KeyModifiers::empty())));
+        assert!(app.profile_switcher.is_none());
+        assert_eq!(app.focus, Focus::Content);
+        assert!(app.focus_stack.is_empty());
+    }
+
+    #[test]
+    fn test_file_browser_reopens_in_last_directory() {
+        let base = std::env::temp_dir().join("dc2_last_browser_dir");
+        let picked = base.join("sketches").join("blink");
+        std::fs::create_dir_all(&picked).unwrap();
+
+        let mut app = create_test_app();
+        app.selected_field_index = 1;
+        app.config.application.sketch_root = Some(base.to_string_lossy().into_owned());
+
+        // First open starts at the configured root, then the user descends and cancels
+        app.exec_settings_action();
+        assert_eq!(app.modal.as_ref().unwrap().content.current_dir, base);
+        app.modal.as_mut().unwrap().content = FileBrowser::new(picked.clone());
+        app.update(Message::Key(press(KeyCode::Esc, KeyModifiers::empty())));
+        assert!(app.modal.is_none());
+
+        app.exec_settings_action();
+        assert_eq!(app.modal.as_ref().unwrap().content.current_dir, picked);
+
+        // Ctrl+R forgets the remembered directory
+        app.update(Message::Key(press(KeyCode::Char('r'), KeyModifiers::CONTROL)));
+        assert!(app.last_browser_dir.is_none());
+        assert_eq!(app.modal.as_ref().unwrap().content.current_dir, base);
+
+        let _ = std::fs::remove_dir_all(&base);
+    }
+
     #[test]
     fn test_modal_blocks_tab_switching() {
         let mut app = create_test_app();
@@ -436,6 +1231,42 @@ mod navigation {
         assert_eq!(app.focus, Focus::Sidebar);
     }
 
+    #[test]
+    fn test_alt_accelerator_activates_tab() {
+        let mut app = create_test_app();
+        app.tab_bar_map.get_mut("MainContentTabBar").unwrap().accelerators = true;
+
+        app.update(Message::Key(press(KeyCode::Char('p'), KeyModifiers::ALT)));
+        assert!(app.tabs[1].active && !app.tabs[0].active);
+
+        app.update(Message::Key(press(KeyCode::Char('D'), KeyModifiers::ALT)));
+        assert!(app.tabs[0].active && !app.tabs[1].active);
+    }
+
+    #[test]
+    fn test_tab_transition_advances_per_tick_and_completes() {
+        let mut app = create_test_app();
+        app.exec_next_tab();
+        assert_eq!(app.tab_transition_progress(), None);
+        assert!(!app.is_animating());
+
+        app.config.application.tab_transition = crate::config::TabTransition::Fade;
+        app.exec_next_tab();
+        assert_eq!(app.tab_transition_progress(), Some(0.0));
+        assert!(app.is_animating());
+
+        let mut last = 0.0;
+        while app.tab_transition_progress().is_some() {
+            app.tick();
+            if let Some(next) = app.tab_transition_progress() {
+                assert!(next > last);
+                last = next;
+            }
+        }
+        assert!(last > 0.5);
+        assert!(!app.is_animating());
+    }
+
     #[test]
     fn test_sidebar_content_transition_via_tab() {
         let mut app = create_test_app();
@@ -8,6 +8,23 @@ pub struct ThemeConfig {
     pub styles: HashMap<String, String>,
     #[serde(default)]
     pub message_types: HashMap<String, MessageTypeConfig>,
+    /// Glyph shown before each command name in the command list, keyed by command label.
+    #[serde(default)]
+    pub command_icons: HashMap<String, CommandIconConfig>,
+    /// Use each command icon's `ascii` fallback (for terminals without a nerd font).
+    #[serde(default)]
+    pub ascii_icons: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct CommandIconConfig {
+    pub icon: String,
+    /// Plain-ASCII replacement used when `ascii_icons` is on; no glyph is shown without one.
+    #[serde(default)]
+    pub ascii: Option<String>,
+    /// Defaults to the `commands_icon` style.
+    #[serde(default)]
+    pub style: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
@@ -21,6 +38,8 @@ pub struct MessageTypeConfig {
 pub struct Theme {
     resolved: HashMap<String, Style>,
     message_templates: HashMap<String, MessageTypeConfig>,
+    command_icons: HashMap<String, CommandIconConfig>,
+    ascii_icons: bool,
 }
 
 impl Theme {
@@ -32,6 +51,8 @@ impl Theme {
         Self { 
             resolved,
             message_templates: config.message_types.clone(),
+            command_icons: config.command_icons.clone(),
+            ascii_icons: config.ascii_icons,
         }
     }
 }
@@ -72,6 +93,14 @@ impl Theme {
         )
     }
 
+    /// Glyph and style shown before `command` in the command list, if one is configured.
+    pub fn command_icon(&self, command: &str) -> Option<(String, Style)> {
+        let config = self.command_icons.get(command)?;
+        let icon = if self.ascii_icons { config.ascii.clone()? } else { config.icon.clone() };
+        let style = config.style.as_deref().map(parse_style).unwrap_or_else(|| self.style("commands_icon"));
+        Some((icon, style))
+    }
+
     pub fn style(&self, name: &str) -> Style {
         self.resolved.get(name).cloned().unwrap_or_else(|| {
             // Provide sensible defaults if the key is missing from YAML
@@ -0,0 +1,110 @@
+use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Default UI state location, relative to the working directory.
+pub const DEFAULT_UI_STATE_PATH: &str = ".dev-console/ui_state.json";
+
+/// Persisted UI state that survives restarts.
+///>
+/// Unlike `config.yaml`, this file is written by the application itself and
+/// holds per-profile bookkeeping such as when each board was last flashed.
+/// Missing or malformed files load as an empty state.
+///<
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct UiState {
+    /// Profile id -> Unix timestamp (seconds) of the last successful upload.
+    #[serde(default)]
+    pub last_flashed: HashMap<String, u64>,
+}
+
+impl UiState {
+    /// Loads UI state from a JSON file.
+    pub fn load(path: &Path) -> Self {
+        if let Ok(content) = fs::read_to_string(path) { //>
+            serde_json::from_str(&content).unwrap_or_default()
+        } else {
+            Self::default()
+        } //<
+    }
+
+    /// Persists UI state to a JSON file.
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(parent) = path.parent() { //>
+            fs::create_dir_all(parent)?;
+        } //<
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Records a successful upload for the given profile.
+    pub fn record_flash(&mut self, profile_id: &str, timestamp: u64) {
+        self.last_flashed.insert(profile_id.to_string(), timestamp);
+    }
+}
+
+/// Current time as seconds since the Unix epoch.
+pub fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// Formats a past timestamp relative to `now`.
+///>
+/// Produces "just now" under a minute, "Nm ago" under an hour, "Nh ago"
+/// under a day, and an ISO date (`YYYY-MM-DD`, UTC) beyond that.
+///<
+pub fn format_relative_time(timestamp: u64, now: u64) -> String {
+    let elapsed = now.saturating_sub(timestamp);
+    match elapsed {
+        0..=59 => "just now".to_string(),
+        60..=3599 => format!("{}m ago", elapsed / 60),
+        3600..=86399 => format!("{}h ago", elapsed / 3600),
+        _ => {
+            let (year, month, day) = civil_from_days((timestamp / 86400) as i64);
+            format!("{:04}-{:02}-{:02}", year, month, day)
+        }
+    }
+}
+
+/// Converts days since the Unix epoch into a (year, month, day) civil date.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_relative_time() {
+        let now = 1_760_000_000;
+        assert_eq!(format_relative_time(now, now), "just now");
+        assert_eq!(format_relative_time(now - 59, now), "just now");
+        assert_eq!(format_relative_time(now - 60, now), "1m ago");
+        assert_eq!(format_relative_time(now - 45 * 60, now), "45m ago");
+        assert_eq!(format_relative_time(now - 2 * 3600, now), "2h ago");
+        assert_eq!(format_relative_time(now - 23 * 3600 - 59, now), "23h ago");
+        // Beyond a day the absolute date is shown (1_760_000_000 is 2025-10-09 UTC)
+        assert_eq!(format_relative_time(now - 3 * 86400, now), "2025-10-06");
+        // Clock skew (timestamp in the future) is treated as just now
+        assert_eq!(format_relative_time(now + 30, now), "just now");
+    }
+
+    #[test]
+    fn test_civil_from_days() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(19_723), (2024, 1, 1));
+    }
+}
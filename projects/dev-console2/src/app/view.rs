@@ -6,7 +6,8 @@ use ratatui::{
     Frame,
 };
 use crate::app::{App, AppLayout, TaskState};
-use crate::widgets::tab_bar::{TabBarItem, TabBarWidget};
+use crate::widgets::tab_bar::TabBarWidget;
+use crate::widgets::title_bar::TitleBarWidget;
 use crate::widgets::selection_list::SelectionListWidget;
 use crate::widgets::progress_bar::ProgressBarWidget;
 use crate::widgets::status_box::StatusBoxWidget;
@@ -56,6 +57,7 @@ impl App {
         let layout = self.layout; 
         self.render_title_bar(frame, layout.title);
         self.render_main_content(frame, layout);
+        self.render_tab_transition(frame, layout.main);
         self.render_bindings(frame, layout.bindings);
         self.render_status_bar(frame, layout.status_bar);
         
@@ -65,26 +67,42 @@ impl App {
             crate::widgets::dimmer::apply_dimming(frame.buffer_mut(), area);
             frame.render_widget(modal, area);
         }
+        if let Some(trend_modal) = &self.trend_modal {
+            let area = frame.area();
+            crate::widgets::dimmer::apply_dimming(frame.buffer_mut(), area);
+            frame.render_widget(trend_modal, area);
+        }
+        if let Some(map_report) = &self.map_report_modal {
+            let area = frame.area();
+            crate::widgets::dimmer::apply_dimming(frame.buffer_mut(), area);
+            frame.render_widget(map_report, area);
+        }
+        if let Some(switcher) = &self.profile_switcher {
+            let area = frame.area();
+            crate::widgets::dimmer::apply_dimming(frame.buffer_mut(), area);
+            frame.render_widget(switcher, area);
+        }
+
+        if let Some(tint) = self.active_flash() {
+            let area = frame.area();
+            crate::widgets::dimmer::apply_flash(frame.buffer_mut(), area, tint);
+        }
 
         frame.render_widget(ToastWidget::new(&mut self.toast_manager), frame.area());
     }
 
     /// Renders the centered application title with decorative borders.
     fn render_title_bar(&self, frame: &mut Frame, area: Rect) {
-        let title_text = &self.config.application.title;
-        let line = if (area.width as usize) <= title_text.len() + 2 { //> 
-             Line::from(Span::styled(title_text, Style::default().fg(Color::White).add_modifier(Modifier::BOLD)))
-        } else {
-            let dash_count = (area.width as usize).saturating_sub(title_text.len() + 2);
-            let left = dash_count / 2;
-            let right = dash_count - left;
-            Line::from(vec![
-                Span::styled("═".repeat(left), Style::default().fg(Color::White)),
-                Span::styled(format!(" {} ", title_text), Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
-                Span::styled("═".repeat(right), Style::default().fg(Color::White)),
-            ])
-        }; //< 
-        frame.render_widget(Paragraph::new(line).alignment(Alignment::Center), area);
+        frame.render_widget(TitleBarWidget::new(&self.config.application.title).info(self.title_info()), area);
+    }
+
+    /// Active profile and its port, shown at the right of the title bar.
+    fn title_info(&self) -> Option<String> {
+        let profile_id = self.get_current_sketch_id()?;
+        Some(match self.get_settings_from_profile() {
+            Ok(settings) if !settings.port.is_empty() => format!("{} · {}", profile_id, settings.port),
+            _ => profile_id,
+        })
     }
 
     /// Displays an error message when the terminal window is too small.
@@ -111,11 +129,31 @@ impl App {
             if let Some(settings_layout) = layout.settings {
                 self.render_profiles_tab(frame, settings_layout);
             }
+        } else if active_tab_id == "history" {
+            if let Some(history_area) = layout.history {
+                frame.render_widget(&self.history_view, history_area);
+            }
         } else {
             self.render_dashboard_tab(frame, layout);
         }
     }
 
+    /// Plays the configured tab transition over freshly drawn tab content.
+    fn render_tab_transition(&self, frame: &mut Frame, main: Rect) {
+        let Some(progress) = self.tab_transition_progress() else { return };
+        let area = self.tab_content_area(main);
+        match self.config.application.tab_transition {
+            crate::config::TabTransition::Fade => {
+                crate::widgets::dimmer::apply_dimming(frame.buffer_mut(), area);
+            }
+            crate::config::TabTransition::Slide => {
+                let offset = ((1.0 - progress) * area.width as f32 / 8.0).round() as u16;
+                crate::widgets::dimmer::apply_slide(frame.buffer_mut(), area, offset);
+            }
+            crate::config::TabTransition::Off => {}
+        }
+    }
+
     fn render_dashboard_tab(&mut self, frame: &mut Frame, layout: AppLayout) {
         // Render Profile Panel
         let profile_block = Block::bordered().title(" Profile ");
@@ -129,7 +167,15 @@ impl App {
             } else { 
                 current_profile.to_string() 
             };
-            frame.render_widget(Paragraph::new(profile_text).style(Style::default().fg(Color::Cyan)).block(Block::bordered().border_type(BorderType::Rounded).title(" Sketch Profile ").title_style(Style::default().fg(Color::Yellow))), inner_profile_area);
+            let last_flashed = match self.ui_state.last_flashed.get(current_profile) { //> 
+                Some(ts) => crate::app::ui_state::format_relative_time(*ts, crate::app::ui_state::unix_now()),
+                None => "never".to_string(),
+            }; //< 
+            let profile_lines = vec![
+                Line::from(Span::styled(profile_text, Style::default().fg(Color::Cyan))),
+                Line::from(Span::styled(format!("Last flashed: {}", last_flashed), Style::default().fg(Color::DarkGray))),
+            ];
+            frame.render_widget(Paragraph::new(profile_lines).block(Block::bordered().border_type(BorderType::Rounded).title(" Sketch Profile ").title_style(Style::default().fg(Color::Yellow))), inner_profile_area);
         } else {
             frame.render_widget(Paragraph::new("No profiles found").style(Style::default().fg(Color::DarkGray)).block(Block::bordered().border_type(BorderType::Rounded).title(" Sketch Profile ").title_style(Style::default().fg(Color::Yellow))), inner_profile_area);
         } //< 
@@ -144,7 +190,8 @@ impl App {
         frame.render_widget(
             SelectionListWidget::new(&self.commands, self.selected_command_index, self.hovered_command_index)
                 .normal_style(Style::default().fg(Color::DarkGray))
-                .highlight_style(self.theme.style("commands_highlight")),
+                .highlight_style(self.theme.style("commands_highlight"))
+                .icons(self.commands.iter().map(|c| self.theme.command_icon(c)).collect()),
             commands_area
         );
 
@@ -172,6 +219,7 @@ impl App {
                     ProgressBarWidget::new("Status".to_string(), *visual_percentage, stage.clone())
                         .elapsed(elapsed_str)
                         .eta(eta_str)
+                        .file(self.current_build_file.clone().unwrap_or_default())
                         .border_style(self.theme.style("progress_border")),
                     layout.status
                 );
@@ -182,15 +230,18 @@ impl App {
         } //< 
         
         // Output Panel with Scrolling and Scrollbar
-        let display_lines = if self.output_cached_lines.is_empty() {
+        let visible_lines = self.visible_cached_lines();
+        let display_lines = if visible_lines.is_empty() {
             vec![Line::from(Span::styled("No output yet.", Style::default().fg(Color::DarkGray)))]
         } else {
-            self.output_cached_lines.clone()
+            visible_lines
         };
 
         frame.render_widget(
             OutputBoxWidget::new(&display_lines, self.output_scroll, &self.theme)
                 .autoscroll(self.output_autoscroll)
+                .highlight(self.active_line_flash().and_then(|i| self.visible_output_position(i)))
+                .counts(self.output_lines.len(), self.output_is_filtered().then(|| self.visible_output_len()))
                 .input(self.input_active, self.input.value(), self.input.visual_cursor()),
             layout.output
         );
@@ -209,7 +260,7 @@ impl App {
         }
         
         // Render Output Auto-Toggle (Static Tab)
-        let output_static_tabs = vec![TabBarItem { id: "autoscroll".to_string(), name: "Auto".to_string(), active: self.output_autoscroll }];
+        let output_static_tabs = self.output_static_tabs();
         if let Some((widget, horizontal, vertical, off_x, off_y)) = TabBarWidget::from_config(&self.config, &output_static_tabs, "OutputPanelStaticOptions") { //> 
             widget.render_aligned(layout.output, horizontal, vertical, off_x, off_y, frame.buffer_mut()); 
         } //< 
@@ -378,6 +429,12 @@ impl App {
 
     /// Renders context-sensitive keybinding help text.
     fn render_bindings(&self, frame: &mut Frame, area: Rect) {
+        if let Some(bindings_config) = self.active_tab_bindings().filter(|b| b.buttons) {
+            let buttons = crate::widgets::button_bar::Button::from_bindings(bindings_config);
+            frame.render_widget(crate::widgets::button_bar::ButtonBarWidget::new(&buttons), area);
+            return;
+        }
+
         let mut spans = Vec::new();
         
         // 1. Tab-specific Bindings (Cyan)
@@ -405,7 +462,7 @@ impl App {
         
         let text_area = Rect { x: area.x, y: area.y + 1, width: area.width, height: 1 };
         if text_area.height > 0 && text_area.width > 0 { //> 
-            let val = if self.config.application.status_bar.default_text.is_empty() { "Status: Ready".to_string() } else { self.config.application.status_bar.default_text.clone() };
+            let val = self.config.application.status_bar.text();
             frame.render_widget(Paragraph::new(Line::from(vec![Span::styled(format!("{} ", val), Style::default().fg(Color::White))])), text_area);
         } //< 
 
@@ -0,0 +1,254 @@
+use std::io::Write;
+use std::sync::atomic::AtomicBool;
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::time::Instant;
+
+use color_eyre::{eyre, Result};
+use serde_json::json;
+
+use crate::commands::{HistoryManager, ProgressPredictor, ProgressUpdate};
+
+/// Headless execution of build commands for CI and scripting.
+///>
+/// Batch mode bypasses the TUI entirely: a single command is run against the
+/// active profile and its `ProgressUpdate` stream is written to stdout, either
+/// as raw text or (with `--json`) as one JSON object per line followed by a
+/// summary object.
+///<
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchCommand {
+    Compile,
+    Upload,
+}
+
+/// Command-line options controlling a batch run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchOptions {
+    pub command: BatchCommand,
+    pub json: bool,
+    pub profile: Option<String>,
+}
+
+impl BatchOptions {
+    /// Parses `--batch <compile|upload> [--json]` from the process arguments.
+    ///>
+    /// Returns `Ok(None)` when `--batch` is absent so the caller falls through
+    /// to the interactive TUI.
+    ///<
+    pub fn from_args(args: &[String]) -> Result<Option<Self>> {
+        let Some(pos) = args.iter().position(|a| a == "--batch") else {
+            return Ok(None);
+        };
+        let command = match args.get(pos + 1).map(|s| s.to_lowercase()).as_deref() {
+            Some("compile") => BatchCommand::Compile,
+            Some("upload") => BatchCommand::Upload,
+            other => return Err(eyre::eyre!("--batch expects 'compile' or 'upload', got {:?}", other)),
+        };
+        let json = args.iter().any(|a| a == "--json");
+        let profile = profile_from_args(args)?;
+        Ok(Some(Self { command, json, profile }))
+    }
+}
+
+/// Parses `--profile <id>` from the process arguments.
+///>
+/// Shared by batch mode and the interactive TUI so both select the same
+/// sketch profile before any command runs.
+///<
+pub fn profile_from_args(args: &[String]) -> Result<Option<String>> {
+    let Some(pos) = args.iter().position(|a| a == "--profile") else {
+        return Ok(None);
+    };
+    match args.get(pos + 1) {
+        Some(id) if !id.starts_with("--") => Ok(Some(id.clone())),
+        _ => Err(eyre::eyre!("--profile expects a profile id")),
+    }
+}
+
+/// Picks the sketch profile a batch run builds.
+///>
+/// Without `--profile` the choice is only implicit when `config.yaml` holds a
+/// single sketch; with several, guessing would silently build the wrong one,
+/// so the run fails and lists the ids to choose from.
+///<
+pub fn select_profile_id(requested: Option<&str>, available: &[String]) -> Result<String> {
+    match (requested, available) {
+        (Some(id), _) => Ok(id.to_string()),
+        (None, [only]) => Ok(only.clone()),
+        (None, []) => Err(eyre::eyre!("No sketch profiles found in config.yaml")),
+        (None, _) => Err(eyre::eyre!(
+            "config.yaml has {} sketch profiles; pass --profile <id> (available: {})",
+            available.len(),
+            available.join(", ")
+        )),
+    }
+}
+
+/// Runs a single command headlessly and reports its progress to stdout.
+///>
+/// Returns `true` if the command completed successfully so the caller can
+/// translate the outcome into a process exit code.
+///<
+pub fn run(options: &BatchOptions) -> Result<bool> {
+    let profile_config = crate::config::load_profile_config()?;
+    let ids: Vec<String> = profile_config.sketches.iter().map(|s| s.id.clone()).collect();
+    let sketch_id = select_profile_id(options.profile.as_deref(), &ids)?;
+    let settings = crate::config::resolve_profile_settings(&profile_config, &sketch_id)?;
+    let configured_history = crate::config::load_config().ok().and_then(|c| c.application.history_path);
+    let history = HistoryManager::load(&HistoryManager::resolve_path(configured_history.as_deref()));
+    let stats = ProgressPredictor::with_stats(history.get_stats(&sketch_id)).get_stats();
+    let cancel_signal = Arc::new(AtomicBool::new(false));
+
+    let (tx, rx) = mpsc::channel();
+    let command = options.command;
+    let worker = std::thread::spawn(move || {
+        let callback = move |update| {
+            let _ = tx.send(update);
+        };
+        match command {
+            BatchCommand::Compile => crate::commands::run_compile(&settings, stats, cancel_signal, callback),
+            BatchCommand::Upload => crate::commands::run_upload(&settings, stats, cancel_signal, callback),
+        }
+    });
+
+    let stdout = std::io::stdout();
+    let success = report(rx, options.json, &mut stdout.lock(), Instant::now())?;
+    let _ = worker.join();
+    Ok(success)
+}
+
+/// Drains progress updates into `out` until the sender hangs up.
+///>
+/// In JSON mode each update becomes one object per line and a final
+/// `summary` object carries the outcome and total duration. In text mode
+/// output lines are passed through verbatim.
+///<
+pub fn report(rx: Receiver<ProgressUpdate>, json: bool, out: &mut impl Write, start: Instant) -> Result<bool> {
+    let mut success = false;
+    for update in rx {
+        match &update {
+            ProgressUpdate::CompletedWithMetrics { .. } | ProgressUpdate::Completed(_) => success = true,
+            ProgressUpdate::Failed(_) => success = false,
+            ProgressUpdate::BatchCompleted { failed, .. } => success = *failed == 0,
+            _ => {}
+        }
+        if json {
+            writeln!(out, "{}", event_to_json(&update))?;
+        } else if let Some(text) = event_to_text(&update) {
+            writeln!(out, "{}", text)?;
+        }
+    }
+
+    let duration_secs = start.elapsed().as_secs_f64();
+    if json {
+        writeln!(out, "{}", json!({ "type": "summary", "success": success, "duration_secs": duration_secs }))?;
+    } else {
+        writeln!(out, "{} in {:.1}s", if success { "Succeeded" } else { "Failed" }, duration_secs)?;
+    }
+    Ok(success)
+}
+
+/// Converts a progress update into its structured JSON event.
+fn event_to_json(update: &ProgressUpdate) -> serde_json::Value {
+    match update {
+        ProgressUpdate::OutputLine(line) => json!({ "type": "output", "line": line }),
+        ProgressUpdate::ReplaceLine(line) => json!({ "type": "output", "line": line, "replace": true }),
+        ProgressUpdate::Percentage(p) => json!({ "type": "progress", "percent": p }),
+        ProgressUpdate::Stage(stage) => json!({ "type": "stage", "stage": stage }),
+        ProgressUpdate::CompletedWithMetrics { stage_times } => {
+            let stages: serde_json::Map<String, serde_json::Value> = stage_times.iter()
+                .map(|(stage, secs)| (format!("{:?}", stage), json!(secs)))
+                .collect();
+            json!({ "type": "completed", "stage_times": stages })
+        }
+        ProgressUpdate::Completed(message) => json!({ "type": "completed", "message": message }),
+        ProgressUpdate::Failed(error) => json!({ "type": "failed", "error": error }),
+        ProgressUpdate::ProfileStarted { index, total, id } => json!({ "type": "profile", "index": index, "total": total, "id": id }),
+        ProgressUpdate::BatchCompleted { succeeded, failed } => json!({ "type": "batch_completed", "succeeded": succeeded, "failed": failed }),
+    }
+}
+
+/// Converts a progress update into a plain text line, if it has one.
+fn event_to_text(update: &ProgressUpdate) -> Option<String> {
+    match update {
+        ProgressUpdate::OutputLine(line) | ProgressUpdate::ReplaceLine(line) => Some(line.clone()),
+        ProgressUpdate::Stage(stage) => Some(format!("[{}]", stage)),
+        ProgressUpdate::Completed(message) => Some(message.clone()),
+        ProgressUpdate::Failed(error) => Some(format!("Error: {}", error)),
+        ProgressUpdate::ProfileStarted { index, total, id } => Some(format!("[{}/{}] {}", index + 1, total, id)),
+        ProgressUpdate::BatchCompleted { succeeded, failed } => Some(format!("{} ok, {} failed", succeeded, failed)),
+        ProgressUpdate::Percentage(_) | ProgressUpdate::CompletedWithMetrics { .. } => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_from_args_parses_batch_and_json() {
+        let args: Vec<String> = ["dev-console-v2", "--batch", "compile", "--json"].iter().map(|s| s.to_string()).collect();
+        let options = BatchOptions::from_args(&args).unwrap().unwrap();
+        assert_eq!(options.command, BatchCommand::Compile);
+        assert!(options.json);
+
+        assert_eq!(options.profile, None);
+
+        let args: Vec<String> = vec!["dev-console-v2".to_string()];
+        assert!(BatchOptions::from_args(&args).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_profile_from_args() {
+        let args: Vec<String> = ["dev-console-v2", "--profile", "sensor", "--batch", "upload"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(profile_from_args(&args).unwrap().as_deref(), Some("sensor"));
+        assert_eq!(BatchOptions::from_args(&args).unwrap().unwrap().profile.as_deref(), Some("sensor"));
+
+        let args: Vec<String> = ["dev-console-v2", "--profile", "--json"].iter().map(|s| s.to_string()).collect();
+        assert!(profile_from_args(&args).is_err());
+    }
+
+    #[test]
+    fn test_select_profile_id_requires_profile_when_ambiguous() {
+        let one = vec!["sensor".to_string()];
+        let two = vec!["sensor".to_string(), "display".to_string()];
+        assert_eq!(select_profile_id(None, &one).unwrap(), "sensor");
+        assert_eq!(select_profile_id(Some("display"), &two).unwrap(), "display");
+        assert!(select_profile_id(None, &[]).is_err());
+
+        let err = select_profile_id(None, &two).unwrap_err().to_string();
+        assert!(err.contains("--profile"), "{}", err);
+        assert!(err.contains("sensor, display"), "{}", err);
+    }
+
+    #[test]
+    fn test_report_json_emits_parseable_lines_with_summary() {
+        let (tx, rx) = mpsc::channel();
+        tx.send(ProgressUpdate::Stage("Compiling".to_string())).unwrap();
+        tx.send(ProgressUpdate::Percentage(42.5)).unwrap();
+        tx.send(ProgressUpdate::OutputLine("Compiling sketch.ino".to_string())).unwrap();
+        tx.send(ProgressUpdate::CompletedWithMetrics { stage_times: HashMap::new() }).unwrap();
+        drop(tx);
+
+        let mut out = Vec::new();
+        let success = report(rx, true, &mut out, Instant::now()).unwrap();
+        assert!(success);
+
+        let events: Vec<serde_json::Value> = String::from_utf8(out).unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).expect("each line must be valid JSON"))
+            .collect();
+        assert_eq!(events.len(), 5);
+        assert_eq!(events[0]["type"], "stage");
+        assert_eq!(events[0]["stage"], "Compiling");
+        assert_eq!(events[1]["percent"], 42.5);
+        assert_eq!(events[2]["line"], "Compiling sketch.ino");
+
+        let summary = events.last().unwrap();
+        assert_eq!(summary["type"], "summary");
+        assert_eq!(summary["success"], true);
+        assert!(summary["duration_secs"].is_number());
+    }
+}
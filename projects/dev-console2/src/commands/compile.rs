@@ -1,4 +1,4 @@
-use super::{compile_state, compile_parser, path_utils, process::ProcessHandler, traits::{CommandRunner, FileSystem, RealCommandRunner, RealFileSystem}};
+use super::{compile_state, compile_parser, path_utils, process::{ProcessHandler, OutputChunk}, traits::{CommandRunner, FileSystem, RealCommandRunner, RealFileSystem}};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::{Arc, Mutex};
@@ -30,12 +30,29 @@ pub struct Settings {
 #[derive(Debug, Clone, PartialEq)]
 pub enum ProgressUpdate {
     OutputLine(String),
+    /// Replaces the most recent output line (carriage-return progress frames).
+    ReplaceLine(String),
     Percentage(f64),
     Stage(String),
     CompletedWithMetrics {
         stage_times: std::collections::HashMap<crate::commands::predictor::CompileStage, f64>,
     },
+    /// A task without build metrics (such as a flash erase) finished; carries its status message.
+    Completed(String),
     Failed(String),
+    /// An "All Profiles" compile moved on to the profile at `index` (zero-based) of `total`.
+    ProfileStarted { index: usize, total: usize, id: String },
+    /// An "All Profiles" compile finished; carries how many profiles passed and failed.
+    BatchCompleted { succeeded: usize, failed: usize },
+}
+
+impl From<OutputChunk> for ProgressUpdate {
+    fn from(chunk: OutputChunk) -> Self {
+        match chunk {
+            OutputChunk::Line(line) => ProgressUpdate::OutputLine(line),
+            OutputChunk::Overwrite(line) => ProgressUpdate::ReplaceLine(line),
+        }
+    }
 }
 
 /// Spawns a background thread to compile an Arduino sketch.
@@ -133,14 +150,15 @@ pub fn run_compile_with_runners(
 
     let callback_clone = callback.clone();
     let state_clone = compile_state.clone();
-    let result = process_handler.read_output(cancel_signal.clone(), move |line| { //>
+    let result = process_handler.read_output(cancel_signal.clone(), move |chunk| { //>
+        let line = chunk.text().to_string();
         let mut cb = callback_clone.lock().unwrap();
         let mut state = state_clone.lock().unwrap();
         let (stage_changed, should_continue) = compile_parser::detect_stage_change(&line, &mut state, 0.0, &mut |msg| {
             cb(ProgressUpdate::OutputLine(msg));
         });
         if !should_continue {
-            cb(ProgressUpdate::OutputLine(line));
+            cb(chunk.into());
             return;
         }
         
@@ -151,7 +169,7 @@ pub fn run_compile_with_runners(
         compile_parser::parse_compilation_info(&line, &mut state);
         let progress = state.calculate_progress();
         cb(ProgressUpdate::Percentage(progress));
-        cb(ProgressUpdate::OutputLine(line));
+        cb(chunk.into());
 
         // Watchdog: Check if we are stuck in a stage without markers
         if let Some(warning) = state.check_for_missing_markers() {
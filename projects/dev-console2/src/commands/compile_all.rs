@@ -0,0 +1,82 @@
+// Sequential compile of every sketch profile
+
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use crate::commands::compile::{ProgressUpdate, Settings};
+use crate::commands::history::StageStats;
+
+/// One profile queued for an "All Profiles" compile.
+#[derive(Debug, Clone)]
+pub struct ProfileBuild {
+    pub id: String,
+    pub settings: Settings,
+    pub stats: StageStats,
+}
+
+/// Overall progress of a batch given the profiles already finished and the current one's percentage.
+pub fn batch_percentage(completed: usize, current_percent: f64, total: usize) -> f64 {
+    if total == 0 {
+        return 100.0;
+    }
+    let current = (current_percent / 100.0).clamp(0.0, 1.0);
+    ((completed as f64 + current) / total as f64 * 100.0).min(100.0)
+}
+
+/// Compiles every profile in turn with `run_compile`.
+pub fn run_compile_all(
+    profiles: Vec<ProfileBuild>,
+    cancel_signal: Arc<AtomicBool>,
+    progress_callback: impl FnMut(ProgressUpdate) + Send + 'static
+) {
+    run_compile_all_with(profiles, cancel_signal, progress_callback, |settings, stats, cancel_signal, callback| {
+        crate::commands::run_compile(settings, stats, cancel_signal, callback)
+    })
+}
+
+/// Compiles every profile in turn with the provided compile function.
+///>
+/// Each profile is announced with `ProfileStarted`, after which its updates
+/// (including its own `CompletedWithMetrics` or `Failed`) are forwarded
+/// unchanged so the receiver can attribute progress and history to that
+/// sketch. A failed profile does not stop the batch; only a cancel does. The
+/// run always ends with one `BatchCompleted` carrying the pass/fail counts.
+///<
+pub fn run_compile_all_with(
+    profiles: Vec<ProfileBuild>,
+    cancel_signal: Arc<AtomicBool>,
+    progress_callback: impl FnMut(ProgressUpdate) + Send + 'static,
+    mut compile: impl FnMut(&Settings, StageStats, Arc<AtomicBool>, Box<dyn FnMut(ProgressUpdate) + Send>),
+) {
+    let callback = Arc::new(Mutex::new(progress_callback));
+    let total = profiles.len();
+    let (mut succeeded, mut failed) = (0, 0);
+
+    for (index, profile) in profiles.into_iter().enumerate() {
+        if cancel_signal.load(Ordering::SeqCst) {
+            break;
+        }
+        callback.lock().unwrap()(ProgressUpdate::ProfileStarted { index, total, id: profile.id.clone() });
+
+        let outcome: Arc<Mutex<Option<bool>>> = Arc::new(Mutex::new(None));
+        let outcome_clone = outcome.clone();
+        let callback_clone = callback.clone();
+        compile(&profile.settings, profile.stats, cancel_signal.clone(), Box::new(move |update: ProgressUpdate| {
+            match update {
+                ProgressUpdate::CompletedWithMetrics { .. } | ProgressUpdate::Completed(_) => {
+                    *outcome_clone.lock().unwrap() = Some(true);
+                }
+                ProgressUpdate::Failed(_) => *outcome_clone.lock().unwrap() = Some(false),
+                _ => {}
+            }
+            callback_clone.lock().unwrap()(update);
+        }));
+
+        if *outcome.lock().unwrap() == Some(true) {
+            succeeded += 1;
+        } else {
+            failed += 1;
+        }
+    }
+
+    callback.lock().unwrap()(ProgressUpdate::BatchCompleted { succeeded, failed });
+}
@@ -15,6 +15,9 @@ lazy_static! {
     static ref RE_COMPILED_FILE: Regex = Regex::new(
         r"(?i)\.(cpp|c|ino|S)\.o|gcc-ar|compiled\s+[^\s]+\.(cpp|c|ino|S)|using previously compiled file"
     ).unwrap();
+    static ref RE_DIAGNOSTIC: Regex = Regex::new(
+        r"(?i)^\s*(error|warning)\b|:\s*(fatal\s+)?(error|warning):"
+    ).unwrap();
 }
 
 /// Identifies compilation stage transitions from raw process output.
@@ -163,4 +166,73 @@ pub fn parse_compilation_info(line: &str, compile_state: &mut CompileState) {
             compile_state.files_compiled = compile_state.compiled_lines_seen.len();
         } //< 
     } //< 
-}
\ No newline at end of file
+}
+
+/// Identifies raw toolchain invocations that quiet mode hides.
+///>
+/// Diagnostics (`file:1:2: error:`, `: warning:`, or a line starting with
+/// Error/Warning) are always kept; flags such as `-Werror` do not count.
+/// Otherwise a line is treated as a raw command (gcc, ar, esptool, ...) when
+/// it carries three or more `-flag` arguments; stage markers,
+/// "Compiling x.cpp" lines and size summaries never do.
+///< 
+pub fn is_verbose_line(line: &str) -> bool {
+    let cleaned = remove_ansi_escapes(line);
+    if RE_DIAGNOSTIC.is_match(&cleaned) { //> 
+        return false;
+    } //< 
+    let flag_count = cleaned.split_whitespace()
+        .filter(|token| token.len() > 1 && token.starts_with('-') && !token.starts_with("--"))
+        .count();
+    flag_count >= 3
+}
+
+/// Picks the part of the output worth pasting into a search or an issue.
+///>
+/// Returns the contiguous diagnostic block around the first error: the error
+/// itself plus neighbouring warning/note lines and indented context (source
+/// excerpts, carets), bounded by blank lines or ordinary output. Without an
+/// error, everything after the last command marker (`⮻`) is returned instead.
+/// Lines come back with ANSI codes removed.
+///<
+pub fn extract_error_block(lines: &[&str]) -> Vec<String> {
+    let cleaned: Vec<String> = lines.iter().map(|l| remove_ansi_escapes(l)).collect();
+    let Some(first_error) = cleaned.iter().position(|l| is_error_line(l)) else {
+        let start = cleaned.iter()
+            .rposition(|l| l.trim_start().starts_with('⮻'))
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        return cleaned[start..].to_vec();
+    };
+
+    let mut start = first_error;
+    while start > 0 && is_diagnostic_line(&cleaned[start - 1]) {
+        start -= 1;
+    }
+    let mut end = first_error + 1;
+    while end < cleaned.len() && is_diagnostic_line(&cleaned[end]) {
+        end += 1;
+    }
+    cleaned[start..end].to_vec()
+}
+
+fn is_error_line(line: &str) -> bool {
+    let line_lower = line.to_lowercase();
+    line.trim_start().starts_with('✗') || line_lower.contains("error:")
+}
+
+/// Lines that belong to a compiler diagnostic: messages, notes and indented context.
+fn is_diagnostic_line(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    if trimmed.is_empty() {
+        return false;
+    }
+    let line_lower = line.to_lowercase();
+    is_error_line(line)
+        || trimmed.starts_with('⚠')
+        || line_lower.contains("warning:")
+        || line_lower.contains("note:")
+        || line_lower.contains("in function")
+        || line_lower.starts_with("in file included from")
+        || line.starts_with(char::is_whitespace)
+}
@@ -0,0 +1,74 @@
+use super::{process::ProcessHandler, traits::{CommandRunner, RealCommandRunner}};
+use std::process::Command;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use crate::commands::compile::{Settings, ProgressUpdate};
+
+/// Flash tool invoked for a full chip erase.
+pub const ESPTOOL: &str = "esptool.py";
+
+/// True for FQBNs of Espressif boards, the only ones `esptool` can erase.
+pub fn is_esp_board(fqbn: &str) -> bool {
+    let vendor = fqbn.split(':').next().unwrap_or_default();
+    vendor.eq_ignore_ascii_case("esp32") || vendor.eq_ignore_ascii_case("esp8266")
+}
+
+/// Builds the `esptool` invocation that erases the whole flash on the profile's port.
+pub fn erase_flash_command(settings: &Settings) -> Command {
+    let mut cmd = Command::new(ESPTOOL);
+    cmd.arg("--port").arg(&settings.port).arg("erase_flash");
+    cmd
+}
+
+/// Erases the entire flash of the connected ESP board.
+pub fn run_erase_flash(
+    settings: &Settings,
+    cancel_signal: Arc<AtomicBool>,
+    progress_callback: impl FnMut(ProgressUpdate) + Send + 'static
+) {
+    run_erase_flash_with_runner(&RealCommandRunner, settings, cancel_signal, progress_callback)
+}
+
+/// Erases the entire flash of the connected ESP board with a provided runner.
+///>
+/// `esptool` reports no percentage while erasing, so progress only jumps to
+/// 100% once the tool exits successfully. Output streams like any build.
+///<
+pub fn run_erase_flash_with_runner(
+    runner: &dyn CommandRunner,
+    settings: &Settings,
+    cancel_signal: Arc<AtomicBool>,
+    mut progress_callback: impl FnMut(ProgressUpdate) + Send + 'static
+) {
+    progress_callback(ProgressUpdate::Stage("Erasing".to_string()));
+
+    let process_handler = match ProcessHandler::spawn(runner, erase_flash_command(settings)) {
+        Ok(handler) => handler,
+        Err(e) => {
+            progress_callback(ProgressUpdate::Failed(format!("Failed to spawn {}: {}", ESPTOOL, e)));
+            return;
+        }
+    };
+
+    let callback = Arc::new(std::sync::Mutex::new(progress_callback));
+    let callback_clone = callback.clone();
+    let result = process_handler.read_output(cancel_signal.clone(), move |chunk| {
+        callback_clone.lock().unwrap()(chunk.into());
+    });
+
+    let mut cb = callback.lock().unwrap();
+    match result {
+        Ok(true) => {
+            cb(ProgressUpdate::Percentage(100.0));
+            cb(ProgressUpdate::Completed(format!("Flash erased on {}.", settings.port)));
+        }
+        Ok(false) => {
+            if cancel_signal.load(Ordering::SeqCst) {
+                cb(ProgressUpdate::Failed("Erase cancelled by user.".to_string()))
+            } else {
+                cb(ProgressUpdate::Failed("Erase failed (see output for details).".to_string()))
+            }
+        }
+        Err(e) => cb(ProgressUpdate::Failed(format!("Error reading process output: {}", e))),
+    }
+}
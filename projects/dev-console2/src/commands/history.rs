@@ -1,9 +1,26 @@
 use serde::{Serialize, Deserialize};
 use std::collections::HashMap;
-use std::path::{Path};
+use std::path::{Path, PathBuf};
 use std::fs;
 use crate::commands::predictor::CompileStage;
 
+/// Default history location, relative to the working directory.
+pub const DEFAULT_HISTORY_PATH: &str = ".dev-console/progress_history.json";
+
+/// Number of finished builds kept across all profiles.
+const MAX_BUILD_RECORDS: usize = 200;
+
+/// One finished compile or upload, as listed in the History tab.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BuildRecord {
+    pub sketch_id: String,
+    pub command: String,
+    pub duration_secs: f64,
+    pub success: bool,
+    /// Unix time the build finished.
+    pub finished_at: u64,
+}
+
 /// Historical performance metrics for a specific Arduino sketch.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SketchHistory {
@@ -19,6 +36,9 @@ pub struct SketchHistory {
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct HistoryManager {
     pub sketches: HashMap<String, SketchHistory>,
+    /// Finished builds across all profiles, oldest first.
+    #[serde(default)]
+    pub builds: Vec<BuildRecord>,
 }
 
 /// Aggregated statistical data for a specific sketch.
@@ -29,6 +49,26 @@ pub struct StageStats {
 }
 
 impl HistoryManager {
+    /// Resolves the history file location from an optional configured path.
+    ///>
+    /// Absolute paths are used as-is. Relative paths are anchored at the
+    /// workspace root when `WORKSPACE_ROOT` is set, otherwise at the working
+    /// directory. With no configured path the legacy default is returned.
+    ///<
+    pub fn resolve_path(configured: Option<&str>) -> PathBuf {
+        let Some(configured) = configured.filter(|p| !p.trim().is_empty()) else {
+            return PathBuf::from(DEFAULT_HISTORY_PATH);
+        };
+        let path = PathBuf::from(configured);
+        if path.is_absolute() { //>
+            return path;
+        } //<
+        match super::path_utils::find_workspace_root() {
+            Ok(root) => root.join(path),
+            Err(_) => path,
+        }
+    }
+
     /// Loads history from a JSON file.
     pub fn load(path: &Path) -> Self {
         if let Ok(content) = fs::read_to_string(path) { //>
@@ -104,6 +144,37 @@ impl HistoryManager {
         Some(StageStats { weights, averages })
     }
 
+    /// Reconstructs total build durations for a sketch, oldest first.
+    ///>
+    /// Each stage keeps its own rolling window, so runs are aligned from the
+    /// most recent entry backwards. Only compile stages are summed; upload
+    /// stages are excluded so the series reflects build time alone.
+    ///<
+    pub fn build_durations(&self, sketch_id: &str) -> Vec<f64> {
+        let Some(history) = self.sketches.get(sketch_id) else {
+            return Vec::new();
+        };
+        let build_stages = ["Initializing", "DetectingLibraries", "Compiling", "Linking", "Generating"];
+        let series: Vec<&Vec<f64>> = build_stages.iter()
+            .filter_map(|name| history.stage_times.get(*name))
+            .collect();
+        let run_count = series.iter().map(|times| times.len()).max().unwrap_or(0);
+
+        (0..run_count).map(|run| { //>
+            let offset = run_count - run;
+            series.iter()
+                .filter_map(|times| times.len().checked_sub(offset).map(|i| times[i]))
+                .sum()
+        }).collect() //<
+    }
+
+    /// Appends a finished build, dropping the oldest beyond `MAX_BUILD_RECORDS`.
+    pub fn record_build(&mut self, record: BuildRecord) {
+        self.builds.push(record);
+        let excess = self.builds.len().saturating_sub(MAX_BUILD_RECORDS);
+        self.builds.drain(..excess);
+    }
+
     /// Records the results of a single compilation or upload run.
     ///>
     /// Stores the last 10 durations for each stage to enable rolling 
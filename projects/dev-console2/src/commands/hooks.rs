@@ -0,0 +1,102 @@
+// Pre/post build hook execution
+
+use std::path::Path;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::AtomicBool;
+use super::process::ProcessHandler;
+use super::traits::CommandRunner;
+use crate::commands::compile::ProgressUpdate;
+use crate::config::HooksConfig;
+
+/// Wraps a build or upload with the configured pre/post hooks.
+///>
+/// Pre-build hooks run first, in order; the first failure reports `Failed`
+/// and the main command is never started. Post-build hooks run only after the
+/// main command completed successfully; its completion is held back until
+/// they finish so the task stays running. A failing post hook only adds a
+/// warning line: the build itself succeeded, so its completion (and with it
+/// the stage metrics and history record) is still reported.
+///<
+pub fn run_with_hooks<F>(
+    runner: &dyn CommandRunner,
+    hooks: &HooksConfig,
+    cwd: &Path,
+    cancel_signal: Arc<AtomicBool>,
+    callback: F,
+    main: impl FnOnce(Box<dyn FnMut(ProgressUpdate) + Send>),
+) where
+    F: FnMut(ProgressUpdate) + Clone + Send + 'static,
+{
+    let mut report = callback.clone();
+
+    if let Err(e) = run_hooks(runner, "pre-build", &hooks.pre_build, cwd, cancel_signal.clone(), callback.clone()) {
+        report(ProgressUpdate::Failed(e));
+        return;
+    }
+
+    let completion: Arc<Mutex<Option<ProgressUpdate>>> = Arc::new(Mutex::new(None));
+    let completion_slot = completion.clone();
+    let mut main_callback = callback.clone();
+    main(Box::new(move |update: ProgressUpdate| {
+        if matches!(update, ProgressUpdate::CompletedWithMetrics { .. }) {
+            *completion_slot.lock().unwrap() = Some(update);
+        } else {
+            main_callback(update);
+        }
+    }));
+
+    let Some(completed) = completion.lock().unwrap().take() else { return; };
+    if let Err(e) = run_hooks(runner, "post-build", &hooks.post_build, cwd, cancel_signal, callback) {
+        report(ProgressUpdate::OutputLine(format!("⚠ {} (build result unchanged)", e)));
+    }
+    report(completed);
+}
+
+/// Runs each hook in sequence, streaming its output with a `[hook]` tag.
+fn run_hooks<F>(
+    runner: &dyn CommandRunner,
+    phase: &str,
+    hooks: &[String],
+    cwd: &Path,
+    cancel_signal: Arc<AtomicBool>,
+    callback: F,
+) -> Result<(), String>
+where
+    F: FnMut(ProgressUpdate) + Clone + Send + 'static,
+{
+    for hook in hooks {
+        let mut announce = callback.clone();
+        announce(ProgressUpdate::OutputLine(format!("[hook] $ {}", hook)));
+
+        let mut cmd = shell_command(hook);
+        cmd.current_dir(cwd);
+        let handler = ProcessHandler::spawn(runner, cmd)
+            .map_err(|e| format!("{} hook '{}' failed to start: {}", phase, hook, e))?;
+
+        let mut stream = callback.clone();
+        let succeeded = handler
+            .read_output(cancel_signal.clone(), move |chunk| {
+                stream(ProgressUpdate::OutputLine(format!("[hook] {}", chunk.text())));
+            })
+            .map_err(|e| format!("{} hook '{}' failed: {}", phase, hook, e))?;
+
+        if !succeeded {
+            return Err(format!("{} hook '{}' failed", phase, hook));
+        }
+    }
+    Ok(())
+}
+
+/// Builds a platform shell invocation for a hook command line.
+fn shell_command(hook: &str) -> Command {
+    if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", hook]);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", hook]);
+        cmd
+    }
+}
@@ -0,0 +1,35 @@
+use std::path::{Path, PathBuf};
+
+/// Linker map files longer than this are cut off in the viewer.
+pub const MAX_MAP_LINES: usize = 20_000;
+
+/// Finds the linker map produced by the last build in `build_dir`.
+///>
+/// arduino-cli writes `<sketch>.ino.map` next to the firmware; that name is
+/// preferred, otherwise the most recently modified `.map` in the folder wins
+/// (ESP-IDF names it after the project).
+///<
+pub fn find_map_file(build_dir: &Path, sketch_name: &str) -> Option<PathBuf> {
+    let stem = sketch_name.trim_end_matches(".ino");
+    let preferred = build_dir.join(format!("{}.ino.map", stem));
+    if preferred.is_file() {
+        return Some(preferred);
+    }
+
+    std::fs::read_dir(build_dir).ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "map"))
+        .max_by_key(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+}
+
+/// Reads a map file for display, truncated to `MAX_MAP_LINES`.
+pub fn load_map_lines(path: &Path) -> std::io::Result<Vec<String>> {
+    let contents = std::fs::read_to_string(path)?;
+    let total = contents.lines().count();
+    let mut lines: Vec<String> = contents.lines().take(MAX_MAP_LINES).map(str::to_string).collect();
+    if total > MAX_MAP_LINES {
+        lines.push(format!("… {} more lines not shown", total - MAX_MAP_LINES));
+    }
+    Ok(lines)
+}
@@ -6,6 +6,11 @@ pub mod serial_v2;
 pub mod mqtt;
 pub mod traits;
 pub mod discovery;
+pub mod erase;
+pub mod compile_all;
+pub mod map_report;
+pub mod notify;
+mod hooks;
 mod compile_state;
 mod compile_parser;
 mod utils;
@@ -13,13 +18,19 @@ mod path_utils;
 mod process;
 
 pub use compile::{run_compile, ProgressUpdate, Settings};
+pub use compile_parser::{extract_error_block, is_verbose_line};
 pub use upload::{run_upload};
 pub use predictor::{ProgressPredictor};
-pub use history::{HistoryManager};
-pub use serial_v2::{run_serial_monitor, SerialCommand};
+pub use history::{BuildRecord, HistoryManager};
+pub use serial_v2::{SerialCommand, SerialOptions};
 pub use mqtt::{run_mqtt_monitor, MqttCommand};
 pub use traits::{PortScanner, PortInfo, RealPortScanner};
 pub use discovery::{scan_ports};
+pub use utils::extract_current_file;
+pub use notify::DesktopNotification;
+pub use hooks::run_with_hooks;
+pub use erase::{is_esp_board, run_erase_flash};
+pub use compile_all::{run_compile_all, ProfileBuild};
 
 #[cfg(test)]
 mod tests;
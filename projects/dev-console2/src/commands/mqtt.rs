@@ -7,6 +7,8 @@ use rumqttc::{Client, MqttOptions, QoS, Event, Packet};
 /// Commands sent from the TUI to the background MQTT thread.
 pub enum MqttCommand {
     Publish { topic: String, payload: String },
+    /// Disconnects from the broker and ends the monitor loop.
+    Stop,
 }
 
 /// Spawns a background task to monitor and interact with an MQTT broker.
@@ -49,6 +51,10 @@ pub fn run_mqtt_monitor(
         // 1. Check for commands to send
         if let Ok(cmd) = command_rx.try_recv() { //>
             match cmd { //>
+                MqttCommand::Stop => { //>
+                    let _ = client.disconnect();
+                    break;
+                } //<
                 MqttCommand::Publish { topic, payload } => { //>
                     if let Err(e) = client.publish(&topic, QoS::AtMostOnce, false, payload.as_bytes()) { //>
                         callback(ProgressUpdate::OutputLine(format!("✗ Publish Error: {}", e)));
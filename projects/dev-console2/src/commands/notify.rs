@@ -0,0 +1,70 @@
+// Desktop notification module
+
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// A desktop notification raised when a build or upload finishes.
+///>
+/// Posting goes through the platform's own notifier (`notify-send`,
+/// `osascript` or PowerShell) on a background thread. Any failure to post is
+/// ignored, since the in-app status already reports the result.
+///<
+#[derive(Debug, Clone, PartialEq)]
+pub struct DesktopNotification {
+    pub title: String,
+    pub body: String,
+}
+
+impl DesktopNotification {
+    /// Builds the payload for a finished build or upload.
+    pub fn completion(operation: &str, sketch_id: &str, success: bool, elapsed: Duration) -> Self {
+        let outcome = if success { "succeeded" } else { "failed" };
+        Self {
+            title: format!("{} {}", operation, outcome),
+            body: format!("{} {} after {:.1}s", sketch_id, outcome, elapsed.as_secs_f64()),
+        }
+    }
+
+    /// Posts the notification without blocking; errors are silently dropped.
+    pub fn post(&self) {
+        let mut cmd = self.platform_command();
+        cmd.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null());
+        std::thread::spawn(move || {
+            let _ = cmd.status();
+        });
+    }
+
+    #[cfg(target_os = "windows")]
+    fn platform_command(&self) -> Command {
+        let script = format!(
+            "Add-Type -AssemblyName System.Windows.Forms; \
+             $n = New-Object System.Windows.Forms.NotifyIcon; \
+             $n.Icon = [System.Drawing.SystemIcons]::Information; $n.Visible = $true; \
+             $n.ShowBalloonTip(5000, '{}', '{}', 'Info'); Start-Sleep -Seconds 6; $n.Dispose()",
+            self.title.replace('\'', "''"),
+            self.body.replace('\'', "''"),
+        );
+        let mut cmd = Command::new("powershell");
+        cmd.args(["-NoProfile", "-NonInteractive", "-Command", &script]);
+        cmd
+    }
+
+    #[cfg(target_os = "macos")]
+    fn platform_command(&self) -> Command {
+        let script = format!(
+            "display notification \"{}\" with title \"{}\"",
+            self.body.replace('"', "\\\""),
+            self.title.replace('"', "\\\""),
+        );
+        let mut cmd = Command::new("osascript");
+        cmd.args(["-e", &script]);
+        cmd
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    fn platform_command(&self) -> Command {
+        let mut cmd = Command::new("notify-send");
+        cmd.args(["--app-name=dev-console", &self.title, &self.body]);
+        cmd
+    }
+}
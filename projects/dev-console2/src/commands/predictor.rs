@@ -133,6 +133,12 @@ impl ProgressPredictor {
         } //<
     }
 
+    /// Stage the predictor is currently timing.
+    #[cfg(test)]
+    pub fn current_stage(&self) -> CompileStage {
+        self.current_stage
+    }
+
     /// Returns the current configuration and averages used for prediction.
     pub fn get_stats(&self) -> StageStats {
         StageStats {
@@ -7,6 +7,81 @@ use std::thread;
 use std::time::Duration;
 use super::traits::{CommandRunner, ChildProcess};
 
+/// A unit of process output produced by the byte reader.
+///>
+/// Tools like esptool and alive_progress redraw a single line by ending each
+/// frame with `\r`. Those frames arrive as `Overwrite` so the consumer can
+/// replace the last output line in place instead of appending every frame.
+///<
+#[derive(Debug, Clone, PartialEq)]
+pub enum OutputChunk {
+    /// A new line to append.
+    Line(String),
+    /// A carriage-return frame that replaces the previously emitted line.
+    Overwrite(String),
+}
+
+impl OutputChunk {
+    /// Returns the text of the chunk regardless of kind.
+    pub fn text(&self) -> &str {
+        match self {
+            OutputChunk::Line(s) | OutputChunk::Overwrite(s) => s,
+        }
+    }
+}
+
+/// Incremental splitter turning a byte stream into `OutputChunk`s.
+///>
+/// `\n` commits the current line. `\r` emits the current buffer immediately
+/// and marks it as in-place, so the next frame overwrites it. A `\r\n` pair
+/// therefore behaves like a plain newline.
+///<
+#[derive(Debug, Default)]
+pub struct LineAssembler {
+    buffer: Vec<u8>,
+    in_place: bool,
+}
+
+impl LineAssembler {
+    /// Feeds a single byte, returning a chunk when a line boundary is reached.
+    pub fn push(&mut self, byte: u8) -> Option<OutputChunk> {
+        match byte {
+            b'\r' => {
+                let chunk = self.take_chunk();
+                if chunk.is_some() { //>
+                    self.in_place = true;
+                } //<
+                chunk
+            }
+            b'\n' => {
+                let chunk = self.take_chunk();
+                self.in_place = false;
+                chunk
+            }
+            _ => {
+                self.buffer.push(byte);
+                None
+            }
+        }
+    }
+
+    /// Emits any trailing text left when the stream ends without a newline.
+    pub fn flush(&mut self) -> Option<OutputChunk> {
+        let chunk = self.take_chunk();
+        self.in_place = false;
+        chunk
+    }
+
+    fn take_chunk(&mut self) -> Option<OutputChunk> {
+        if self.buffer.is_empty() {
+            return None;
+        }
+        let text = String::from_utf8_lossy(&self.buffer).to_string();
+        self.buffer.clear();
+        Some(if self.in_place { OutputChunk::Overwrite(text) } else { OutputChunk::Line(text) })
+    }
+}
+
 /// Managed wrapper for spawning and monitoring external child processes.
 ///>
 /// Provides real-time line-based output capture from both stdout and stderr, 
@@ -26,12 +101,12 @@ impl ProcessHandler {
     /// Monitors the process output and handles lifecycle events.
     ///>
     /// Spawns background threads to read stdout/stderr byte-by-byte, 
-    /// reassembling them into lines for the provided callback. Blocks until 
-    /// the process terminates or is killed via the `cancel_signal`.
+    /// reassembling them into `OutputChunk`s for the provided callback. Blocks 
+    /// until the process terminates or is killed via the `cancel_signal`.
     ///< 
     pub fn read_output<F>(mut self, cancel_signal: Arc<AtomicBool>, mut callback: F) -> Result<bool, std::io::Error>
     where
-        F: FnMut(String) + Send + 'static,
+        F: FnMut(OutputChunk) + Send + 'static,
     {
         let stdout = self.child.stdout().ok_or_else(|| std::io::Error::new(std::io::ErrorKind::Other, "Failed to capture stdout"))?;
         let stderr = self.child.stderr().ok_or_else(|| std::io::Error::new(std::io::ErrorKind::Other, "Failed to capture stderr"))?;
@@ -39,24 +114,20 @@ impl ProcessHandler {
         let (tx, rx) = mpsc::channel();
 
         // Helper to spawn a real-time byte-reader for a stream
-        fn spawn_byte_reader<R: Read + Send + 'static>(stream: R, tx: mpsc::Sender<String>) {
+        fn spawn_byte_reader<R: Read + Send + 'static>(stream: R, tx: mpsc::Sender<OutputChunk>) {
             thread::spawn(move || {
                 let mut reader = stream;
-                let mut buffer = Vec::new();
+                let mut assembler = LineAssembler::default();
                 let mut byte = [0u8; 1];
                 
                 while reader.read_exact(&mut byte).is_ok() { //> 
-                    let b = byte[0];
-                    if b == b'\n' || b == b'\r' { //> 
-                        if !buffer.is_empty() { //> 
-                            let s = String::from_utf8_lossy(&buffer).to_string();
-                            if tx.send(s).is_err() { break; } 
-                            buffer.clear();
-                        } //< 
-                    } else {
-                        buffer.push(b);
+                    if let Some(chunk) = assembler.push(byte[0]) { //> 
+                        if tx.send(chunk).is_err() { return; } 
                     } //< 
                 } //< 
+                if let Some(chunk) = assembler.flush() { //> 
+                    let _ = tx.send(chunk);
+                } //< 
             });
         }
 
@@ -71,15 +142,15 @@ impl ProcessHandler {
             } //< 
 
             // Try to receive output without blocking too long to keep checking cancel_signal
-            if let Ok(line) = rx.try_recv() { //> 
-                callback(line);
+            if let Ok(chunk) = rx.try_recv() { //> 
+                callback(chunk);
             } else {
                 // Check if child has exited
                 match self.child.try_wait()? { //> 
                     Some(status) => { //> 
                         // Process remaining messages in channel
-                        while let Ok(line) = rx.try_recv() { //> 
-                            callback(line);
+                        while let Ok(chunk) = rx.try_recv() { //> 
+                            callback(chunk);
                         } //< 
                         return Ok(status.success());
                     } //< 
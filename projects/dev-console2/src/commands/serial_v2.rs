@@ -2,29 +2,103 @@ use std::time::Duration;
 use std::sync::{Arc, atomic::{AtomicBool, Ordering}};
 use std::sync::mpsc;
 use crate::commands::compile::ProgressUpdate;
-use super::traits::{SerialProvider, RealSerialProvider};
+use super::traits::SerialProvider;
 
 /// Commands sent from the TUI to the background serial thread.
 pub enum SerialCommand {
     SendData(String),
+    /// Closes the port and ends the monitor loop.
+    Stop,
+    /// Releases the port (e.g. so an upload can use it) without ending the monitor.
+    /// The sender is signalled once the port is closed.
+    Pause(mpsc::Sender<()>),
+    /// Reopens the port after a `Pause`, retrying while the board resets.
+    Resume,
 }
 
-/// A high-performance Serial Monitor implementation with byte-level line buffering.
-pub fn run_serial_monitor(
-    port_name: String,
-    baud_rate: u32,
-    cancel_signal: Arc<AtomicBool>,
-    command_rx: mpsc::Receiver<SerialCommand>,
-    callback: impl FnMut(ProgressUpdate) + Send + 'static,
-) {
-    run_serial_monitor_with_provider(
-        &RealSerialProvider,
-        port_name,
-        baud_rate,
-        cancel_signal,
-        command_rx,
-        callback,
-    )
+/// Delay between reopen attempts while the board is still resetting.
+const RESUME_RETRY: Duration = Duration::from_millis(250);
+
+/// Stream handling switches for the serial monitor, taken from the application config.
+#[derive(Debug, Clone, Copy)]
+pub struct SerialOptions {
+    pub normalize_line_endings: bool,
+    pub validate_baud: bool,
+}
+
+/// Share of non-printable bytes above which the first read looks like a baud mismatch.
+const GARBAGE_RATIO_THRESHOLD: f64 = 0.3;
+
+/// Reads shorter than this are too small to judge.
+const MIN_BAUD_SAMPLE: usize = 8;
+
+/// Fraction of `bytes` that are neither printable ASCII nor common whitespace.
+pub fn garbage_ratio(bytes: &[u8]) -> f64 {
+    if bytes.is_empty() {
+        return 0.0;
+    }
+    let garbage = bytes.iter()
+        .filter(|&&b| !(b.is_ascii_graphic() || matches!(b, b' ' | b'\t' | b'\r' | b'\n')))
+        .count();
+    garbage as f64 / bytes.len() as f64
+}
+
+/// Warning for a first read that is mostly noise, which usually means the wrong baud rate.
+///>
+/// Bootloaders such as the ESP32 ROM print readable text at their configured
+/// speed, so a mostly non-printable first chunk is a strong hint that the
+/// monitor and the device disagree on the baud rate.
+///<
+pub fn baud_mismatch_warning(first_read: &[u8], baud_rate: u32) -> Option<String> {
+    if first_read.len() < MIN_BAUD_SAMPLE {
+        return None;
+    }
+    let ratio = garbage_ratio(first_read);
+    (ratio > GARBAGE_RATIO_THRESHOLD).then(|| format!(
+        "⚠ {:.0}% of the first bytes are unreadable at {} baud; check the device's baud rate.",
+        ratio * 100.0,
+        baud_rate
+    ))
+}
+
+/// Splits raw serial bytes into display lines.
+///>
+/// With normalization on, `\r\n`, `\n` and a lone `\r` each end exactly one
+/// line, so firmwares mixing terminators no longer produce blank rows. With it
+/// off every `\r` and `\n` ends a line, showing the stream as sent.
+///<
+pub struct LineSplitter {
+    buffer: Vec<u8>,
+    normalize: bool,
+    after_cr: bool,
+}
+
+impl LineSplitter {
+    pub fn new(normalize: bool) -> Self {
+        Self { buffer: Vec::new(), normalize, after_cr: false }
+    }
+
+    /// Feeds one byte, returning the line it completed, if any.
+    pub fn push(&mut self, byte: u8) -> Option<String> {
+        match byte {
+            b'\n' if self.normalize && self.after_cr => {
+                // Second half of a CRLF pair: the line was already emitted on '\r'
+                self.after_cr = false;
+                None
+            }
+            b'\r' | b'\n' => {
+                self.after_cr = byte == b'\r';
+                let line = String::from_utf8_lossy(&self.buffer).into_owned();
+                self.buffer.clear();
+                Some(line)
+            }
+            _ => {
+                self.after_cr = false;
+                self.buffer.push(byte);
+                None
+            }
+        }
+    }
 }
 
 /// A high-performance Serial Monitor implementation with byte-level line buffering.
@@ -32,12 +106,14 @@ pub fn run_serial_monitor(
 /// Operates in a background thread to prevent blocking the TUI. Handles 
 /// bidirectional communication with hardware, split-line reassembly for 
 /// UTF-8 data, and semantic tagging for different message types to support 
-/// consistent UI theming.
+/// consistent UI theming. With `options.validate_baud` set, the first chunk
+/// read is checked for noise and a warning is shown if the baud rate looks wrong.
 ///< 
 pub fn run_serial_monitor_with_provider(
     provider: &dyn SerialProvider,
     port_name: String,
     baud_rate: u32,
+    options: SerialOptions,
     cancel_signal: Arc<AtomicBool>,
     command_rx: mpsc::Receiver<SerialCommand>,
     mut callback: impl FnMut(ProgressUpdate) + Send + 'static,
@@ -48,7 +124,7 @@ pub fn run_serial_monitor_with_provider(
     let mut port = match port_result {
         Ok(p) => {
             callback(ProgressUpdate::OutputLine(format!("⇄ Connected to {} at {} baud.", port_name, baud_rate)));
-            p
+            Some(p)
         }
         Err(e) => {
             // Signal error using the requested icon (will be themed by App)
@@ -57,17 +133,41 @@ pub fn run_serial_monitor_with_provider(
         }
     };
 
+    if options.normalize_line_endings {
+        callback(ProgressUpdate::OutputLine("⬒ Normalizing serial line endings (\\r\\n, \\r → \\n).".to_string()));
+    }
+
     let mut read_buffer = [0u8; 1024];
-    let mut line_buffer = Vec::new();
+    let mut splitter = LineSplitter::new(options.normalize_line_endings);
+    let mut baud_checked = !options.validate_baud;
     
+    let mut paused = false;
+    let mut last_resume_attempt: Option<std::time::Instant> = None;
+
     while !cancel_signal.load(Ordering::SeqCst) {
         // 2. Process Outgoing Data (TX)
         // We drain the channel to handle multiple commands between reads
+        let mut stop_requested = false;
         while let Ok(cmd) = command_rx.try_recv() {
             match cmd {
+                SerialCommand::Stop => stop_requested = true,
+                SerialCommand::Pause(released) => {
+                    paused = true;
+                    port = None;
+                    callback(ProgressUpdate::OutputLine("⬒ Serial monitor paused for upload.".to_string()));
+                    let _ = released.send(());
+                }
+                SerialCommand::Resume => {
+                    paused = false;
+                    last_resume_attempt = None;
+                }
                 SerialCommand::SendData(data) => {
+                    let Some(open_port) = port.as_mut() else {
+                        callback(ProgressUpdate::OutputLine("✗ Write Error: serial monitor is paused".to_string()));
+                        continue;
+                    };
                     let to_send = format!("{}\n", data);
-                    if let Err(e) = port.write_all(to_send.as_bytes()) {
+                    if let Err(e) = open_port.write_all(to_send.as_bytes()) {
                         callback(ProgressUpdate::OutputLine(format!("✗ Write Error: {}", e)));
                     } else {
                         // Semantic tag for TX info (Info icon, bold, no space)
@@ -76,27 +176,38 @@ pub fn run_serial_monitor_with_provider(
                 }
             }
         }
+        if stop_requested {
+            break;
+        }
+
+        // Reopen after a resume; the board may still be rebooting, so keep retrying
+        if port.is_none() && !paused && last_resume_attempt.is_none_or(|t| t.elapsed() >= RESUME_RETRY) {
+            last_resume_attempt = Some(std::time::Instant::now());
+            if let Ok(p) = provider.open(&port_name, baud_rate) {
+                callback(ProgressUpdate::OutputLine(format!("⇄ Reconnected to {} at {} baud.", port_name, baud_rate)));
+                port = Some(p);
+            }
+        }
+
+        let Some(open_port) = port.as_mut() else {
+            std::thread::sleep(Duration::from_millis(10));
+            continue;
+        };
 
         // 3. Process Incoming Data (RX)
         // We read raw bytes and only flush complete lines to the UI
-        match port.read(&mut read_buffer) {
+        match open_port.read(&mut read_buffer) {
             Ok(n) if n > 0 => {
+                if !baud_checked {
+                    baud_checked = true;
+                    if let Some(warning) = baud_mismatch_warning(&read_buffer[..n], baud_rate) {
+                        callback(ProgressUpdate::OutputLine(warning));
+                    }
+                }
                 for &byte in &read_buffer[..n] {
-                    // Split on newline or carriage return
-                    if byte == b'\n' || byte == b'\r' {
-                        if !line_buffer.is_empty() {
-                            if let Ok(s) = String::from_utf8(line_buffer.clone()) {
-                                // Send raw serial data (no prefix here, let board speak)
-                                callback(ProgressUpdate::OutputLine(s));
-                            } else {
-                                // If UTF-8 fails, try lossy conversion
-                                let s = String::from_utf8_lossy(&line_buffer).to_string();
-                                callback(ProgressUpdate::OutputLine(s));
-                            }
-                            line_buffer.clear();
-                        }
-                    } else {
-                        line_buffer.push(byte);
+                    if let Some(line) = splitter.push(byte) {
+                        // Send raw serial data (no prefix here, let board speak)
+                        callback(ProgressUpdate::OutputLine(line));
                     }
                 }
             }
@@ -2,6 +2,7 @@
 use super::upload::*;
 use super::serial_v2::*;
 use super::traits::*;
+use super::process::{LineAssembler, OutputChunk};
 use crate::commands::HistoryManager;
 use crate::commands::predictor::CompileStage;
 use std::sync::{Arc, Mutex};
@@ -241,6 +242,7 @@ fn test_run_serial_monitor_success() {
             &mock_provider,
             "COM3".to_string(),
             115200,
+            SerialOptions { normalize_line_endings: true, validate_baud: false },
             cancel_signal_clone,
             rx,
             move |update| {
@@ -345,6 +347,29 @@ fn test_history_manager_basic_ops() {
     let _ = std::fs::remove_dir_all(&temp_dir);
 }
 
+#[test]
+fn test_history_manager_configured_path() {
+    let temp_dir = std::env::temp_dir().join("dev-console-test-history-configured");
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    let configured = temp_dir.join("custom").join("history.json");
+
+    let resolved = HistoryManager::resolve_path(Some(configured.to_str().unwrap()));
+    assert_eq!(resolved, configured);
+    assert_eq!(HistoryManager::resolve_path(None), PathBuf::from(crate::commands::history::DEFAULT_HISTORY_PATH));
+
+    let mut manager = HistoryManager::default();
+    let mut times = std::collections::HashMap::new();
+    times.insert(CompileStage::Compiling, 7.0);
+    manager.record_run("configured_sketch", times);
+    manager.save(&resolved).expect("Failed to save history");
+
+    assert!(configured.exists());
+    let loaded = HistoryManager::load(&resolved);
+    assert_eq!(loaded.sketches["configured_sketch"].stage_times["Compiling"], vec![7.0]);
+
+    let _ = std::fs::remove_dir_all(&temp_dir);
+}
+
 #[test]
 fn test_history_manager_get_stats_empty() {
     let manager = HistoryManager::default();
@@ -470,3 +495,286 @@ fn test_command_utils() {
     assert_eq!(extract_current_file("  - my_lib.ino"), Some("my_lib.ino".to_string()));
     assert_eq!(extract_current_file("Building project.S"), Some("project.S".to_string()));
 }
+
+#[test]
+fn test_line_assembler_carriage_return_overwrites() {
+    let mut assembler = LineAssembler::default();
+    let mut chunks: Vec<OutputChunk> = b"Uploading\n 10%\r 55%\r100%\r\nDone"
+        .iter()
+        .filter_map(|&b| assembler.push(b))
+        .collect();
+    chunks.extend(assembler.flush());
+
+    assert_eq!(chunks, vec![
+        OutputChunk::Line("Uploading".to_string()),
+        OutputChunk::Line(" 10%".to_string()),
+        OutputChunk::Overwrite(" 55%".to_string()),
+        OutputChunk::Overwrite("100%".to_string()),
+        OutputChunk::Line("Done".to_string()),
+    ]);
+}
+
+#[test]
+fn test_desktop_notification_payload_for_successful_build() {
+    let note = super::DesktopNotification::completion("Build", "blink", true, std::time::Duration::from_millis(42_350));
+    assert_eq!(note.title, "Build succeeded");
+    assert_eq!(note.body, "blink succeeded after 42.4s");
+
+    let failed = super::DesktopNotification::completion("Upload", "blink", false, std::time::Duration::from_secs(3));
+    assert_eq!(failed.title, "Upload failed");
+}
+
+#[test]
+fn test_line_splitter_normalizes_mixed_line_endings() {
+    let split = |normalize: bool| {
+        let mut splitter = LineSplitter::new(normalize);
+        b"boot\r\nready\nvalue=1\r\n\r\nerr\rdone\n".iter().filter_map(|&b| splitter.push(b)).collect::<Vec<_>>()
+    };
+
+    assert_eq!(split(true), vec!["boot", "ready", "value=1", "", "err", "done"]);
+    // Raw mode keeps one break per terminator byte
+    assert_eq!(split(false), vec!["boot", "", "ready", "value=1", "", "", "", "err", "done"]);
+}
+
+#[test]
+fn test_failing_pre_hook_prevents_main_command() {
+    let mut mock_runner = MockCommandRunner::new();
+    mock_runner.expect_spawn()
+        .times(1)
+        .returning(|_| {
+            let mut mock_child = MockChildProcess::new();
+            mock_child.expect_stdout().return_once(|| Some(Box::new(std::io::Cursor::new("format error\n"))));
+            mock_child.expect_stderr().return_once(|| Some(Box::new(std::io::Cursor::new(""))));
+            mock_child.expect_try_wait().returning(|| Ok(Some(create_exit_status(1))));
+            Ok(Box::new(mock_child))
+        });
+
+    let hooks = crate::config::HooksConfig {
+        pre_build: vec!["clang-format --dry-run".to_string(), "never-run".to_string()],
+        post_build: vec!["size-report".to_string()],
+    };
+    let updates = Arc::new(Mutex::new(Vec::new()));
+    let updates_clone = updates.clone();
+    let main_ran = Arc::new(AtomicBool::new(false));
+    let main_ran_clone = main_ran.clone();
+
+    super::run_with_hooks(
+        &mock_runner,
+        &hooks,
+        std::path::Path::new("."),
+        Arc::new(AtomicBool::new(false)),
+        move |update| updates_clone.lock().unwrap().push(update),
+        move |_callback| main_ran_clone.store(true, std::sync::atomic::Ordering::SeqCst),
+    );
+
+    assert!(!main_ran.load(std::sync::atomic::Ordering::SeqCst));
+    let updates = updates.lock().unwrap();
+    assert!(updates.contains(&ProgressUpdate::OutputLine("[hook] $ clang-format --dry-run".to_string())));
+    assert!(matches!(updates.last(), Some(ProgressUpdate::Failed(e)) if e.contains("pre-build hook 'clang-format --dry-run' failed")));
+}
+
+#[test]
+fn test_post_hooks_finish_before_completion_is_reported() {
+    let hook_exit = |code: i32| move |_: std::process::Command| -> std::io::Result<Box<dyn ChildProcess>> {
+        let mut mock_child = MockChildProcess::new();
+        mock_child.expect_stdout().return_once(|| Some(Box::new(std::io::Cursor::new("size report\n"))));
+        mock_child.expect_stderr().return_once(|| Some(Box::new(std::io::Cursor::new(""))));
+        mock_child.expect_try_wait().returning(move || Ok(Some(create_exit_status(code))));
+        Ok(Box::new(mock_child))
+    };
+    let hooks = crate::config::HooksConfig {
+        pre_build: Vec::new(),
+        post_build: vec!["size-report".to_string()],
+    };
+    let run = |runner: &MockCommandRunner| {
+        let updates = Arc::new(Mutex::new(Vec::new()));
+        let updates_clone = updates.clone();
+        super::run_with_hooks(
+            runner,
+            &hooks,
+            std::path::Path::new("."),
+            Arc::new(AtomicBool::new(false)),
+            move |update| updates_clone.lock().unwrap().push(update),
+            |mut callback| callback(ProgressUpdate::CompletedWithMetrics { stage_times: Default::default() }),
+        );
+        let updates = updates.lock().unwrap().clone();
+        updates
+    };
+
+    let mut mock_runner = MockCommandRunner::new();
+    mock_runner.expect_spawn().times(1).returning(hook_exit(0));
+    let updates = run(&mock_runner);
+    assert_eq!(updates.first(), Some(&ProgressUpdate::OutputLine("[hook] $ size-report".to_string())));
+    assert_eq!(updates.last(), Some(&ProgressUpdate::CompletedWithMetrics { stage_times: Default::default() }));
+
+    let mut mock_runner = MockCommandRunner::new();
+    mock_runner.expect_spawn().times(1).returning(hook_exit(1));
+    let updates = run(&mock_runner);
+    assert!(!updates.iter().any(|u| matches!(u, ProgressUpdate::Failed(_))));
+    let warning = ProgressUpdate::OutputLine("⚠ post-build hook 'size-report' failed (build result unchanged)".to_string());
+    assert_eq!(updates[updates.len() - 2], warning);
+    assert_eq!(updates.last(), Some(&ProgressUpdate::CompletedWithMetrics { stage_times: Default::default() }));
+}
+
+#[test]
+fn test_verbose_line_ignores_error_flags_but_keeps_diagnostics() {
+    use crate::commands::is_verbose_line;
+    let gcc = "xtensa-esp32s3-elf-g++ -c -Os -Werror=return-type -Wno-error=unused-variable -o main.cpp.o main.cpp";
+    assert!(is_verbose_line(gcc));
+    assert!(!is_verbose_line("/src/main.cpp:12:5: error: 'ledPin' was not declared -Wall -Wextra -Os"));
+    assert!(!is_verbose_line("/src/main.cpp:3:1: warning: unused variable 'x' [-Wunused-variable] -a -b"));
+    assert!(!is_verbose_line("Error during build: exit status 1 -a -b -c"));
+    assert!(!is_verbose_line("WARNING: library claims to run on esp32 -a -b -c"));
+}
+
+#[test]
+fn test_error_block_extracts_contiguous_diagnostic_lines() {
+    use crate::commands::compile_parser::extract_error_block;
+    let output = [
+        "⮻ Starting compile...",
+        "Compiling sketch...",
+        "/src/main.cpp: In function 'void setup()':",
+        "/src/main.cpp:12:5: error: 'ledPin' was not declared in this scope",
+        "   12 |     ledPin = 4;",
+        "      |     ^~~~~~",
+        "/src/main.cpp:3:1: note: suggested alternative: 'LED_PIN'",
+        "",
+        "exit status 1",
+    ];
+
+    let block = extract_error_block(&output);
+    assert_eq!(block, vec![
+        "/src/main.cpp: In function 'void setup()':",
+        "/src/main.cpp:12:5: error: 'ledPin' was not declared in this scope",
+        "   12 |     ledPin = 4;",
+        "      |     ^~~~~~",
+        "/src/main.cpp:3:1: note: suggested alternative: 'LED_PIN'",
+    ]);
+
+    // Without an error the tail after the last command marker is used
+    let clean = ["⮻ Starting compile...", "old", "\x1b[36m⮻\x1b[0m Starting upload...", "Writing at 0x1000"];
+    assert_eq!(extract_error_block(&clean), vec!["Writing at 0x1000"]);
+}
+
+#[test]
+fn test_mostly_unreadable_first_read_warns_about_baud() {
+    // What a 115200 baud boot banner looks like when read at 9600
+    let noise = [0x00, 0xf8, 0x80, 0xfe, 0x1c, b'x', 0x86, 0xe0, 0x98, 0x00, 0xfc, 0x06];
+    let warning = baud_mismatch_warning(&noise, 9600).expect("noise should warn");
+    assert!(warning.contains("9600 baud"));
+
+    assert_eq!(baud_mismatch_warning(b"rst:0x1 (POWERON_RESET),boot:0x8\r\n", 115200), None);
+    // Too short to judge
+    assert_eq!(baud_mismatch_warning(&[0xff, 0xfe], 9600), None);
+}
+
+#[test]
+fn test_erase_flash_invokes_esptool_on_profile_port() {
+    use super::erase::*;
+
+    let settings = Settings {
+        sketch_directory: "test_sketch".to_string(),
+        sketch_name: "test_sketch".to_string(),
+        fqbn: "esp32:esp32:esp32s3".to_string(),
+        port: "COM3".to_string(),
+        baudrate: 115200,
+        board_model: "esp32s3".to_string(),
+        env: "arduino".to_string(),
+    };
+    assert!(is_esp_board(&settings.fqbn));
+    assert!(!is_esp_board("arduino:avr:uno"));
+
+    let mut mock_child = MockChildProcess::new();
+    mock_child.expect_stdout()
+        .return_once(|| Some(Box::new(std::io::Cursor::new("Erasing flash (this may take a while)...\nChip erase completed successfully\n"))));
+    mock_child.expect_stderr()
+        .return_once(|| Some(Box::new(std::io::Cursor::new(""))));
+    let mut wait_count = 0;
+    mock_child.expect_try_wait()
+        .returning(move || {
+            wait_count += 1;
+            Ok((wait_count > 5).then(|| create_exit_status(0)))
+        });
+
+    let mut mock_runner = MockCommandRunner::new();
+    mock_runner.expect_spawn()
+        .withf(|cmd| {
+            let args: Vec<_> = cmd.get_args().map(|a| a.to_string_lossy().into_owned()).collect();
+            cmd.get_program() == ESPTOOL && args == ["--port", "COM3", "erase_flash"]
+        })
+        .return_once(|_| Ok(Box::new(mock_child)));
+
+    let updates = Arc::new(Mutex::new(Vec::new()));
+    let updates_clone = updates.clone();
+    run_erase_flash_with_runner(&mock_runner, &settings, Arc::new(AtomicBool::new(false)), move |update| {
+        updates_clone.lock().unwrap().push(update);
+    });
+
+    let updates = updates.lock().unwrap();
+    assert!(updates.contains(&ProgressUpdate::OutputLine("Chip erase completed successfully".to_string())));
+    assert_eq!(updates.last(), Some(&ProgressUpdate::Completed("Flash erased on COM3.".to_string())));
+}
+
+#[test]
+fn test_find_map_file_prefers_sketch_map_in_build_dir() {
+    use super::map_report::find_map_file;
+
+    let build_dir = std::env::temp_dir().join("dc2_map_report_build");
+    let _ = std::fs::remove_dir_all(&build_dir);
+    std::fs::create_dir_all(&build_dir).unwrap();
+    assert_eq!(find_map_file(&build_dir, "blink"), None);
+
+    std::fs::write(build_dir.join("bootloader.map"), "bootloader").unwrap();
+    std::fs::write(build_dir.join("blink.ino.bin"), "firmware").unwrap();
+    assert_eq!(find_map_file(&build_dir, "blink"), Some(build_dir.join("bootloader.map")));
+
+    std::fs::write(build_dir.join("blink.ino.map"), "Memory Configuration").unwrap();
+    assert_eq!(find_map_file(&build_dir, "blink.ino"), Some(build_dir.join("blink.ino.map")));
+    let _ = std::fs::remove_dir_all(&build_dir);
+}
+
+#[test]
+fn test_compile_all_continues_past_failures_and_forwards_profile_updates() {
+    use super::compile_all::*;
+
+    let settings = Settings {
+        sketch_directory: "sketches".to_string(),
+        sketch_name: "blink".to_string(),
+        fqbn: "esp32:esp32:esp32s3".to_string(),
+        port: "COM3".to_string(),
+        baudrate: 115200,
+        board_model: "esp32s3".to_string(),
+        env: "arduino".to_string(),
+    };
+    let profiles: Vec<ProfileBuild> = ["alpha", "beta", "gamma"].iter()
+        .map(|id| ProfileBuild { id: id.to_string(), settings: settings.clone(), stats: StageStats::default() })
+        .collect();
+
+    let updates = Arc::new(Mutex::new(Vec::new()));
+    let updates_clone = updates.clone();
+    let mut calls = 0;
+    run_compile_all_with(profiles, Arc::new(AtomicBool::new(false)), move |update| {
+        updates_clone.lock().unwrap().push(update);
+    }, |_, _, _, mut callback| {
+        calls += 1;
+        callback(ProgressUpdate::Percentage(50.0));
+        if calls == 2 {
+            callback(ProgressUpdate::Failed("Compilation failed (see output for details).".to_string()));
+        } else {
+            callback(ProgressUpdate::CompletedWithMetrics { stage_times: Default::default() });
+        }
+    });
+
+    let updates = updates.lock().unwrap();
+    let started = |index: usize, id: &str| ProgressUpdate::ProfileStarted { index, total: 3, id: id.to_string() };
+    assert_eq!(updates[0], started(0, "alpha"));
+    assert_eq!(updates[1], ProgressUpdate::Percentage(50.0));
+    assert!(matches!(updates[2], ProgressUpdate::CompletedWithMetrics { .. }));
+    assert_eq!(updates[3], started(1, "beta"));
+    assert_eq!(updates[5], ProgressUpdate::Failed("Compilation failed (see output for details).".to_string()));
+    assert_eq!(updates[6], started(2, "gamma"));
+    assert_eq!(updates.last(), Some(&ProgressUpdate::BatchCompleted { succeeded: 2, failed: 1 }));
+
+    assert_eq!(batch_percentage(2, 50.0, 5), 50.0);
+    assert_eq!(batch_percentage(0, 0.0, 0), 100.0);
+}
@@ -11,6 +11,12 @@ pub trait CommandRunner: Send + Sync {
     fn spawn(&self, command: Command) -> io::Result<Box<dyn ChildProcess>>;
 }
 
+impl std::fmt::Debug for dyn CommandRunner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("CommandRunner")
+    }
+}
+
 /// Trait for interacting with a spawned child process.
 #[cfg_attr(test, automock)]
 pub trait ChildProcess: Send {
@@ -37,6 +43,12 @@ pub trait SerialProvider: Send + Sync {
     fn open(&self, port_name: &str, baud_rate: u32) -> Result<Box<dyn SerialPort>, serialport::Error>;
 }
 
+impl std::fmt::Debug for dyn SerialProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SerialProvider")
+    }
+}
+
 /// Rich metadata for a discovered serial port.
 #[derive(Debug, Clone, PartialEq)]
 pub struct PortInfo {
@@ -77,8 +77,8 @@ pub fn run_upload_with_runner(
     let callback_clone = callback.clone();
     let state_clone = upload_state.clone();
     
-    let result = process_handler.read_output(cancel_signal.clone(), move |line| {
-        let cleaned = crate::commands::utils::remove_ansi_escapes(&line);
+    let result = process_handler.read_output(cancel_signal.clone(), move |chunk| {
+        let cleaned = crate::commands::utils::remove_ansi_escapes(chunk.text());
         let line_lower = cleaned.to_lowercase();
 
         let mut cb = callback_clone.lock().unwrap();
@@ -119,7 +119,7 @@ pub fn run_upload_with_runner(
 
         let progress = state.calculate_progress();
         cb(ProgressUpdate::Percentage(progress));
-        cb(ProgressUpdate::OutputLine(line));
+        cb(chunk.into());
     });
 
     let mut cb = callback.lock().unwrap();
@@ -30,7 +30,6 @@ pub fn extract_percentage(line: &str) -> Option<f64> {
 }
 
 /// Attempts to identify a source file reference within a line of text.
-#[allow(dead_code)]
 pub fn extract_current_file(line: &str) -> Option<String> {
     if let Some(captures) = FILE_RE.captures(line) {
         return Some(captures[1].to_string());
@@ -19,6 +19,10 @@ pub struct ApplicationConfig {
     pub min_width: u16,
     #[serde(default = "default_min_height")]
     pub min_height: u16,
+    #[serde(default = "default_compact_min_width")]
+    pub compact_min_width: u16,
+    #[serde(default = "default_compact_min_height")]
+    pub compact_min_height: u16,
     #[serde(default)]
     pub show_terminal_size: bool,
     #[serde(default)]
@@ -27,6 +31,151 @@ pub struct ApplicationConfig {
     pub status_bar: StatusBarConfig,
     #[serde(default)]
     pub bindings: BindingsConfig,
+    #[serde(default)]
+    pub history_path: Option<String>,
+    #[serde(default = "default_max_output_lines")]
+    pub max_output_lines: usize,
+    #[serde(default)]
+    pub auto_monitor_after_upload: bool,
+    #[serde(default = "default_progress_smoothing")]
+    pub progress_smoothing: f64,
+    #[serde(default)]
+    pub sketch_root: Option<String>,
+    #[serde(default)]
+    pub notify_on_complete: CompletionNotify,
+    #[serde(default)]
+    pub desktop_notifications: bool,
+    #[serde(default)]
+    pub output_timestamps: bool,
+    #[serde(default = "default_true")]
+    pub normalize_serial_line_endings: bool,
+    #[serde(default)]
+    pub validate_serial_baud: bool,
+    #[serde(default = "default_true")]
+    pub quiet_startup: bool,
+    #[serde(default)]
+    pub persist_last_output: bool,
+    #[serde(default)]
+    pub pause_monitor_during_upload: bool,
+    #[serde(default)]
+    pub tab_transition: TabTransition,
+    #[serde(default)]
+    pub hooks: HooksConfig,
+    #[serde(default)]
+    pub autoscroll: AutoscrollConfig,
+    #[serde(default)]
+    pub log: LogConfig,
+}
+
+/// The application's own diagnostic log (config loads, errors, thread spawns).
+#[derive(Debug, Deserialize, Clone)]
+pub struct LogConfig {
+    #[serde(default)]
+    pub level: LogLevel,
+    /// Defaults to `.dev-console/app.log`; rotated copies get `.1` and `.2` suffixes.
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default = "default_log_max_bytes")]
+    pub max_bytes: u64,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self { level: LogLevel::default(), path: None, max_bytes: default_log_max_bytes() }
+    }
+}
+
+/// Diagnostic log verbosity; each level includes the ones before it.
+#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq, PartialOrd)]
+#[serde(rename_all = "snake_case")]
+pub enum LogLevel {
+    Off,
+    Error,
+    #[default]
+    Info,
+    Debug,
+}
+
+impl LogLevel {
+    pub fn label(self) -> &'static str {
+        match self {
+            LogLevel::Off => "OFF",
+            LogLevel::Error => "ERROR",
+            LogLevel::Info => "INFO",
+            LogLevel::Debug => "DEBUG",
+        }
+    }
+}
+
+/// Output autoscroll state applied when each kind of command starts.
+///>
+/// The runtime autoscroll toggle still overrides this until the next command.
+///<
+#[derive(Debug, Deserialize, Clone)]
+pub struct AutoscrollConfig {
+    #[serde(default = "default_true")]
+    pub compile: bool,
+    #[serde(default = "default_true")]
+    pub upload: bool,
+    #[serde(default = "default_true")]
+    pub monitor: bool,
+}
+
+impl Default for AutoscrollConfig {
+    fn default() -> Self {
+        Self { compile: true, upload: true, monitor: true }
+    }
+}
+
+/// Shell commands run in the sketch directory around compile and upload.
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct HooksConfig {
+    /// Run in order before the command; the first failure aborts it.
+    #[serde(default)]
+    pub pre_build: Vec<String>,
+    /// Run in order after a successful command; failures only warn.
+    #[serde(default)]
+    pub post_build: Vec<String>,
+}
+
+/// How a finished build or upload is announced beyond the status bar.
+///>
+/// Every mode other than `off` also raises a toast (success or error level).
+/// `bell` rings the terminal bell, `flash` briefly tints the whole screen.
+///<
+#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CompletionNotify {
+    #[default]
+    Off,
+    Toast,
+    Bell,
+    Flash,
+    Both,
+}
+
+/// Cosmetic effect played over the tab content when switching main tabs.
+///>
+/// `fade` brings the new content up from dimmed colors, `slide` eases it in
+/// from the right. Either lasts a few animation ticks.
+///<
+#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TabTransition {
+    #[default]
+    Off,
+    Fade,
+    Slide,
+}
+
+impl CompletionNotify {
+    pub fn rings_bell(self) -> bool {
+        matches!(self, CompletionNotify::Bell | CompletionNotify::Both)
+    }
+
+    pub fn flashes(self) -> bool {
+        matches!(self, CompletionNotify::Flash | CompletionNotify::Both)
+    }
 }
 
 #[derive(Debug, Deserialize, Default, Clone)]
@@ -34,8 +183,21 @@ pub struct StatusBarConfig {
     pub default_text: String,
 }
 
+impl StatusBarConfig {
+    /// The configured default status, falling back to "Status: Ready" when unset.
+    pub fn text(&self) -> String {
+        if self.default_text.is_empty() { "Status: Ready".to_string() } else { self.default_text.clone() }
+    }
+}
+
 fn default_min_width() -> u16 { 80 }
 fn default_min_height() -> u16 { 21 }
+fn default_compact_min_width() -> u16 { 40 }
+fn default_compact_min_height() -> u16 { 16 }
+fn default_max_output_lines() -> usize { 2000 }
+fn default_progress_smoothing() -> f64 { 0.22 }
+fn default_true() -> bool { true }
+fn default_log_max_bytes() -> u64 { 512 * 1024 }
 
 #[derive(Debug, Deserialize, Default, Clone)]
 pub struct BindingConfig {
@@ -51,6 +213,9 @@ pub struct BindingsConfig {
     pub separator: String,
     #[serde(default)]
     pub items: Vec<BindingConfig>,
+    /// Render tab bindings as clickable buttons instead of hint text.
+    #[serde(default)]
+    pub buttons: bool,
 }
 
 #[derive(Debug, Deserialize, Default, Clone)]
@@ -71,6 +236,16 @@ pub struct TabBarConfig {
     pub min_tab_width: u16,
     #[serde(default)]
     pub tab_tooltips: bool,
+    /// Underline one letter per tab and activate the tab on Alt+letter.
+    #[serde(default)]
+    pub accelerators: bool,
+    /// Text drawn between tabs; defaults to "─".
+    pub separator: Option<String>,
+    /// Spaces on each side of a tab name; defaults to 1.
+    pub tab_padding: Option<u16>,
+    /// Pad every tab to the width of the widest one.
+    #[serde(default)]
+    pub equal_width: bool,
     #[serde(default)]
     pub navigation: Navigation,
     #[serde(default)]
@@ -156,6 +331,9 @@ pub struct Sketch {
     pub connection: String,
     pub device: String,
     pub mqtt: String,
+    /// Line written to the serial monitor once it reconnects after a flash.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_flash_serial: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -206,34 +384,55 @@ pub fn load_command_settings() -> Result<Settings> {
 }
 
 fn extract_settings_from_profile(profile_config: &ProfileConfig) -> Result<Settings> {
-    if let Some(first_sketch) = profile_config.sketches.first() {
-        let device = profile_config.devices.iter()
-            .find(|d| d.id == first_sketch.device);
-        let connection = profile_config.connections.iter()
-            .find(|c| c.id == first_sketch.connection);
-        
-        if let (Some(device), Some(connection)) = (device, connection) {
-            let path = std::path::Path::new(&first_sketch.path);
-            let sketch_directory = path.parent()
-                .map(|p| p.to_string_lossy().into_owned())
-                .unwrap_or_default();
-            let sketch_name = path.file_stem()
-                .and_then(|s| s.to_str())
-                .unwrap_or("sketch")
-                .to_string();
-            
-            return Ok(Settings {
-                sketch_directory,
-                sketch_name,
-                fqbn: device.fbqn.clone(),
-                port: connection.port.clone(),
-                baudrate: connection.baudrate,
-                board_model: device.board_model.clone(),
-                env: if connection.compiler == "arduino-cli" { "arduino" } else { "windows" }.to_string(),
-            });
-        }
-    }
-    Err(eyre::eyre!("No valid sketch configuration found in config.yaml"))
+    profile_config.sketches.first()
+        .and_then(|sketch| settings_for_sketch(profile_config, sketch))
+        .ok_or_else(|| eyre::eyre!("No valid sketch configuration found in config.yaml"))
+}
+
+/// Resolves the settings for the sketch profile with the given id.
+///>
+/// An unknown id, or one whose device/connection references are broken,
+/// produces an error listing the available profile ids.
+///<
+pub fn resolve_profile_settings(profile_config: &ProfileConfig, profile_id: &str) -> Result<Settings> {
+    profile_config.sketches.iter()
+        .find(|s| s.id == profile_id)
+        .and_then(|sketch| settings_for_sketch(profile_config, sketch))
+        .ok_or_else(|| {
+            let ids: Vec<String> = profile_config.sketches.iter().map(|s| s.id.clone()).collect();
+            unknown_profile_error(profile_id, &ids)
+        })
+}
+
+/// Builds the error reported when a requested profile id does not exist.
+pub fn unknown_profile_error(profile_id: &str, available: &[String]) -> eyre::Report {
+    eyre::eyre!("Unknown profile '{}'. Available profiles: {}", profile_id, available.join(", "))
+}
+
+fn settings_for_sketch(profile_config: &ProfileConfig, sketch: &Sketch) -> Option<Settings> {
+    let device = profile_config.devices.iter()
+        .find(|d| d.id == sketch.device)?;
+    let connection = profile_config.connections.iter()
+        .find(|c| c.id == sketch.connection)?;
+
+    let path = std::path::Path::new(&sketch.path);
+    let sketch_directory = path.parent()
+        .map(|p| p.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let sketch_name = path.file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("sketch")
+        .to_string();
+
+    Some(Settings {
+        sketch_directory,
+        sketch_name,
+        fqbn: device.fbqn.clone(),
+        port: connection.port.clone(),
+        baudrate: connection.baudrate,
+        board_model: device.board_model.clone(),
+        env: if connection.compiler == "arduino-cli" { "arduino" } else { "windows" }.to_string(),
+    })
 }
 
 /// Loads the main application UI configuration from `build-config.yaml`.
@@ -293,6 +492,8 @@ tab_bars: []
         let config = parse_config(yaml).unwrap();
         assert_eq!(config.application.min_width, 80);
         assert_eq!(config.application.min_height, 21);
+        assert_eq!(config.application.max_output_lines, 2000);
+        assert_eq!(config.application.progress_smoothing, 0.22);
     }
 
     #[test]
@@ -348,6 +549,7 @@ sketches:
                 connection: "c1".to_string(),
                 device: "d1".to_string(),
                 mqtt: "m1".to_string(),
+                post_flash_serial: None,
             }],
         };
         let settings = extract_settings_from_profile(&profile).unwrap();
@@ -356,6 +558,46 @@ sketches:
         assert_eq!(settings.env, "arduino");
     }
 
+    #[test]
+    fn test_resolve_profile_settings() {
+        let yaml = r#"
+connections:
+  - id: conn1
+    compiler: arduino-cli
+    port: COM1
+    baudrate: 115200
+  - id: conn2
+    compiler: arduino-cli
+    port: COM7
+    baudrate: 921600
+devices:
+  - id: dev1
+    board_model: esp32
+    fbqn: esp32:esp32:esp32
+mqtt: []
+sketches:
+  - id: blink
+    path: /sketches/blink/blink.ino
+    connection: conn1
+    device: dev1
+    mqtt: none
+  - id: sensor
+    path: /sketches/sensor/sensor.ino
+    connection: conn2
+    device: dev1
+    mqtt: none
+"#;
+        let config = parse_profile_config(yaml).unwrap();
+        let settings = resolve_profile_settings(&config, "sensor").unwrap();
+        assert_eq!(settings.sketch_name, "sensor");
+        assert_eq!(settings.port, "COM7");
+        assert_eq!(settings.baudrate, 921600);
+
+        let err = resolve_profile_settings(&config, "missing").unwrap_err().to_string();
+        assert!(err.contains("Unknown profile 'missing'"));
+        assert!(err.contains("blink, sensor"));
+    }
+
     #[test]
     fn test_extract_settings_no_sketches() {
         let profile = ProfileConfig {
@@ -562,6 +804,10 @@ sketches:
 
         
 
+                                post_flash_serial: None,
+
+        
+
                             }],
 
         
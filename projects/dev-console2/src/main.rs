@@ -8,6 +8,7 @@ mod config;
 mod terminal;
 mod widgets;
 mod commands;
+mod batch;
 
 use app::{App, Message};
 
@@ -22,8 +23,18 @@ fn main() -> Result<()> {
     color_eyre::install()?;
     terminal::install_panic_hook();
 
+    // Headless batch mode skips the TUI entirely
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(options) = batch::BatchOptions::from_args(&args)? {
+        let success = batch::run(&options)?;
+        std::process::exit(if success { 0 } else { 1 });
+    }
+
     // Create application state first (so config errors print to stderr before terminal takeover)
     let mut app = App::new()?;
+    if let Some(profile_id) = batch::profile_from_args(&args)? {
+        app.select_profile(&profile_id)?;
+    }
 
     // Initialize terminal
     let mut terminal = terminal::init_terminal()?;
@@ -50,6 +61,10 @@ fn main() -> Result<()> {
             terminal.draw(|f| app.view(f))?;
             app.should_redraw = false;
         }
+
+        if app.take_bell() {
+            terminal::ring_bell()?;
+        }
     }
 
     // Restore terminal
@@ -47,6 +47,14 @@ pub fn restore_terminal() -> io::Result<()> {
     Ok(())
 }
 
+/// Writes the BEL character so the terminal beeps or flags the window.
+pub fn ring_bell() -> io::Result<()> {
+    use std::io::Write;
+    let mut stdout = io::stdout();
+    stdout.write_all(b"\x07")?;
+    stdout.flush()
+}
+
 /// Installs a custom panic hook to prevent terminal corruption.
 ///>
 /// If the application crashes, this hook ensures the terminal is restored to 
@@ -0,0 +1,172 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Cell, Paragraph, Row, StatefulWidget, Table, TableState, Widget},
+};
+use crate::app::ui_state::{format_relative_time, unix_now};
+use crate::commands::BuildRecord;
+
+/// Column the history table is ordered by.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum HistorySort {
+    #[default]
+    Newest,
+    Profile,
+    Duration,
+    Outcome,
+}
+
+impl HistorySort {
+    /// The next column in the cycle.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Newest => Self::Profile,
+            Self::Profile => Self::Duration,
+            Self::Duration => Self::Outcome,
+            Self::Outcome => Self::Newest,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Newest => "newest",
+            Self::Profile => "profile",
+            Self::Duration => "duration",
+            Self::Outcome => "outcome",
+        }
+    }
+}
+
+/// Scrollable table of recent builds across all profiles.
+///>
+/// Rows start newest first; `cycle_sort` reorders them by profile, slowest
+/// duration or failures first. Ties always fall back to newest first so the
+/// order is stable between renders.
+///<
+#[derive(Debug, Clone, Default)]
+pub struct BuildHistory {
+    rows: Vec<BuildRecord>,
+    selected: usize,
+    sort: HistorySort,
+}
+
+impl BuildHistory {
+    pub fn new(records: &[BuildRecord]) -> Self {
+        let mut history = Self { rows: records.to_vec(), selected: 0, sort: HistorySort::Newest };
+        history.apply_sort();
+        history
+    }
+
+    pub fn rows(&self) -> &[BuildRecord] {
+        &self.rows
+    }
+
+    pub fn selected(&self) -> Option<&BuildRecord> {
+        self.rows.get(self.selected)
+    }
+
+    pub fn sort(&self) -> HistorySort {
+        self.sort
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.rows.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Orders rows by the next column and moves the selection to the top.
+    pub fn cycle_sort(&mut self) {
+        self.sort = self.sort.next();
+        self.apply_sort();
+        self.selected = 0;
+    }
+
+    fn apply_sort(&mut self) {
+        self.rows.sort_by(|a, b| {
+            let primary = match self.sort {
+                HistorySort::Newest => std::cmp::Ordering::Equal,
+                HistorySort::Profile => a.sketch_id.cmp(&b.sketch_id),
+                HistorySort::Duration => b.duration_secs.total_cmp(&a.duration_secs),
+                HistorySort::Outcome => a.success.cmp(&b.success),
+            };
+            primary.then(b.finished_at.cmp(&a.finished_at))
+        });
+    }
+}
+
+impl Widget for &BuildHistory {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered()
+            .title(format!(" Build History ({} builds, sorted by {}) ", self.rows.len(), self.sort.label()));
+        if self.rows.is_empty() {
+            Paragraph::new("No builds recorded yet.")
+                .style(Style::default().fg(Color::DarkGray))
+                .block(block)
+                .render(area, buf);
+            return;
+        }
+
+        let now = unix_now();
+        let rows = self.rows.iter().map(|record| {
+            let (outcome, color) = if record.success { ("ok", Color::Green) } else { ("failed", Color::Red) };
+            Row::new(vec![
+                Cell::from(record.sketch_id.clone()),
+                Cell::from(record.command.clone()),
+                Cell::from(format!("{:.1}s", record.duration_secs)),
+                Cell::from(outcome).style(Style::default().fg(color)),
+                Cell::from(format_relative_time(record.finished_at, now)),
+            ])
+        });
+        let table = Table::new(rows, [
+            Constraint::Min(16),
+            Constraint::Length(9),
+            Constraint::Length(9),
+            Constraint::Length(8),
+            Constraint::Length(12),
+        ])
+        .header(Row::new(["Profile", "Command", "Duration", "Outcome", "Finished"])
+            .style(Style::default().add_modifier(Modifier::BOLD)))
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .block(block);
+
+        let mut state = TableState::default().with_selected(Some(self.selected));
+        StatefulWidget::render(table, area, buf, &mut state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(sketch_id: &str, duration_secs: f64, success: bool, finished_at: u64) -> BuildRecord {
+        BuildRecord { sketch_id: sketch_id.to_string(), command: "Compile".to_string(), duration_secs, success, finished_at }
+    }
+
+    #[test]
+    fn test_rows_are_newest_first_and_resortable() {
+        let mut manager = crate::commands::HistoryManager::default();
+        manager.record_build(record("blink", 12.0, true, 100));
+        manager.record_build(record("sensor", 30.0, false, 300));
+        manager.record_build(record("blink", 20.0, true, 200));
+
+        let mut history = BuildHistory::new(&manager.builds);
+        let finished: Vec<u64> = history.rows().iter().map(|r| r.finished_at).collect();
+        assert_eq!(finished, vec![300, 200, 100]);
+        assert_eq!(history.selected().unwrap().sketch_id, "sensor");
+
+        history.cycle_sort();
+        assert_eq!(history.sort(), HistorySort::Profile);
+        let order: Vec<(&str, u64)> = history.rows().iter().map(|r| (r.sketch_id.as_str(), r.finished_at)).collect();
+        assert_eq!(order, vec![("blink", 200), ("blink", 100), ("sensor", 300)]);
+
+        let snapshot = crate::widgets::snapshot::widget_snapshot(&history, 70, 6);
+        assert!(snapshot.contains("sorted by profile"));
+        assert!(snapshot.contains("failed"));
+    }
+}
@@ -0,0 +1,158 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use crate::widgets::{InteractiveWidget, WidgetOutcome};
+
+/// Block characters used for sparklines, from lowest to highest.
+const SPARK_BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Maps a series of values to sparkline block characters scaled to its min/max.
+///>
+/// A flat series (all values equal) renders at mid height so it remains
+/// visible without implying growth.
+///<
+pub fn sparkline(values: &[f64]) -> String {
+    let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+    let top = (SPARK_BLOCKS.len() - 1) as f64;
+
+    values.iter().map(|v| { //>
+        if range <= f64::EPSILON {
+            SPARK_BLOCKS[SPARK_BLOCKS.len() / 2 - 1]
+        } else {
+            SPARK_BLOCKS[(((v - min) / range) * top).round() as usize]
+        }
+    }).collect() //<
+}
+
+/// Popup content showing recent build durations for a sketch.
+///>
+/// Displays a sparkline of the last N builds alongside the mean and a trend
+/// figure (the mean of the newer half minus the mean of the older half), so
+/// gradual slow-downs are easy to spot.
+///<
+#[derive(Debug, Clone)]
+pub struct BuildTrend {
+    pub sketch_id: String,
+    pub durations: Vec<f64>,
+}
+
+impl BuildTrend {
+    pub fn new(sketch_id: String, durations: Vec<f64>) -> Self {
+        Self { sketch_id, durations }
+    }
+
+    /// Average duration across all recorded builds.
+    pub fn mean(&self) -> Option<f64> {
+        if self.durations.is_empty() {
+            return None;
+        }
+        Some(self.durations.iter().sum::<f64>() / self.durations.len() as f64)
+    }
+
+    /// Difference between the newer and older halves of the series.
+    pub fn trend(&self) -> Option<f64> {
+        if self.durations.len() < 2 {
+            return None;
+        }
+        let (older, newer) = self.durations.split_at(self.durations.len() / 2);
+        let avg = |s: &[f64]| s.iter().sum::<f64>() / s.len() as f64;
+        Some(avg(newer) - avg(older))
+    }
+}
+
+impl Widget for BuildTrend {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        (&self).render(area, buf);
+    }
+}
+
+impl Widget for &BuildTrend {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let label = Style::default().fg(Color::DarkGray);
+        let value = Style::default().fg(Color::White).add_modifier(Modifier::BOLD);
+
+        let mut lines = vec![Line::from(vec![
+            Span::styled("Sketch: ", label),
+            Span::styled(self.sketch_id.clone(), value),
+        ])];
+
+        match (self.mean(), self.durations.last()) {
+            (Some(mean), Some(last)) => {
+                let min = self.durations.iter().cloned().fold(f64::INFINITY, f64::min);
+                let max = self.durations.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+                lines.push(Line::from(""));
+                lines.push(Line::from(Span::styled(sparkline(&self.durations), Style::default().fg(Color::Cyan))));
+                lines.push(Line::from(""));
+                lines.push(Line::from(vec![
+                    Span::styled("Last: ", label), Span::styled(format!("{:.1}s", last), value),
+                    Span::styled("  Mean: ", label), Span::styled(format!("{:.1}s", mean), value),
+                    Span::styled("  Range: ", label), Span::styled(format!("{:.1}s - {:.1}s", min, max), value),
+                ]));
+                if let Some(trend) = self.trend() {
+                    let (text, color) = if trend > 0.05 {
+                        (format!("+{:.1}s (slower)", trend), Color::Yellow)
+                    } else if trend < -0.05 {
+                        (format!("{:.1}s (faster)", trend), Color::Green)
+                    } else {
+                        ("steady".to_string(), Color::White)
+                    };
+                    lines.push(Line::from(vec![
+                        Span::styled("Trend: ", label),
+                        Span::styled(text, Style::default().fg(color)),
+                    ]));
+                }
+            }
+            _ => {
+                lines.push(Line::from(""));
+                lines.push(Line::from(Span::styled("No build history recorded for this sketch yet.", label)));
+            }
+        }
+
+        Paragraph::new(lines).render(area, buf);
+    }
+}
+
+impl InteractiveWidget for BuildTrend {
+    type Outcome = ();
+
+    fn handle_key(&mut self, key: KeyEvent) -> WidgetOutcome<()> {
+        match key.code {
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => WidgetOutcome::Canceled,
+            _ => WidgetOutcome::Consumed,
+        }
+    }
+
+    fn handle_mouse(&mut self, _mouse: crossterm::event::MouseEvent, _area: Rect) -> WidgetOutcome<()> {
+        WidgetOutcome::None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sparkline_scales_to_min_max() {
+        let series = [10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0, 17.0];
+        assert_eq!(sparkline(&series), "▁▂▃▄▅▆▇█");
+
+        assert_eq!(sparkline(&[30.0, 60.0, 45.0]), "▁█▅");
+        assert_eq!(sparkline(&[5.0, 5.0, 5.0]), "▄▄▄");
+        assert_eq!(sparkline(&[]), "");
+    }
+
+    #[test]
+    fn test_build_trend_mean_and_trend() {
+        let trend = BuildTrend::new("s".into(), vec![10.0, 10.0, 14.0, 14.0]);
+        assert_eq!(trend.mean(), Some(12.0));
+        assert_eq!(trend.trend(), Some(4.0));
+        assert_eq!(BuildTrend::new("s".into(), vec![]).mean(), None);
+    }
+}
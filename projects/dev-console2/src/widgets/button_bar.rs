@@ -0,0 +1,116 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Rect, Position},
+    style::{Color, Style},
+    widgets::Widget,
+};
+use crossterm::event::{MouseEvent, MouseButton, MouseEventKind};
+use crate::config::BindingsConfig;
+
+/// A clickable button mapped to a semantic action name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Button {
+    pub label: String,
+    pub action: String,
+}
+
+impl Button {
+    /// Builds buttons from a tab's bindings.
+    ///>
+    /// A binding becomes a button only when all of its triggers map to the same
+    /// action; bindings like `[🡙]` that fan out to several actions have no
+    /// single meaning for a click and are skipped.
+    ///<
+    pub fn from_bindings(bindings: &BindingsConfig) -> Vec<Button> {
+        bindings.items.iter()
+            .filter_map(|binding| {
+                let mut actions = binding.triggers.values();
+                let action = actions.next()?;
+                if actions.any(|other| other != action) {
+                    return None;
+                }
+                Some(Button { label: binding.description.clone(), action: action.clone() })
+            })
+            .collect()
+    }
+
+    fn width(&self) -> u16 {
+        self.label.chars().count() as u16 + 4
+    }
+}
+
+/// A single-row bar of `[ Label ]` buttons for mouse-driven tab actions.
+pub struct ButtonBarWidget<'a> {
+    buttons: &'a [Button],
+    style: Style,
+}
+
+impl<'a> ButtonBarWidget<'a> {
+    pub fn new(buttons: &'a [Button]) -> Self {
+        Self { buttons, style: Style::default().fg(Color::Cyan) }
+    }
+
+    /// Screen areas of each button, laid out left to right with one space between.
+    pub fn button_areas(&self, area: Rect) -> Vec<Rect> {
+        let mut x = area.x;
+        let mut areas = Vec::with_capacity(self.buttons.len());
+        for button in self.buttons { //>
+            let width = button.width().min(area.right().saturating_sub(x));
+            if width == 0 { break; }
+            areas.push(Rect::new(x, area.y, width, 1.min(area.height)));
+            x = x.saturating_add(button.width() + 1);
+        } //<
+        areas
+    }
+
+    /// Returns the index of the button clicked by a left mouse press.
+    pub fn handle_mouse_event(&self, area: Rect, mouse_event: MouseEvent) -> Option<usize> {
+        if !matches!(mouse_event.kind, MouseEventKind::Down(MouseButton::Left)) {
+            return None;
+        }
+        let mouse_pos = Position::new(mouse_event.column, mouse_event.row);
+        self.button_areas(area).iter().position(|rect| rect.contains(mouse_pos))
+    }
+}
+
+impl<'a> Widget for ButtonBarWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        for (button, rect) in self.buttons.iter().zip(self.button_areas(area)) {
+            buf.set_stringn(rect.x, rect.y, format!("[ {} ]", button.label), rect.width as usize, self.style);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::BindingConfig;
+    use crossterm::event::KeyModifiers;
+
+    #[test]
+    fn test_buttons_skip_multi_action_bindings() {
+        let bindings = BindingsConfig {
+            items: vec![
+                BindingConfig {
+                    key: "[🡙]".to_string(),
+                    description: "Navigate".to_string(),
+                    triggers: [("[Up]".to_string(), "commands_up".to_string()), ("[Down]".to_string(), "commands_down".to_string())].into_iter().collect(),
+                },
+                BindingConfig {
+                    key: "[v]".to_string(),
+                    description: "Quiet".to_string(),
+                    triggers: [("v".to_string(), "toggle_quiet".to_string())].into_iter().collect(),
+                },
+            ],
+            ..Default::default()
+        };
+        let buttons = Button::from_bindings(&bindings);
+        assert_eq!(buttons, vec![Button { label: "Quiet".to_string(), action: "toggle_quiet".to_string() }]);
+
+        let widget = ButtonBarWidget::new(&buttons);
+        let area = Rect::new(2, 5, 40, 1);
+        let click = |column| MouseEvent { kind: MouseEventKind::Down(MouseButton::Left), column, row: 5, modifiers: KeyModifiers::empty() };
+        assert_eq!(widget.handle_mouse_event(area, click(3)), Some(0));
+        assert_eq!(widget.handle_mouse_event(area, click(20)), None);
+    }
+}
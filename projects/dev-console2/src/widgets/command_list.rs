@@ -6,6 +6,7 @@ use ratatui::{
     widgets::{Block, Widget},
 };
 use crossterm::event::{MouseEvent, MouseButton, MouseEventKind, KeyModifiers};
+use super::selection_list::scroll_offset;
 
 /// Semantic result of a mouse interaction with the command list.
 pub enum CommandListInteraction {
@@ -73,12 +74,13 @@ impl<'a> CommandListWidget<'a> {
         }
 
         let inner_area = Block::bordered().inner(area);
-        let rel_y = mouse_event.row.saturating_sub(inner_area.y) as usize;
+        let offset = scroll_offset(self.commands.len(), self.selected_index, inner_area.height as usize);
+        let idx = mouse_event.row.saturating_sub(inner_area.y) as usize + offset;
         
-        if rel_y < self.commands.len() && inner_area.contains(mouse_pos) { //>
+        if idx < self.commands.len() && inner_area.contains(mouse_pos) { //>
             match mouse_event.kind {
-                MouseEventKind::Down(MouseButton::Left) => Some(CommandListInteraction::Click(rel_y)),
-                MouseEventKind::Moved | MouseEventKind::Drag(MouseButton::Left) => Some(CommandListInteraction::Hover(rel_y)),
+                MouseEventKind::Down(MouseButton::Left) => Some(CommandListInteraction::Click(idx)),
+                MouseEventKind::Moved | MouseEventKind::Drag(MouseButton::Left) => Some(CommandListInteraction::Hover(idx)),
                 _ => None,
             }
         } else {
@@ -100,10 +102,10 @@ impl<'a> Widget for CommandListWidget<'a> {
         let inner_area = block.inner(area);
         block.render(area, buf);
 
-        for (idx, cmd) in self.commands.iter().enumerate() { //>
-            if idx >= inner_area.height as usize { break; }
-            
-            let item_y = inner_area.y + idx as u16;
+        let height = inner_area.height as usize;
+        let offset = scroll_offset(self.commands.len(), self.selected_index, height);
+        for (idx, cmd) in self.commands.iter().enumerate().skip(offset).take(height) { //>
+            let item_y = inner_area.y + (idx - offset) as u16;
             let item_area = Rect::new(inner_area.x, item_y, inner_area.width, 1);
             
             let is_selected = idx == self.selected_index;
@@ -130,6 +132,18 @@ impl<'a> Widget for CommandListWidget<'a> {
 
             buf.set_string(item_area.x + 1, item_y, format!(" {}", cmd), style);
         } //< 
+
+        // Scroll indicators in the right-hand column of the inner area
+        if inner_area.width > 0 && height > 0 { //>
+            let indicator_x = inner_area.right() - 1;
+            let indicator_style = Style::default().fg(Color::DarkGray);
+            if offset > 0 {
+                buf.set_string(indicator_x, inner_area.y, "▲", indicator_style);
+            }
+            if offset + height < self.commands.len() {
+                buf.set_string(indicator_x, inner_area.bottom() - 1, "▼", indicator_style);
+            }
+        } //< 
     }
 }
 
@@ -199,6 +213,41 @@ mod tests {
         } else { panic!(); }
     }
 
+    #[test]
+    fn test_overflowing_command_list_scrolls_and_shows_markers() {
+        // Two inner rows for five commands
+        let backend = TestBackend::new(20, 4);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let commands: Vec<String> = (0..5).map(|i| format!("Cmd {}", i)).collect();
+        let rows = |terminal: &Terminal<TestBackend>| -> Vec<String> {
+            let buffer = terminal.backend().buffer();
+            (0..4).map(|y| (0..20).map(|x| buffer[(x, y)].symbol()).collect::<String>()).collect()
+        };
+
+        terminal.draw(|f| f.render_widget(CommandListWidget::new(&commands, 0, None), f.area())).unwrap();
+        let top = rows(&terminal);
+        assert!(top[1].contains("Cmd 0") && !top[1].contains('▲'));
+        assert!(top[2].contains("Cmd 1") && top[2].contains('▼'));
+
+        terminal.draw(|f| f.render_widget(CommandListWidget::new(&commands, 2, None), f.area())).unwrap();
+        let middle = rows(&terminal);
+        assert!(middle[1].contains("Cmd 1") && middle[1].contains('▲'));
+        assert!(middle[2].contains("Cmd 2") && middle[2].contains('▼'));
+
+        terminal.draw(|f| f.render_widget(CommandListWidget::new(&commands, 4, None), f.area())).unwrap();
+        let bottom = rows(&terminal);
+        assert!(bottom[1].contains("Cmd 3") && bottom[1].contains('▲'));
+        assert!(bottom[2].contains("Cmd 4") && !bottom[2].contains('▼'));
+
+        // Clicks map through the scroll offset
+        let widget = CommandListWidget::new(&commands, 4, None);
+        let event = MouseEvent { kind: MouseEventKind::Down(MouseButton::Left), column: 5, row: 1, modifiers: KeyModifiers::empty() };
+        match widget.handle_mouse_event(Rect::new(0, 0, 20, 4), event) {
+            Some(CommandListInteraction::Click(idx)) => assert_eq!(idx, 3),
+            _ => panic!("Expected Click"),
+        }
+    }
+
     #[test]
     fn test_command_list_highlighting() {
         let backend = TestBackend::new(20, 5);
@@ -29,6 +29,32 @@ pub fn apply_dimming(buf: &mut Buffer, area: Rect) {
     }
 }
 
+/// Tints the background of every cell in the area, used for completion flashes.
+pub fn apply_flash(buf: &mut Buffer, area: Rect, color: Color) {
+    let intersection = area.intersection(buf.area);
+    for y in intersection.top()..intersection.bottom() {
+        for x in intersection.left()..intersection.right() {
+            buf[(x, y)].set_bg(color);
+        }
+    }
+}
+
+/// Shifts every row of the area `offset` columns right, blanking the uncovered left edge.
+pub fn apply_slide(buf: &mut Buffer, area: Rect, offset: u16) {
+    let intersection = area.intersection(buf.area);
+    if offset == 0 || offset >= intersection.width {
+        return;
+    }
+    for y in intersection.top()..intersection.bottom() {
+        for x in (intersection.left() + offset..intersection.right()).rev() {
+            buf[(x, y)] = buf[(x - offset, y)].clone();
+        }
+        for x in intersection.left()..intersection.left() + offset {
+            buf[(x, y)].reset();
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,13 +1,79 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
     style::{Color, Modifier, Style},
-    widgets::{List, ListItem, ListState, Widget},
+    text::{Line, Span},
+    widgets::{List, ListItem, ListState, Paragraph, Widget},
 };
 use crossterm::event::{KeyCode, KeyEvent};
 use crate::widgets::{InteractiveWidget, WidgetOutcome};
 
+/// Separator placed between path components in the breadcrumb.
+const BREADCRUMB_SEPARATOR: &str = " › ";
+
+/// Renders `path` as a breadcrumb that fits within `max_width` columns.
+///>
+/// When the full path is too long, the leading components are replaced by
+/// an ellipsis so the directories closest to the current one stay visible.
+///<
+pub fn breadcrumb(path: &Path, max_width: usize) -> String {
+    let parts: Vec<String> = path.components()
+        .map(|c| c.as_os_str().to_string_lossy().trim_end_matches(['/', '\\']).to_string())
+        .filter(|p| !p.is_empty())
+        .collect();
+    let full = parts.join(BREADCRUMB_SEPARATOR);
+    if full.chars().count() <= max_width {
+        return full;
+    }
+
+    let mut tail: Vec<&str> = Vec::new();
+    let mut width = 1; // leading ellipsis
+    for part in parts.iter().rev() {
+        let added = part.chars().count() + BREADCRUMB_SEPARATOR.chars().count();
+        if width + added > max_width && !tail.is_empty() {
+            break;
+        }
+        width += added;
+        tail.push(part);
+    }
+    tail.reverse();
+    format!("…{}{}", BREADCRUMB_SEPARATOR, tail.join(BREADCRUMB_SEPARATOR))
+}
+
+/// Picks the directory the browser should open in.
+///>
+/// Prefers the directory of the current sketch path (or the path itself if it
+/// is a directory), then the configured default root, then the user's home
+/// directory, and finally the working directory.
+///<
+pub fn resolve_start_dir(sketch_path: Option<&str>, default_root: Option<&str>) -> PathBuf {
+    let sketch_dir = sketch_path.filter(|p| !p.trim().is_empty()).map(PathBuf::from).and_then(|path| {
+        if path.is_file() {
+            path.parent().map(Path::to_path_buf)
+        } else {
+            Some(path)
+        }
+    });
+    let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")).map(PathBuf::from);
+
+    sketch_dir.into_iter()
+        .chain(default_root.filter(|p| !p.trim().is_empty()).map(PathBuf::from))
+        .chain(home)
+        .find(|dir| dir.is_dir())
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// Area used by the entry list: below the breadcrumb row, with one column of left padding.
+fn list_area(area: Rect) -> Rect {
+    Rect {
+        x: area.x.saturating_add(1),
+        y: area.y.saturating_add(1),
+        width: area.width.saturating_sub(1),
+        height: area.height.saturating_sub(1),
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FileEntry {
     pub name: String,
@@ -118,6 +184,23 @@ impl FileBrowser {
         }
     }
 
+    /// Moves to the parent directory, unwinding history when it leads there.
+    ///>
+    /// If the previous history entry is the parent (the usual case after
+    /// descending), it is popped so Backspace and history stay in sync.
+    ///<
+    pub fn navigate_up(&mut self) {
+        let parent_is_prior = match (self.history.last(), self.current_dir.parent()) {
+            (Some(prior), Some(parent)) => prior == parent,
+            _ => false,
+        };
+        if parent_is_prior {
+            self.navigate_back();
+        } else {
+            self.navigate_parent();
+        }
+    }
+
     pub fn navigate_into(&mut self) {
         if let Some(entry) = self.entries.get(self.selected_index).cloned() {
             if entry.is_dir {
@@ -147,7 +230,7 @@ impl FileBrowser {
 
     /// Renders the browser using a mutable reference to track dimensions
     pub fn render_stateful(&mut self, area: Rect, buf: &mut Buffer) {
-        self.last_visible_height = area.height as usize;
+        self.last_visible_height = list_area(area).height as usize;
         (&*self).render(area, buf);
     }
 }
@@ -160,14 +243,17 @@ impl Widget for FileBrowser {
 
 impl Widget for &FileBrowser {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        // Add single column padding on the left only
-        let list_area = Rect {
-            x: area.x.saturating_add(1),
-            y: area.y,
-            width: area.width.saturating_sub(1),
-            height: area.height,
-        };
+        if area.height == 0 {
+            return;
+        }
 
+        // Breadcrumb of the current directory on the first row
+        let crumb_area = Rect { height: 1, ..list_area(area) };
+        let crumb = breadcrumb(&self.current_dir, crumb_area.width as usize);
+        Paragraph::new(Line::from(Span::styled(crumb, Style::default().fg(Color::DarkGray))))
+            .render(Rect { y: area.y, ..crumb_area }, buf);
+
+        let list_area = list_area(area);
         let items: Vec<ListItem> = self.entries.iter().map(|entry| {
             let icon = if entry.is_dir { "📁 " } else { "📄 " };
             let style = if entry.is_dir {
@@ -256,7 +342,7 @@ impl InteractiveWidget for FileBrowser {
                 }
             }
             KeyCode::Backspace => {
-                self.navigate_back();
+                self.navigate_up();
                 WidgetOutcome::Changed(self.current_dir.clone())
             }
             KeyCode::Esc => WidgetOutcome::Canceled,
@@ -265,13 +351,8 @@ impl InteractiveWidget for FileBrowser {
     }
 
     fn handle_mouse(&mut self, mouse: crossterm::event::MouseEvent, area: Rect) -> WidgetOutcome<PathBuf> {
-        // Account for the same padding as in render (Left only)
-        let list_area = Rect {
-            x: area.x.saturating_add(1),
-            y: area.y,
-            width: area.width.saturating_sub(1),
-            height: area.height,
-        };
+        // Account for the same breadcrumb row and padding as in render
+        let list_area = list_area(area);
 
         if !list_area.contains(ratatui::layout::Position::new(mouse.column, mouse.row)) {
             return WidgetOutcome::None;
@@ -513,6 +594,56 @@ mod tests {
         assert!(content.contains("📄  b_file.txt"));
     }
 
+    #[test]
+    fn test_backspace_navigates_to_parent() {
+        let mut browser = FileBrowser::new(PathBuf::from("projects/sketch/src"));
+        assert!(browser.history.is_empty());
+
+        browser.handle_key(make_key(KeyCode::Backspace));
+        assert_eq!(browser.current_dir, PathBuf::from("projects/sketch"));
+        browser.handle_key(make_key(KeyCode::Backspace));
+        assert_eq!(browser.current_dir, PathBuf::from("projects"));
+    }
+
+    #[test]
+    fn test_breadcrumb_shortens_long_paths() {
+        let path = PathBuf::from("workspace/boards/esp32/sketches/blink");
+        assert_eq!(breadcrumb(&path, 80), "workspace › boards › esp32 › sketches › blink");
+        assert_eq!(breadcrumb(&path, 20), "… › sketches › blink");
+        assert_eq!(breadcrumb(&path, 4), "… › blink");
+
+        let mut browser = FileBrowser::new(path);
+        browser.entries = vec![FileEntry { name: "blink.ino".into(), path: "blink.ino".into(), is_dir: false }];
+        let area = Rect::new(0, 0, 60, 4);
+        let mut buf = Buffer::empty(area);
+        (&browser).render(area, &mut buf);
+        let content = buffer_content(&buf);
+        assert!(content.lines().next().unwrap().contains("esp32 › sketches › blink"));
+        assert!(content.lines().nth(1).unwrap().contains("blink.ino"));
+    }
+
+    #[test]
+    fn test_start_dir_prefers_valid_sketch_dir() {
+        let base = std::env::temp_dir().join("dc2_start_dir_test");
+        let sketch_dir = base.join("blink");
+        let default_root = base.join("sketches");
+        std::fs::create_dir_all(&sketch_dir).unwrap();
+        std::fs::create_dir_all(&default_root).unwrap();
+        let sketch_file = sketch_dir.join("blink.ino");
+        std::fs::write(&sketch_file, "").unwrap();
+        let root = default_root.to_str();
+
+        assert_eq!(resolve_start_dir(sketch_file.to_str(), root), sketch_dir);
+        assert_eq!(resolve_start_dir(sketch_dir.to_str(), root), sketch_dir);
+
+        // Missing or unset sketch paths fall back to the configured root
+        let missing = base.join("missing/missing.ino");
+        assert_eq!(resolve_start_dir(missing.to_str(), root), default_root);
+        assert_eq!(resolve_start_dir(Some(""), root), default_root);
+
+        let _ = std::fs::remove_dir_all(&base);
+    }
+
     fn buffer_content(buf: &Buffer) -> String {
         let mut content = String::new();
         for y in 0..buf.area.height {
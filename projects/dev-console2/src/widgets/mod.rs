@@ -1,4 +1,5 @@
 pub mod tab_bar;
+pub mod title_bar;
 pub mod progress_bar;
 pub mod status_box;
 pub mod command_list;
@@ -9,6 +10,13 @@ pub mod output_box;
 pub mod dimmer;
 pub mod popup;
 pub mod file_browser;
+pub mod build_trend;
+pub mod build_history;
+pub mod button_bar;
+pub mod profile_switcher;
+pub mod text_viewer;
+#[cfg(test)]
+pub mod snapshot;
 
 /// Generic outcome for interactive widgets.
 /// Used to communicate state changes from encapsulated widgets to the parent view.
@@ -1,22 +1,47 @@
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Layout, Margin, Rect, Position},
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Paragraph, Widget},
 };
 use crate::app::theme::Theme;
 use crate::widgets::smooth_scrollbar::{ScrollBar, ScrollLengths};
 
+/// Formats a count with comma thousands separators (e.g. `1,234`).
+pub fn format_thousands(n: usize) -> String {
+    let digits = n.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Builds the output box title from the buffer size and, when filtered, the shown count.
+pub fn output_title(total: Option<usize>, shown: Option<usize>) -> String {
+    match (total, shown) {
+        (None, _) => " Output ".to_string(),
+        (Some(total), None) => format!(" Output ({} lines) ", format_thousands(total)),
+        (Some(total), Some(shown)) => format!(" Output ({} lines / {} shown) ", format_thousands(total), format_thousands(shown)),
+    }
+}
+
 /// A widget for displaying the application's output log with scrolling and input support.
 pub struct OutputBoxWidget<'a> {
     lines: &'a [Line<'a>],
     scroll: u16,
+    total_lines: Option<usize>,
+    shown_lines: Option<usize>,
     autoscroll: bool,
     theme: &'a Theme,
     input_active: bool,
     input_value: &'a str,
     input_cursor: usize,
+    highlight: Option<usize>,
 }
 
 impl<'a> OutputBoxWidget<'a> {
@@ -24,11 +49,14 @@ impl<'a> OutputBoxWidget<'a> {
         Self {
             lines,
             scroll,
+            total_lines: None,
+            shown_lines: None,
             autoscroll: true,
             theme,
             input_active: false,
             input_value: "",
             input_cursor: 0,
+            highlight: None,
         }
     }
 
@@ -37,6 +65,19 @@ impl<'a> OutputBoxWidget<'a> {
         self
     }
 
+    /// Shows the buffer line count (and filtered count, if any) in the title.
+    pub fn counts(mut self, total: usize, shown: Option<usize>) -> Self {
+        self.total_lines = Some(total);
+        self.shown_lines = shown;
+        self
+    }
+
+    /// Highlights the line at `index` in `lines` (e.g. one just copied).
+    pub fn highlight(mut self, index: Option<usize>) -> Self {
+        self.highlight = index;
+        self
+    }
+
     pub fn input(mut self, active: bool, value: &'a str, cursor: usize) -> Self {
         self.input_active = active;
         self.input_value = value;
@@ -48,7 +89,7 @@ impl<'a> OutputBoxWidget<'a> {
 impl<'a> Widget for OutputBoxWidget<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let output_block = Block::bordered()
-            .title(Span::styled(" Output ", self.theme.style("output_title")))
+            .title(Span::styled(output_title(self.total_lines, self.shown_lines), self.theme.style("output_title")))
             .border_style(self.theme.style("output_border"));
         let inner_output_area = output_block.inner(area);
         output_block.render(area, buf);
@@ -89,11 +130,14 @@ impl<'a> Widget for OutputBoxWidget<'a> {
         let mut text_area = actual_text_area.inner(Margin { vertical: 0, horizontal: 1 });
         if show_scrollbar { text_area.width = text_area.width.saturating_sub(1); }
 
-        let display_lines = if self.lines.is_empty() {
+        let mut display_lines = if self.lines.is_empty() {
             vec![Line::from(Span::styled("No output yet.", Style::default().fg(Color::DarkGray)))]
         } else {
             self.lines.to_vec()
         };
+        if let Some(line) = self.highlight.and_then(|i| display_lines.get_mut(i)) {
+            line.style = line.style.add_modifier(Modifier::REVERSED);
+        }
 
         Paragraph::new(display_lines)
             .scroll((self.scroll, 0))
@@ -150,6 +194,18 @@ mod tests {
         assert!(s.contains("No output yet."));
     }
 
+    #[test]
+    fn test_output_title_counts() {
+        assert_eq!(format_thousands(0), "0");
+        assert_eq!(format_thousands(999), "999");
+        assert_eq!(format_thousands(1_234), "1,234");
+        assert_eq!(format_thousands(1_234_567), "1,234,567");
+
+        assert_eq!(output_title(None, None), " Output ");
+        assert_eq!(output_title(Some(1_234), None), " Output (1,234 lines) ");
+        assert_eq!(output_title(Some(1_234), Some(56)), " Output (1,234 lines / 56 shown) ");
+    }
+
     #[test]
     fn test_output_box_render_with_lines() {
         let theme = Theme::default();
@@ -0,0 +1,238 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
+use crate::widgets::{InteractiveWidget, WidgetOutcome};
+use crate::widgets::selection_list::{SelectionListInteraction, SelectionListWidget};
+
+/// Char indices of `candidate` matched by `query`, or `None` when it doesn't match.
+///>
+/// Matches when every character of `query` appears in `candidate` in order
+/// (case-insensitive). A contiguous occurrence is preferred, so typing a
+/// substring highlights that substring; otherwise the first in-order match is used.
+///<
+pub fn match_positions(candidate: &str, query: &str) -> Option<Vec<usize>> {
+    let fold = |c: char| c.to_lowercase().next().unwrap_or(c);
+    let candidate: Vec<char> = candidate.chars().map(fold).collect();
+    let query: Vec<char> = query.chars().map(fold).collect();
+    if query.is_empty() {
+        return Some(Vec::new());
+    }
+    if let Some(start) = candidate.windows(query.len()).position(|window| window == query.as_slice()) {
+        return Some((start..start + query.len()).collect());
+    }
+
+    let mut positions = Vec::with_capacity(query.len());
+    let mut chars = candidate.iter().enumerate();
+    for q in &query {
+        let (idx, _) = chars.by_ref().find(|(_, c)| *c == q)?;
+        positions.push(idx);
+    }
+    Some(positions)
+}
+
+/// Quick-switcher modal that filters profile ids as the user types.
+///>
+/// Confirming yields the chosen profile id; the list below the query line
+/// reuses `SelectionListWidget` for rendering and mouse hits, with the matched
+/// characters highlighted. The first Esc clears a non-empty query, the next
+/// one closes the switcher.
+///<
+#[derive(Debug)]
+pub struct ProfileSwitcher {
+    profile_ids: Vec<String>,
+    query: String,
+    matches: Vec<String>,
+    /// Matched char indices for each entry of `matches`.
+    highlights: Vec<Vec<usize>>,
+    selected: usize,
+}
+
+impl ProfileSwitcher {
+    pub fn new(profile_ids: Vec<String>) -> Self {
+        let matches = profile_ids.clone();
+        let highlights = vec![Vec::new(); matches.len()];
+        Self { profile_ids, query: String::new(), matches, highlights, selected: 0 }
+    }
+
+    /// The text typed so far.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Index into `matches` of the highlighted entry.
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Profile ids matching the current query, in their original order.
+    pub fn matches(&self) -> &[String] {
+        &self.matches
+    }
+
+    /// Re-applies the query; the selection moves to the first match.
+    fn refilter(&mut self) {
+        let (matches, highlights) = self.profile_ids.iter()
+            .filter_map(|id| match_positions(id, &self.query).map(|positions| (id.clone(), positions)))
+            .unzip();
+        self.matches = matches;
+        self.highlights = highlights;
+        self.selected = 0;
+    }
+
+    /// Splits the content area into the query line and the result list.
+    fn list_area(area: Rect) -> Rect {
+        Rect { y: area.y + 2, height: area.height.saturating_sub(2), ..area }
+    }
+}
+
+impl Widget for &ProfileSwitcher {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let query_line = Line::from(vec![
+            Span::styled("> ", Style::default().fg(Color::Cyan)),
+            Span::raw(self.query.clone()),
+        ]);
+        Paragraph::new(query_line).render(Rect { height: 1.min(area.height), ..area }, buf);
+
+        let list_area = ProfileSwitcher::list_area(area);
+        if self.matches.is_empty() {
+            Paragraph::new("No matching profiles")
+                .style(Style::default().fg(Color::DarkGray))
+                .render(list_area, buf);
+        } else {
+            SelectionListWidget::new(&self.matches, self.selected, None)
+                .highlights(self.highlights.clone())
+                .render(list_area, buf);
+        }
+    }
+}
+
+impl InteractiveWidget for ProfileSwitcher {
+    type Outcome = String;
+
+    fn handle_key(&mut self, key: KeyEvent) -> WidgetOutcome<String> {
+        match key.code {
+            KeyCode::Esc if !self.query.is_empty() => {
+                self.query.clear();
+                self.refilter();
+                WidgetOutcome::Changed(self.query.clone())
+            }
+            KeyCode::Esc => WidgetOutcome::Canceled,
+            KeyCode::Enter => match self.matches.get(self.selected) {
+                Some(id) => WidgetOutcome::Confirmed(id.clone()),
+                None => WidgetOutcome::Consumed,
+            },
+            KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                WidgetOutcome::Consumed
+            }
+            KeyCode::Down => {
+                if self.selected + 1 < self.matches.len() {
+                    self.selected += 1;
+                }
+                WidgetOutcome::Consumed
+            }
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.refilter();
+                WidgetOutcome::Changed(self.query.clone())
+            }
+            KeyCode::Char(c) => {
+                self.query.push(c);
+                self.refilter();
+                WidgetOutcome::Changed(self.query.clone())
+            }
+            _ => WidgetOutcome::Consumed,
+        }
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent, area: Rect) -> WidgetOutcome<String> {
+        let list = SelectionListWidget::new(&self.matches, self.selected, None);
+        match list.handle_mouse_event(ProfileSwitcher::list_area(area), mouse) {
+            Some(SelectionListInteraction::Click(idx)) => WidgetOutcome::Confirmed(self.matches[idx].clone()),
+            Some(SelectionListInteraction::Hover(_)) | None => WidgetOutcome::Consumed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_matches_in_order_ignoring_case() {
+        assert!(match_positions("esp32-s3-led", "S3LED").is_some());
+        assert!(match_positions("esp32-s3-led", "").is_some());
+        assert!(match_positions("esp32-s3-led", "dele").is_none());
+    }
+
+    #[test]
+    fn test_match_positions_prefer_contiguous_substring() {
+        assert_eq!(match_positions("esp32-s3-led", "S3"), Some(vec![6, 7]));
+        assert_eq!(match_positions("esp32-s3-led", "e3l"), Some(vec![0, 3, 9]));
+        assert_eq!(match_positions("esp32-s3-led", ""), Some(vec![]));
+        assert_eq!(match_positions("esp32-s3-led", "xyz"), None);
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, crossterm::event::KeyModifiers::empty())
+    }
+
+    #[test]
+    fn test_typing_filters_and_selects_first_match() {
+        let ids = ["c3-sensor", "esp32-s3-led", "s3-matrix", "uno"].map(String::from).to_vec();
+        let mut switcher = ProfileSwitcher::new(ids);
+        switcher.handle_key(key(KeyCode::Down));
+        switcher.handle_key(key(KeyCode::Down));
+        assert_eq!(switcher.selected(), 2);
+
+        assert_eq!(switcher.handle_key(key(KeyCode::Char('s'))), WidgetOutcome::Changed("s".to_string()));
+        assert_eq!(switcher.handle_key(key(KeyCode::Char('3'))), WidgetOutcome::Changed("s3".to_string()));
+        assert_eq!(switcher.matches(), ["esp32-s3-led".to_string(), "s3-matrix".to_string()]);
+        assert_eq!(switcher.selected(), 0);
+
+        switcher.handle_key(key(KeyCode::Char('m')));
+        assert_eq!(switcher.matches(), ["s3-matrix".to_string()]);
+        switcher.handle_key(key(KeyCode::Backspace));
+        assert_eq!(switcher.query(), "s3");
+        assert_eq!(switcher.matches().len(), 2);
+        assert_eq!(switcher.handle_key(key(KeyCode::Enter)), WidgetOutcome::Confirmed("esp32-s3-led".to_string()));
+    }
+
+    #[test]
+    fn test_esc_clears_query_before_closing() {
+        let mut switcher = ProfileSwitcher::new(vec!["c3-sensor".to_string(), "uno".to_string()]);
+        switcher.handle_key(key(KeyCode::Char('u')));
+        assert_eq!(switcher.matches().len(), 1);
+
+        assert_eq!(switcher.handle_key(key(KeyCode::Esc)), WidgetOutcome::Changed(String::new()));
+        assert_eq!(switcher.query(), "");
+        assert_eq!(switcher.matches().len(), 2);
+        assert_eq!(switcher.handle_key(key(KeyCode::Esc)), WidgetOutcome::Canceled);
+    }
+
+    #[test]
+    fn test_matched_substring_is_highlighted() {
+        use ratatui::style::Modifier;
+        let mut switcher = ProfileSwitcher::new(vec!["c3-sensor".to_string(), "esp32-s3-led".to_string()]);
+        switcher.handle_key(key(KeyCode::Char('l')));
+        switcher.handle_key(key(KeyCode::Char('e')));
+        switcher.handle_key(key(KeyCode::Char('d')));
+
+        let area = Rect::new(0, 0, 20, 4);
+        let mut buf = Buffer::empty(area);
+        (&switcher).render(area, &mut buf);
+        // List starts two rows down; items are indented by one column plus a space
+        let row: String = (0..area.width).map(|x| buf[(x, 2)].symbol()).collect();
+        assert!(row.contains("esp32-s3-led"), "row: {}", row);
+        let start = 2;
+        for x in start..start + 12 {
+            let bold = buf[(x, 2)].modifier.contains(Modifier::BOLD);
+            assert_eq!(bold, (start + 9..start + 12).contains(&x), "column {}", x);
+        }
+    }
+}
@@ -3,6 +3,34 @@
     widgets::{Block, Borders, Paragraph, Widget},
 };
 
+/// Builds the one-line build status, e.g. `Compiling 45% · foo.cpp · ETA 00:30 · 00:12 elapsed`.
+///>
+/// When the line is wider than `width`, lower-priority segments are dropped
+/// first: the file name, then the ETA, then the elapsed time. An unknown ETA
+/// (empty or `--:--`) is left out. Whatever remains is cut to `width`.
+///<
+pub fn compact_status_line(stage: &str, percentage: f64, file: &str, eta: &str, elapsed: &str, width: usize) -> String {
+    let head = if stage.is_empty() { format!("{:.0}%", percentage) } else { format!("{} {:.0}%", stage, percentage) };
+    let file = (!file.is_empty()).then(|| file.to_string());
+    let eta = (!eta.is_empty() && eta != "--:--").then(|| format!("ETA {}", eta));
+    let elapsed = (!elapsed.is_empty()).then(|| format!("{} elapsed", elapsed));
+
+    // Segments in display order, each tagged with its drop priority (lowest dropped first)
+    let mut segments: Vec<(u8, String)> = vec![(3, head)];
+    segments.extend(file.map(|s| (0, s)));
+    segments.extend(eta.map(|s| (1, s)));
+    segments.extend(elapsed.map(|s| (2, s)));
+
+    let join = |segments: &[(u8, String)]| segments.iter().map(|(_, s)| s.as_str()).collect::<Vec<_>>().join(" · ");
+    for priority in 0..3 {
+        if join(&segments).chars().count() <= width {
+            break;
+        }
+        segments.retain(|(p, _)| *p != priority);
+    }
+    join(&segments).chars().take(width).collect()
+}
+
 /// A widget for displaying progress of a background task.
 #[derive(Clone, Debug)]
 pub struct ProgressBarWidget<'a> {
@@ -54,6 +82,12 @@ impl<'a> ProgressBarWidget<'a> {
         self.eta_text = eta;
         self
     }
+
+    /// Sets the file currently being processed (e.g., "main.cpp").
+    pub fn file(mut self, file: String) -> Self {
+        self.file_text = file;
+        self
+    }
 }
 
 impl<'a> Widget for ProgressBarWidget<'a> {
@@ -75,20 +109,13 @@ impl<'a> Widget for ProgressBarWidget<'a> {
             return;
         }
 
-        let line1 = format!(
-            "Progress: {:>5.1}% | Elapsed: {:>5} | ETA: {:>5} | Stage: {}",
+        let line1 = compact_status_line(
+            &self.stage_text,
             self.progress_percentage,
-            if self.elapsed_text.is_empty() {
-                "00:00"
-            } else {
-                &self.elapsed_text
-            },
-            if self.eta_text.is_empty() {
-                "--:--"
-            } else {
-                &self.eta_text
-            },
-            self.stage_text
+            &self.file_text,
+            &self.eta_text,
+            if self.elapsed_text.is_empty() { "00:00" } else { &self.elapsed_text },
+            content_area.width as usize,
         );
 
         let bar_width = (content_area.width as usize).saturating_sub(2);
@@ -96,7 +123,7 @@ impl<'a> Widget for ProgressBarWidget<'a> {
         let empty_width = bar_width.saturating_sub(filled_width);
         let bar_text = format!("[{}{}]", "█".repeat(filled_width), " ".repeat(empty_width));
 
-        let mut lines = vec![
+        let lines = vec![
             Line::from(Span::styled(
                 line1,
                 Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
@@ -104,13 +131,6 @@ impl<'a> Widget for ProgressBarWidget<'a> {
             Line::from(Span::styled(bar_text, Style::default().fg(Color::Green))),
         ];
 
-        if !self.file_text.is_empty() && content_area.height > 2 {
-            lines.push(Line::from(Span::styled(
-                format!("File: {}", self.file_text),
-                Style::default(),
-            )));
-        }
-
         Paragraph::new(lines).render(content_area, buf);
     }
 }
@@ -147,8 +167,7 @@ mod tests {
 
         let buffer = terminal.backend().buffer();
         let s = buffer_content(buffer);
-        assert!(s.contains("50.0%"));
-        assert!(s.contains("Compiling"));
+        assert!(s.contains("Compiling 50%"));
         assert!(s.contains("█"));
     }
 
@@ -158,14 +177,23 @@ mod tests {
         let mut buffer = Buffer::empty(area);
         let widget = ProgressBarWidget::new("T".to_string(), 10.0, "S".to_string())
             .border_style(Style::default().fg(Color::Red))
-            .title_style(Style::default().fg(Color::Yellow));
-
-        // Manual set file_text since there is no setter yet
-        let mut widget = widget;
-        widget.file_text = "test.ino".to_string();
+            .title_style(Style::default().fg(Color::Yellow))
+            .file("test.ino".to_string());
 
         widget.render(area, &mut buffer);
         let s = buffer_content(&buffer);
-        assert!(s.contains("File: test.ino"));
+        assert!(s.contains("S 10% · test.ino · 00:00 elapsed"));
+    }
+
+    #[test]
+    fn test_compact_status_drops_file_then_eta() {
+        let line = |width| compact_status_line("Compiling", 45.0, "foo.cpp", "00:30", "00:12", width);
+        assert_eq!(line(80), "Compiling 45% · foo.cpp · ETA 00:30 · 00:12 elapsed");
+        assert_eq!(line(41), "Compiling 45% · ETA 00:30 · 00:12 elapsed");
+        assert_eq!(line(30), "Compiling 45% · 00:12 elapsed");
+        assert_eq!(line(20), "Compiling 45%");
+        assert_eq!(line(9), "Compiling");
+        // Unknown ETA is omitted rather than shown as dashes
+        assert_eq!(compact_status_line("Linking", 90.0, "", "--:--", "01:05", 80), "Linking 90% · 01:05 elapsed");
     }
 }
@@ -1,7 +1,8 @@
 use ratatui::{
     buffer::Buffer,
     layout::{Rect, Position},
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
     widgets::{Widget},
 };
 use crossterm::event::{MouseEvent, MouseButton, MouseEventKind, KeyModifiers};
@@ -12,6 +13,18 @@ pub enum SelectionListInteraction {
     Hover(usize),
 }
 
+/// First visible row that keeps `selected` inside a window of `height` rows.
+///>
+/// The window only moves once the selection leaves it, so the list scrolls
+/// one row at a time as navigation passes the bottom edge.
+///<
+pub fn scroll_offset(len: usize, selected: usize, height: usize) -> usize {
+    if height == 0 || len <= height {
+        return 0;
+    }
+    selected.min(len - 1).saturating_sub(height - 1)
+}
+
 /// A general-purpose list widget for selecting items.
 /// 
 /// Unlike CommandListWidget, this does not render its own borders or title.
@@ -21,6 +34,9 @@ pub struct SelectionListWidget<'a> {
     hovered_index: Option<usize>,
     highlight_style: Style,
     normal_style: Style,
+    match_style: Style,
+    icons: Vec<Option<(String, Style)>>,
+    highlights: Vec<Vec<usize>>,
 }
 
 impl<'a> SelectionListWidget<'a> {
@@ -31,9 +47,24 @@ impl<'a> SelectionListWidget<'a> {
             hovered_index,
             highlight_style: Style::default().fg(Color::Cyan).bg(Color::Rgb(0, 40, 40)),
             normal_style: Style::default().fg(Color::DarkGray),
+            match_style: Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            icons: Vec::new(),
+            highlights: Vec::new(),
         }
     }
 
+    /// Leading glyph and its style for each item, by index; `None` entries get no glyph.
+    pub fn icons(mut self, icons: Vec<Option<(String, Style)>>) -> Self {
+        self.icons = icons;
+        self
+    }
+
+    /// Char indices to draw with the match style for each item, by index (e.g. a filter's hits).
+    pub fn highlights(mut self, highlights: Vec<Vec<usize>>) -> Self {
+        self.highlights = highlights;
+        self
+    }
+
     pub fn highlight_style(mut self, style: Style) -> Self {
         self.highlight_style = style;
         self
@@ -51,12 +82,13 @@ impl<'a> SelectionListWidget<'a> {
             return None;
         }
 
-        let rel_y = mouse_event.row.saturating_sub(area.y) as usize;
+        let offset = scroll_offset(self.items.len(), self.selected_index, area.height as usize);
+        let idx = mouse_event.row.saturating_sub(area.y) as usize + offset;
         
-        if rel_y < self.items.len() {
+        if idx < self.items.len() {
             match mouse_event.kind {
-                MouseEventKind::Down(MouseButton::Left) => Some(SelectionListInteraction::Click(rel_y)),
-                MouseEventKind::Moved | MouseEventKind::Drag(MouseButton::Left) => Some(SelectionListInteraction::Hover(rel_y)),
+                MouseEventKind::Down(MouseButton::Left) => Some(SelectionListInteraction::Click(idx)),
+                MouseEventKind::Moved | MouseEventKind::Drag(MouseButton::Left) => Some(SelectionListInteraction::Hover(idx)),
                 _ => None,
             }
         } else {
@@ -67,10 +99,10 @@ impl<'a> SelectionListWidget<'a> {
 
 impl<'a> Widget for SelectionListWidget<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        for (idx, item) in self.items.iter().enumerate() {
-            if idx >= area.height as usize { break; }
-            
-            let item_y = area.y + idx as u16;
+        let height = area.height as usize;
+        let offset = scroll_offset(self.items.len(), self.selected_index, height);
+        for (idx, item) in self.items.iter().enumerate().skip(offset).take(height) {
+            let item_y = area.y + (idx - offset) as u16;
             let is_selected = idx == self.selected_index;
             let is_hovered = self.hovered_index == Some(idx);
             
@@ -92,7 +124,33 @@ impl<'a> Widget for SelectionListWidget<'a> {
                 }
             }
 
-            buf.set_string(area.x + 1, item_y, format!(" {}", item), style);
+            let mut spans = vec![Span::styled(" ", style)];
+            if let Some(Some((icon, icon_style))) = self.icons.get(idx) {
+                spans.push(Span::styled(icon.as_str(), style.patch(*icon_style)));
+                spans.push(Span::styled(" ", style));
+            }
+            match self.highlights.get(idx).filter(|positions| !positions.is_empty()) {
+                Some(positions) => {
+                    let match_style = style.patch(self.match_style);
+                    for (pos, c) in item.chars().enumerate() {
+                        let char_style = if positions.contains(&pos) { match_style } else { style };
+                        spans.push(Span::styled(c.to_string(), char_style));
+                    }
+                }
+                None => spans.push(Span::styled(item.as_str(), style)),
+            }
+            buf.set_line(area.x + 1, item_y, &Line::from(spans), area.width.saturating_sub(1));
+        }
+
+        // Scroll indicators in the right-hand column
+        if area.width > 0 && height > 0 {
+            let indicator_x = area.right() - 1;
+            if offset > 0 {
+                buf.set_string(indicator_x, area.y, "▲", self.normal_style);
+            }
+            if offset + height < self.items.len() {
+                buf.set_string(indicator_x, area.bottom() - 1, "▼", self.normal_style);
+            }
         }
     }
 }
@@ -147,4 +205,32 @@ mod tests {
             assert_eq!(idx, 0);
         } else { panic!("Expected Click"); }
     }
+
+    #[test]
+    fn test_selection_below_fold_scrolls_into_view() {
+        let backend = TestBackend::new(20, 4);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let items: Vec<String> = (0..10).map(|i| format!("Item {}", i)).collect();
+
+        terminal.draw(|f| {
+            f.render_widget(SelectionListWidget::new(&items, 7, None), f.area());
+        }).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let rows: Vec<String> = (0..4)
+            .map(|y| (0..20).map(|x| buffer[(x, y)].symbol()).collect::<String>())
+            .collect();
+        assert!(rows[3].contains("Item 7"));
+        assert!(rows[0].contains("Item 4") && rows[0].contains('▲'));
+        assert!(rows[3].contains('▼'));
+        assert!(!rows.iter().any(|r| r.contains("Item 0")));
+
+        // Clicks map through the scroll offset
+        let widget = SelectionListWidget::new(&items, 7, None);
+        let event = MouseEvent { kind: MouseEventKind::Down(MouseButton::Left), column: 5, row: 0, modifiers: KeyModifiers::empty() };
+        match widget.handle_mouse_event(Rect::new(0, 0, 20, 4), event) {
+            Some(SelectionListInteraction::Click(idx)) => assert_eq!(idx, 4),
+            _ => panic!("Expected Click"),
+        }
+    }
 }
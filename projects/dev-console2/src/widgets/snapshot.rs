@@ -0,0 +1,70 @@
+use ratatui::{backend::TestBackend, buffer::Buffer, layout::Rect, widgets::Widget, Frame, Terminal};
+
+/// Flattens a buffer into one line of symbols per row, each ending in `\n`.
+pub fn buffer_to_string(buffer: &Buffer) -> String {
+    let mut result = String::new();
+    for y in buffer.area.top()..buffer.area.bottom() {
+        for x in buffer.area.left()..buffer.area.right() {
+            result.push_str(buffer[(x, y)].symbol());
+        }
+        result.push('\n');
+    }
+    result
+}
+
+/// Renders a view into a `width` x `height` test terminal and returns its text snapshot.
+///>
+/// The closure receives the frame and the full area, so any view with a
+/// `render(f, area)` shape (dashboard, profiles, dropdowns) can be snapshotted
+/// without going through the whole `App`.
+///<
+pub fn render_snapshot(width: u16, height: u16, view: impl FnOnce(&mut Frame, Rect)) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(|f| {
+        let area = f.area();
+        view(f, area);
+    }).unwrap();
+    buffer_to_string(terminal.backend().buffer())
+}
+
+/// Snapshot of a single widget rendered across the whole `width` x `height` area.
+pub fn widget_snapshot(widget: impl Widget, width: u16, height: u16) -> String {
+    let area = Rect::new(0, 0, width, height);
+    let mut buffer = Buffer::empty(area);
+    widget.render(area, &mut buffer);
+    buffer_to_string(&buffer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::tab_bar::{TabBarItem, TabBarStyle, TabBarWidget};
+
+    fn tabs() -> Vec<TabBarItem> {
+        vec![
+            TabBarItem { id: "dashboard".to_string(), name: "Dashboard".to_string(), active: true },
+            TabBarItem { id: "profiles".to_string(), name: "Profiles".to_string(), active: false },
+        ]
+    }
+
+    #[test]
+    fn test_tab_bar_snapshot() {
+        let items = tabs();
+        let snapshot = widget_snapshot(TabBarWidget::new(&items).style(TabBarStyle::Tab), 26, 2);
+        assert_eq!(
+            snapshot,
+            "╭───────────╮             \n\
+             ╯ Dashboard ╰─ Profiles   \n"
+        );
+    }
+
+    #[test]
+    fn test_render_snapshot_matches_widget_snapshot() {
+        let items = tabs();
+        let via_frame = render_snapshot(26, 1, |f, area| {
+            f.render_widget(TabBarWidget::new(&items), area);
+        });
+        assert_eq!(via_frame, widget_snapshot(TabBarWidget::new(&items), 26, 1));
+        assert_eq!(via_frame, " Dashboard ─ Profiles     \n");
+    }
+}
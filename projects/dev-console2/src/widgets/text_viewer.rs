@@ -0,0 +1,81 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    text::Line,
+    widgets::{Paragraph, Widget},
+};
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent, MouseEventKind};
+use crate::widgets::{InteractiveWidget, WidgetOutcome};
+
+/// Lines moved by PageUp/PageDown.
+const PAGE_LINES: isize = 10;
+
+/// Read-only, scrollable block of text for popups (e.g. the linker map report).
+///>
+/// The last line of the content area shows the position; arrows, PageUp/Down,
+/// Home/End and the mouse wheel scroll, Esc or `q` closes.
+///<
+#[derive(Debug, Clone)]
+pub struct TextViewer {
+    lines: Vec<String>,
+    scroll: usize,
+}
+
+impl TextViewer {
+    pub fn new(lines: Vec<String>) -> Self {
+        Self { lines, scroll: 0 }
+    }
+
+    fn scroll_by(&mut self, delta: isize) {
+        let max = self.lines.len().saturating_sub(1);
+        self.scroll = self.scroll.saturating_add_signed(delta).min(max);
+    }
+}
+
+impl Widget for &TextViewer {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 {
+            return;
+        }
+        let body = Rect { height: area.height - 1, ..area };
+        let visible: Vec<Line> = self.lines.iter()
+            .skip(self.scroll)
+            .take(body.height as usize)
+            .map(|line| Line::raw(line.as_str()))
+            .collect();
+        Paragraph::new(visible).render(body, buf);
+
+        let footer = format!(" {}/{} · ↑↓ PgUp/PgDn scroll · Esc close", (self.scroll + 1).min(self.lines.len()), self.lines.len());
+        Paragraph::new(footer)
+            .style(Style::default().fg(Color::DarkGray))
+            .render(Rect { y: area.y + area.height - 1, height: 1, ..area }, buf);
+    }
+}
+
+impl InteractiveWidget for TextViewer {
+    type Outcome = ();
+
+    fn handle_key(&mut self, key: KeyEvent) -> WidgetOutcome<()> {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return WidgetOutcome::Canceled,
+            KeyCode::Up | KeyCode::Char('k') => self.scroll_by(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.scroll_by(1),
+            KeyCode::PageUp => self.scroll_by(-PAGE_LINES),
+            KeyCode::PageDown => self.scroll_by(PAGE_LINES),
+            KeyCode::Home => self.scroll = 0,
+            KeyCode::End => self.scroll = self.lines.len().saturating_sub(1),
+            _ => {}
+        }
+        WidgetOutcome::Consumed
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent, _area: Rect) -> WidgetOutcome<()> {
+        match mouse.kind {
+            MouseEventKind::ScrollUp => self.scroll_by(-3),
+            MouseEventKind::ScrollDown => self.scroll_by(3),
+            _ => return WidgetOutcome::None,
+        }
+        WidgetOutcome::Consumed
+    }
+}
@@ -0,0 +1,113 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Paragraph, Widget},
+};
+
+/// Narrowest right-hand info worth showing; below this it is dropped.
+const MIN_INFO_WIDTH: usize = 5;
+
+/// Shortens `text` to `max` characters by replacing its middle with `…`.
+pub fn truncate_middle(text: &str, max: usize) -> String {
+    let len = text.chars().count();
+    if len <= max {
+        return text.to_string();
+    }
+    if max == 0 {
+        return String::new();
+    }
+    let head = max / 2;
+    let tail = max - 1 - head;
+    let start: String = text.chars().take(head).collect();
+    let end: String = text.chars().skip(len - tail).collect();
+    format!("{}…{}", start, end)
+}
+
+/// Application title row: a centered title on a `═` rule with optional right-aligned info.
+///>
+/// The info (e.g. active profile and port) sits against the right edge and is
+/// shortened from the middle when space runs out; if even a short form does
+/// not fit between the title and the edge it is left out so the title always
+/// stays readable.
+///<
+pub struct TitleBarWidget<'a> {
+    title: &'a str,
+    info: Option<String>,
+}
+
+impl<'a> TitleBarWidget<'a> {
+    pub fn new(title: &'a str) -> Self {
+        Self { title, info: None }
+    }
+
+    pub fn info(mut self, info: Option<String>) -> Self {
+        self.info = info;
+        self
+    }
+}
+
+impl Widget for TitleBarWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let rule_style = Style::default().fg(Color::White);
+        let title_style = rule_style.add_modifier(Modifier::BOLD);
+        let title = format!(" {} ", self.title);
+        let title_width = title.chars().count();
+        let width = area.width as usize;
+
+        if width <= title_width {
+            Paragraph::new(Line::styled(self.title, title_style))
+                .alignment(Alignment::Center)
+                .render(area, buf);
+            return;
+        }
+
+        let title_x = (width - title_width) / 2;
+        buf.set_string(area.x, area.y, "═".repeat(width), rule_style);
+        buf.set_string(area.x + title_x as u16, area.y, &title, title_style);
+
+        if let Some(info) = self.info.filter(|info| !info.is_empty()) {
+            // Keep one rule cell after the title and one at the right edge
+            let room = width.saturating_sub(title_x + title_width + 2).saturating_sub(2);
+            if room >= MIN_INFO_WIDTH {
+                let text = format!(" {} ", truncate_middle(&info, room));
+                let x = width - 1 - text.chars().count();
+                buf.set_string(area.x + x as u16, area.y, text, Style::default().fg(Color::Cyan));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::snapshot::widget_snapshot;
+
+    fn title_bar() -> TitleBarWidget<'static> {
+        TitleBarWidget::new("DEV CONSOLE").info(Some("esp32-s3-led · /dev/ttyUSB0".to_string()))
+    }
+
+    #[test]
+    fn test_title_and_right_info_truncate_when_narrow() {
+        let wide = widget_snapshot(title_bar(), 80, 1);
+        assert!(wide.contains(" DEV CONSOLE "));
+        assert!(wide.ends_with(" esp32-s3-led · /dev/ttyUSB0 ═\n"));
+
+        let narrow = widget_snapshot(title_bar(), 40, 1);
+        assert!(narrow.contains(" DEV CONSOLE "));
+        assert!(narrow.ends_with(" esp32…USB0 ═\n"));
+
+        // No room for info: the title alone is kept
+        let tiny = widget_snapshot(title_bar(), 20, 1);
+        assert!(tiny.contains(" DEV CONSOLE "));
+        assert!(!tiny.contains("esp32"));
+    }
+
+    #[test]
+    fn test_truncate_middle() {
+        assert_eq!(truncate_middle("abcdefghij", 20), "abcdefghij");
+        assert_eq!(truncate_middle("abcdefghij", 5), "ab…ij");
+        assert_eq!(truncate_middle("abcdefghij", 0), "");
+    }
+}